@@ -0,0 +1,122 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use alda::graph::{Direction, Graph, VertexId};
+use alda::heap::{Heap, MinHeap};
+
+/// Density is the number of edges of a generated graph.
+#[derive(Debug, Clone, Copy)]
+enum Density {
+    /// About four edges per vertex.
+    Sparse,
+    /// About a tenth of the pairs of vertices.
+    Dense,
+}
+
+/// Returns a connected undirected graph with random weights.
+///
+/// A path through the vertices keeps the graph connected, and random
+/// edges are added on top of it.
+fn generate(vertices: usize, density: Density) -> Graph<(), u32> {
+    let mut rng = StdRng::seed_from_u64(vertices as u64);
+    let mut graph = Graph::new(Direction::Undirected);
+    let ids: Vec<_> = (0..vertices).map(|_| graph.add_vertex(())).collect();
+    for pair in ids.windows(2) {
+        graph.add_edge(pair[0], pair[1], rng.gen_range(1..100));
+    }
+    let edges = match density {
+        Density::Sparse => 4 * vertices,
+        Density::Dense => vertices * vertices / 20,
+    };
+    for _ in 0..edges {
+        let (from, to) = (rng.gen_range(0..vertices), rng.gen_range(0..vertices));
+        graph.add_edge(ids[from], ids[to], rng.gen_range(1..100));
+    }
+    graph
+}
+
+/// Returns the distances from the source computed by Dijkstra's algorithm
+/// on a min heap without handles.
+///
+/// A shorter distance to a vertex is pushed as a new entry instead of
+/// decreasing the previous one, and the entries of finished vertices are
+/// skipped when they are popped. The heap holds up to one entry per edge,
+/// so each operation takes O(log E) time.
+fn dijkstra_lazy_heap(graph: &Graph<(), u32>, source: VertexId) -> Vec<Option<i64>> {
+    let ids: Vec<_> = graph.vertices().collect();
+    let mut distances = vec![None; graph.vertex_bound()];
+    let mut finished = vec![false; graph.vertex_bound()];
+    let mut heap: Heap<(i64, usize), MinHeap> = Heap::with_capacity(graph.edge_count());
+    distances[source.index()] = Some(0);
+    heap.insert((0, source.index()));
+    while let Some((distance, index)) = heap.extract_min() {
+        if finished[index] {
+            continue;
+        }
+        finished[index] = true;
+        for (neighbor, &weight) in graph.neighbors(ids[index]) {
+            let (neighbor, candidate) = (neighbor.index(), distance + i64::from(weight));
+            if distances[neighbor].map_or(true, |d| candidate < d) {
+                distances[neighbor] = Some(candidate);
+                heap.insert((candidate, neighbor));
+            }
+        }
+    }
+    distances
+}
+
+fn graph_benchmark(c: &mut Criterion) {
+    let graphs: Vec<_> = [128, 512, 2048]
+        .into_iter()
+        .flat_map(|vertices| {
+            [Density::Sparse, Density::Dense].map(|density| {
+                let graph = generate(vertices, density);
+                let parameter = format!("{:?}/{}V/{}E", density, vertices, graph.edge_count());
+                (graph, parameter)
+            })
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("Graph search");
+    for (graph, parameter) in &graphs {
+        let source = graph.vertices().next().expect("the graph is not empty");
+        group.bench_with_input(BenchmarkId::new("BFS", parameter), graph, |b, graph| {
+            b.iter(|| graph.bfs(source))
+        });
+        group.bench_with_input(BenchmarkId::new("DFS", parameter), graph, |b, graph| {
+            b.iter(|| graph.dfs())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("Dijkstra");
+    for (graph, parameter) in &graphs {
+        let source = graph.vertices().next().expect("the graph is not empty");
+        group.bench_with_input(
+            BenchmarkId::new("PriorityQueue", parameter),
+            graph,
+            |b, graph| b.iter(|| graph.dijkstra(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("LazyHeap", parameter),
+            graph,
+            |b, graph| b.iter(|| dijkstra_lazy_heap(graph, source)),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("Minimum spanning tree");
+    for (graph, parameter) in &graphs {
+        group.bench_with_input(BenchmarkId::new("Kruskal", parameter), graph, |b, graph| {
+            b.iter(|| graph.kruskal())
+        });
+        group.bench_with_input(BenchmarkId::new("Prim", parameter), graph, |b, graph| {
+            b.iter(|| graph.prim())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, graph_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use alda::hashtable::chaining::ChainedHashTable;
+use alda::hashtable::cuckoo::CuckooHashTable;
+use alda::hashtable::open_addressing::{OpenAddressingTable, Probing};
+use alda::map::{DirectAddressTable, Map};
+use alda::skiplist::SkipList;
+use alda::tree::avl::AvlTree;
+use alda::tree::btree::BTree;
+use alda::tree::red_black::RedBlackTree;
+use alda::tree::treap::Treap;
+
+/// Inserts every key, looks every key up, then removes every key.
+fn exercise<M: Map<usize, usize>>(map: &mut M, keys: &[usize]) {
+    for &key in keys {
+        map.insert(key, key);
+    }
+    for key in keys {
+        criterion::black_box(map.get(key));
+    }
+    for key in keys {
+        map.remove(key);
+    }
+}
+
+fn map_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Map");
+    for size in [1_000, 10_000] {
+        // Visit the keys in a scattered order.
+        let keys: Vec<usize> = (0..size).map(|key| key * 7_919 % size).collect();
+
+        group.bench_with_input(BenchmarkId::new("AvlTree", size), &keys, |b, keys| {
+            b.iter(|| exercise(&mut AvlTree::new(), keys))
+        });
+
+        group.bench_with_input(BenchmarkId::new("BTree", size), &keys, |b, keys| {
+            b.iter(|| exercise(&mut BTree::<_, _, 6>::new(), keys))
+        });
+
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &keys, |b, keys| {
+            b.iter(|| exercise(&mut BTreeMap::new(), keys))
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("ChainedHashTable", size),
+            &keys,
+            |b, keys| b.iter(|| exercise(&mut ChainedHashTable::new(), keys)),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("CuckooHashTable", size),
+            &keys,
+            |b, keys| b.iter(|| exercise(&mut CuckooHashTable::with_seed(0), keys)),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("DirectAddressTable", size),
+            &keys,
+            |b, keys| b.iter(|| exercise(&mut DirectAddressTable::new(size), keys)),
+        );
+
+        for probing in [Probing::Linear, Probing::Quadratic, Probing::Double] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("OpenAddressingTable/{probing:?}"), size),
+                &keys,
+                |b, keys| b.iter(|| exercise(&mut OpenAddressingTable::new(probing), keys)),
+            );
+        }
+
+        group.bench_with_input(BenchmarkId::new("RedBlackTree", size), &keys, |b, keys| {
+            b.iter(|| exercise(&mut RedBlackTree::new(), keys))
+        });
+
+        group.bench_with_input(BenchmarkId::new("SkipList", size), &keys, |b, keys| {
+            b.iter(|| exercise(&mut SkipList::with_seed(0), keys))
+        });
+
+        group.bench_with_input(BenchmarkId::new("Treap", size), &keys, |b, keys| {
+            b.iter(|| exercise(&mut Treap::with_seed(0), keys))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, map_benchmark);
+criterion_main!(benches);
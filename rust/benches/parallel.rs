@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::Rng;
+
+use alda::sort::{parallel::ParallelSort, Pivot, Sort};
+use alda::Container;
+
+/// The length below which the parallel sorts sort sequentially.
+const THRESHOLD: usize = 4096;
+
+fn parallel_sorting_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Parallel sort");
+    let mut rng = rand::thread_rng();
+    for len in [10_000, 100_000] {
+        let container = Container::new((0..len).map(|_| rng.gen::<i64>()).collect());
+        group.bench_with_input(BenchmarkId::new("MergeSort", len), &container, |b, i| {
+            b.iter(|| {
+                let mut container = i.clone();
+                container.merge_sort(0, container.len());
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("ParMergeSort", len), &container, |b, i| {
+            b.iter(|| {
+                let mut container = i.clone();
+                container.par_merge_sort(THRESHOLD);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("QuickSort", len), &container, |b, i| {
+            b.iter(|| {
+                let mut container = i.clone();
+                container.quick_sort(0, container.len(), Pivot::MedianOfThree);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("ParQuickSort", len), &container, |b, i| {
+            b.iter(|| {
+                let mut container = i.clone();
+                container.par_quick_sort(THRESHOLD);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parallel_sorting_benchmark);
+criterion_main!(benches);
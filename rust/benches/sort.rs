@@ -1,7 +1,11 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
 use alda::Container;
-use alda::{heap::Heap, sort::Sort};
+use alda::{
+    heap::Heap,
+    list::List,
+    sort::{Partition, Pivot, Sort},
+};
 
 mod data;
 use data::DATA;
@@ -82,7 +86,17 @@ fn sorting_benchmark(c: &mut Criterion) {
             |b, i| {
                 b.iter(|| {
                     let mut container = i.clone();
-                    container.quick_sort(0, container.len());
+                    container.quick_sort(0, container.len(), Pivot::Last);
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("MedianOfThreeQuickSort", container.len()),
+            &container,
+            |b, i| {
+                b.iter(|| {
+                    let mut container = i.clone();
+                    container.quick_sort(0, container.len(), Pivot::MedianOfThree);
                 })
             },
         );
@@ -96,10 +110,122 @@ fn sorting_benchmark(c: &mut Criterion) {
                 })
             },
         );
+        for (name, sort) in [
+            (
+                "Pancake",
+                Container::pancake_sort as fn(&mut Container<i64>),
+            ),
+            ("BottomUpMergeSort", Container::bottom_up_merge_sort),
+            ("BufferedMergeSort", Container::buffered_merge_sort),
+            ("Hybrid", Container::hybrid_sort),
+            ("Bubble", |container| container.bubble_sort(|a, b| a > b)),
+            ("Shell", |container| container.shell_sort(|a, b| a > b)),
+            ("Gnome", |container| container.gnome_sort(|a, b| a > b)),
+            ("Comb", |container| container.comb_sort(|a, b| a > b)),
+            ("Cocktail", Container::cocktail_sort),
+        ] {
+            group.bench_with_input(
+                BenchmarkId::new(name, container.len()),
+                &container,
+                |b, i| {
+                    b.iter(|| {
+                        let mut container = i.clone();
+                        sort(&mut container);
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn list_sorting_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("List sort");
+    for keys in [DATA[..].to_vec(), DATA[..DATA.len() / 2].to_vec()] {
+        let mut list = List::new();
+        for key in keys.iter().rev() {
+            list.insert(*key);
+        }
+
+        group.bench_with_input(BenchmarkId::new("MergeSort", keys.len()), &list, |b, i| {
+            b.iter(|| {
+                let mut list = i.clone();
+                list.sort();
+            })
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("ContainerMergeSort", keys.len()),
+            &list,
+            |b, i| {
+                b.iter(|| {
+                    let mut container = Container::new(i.iter().copied().collect());
+                    container.merge_sort(0, container.len());
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn partition_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("QuickSort partition");
+    for (input, keys) in [
+        ("Distinct", DATA.to_vec()),
+        ("FewDistinct", DATA.iter().map(|key| key % 4).collect()),
+        ("Equal", vec![0; DATA.len()]),
+    ] {
+        let container = Container::new(keys);
+        for (name, scheme) in [
+            ("Lomuto", Partition::Lomuto),
+            ("Hoare", Partition::Hoare),
+            ("ThreeWay", Partition::ThreeWay),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, input), &container, |b, i| {
+                b.iter(|| {
+                    let mut container = i.clone();
+                    container.quick_sort_with(0, container.len(), Pivot::Random, scheme);
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn top_k_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Top k");
+    let container = Container::new(DATA.to_vec());
+    for k in [
+        10,
+        container.len() / 10,
+        container.len() / 2,
+        container.len(),
+    ] {
+        group.bench_with_input(BenchmarkId::new("BoundedHeap", k), &k, |b, &k| {
+            b.iter(|| container.top_k(k))
+        });
+
+        group.bench_with_input(BenchmarkId::new("FullSort", k), &k, |b, &k| {
+            b.iter(|| {
+                let mut sorted = container.inner().to_vec();
+                sorted.sort_unstable_by(|a, b| b.cmp(a));
+                sorted.truncate(k);
+                sorted
+            })
+        });
     }
 
     group.finish();
 }
 
-criterion_group!(benches, sorting_benchmark);
+criterion_group!(
+    benches,
+    sorting_benchmark,
+    list_sorting_benchmark,
+    partition_benchmark,
+    top_k_benchmark
+);
 criterion_main!(benches);
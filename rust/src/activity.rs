@@ -0,0 +1,330 @@
+//! A solution to the activity selection problem.
+//!
+//! Given a set of activities which require exclusive use of a common
+//! resource, the problem is to select a maximum-size set of mutually
+//! compatible activities. See CLRS section 16.1.
+//!
+//! When each activity has a weight, the problem is to select a set of
+//! mutually compatible activities of maximum total weight instead, which
+//! has no greedy choice property and is solved by dynamic programming.
+
+use std::ops::Add;
+
+/// The `Activity` type is an activity taking place during the half-open
+/// interval `[start, finish)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Activity<T> {
+    /// Start time of the activity.
+    pub start: T,
+
+    /// Finish time of the activity.
+    pub finish: T,
+}
+
+impl<T: PartialOrd> Activity<T> {
+    /// Creates new activity.
+    pub const fn new(start: T, finish: T) -> Self {
+        Self { start, finish }
+    }
+
+    /// Returns true if the two activities do not overlap.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self.finish <= other.start || other.finish <= self.start
+    }
+}
+
+/// The `Schedule` type is a set of mutually compatible activities.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Schedule<T> {
+    /// The selected activities sorted by finish time.
+    pub activities: Vec<Activity<T>>,
+}
+
+impl<T> Schedule<T> {
+    /// Returns the number of selected activities.
+    pub fn len(&self) -> usize {
+        self.activities.len()
+    }
+
+    /// Returns true if no activity is selected.
+    pub fn is_empty(&self) -> bool {
+        self.activities.is_empty()
+    }
+}
+
+/// The `WeightedSchedule` type is a set of mutually compatible activities
+/// together with their total weight.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WeightedSchedule<T, W> {
+    /// The total weight of the selected activities.
+    pub weight: W,
+
+    /// The selected activities sorted by finish time.
+    pub activities: Vec<Activity<T>>,
+}
+
+/// The `ActivitySelector` type selects a maximum-size set of mutually
+/// compatible activities.
+#[derive(Debug, Clone)]
+pub struct ActivitySelector<T> {
+    /// The activities sorted by monotonically increasing finish time.
+    activities: Vec<Activity<T>>,
+}
+
+impl<T> ActivitySelector<T>
+where
+    T: Ord + Clone,
+{
+    /// Creates new activity selector.
+    ///
+    /// The activities are sorted by finish time, which both greedy
+    /// selectors rely on. Ties are broken by start time so that empty
+    /// activities come after the activities finishing at the same time.
+    pub fn new(mut activities: Vec<Activity<T>>) -> Self {
+        activities.sort_by(|lhs, rhs| {
+            lhs.finish
+                .cmp(&rhs.finish)
+                .then_with(|| lhs.start.cmp(&rhs.start))
+        });
+        Self { activities }
+    }
+
+    /// Returns the activities sorted by finish time.
+    pub fn activities(&self) -> &[Activity<T>] {
+        &self.activities
+    }
+
+    /// Selects the activities with CLRS recursive greedy algorithm.
+    ///
+    /// The activity which finishes first always belongs to some optimal
+    /// solution, so it is selected and the problem is solved recursively
+    /// for the activities starting after it finishes.
+    pub fn recursive_select(&self) -> Schedule<T> {
+        fn select<T: Ord + Clone>(
+            activities: &[Activity<T>],
+            last: Option<&Activity<T>>,
+            schedule: &mut Vec<Activity<T>>,
+        ) {
+            let next = activities
+                .iter()
+                .position(|activity| last.map_or(true, |last| activity.start >= last.finish));
+            if let Some(index) = next {
+                schedule.push(activities[index].clone());
+                select(&activities[index + 1..], Some(&activities[index]), schedule);
+            }
+        }
+
+        let mut activities = Vec::new();
+        select(&self.activities, None, &mut activities);
+        Schedule { activities }
+    }
+
+    /// Selects the activities with CLRS iterative greedy algorithm.
+    pub fn greedy_select(&self) -> Schedule<T> {
+        let mut activities: Vec<Activity<T>> = Vec::new();
+        for activity in &self.activities {
+            if activities
+                .last()
+                .map_or(true, |last| activity.start >= last.finish)
+            {
+                activities.push(activity.clone());
+            }
+        }
+        Schedule { activities }
+    }
+
+    /// Selects the activities of maximum total weight.
+    ///
+    /// `best[i]` is the maximum weight of a schedule among the first `i`
+    /// activities by finish time: either the i-th activity is left out, or
+    /// it is added to the best schedule of the activities finishing before
+    /// it starts, which are found by binary search. It takes O(n log n)
+    /// time. Activities of negative weight are never selected, and
+    /// `Default::default()` is used as the zero weight.
+    pub fn weighted_select<W, F>(&self, weight: F) -> WeightedSchedule<T, W>
+    where
+        W: Copy + Default + Ord + Add<Output = W>,
+        F: Fn(&Activity<T>) -> W,
+    {
+        let n = self.activities.len();
+        let mut best = vec![W::default(); n + 1];
+        // `previous[i]` is the number of activities compatible with the
+        // i-th one which come before it.
+        let mut previous = vec![0; n];
+        let mut taken = vec![false; n];
+        for (i, activity) in self.activities.iter().enumerate() {
+            previous[i] = self
+                .activities
+                .partition_point(|other| other.finish <= activity.start)
+                .min(i);
+            let with = weight(activity) + best[previous[i]];
+            taken[i] = with > best[i];
+            best[i + 1] = if taken[i] { with } else { best[i] };
+        }
+
+        let mut activities = Vec::new();
+        let mut i = n;
+        while i > 0 {
+            if taken[i - 1] {
+                activities.push(self.activities[i - 1].clone());
+                i = previous[i - 1];
+            } else {
+                i -= 1;
+            }
+        }
+        activities.reverse();
+        WeightedSchedule {
+            weight: best[n],
+            activities,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn activities(intervals: &[(u8, u8)]) -> Vec<Activity<u8>> {
+        intervals
+            .iter()
+            .map(|&(start, length)| Activity::new(start, start.saturating_add(length)))
+            .collect()
+    }
+
+    /// Returns the size of an optimal schedule with dynamic programming.
+    ///
+    /// `best[i]` is the size of an optimal schedule among the first `i`
+    /// activities by finish time: either the i-th activity is left out,
+    /// or it is added to an optimal schedule of the activities which
+    /// finish before it starts.
+    fn optimal_size(activities: &[Activity<u8>]) -> usize {
+        let mut activities = activities.to_vec();
+        activities.sort_by_key(|activity| (activity.finish, activity.start));
+        let mut best = vec![0; activities.len() + 1];
+        for (i, activity) in activities.iter().enumerate() {
+            let before = activities.partition_point(|other| other.finish <= activity.start);
+            best[i + 1] = best[i].max(1 + best[before.min(i)]);
+        }
+        best[activities.len()]
+    }
+
+    fn is_feasible(schedule: &Schedule<u8>) -> bool {
+        schedule
+            .activities
+            .windows(2)
+            .all(|pair| pair[0].finish <= pair[1].start)
+    }
+
+    #[test]
+    fn select_clrs_activities() {
+        // Activities from CLRS section 16.1, given in shuffled order.
+        let selector = ActivitySelector::new(vec![
+            Activity::new(8, 12),
+            Activity::new(1, 4),
+            Activity::new(3, 9),
+            Activity::new(5, 7),
+            Activity::new(0, 6),
+            Activity::new(8, 11),
+            Activity::new(3, 5),
+            Activity::new(5, 9),
+            Activity::new(6, 10),
+            Activity::new(12, 16),
+            Activity::new(2, 14),
+        ]);
+        let expected = Schedule {
+            activities: vec![
+                Activity::new(1, 4),
+                Activity::new(5, 7),
+                Activity::new(8, 11),
+                Activity::new(12, 16),
+            ],
+        };
+        assert_eq!(selector.greedy_select(), expected);
+        assert_eq!(selector.recursive_select(), expected);
+        assert_eq!(expected.len(), 4);
+    }
+
+    #[test]
+    fn select_empty_activities() {
+        let selector = ActivitySelector::new(vec![Activity::new(5, 5), Activity::new(3, 5)]);
+        assert_eq!(selector.greedy_select().len(), 2);
+        assert_eq!(selector.recursive_select().len(), 2);
+    }
+
+    #[test]
+    fn select_no_activity() {
+        let selector = ActivitySelector::<u8>::new(vec![]);
+        assert!(selector.greedy_select().is_empty());
+        assert!(selector.recursive_select().is_empty());
+    }
+
+    #[test]
+    fn select_weighted_activities() {
+        let selector = ActivitySelector::new(vec![
+            Activity::new(1, 4),
+            Activity::new(3, 5),
+            Activity::new(0, 6),
+            Activity::new(4, 7),
+            Activity::new(3, 9),
+            Activity::new(5, 9),
+            Activity::new(6, 10),
+            Activity::new(8, 11),
+        ]);
+        // The longest activities cover the most time.
+        let busiest = selector.weighted_select(|activity| activity.finish - activity.start);
+        assert_eq!(busiest.weight, 10);
+        assert_eq!(
+            busiest.activities,
+            vec![Activity::new(0, 6), Activity::new(6, 10)]
+        );
+        // Unit weights give a maximum-size schedule.
+        let unit = selector.weighted_select(|_| 1);
+        assert_eq!(unit.weight, selector.greedy_select().len());
+        // No activity is worth a negative weight.
+        assert_eq!(
+            selector.weighted_select(|_| -1),
+            WeightedSchedule::default()
+        );
+    }
+
+    #[quickcheck]
+    fn weighted_selection_is_optimal(intervals: Vec<(u8, u8)>) -> bool {
+        let activities: Vec<_> = activities(&intervals).into_iter().take(10).collect();
+        let weight = |activity: &Activity<u8>| i32::from(activity.start % 7) - 2;
+        let selector = ActivitySelector::new(activities.clone());
+        let schedule = selector.weighted_select(weight);
+
+        // Every subset of the activities, by brute force.
+        let brute_force = (0..1_u32 << activities.len())
+            .filter_map(|subset| {
+                let chosen: Vec<_> = (0..activities.len())
+                    .filter(|i| subset & (1 << i) != 0)
+                    .map(|i| activities[i])
+                    .collect();
+                let compatible = chosen
+                    .iter()
+                    .enumerate()
+                    .all(|(i, a)| chosen[i + 1..].iter().all(|b| a.is_compatible(b)));
+                compatible.then(|| chosen.iter().map(weight).sum::<i32>())
+            })
+            .max()
+            .unwrap_or(0);
+        let feasible = Schedule {
+            activities: schedule.activities.clone(),
+        };
+        is_feasible(&feasible)
+            && schedule.weight == schedule.activities.iter().map(weight).sum::<i32>()
+            && schedule.weight == brute_force
+    }
+
+    #[quickcheck]
+    fn greedy_selection_is_optimal(intervals: Vec<(u8, u8)>) -> bool {
+        let activities = activities(&intervals);
+        let selector = ActivitySelector::new(activities.clone());
+        let greedy = selector.greedy_select();
+        let recursive = selector.recursive_select();
+        is_feasible(&greedy) && greedy == recursive && greedy.len() == optimal_size(&activities)
+    }
+}
@@ -0,0 +1,172 @@
+//! Empirical analysis of algorithms.
+//!
+//! A doubling experiment runs an algorithm on inputs of size `n`, `2n`,
+//! `4n`, ... If the cost grows like `n^b`, doubling the size multiplies
+//! the cost by `2^b`, so the exponent `b` can be read from the ratio of
+//! successive measurements.
+
+use std::time::{Duration, Instant};
+
+/// Measurement is the cost of one run of an algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    /// The size of the input.
+    pub size: usize,
+
+    /// The number of operations reported by the algorithm.
+    pub operations: u64,
+
+    /// The running time of the algorithm, without generating the input.
+    pub duration: Duration,
+}
+
+/// Report is the result of a doubling experiment.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Report {
+    /// The measurements, by increasing input size.
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    /// Returns the empirical growth exponent of the operation counts.
+    ///
+    /// It is the slope of the least squares line through the points
+    /// `(log n, log operations)`, so `n^b` operations give an exponent
+    /// close to `b`. It returns `None` with less than two distinct sizes
+    /// with a nonzero count.
+    pub fn exponent(&self) -> Option<f64> {
+        slope(
+            self.measurements
+                .iter()
+                .map(|measurement| (measurement.size, measurement.operations as f64)),
+        )
+    }
+
+    /// Returns the empirical growth exponent of the running times.
+    ///
+    /// Timings are noisy, so this exponent is only meaningful for inputs
+    /// large enough to run for at least a few milliseconds.
+    pub fn time_exponent(&self) -> Option<f64> {
+        slope(
+            self.measurements
+                .iter()
+                .map(|measurement| (measurement.size, measurement.duration.as_secs_f64())),
+        )
+    }
+
+    /// Returns the ratios of the operation counts of successive
+    /// measurements.
+    ///
+    /// For doubling sizes a ratio close to `2^b` suggests an `n^b` growth.
+    pub fn ratios(&self) -> Vec<f64> {
+        self.measurements
+            .windows(2)
+            .map(|pair| pair[1].operations as f64 / pair[0].operations as f64)
+            .collect()
+    }
+}
+
+/// Returns `count` sizes starting at `start` and doubling each time.
+pub fn doubling_sizes(start: usize, count: usize) -> Vec<usize> {
+    std::iter::successors(Some(start), |size| size.checked_mul(2))
+        .take(count)
+        .collect()
+}
+
+/// Runs an algorithm on generated inputs of the given sizes.
+///
+/// For each size, `generator` creates an input, and `algorithm` processes
+/// it and returns the number of operations it performed, for example by
+/// counting the calls to a comparison function. Only the algorithm is
+/// timed.
+pub fn measure<I, G, A>(sizes: &[usize], mut generator: G, mut algorithm: A) -> Report
+where
+    G: FnMut(usize) -> I,
+    A: FnMut(I) -> u64,
+{
+    let measurements = sizes
+        .iter()
+        .map(|&size| {
+            let input = generator(size);
+            let start = Instant::now();
+            let operations = algorithm(input);
+            Measurement {
+                size,
+                operations,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+    Report { measurements }
+}
+
+/// Returns the slope of the least squares line through the points in
+/// log-log scale, ignoring the points which have no logarithm.
+fn slope(points: impl Iterator<Item = (usize, f64)>) -> Option<f64> {
+    let points: Vec<(f64, f64)> = points
+        .filter(|&(size, cost)| size > 0 && cost > 0.0)
+        .map(|(size, cost)| ((size as f64).ln(), cost.ln()))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::sort::Sort;
+    use crate::Container;
+
+    #[test]
+    fn doubling() {
+        assert_eq!(doubling_sizes(100, 4), vec![100, 200, 400, 800]);
+        assert_eq!(doubling_sizes(usize::MAX, 3), vec![usize::MAX]);
+    }
+
+    #[test]
+    fn linear_algorithm() {
+        let report = measure(
+            &doubling_sizes(8, 5),
+            |size| vec![0; size],
+            |input| input.len() as u64 * 3,
+        );
+        assert_eq!(report.measurements.len(), 5);
+        assert_eq!(report.ratios(), vec![2.0; 4]);
+        let exponent = report.exponent().expect("sizes are distinct");
+        assert!((exponent - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quadratic_insertion_sort() {
+        let report = measure(
+            &doubling_sizes(64, 4),
+            |size| Container::new((0..size).rev().collect::<Vec<_>>()),
+            |mut container| {
+                let comparisons = Cell::new(0);
+                container.insertion_sort(|a, b| {
+                    comparisons.set(comparisons.get() + 1);
+                    a > b
+                });
+                comparisons.get()
+            },
+        );
+        let exponent = report.exponent().expect("sizes are distinct");
+        assert!((exponent - 2.0).abs() < 0.1, "exponent is {exponent}");
+    }
+
+    #[test]
+    fn not_enough_measurements() {
+        assert_eq!(Report::default().exponent(), None);
+        let report = measure(&[10, 10], |size| size, |size| size as u64);
+        assert_eq!(report.exponent(), None);
+    }
+}
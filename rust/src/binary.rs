@@ -0,0 +1,178 @@
+//! Binary integers arithmetic.
+//!
+//! The integers are slices of bits of any length, stored from the most
+//! significant to the least significant bit, and each bit is either 0
+//! or 1. See CLRS exercise 2.1-4.
+
+use std::cmp::Ordering;
+
+use crate::{error::ErrorKind, Error};
+
+/// Adds two binary integers.
+///
+/// The sum has one more bit than the longest operand, so it never
+/// overflows.
+pub fn add(lhs: &[u8], rhs: &[u8]) -> Vec<u8> {
+    let len = lhs.len().max(rhs.len());
+    let mut sum = vec![0; len + 1];
+    let mut carry = 0;
+    for position in 0..len {
+        let bits = bit(lhs, position) + bit(rhs, position) + carry;
+        sum[len - position] = bits % 2;
+        carry = bits / 2;
+    }
+    sum[0] = carry;
+    sum
+}
+
+/// Subtracts two binary integers.
+///
+/// The difference has as many bits as the longest operand. It fails with
+/// [`ErrorKind::Overflow`] if `rhs` is greater than `lhs`.
+pub fn sub(lhs: &[u8], rhs: &[u8]) -> Result<Vec<u8>, Error> {
+    let len = lhs.len().max(rhs.len());
+    let mut difference = vec![0; len];
+    let mut borrow = 0;
+    for position in 0..len {
+        let subtrahend = bit(rhs, position) + borrow;
+        let minuend = bit(lhs, position);
+        borrow = u8::from(minuend < subtrahend);
+        difference[len - 1 - position] = minuend + 2 * borrow - subtrahend;
+    }
+    if borrow == 0 {
+        Ok(difference)
+    } else {
+        Err(Error::new(ErrorKind::Overflow))
+    }
+}
+
+/// Multiplies two binary integers.
+///
+/// The product has as many bits as both operands together, so it never
+/// overflows. For each bit set in `rhs`, `lhs` shifted by the position of
+/// that bit is added to the product.
+pub fn mul(lhs: &[u8], rhs: &[u8]) -> Vec<u8> {
+    let len = lhs.len() + rhs.len();
+    let mut product = vec![0; len];
+    for shift in (0..rhs.len()).filter(|&position| bit(rhs, position) == 1) {
+        let mut carry = 0;
+        for position in shift..len {
+            let bits = bit(&product, position) + bit(lhs, position - shift) + carry;
+            product[len - 1 - position] = bits % 2;
+            carry = bits / 2;
+        }
+    }
+    product
+}
+
+/// Compares two binary integers.
+///
+/// The leading zeros are ignored, so operands of different lengths are
+/// compared by value.
+pub fn compare(lhs: &[u8], rhs: &[u8]) -> Ordering {
+    let lhs = strip_leading_zeros(lhs);
+    let rhs = strip_leading_zeros(rhs);
+    lhs.len().cmp(&rhs.len()).then_with(|| lhs.cmp(rhs))
+}
+
+/// Converts an integer to its binary representation.
+///
+/// The representation has no leading zeros, except for zero which is
+/// represented by a single bit.
+pub fn from_u64(value: u64) -> Vec<u8> {
+    let len = (u64::BITS - value.leading_zeros()).max(1);
+    (0..len)
+        .rev()
+        .map(|shift| (value >> shift & 1) as u8)
+        .collect()
+}
+
+/// Converts a binary integer to an integer.
+///
+/// It fails with [`ErrorKind::Overflow`] if the value does not fit into
+/// a `u64`.
+pub fn to_u64(bits: &[u8]) -> Result<u64, Error> {
+    let bits = strip_leading_zeros(bits);
+    if bits.len() > u64::BITS as usize {
+        return Err(Error::new(ErrorKind::Overflow));
+    }
+    Ok(bits
+        .iter()
+        .fold(0, |value, &bit| value << 1 | u64::from(bit)))
+}
+
+/// Returns the bit at the given position, counted from the least
+/// significant bit, or zero past the most significant bit.
+fn bit(bits: &[u8], position: usize) -> u8 {
+    bits.len()
+        .checked_sub(position + 1)
+        .map_or(0, |index| bits[index])
+}
+
+/// Removes the leading zeros of a binary integer.
+fn strip_leading_zeros(bits: &[u8]) -> &[u8] {
+    let start = bits.iter().position(|&bit| bit != 0).unwrap_or(bits.len());
+    &bits[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn add_binary_integers() {
+        assert_eq!(add(&[1, 0, 1, 1], &[1, 1, 1]), vec![1, 0, 0, 1, 0]);
+        assert_eq!(add(&[], &[]), vec![0]);
+        assert_eq!(add(&[1, 1], &[1, 1]), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn sub_binary_integers() {
+        assert_eq!(sub(&[1, 0, 0, 0], &[1]), Ok(vec![0, 1, 1, 1]));
+        assert_eq!(sub(&[0, 1, 1], &[1, 1]), Ok(vec![0, 0, 0]));
+        assert_eq!(sub(&[1], &[1, 0]), Err(Error::new(ErrorKind::Overflow)));
+    }
+
+    #[test]
+    fn mul_binary_integers() {
+        assert_eq!(mul(&[1, 0, 1], &[1, 1]), vec![0, 1, 1, 1, 1]);
+        assert_eq!(mul(&[1, 1], &[]), vec![0, 0]);
+    }
+
+    #[test]
+    fn compare_binary_integers() {
+        assert_eq!(compare(&[0, 0, 1, 0], &[1, 0]), Ordering::Equal);
+        assert_eq!(compare(&[1, 0, 0], &[1, 1]), Ordering::Greater);
+        assert_eq!(compare(&[], &[0]), Ordering::Equal);
+    }
+
+    #[test]
+    fn convert_binary_integers() {
+        assert_eq!(from_u64(0), vec![0]);
+        assert_eq!(from_u64(6), vec![1, 1, 0]);
+        assert_eq!(to_u64(&[]), Ok(0));
+        assert_eq!(to_u64(&[0; 100]), Ok(0));
+        assert_eq!(to_u64(&from_u64(u64::MAX)), Ok(u64::MAX));
+
+        let mut too_large = vec![0; 65];
+        too_large[0] = 1;
+        assert_eq!(to_u64(&too_large), Err(Error::new(ErrorKind::Overflow)));
+    }
+
+    #[quickcheck]
+    fn conversion_roundtrip(value: u64) -> bool {
+        to_u64(&from_u64(value)) == Ok(value)
+    }
+
+    #[quickcheck]
+    fn arithmetic_agrees_with_integers(lhs: u32, rhs: u32) -> bool {
+        let (a, b) = (from_u64(lhs.into()), from_u64(rhs.into()));
+        let (lhs, rhs) = (u64::from(lhs), u64::from(rhs));
+        to_u64(&add(&a, &b)) == Ok(lhs + rhs)
+            && to_u64(&mul(&a, &b)) == Ok(lhs * rhs)
+            && sub(&a, &b).and_then(|bits| to_u64(&bits)).ok() == lhs.checked_sub(rhs)
+            && compare(&a, &b) == lhs.cmp(&rhs)
+    }
+}
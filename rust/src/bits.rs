@@ -1,36 +1,358 @@
 //! Bits manipulation algorithms
 
+use std::fmt;
+
+use crate::{error::ErrorKind, Error};
+
 /// Integer bits addition trait.
-trait Add<const N: usize, const M: usize, Rhs = Self> {
+pub trait Add<const N: usize, const M: usize, Rhs = Self> {
+    /// The resulting type.
     type Output;
+
     /// Add two N-bits binary integer.
     fn add(&self, rhs: Rhs) -> Self::Output;
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct BitArray<const N: usize>([usize; N]);
+/// Integer bits subtraction trait.
+pub trait Sub<Rhs = Self> {
+    /// The resulting type.
+    type Output;
+
+    /// Subtract two N-bits binary integer.
+    fn sub(&self, rhs: Rhs) -> Self::Output;
+}
+
+/// Integer bits multiplication trait.
+pub trait Mul<const N: usize, const M: usize, Rhs = Self> {
+    /// The resulting type.
+    type Output;
+
+    /// Multiply two N-bits binary integer.
+    fn mul(&self, rhs: Rhs) -> Self::Output;
+}
+
+/// BitArray is an N-bits unsigned binary integer.
+///
+/// The bits are stored from the most significant to the least
+/// significant one and each of them is either 0 or 1. Since the bits are
+/// stored in that order, comparing two arrays compares their values.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct BitArray<const N: usize>(pub [usize; N]);
 
 impl<const N: usize, const M: usize> Add<N, M> for BitArray<N> {
     type Output = BitArray<M>;
+
+    /// The sum is right-aligned in the M bits of the result, so `M = N + 1`
+    /// holds every sum while the high bits are dropped if `M <= N`.
     fn add(&self, rhs: Self) -> Self::Output {
         let mut result = BitArray([0; M]);
         let mut carry = 0;
         for index in (0..N).rev() {
             let sum = self.0[index] + rhs.0[index] + carry;
+            if let Some(position) = (M + index).checked_sub(N) {
+                result.0[position] = sum % 2;
+            }
+            carry = sum.div_euclid(2);
+        }
+
+        if carry != 0 && M > N {
+            result.0[M - N - 1] = carry;
+        }
+        result
+    }
+}
+
+impl<const N: usize> Sub for BitArray<N> {
+    type Output = Self;
+
+    /// The difference is computed in two's complement by adding the
+    /// complement of `rhs` plus one, so it wraps around modulo `2^N`.
+    fn sub(&self, rhs: Self) -> Self::Output {
+        let mut result = BitArray([0; N]);
+        let mut carry = 1;
+        for index in (0..N).rev() {
+            let sum = self.0[index] + (1 - rhs.0[index]) + carry;
             result.0[index] = sum % 2;
             carry = sum.div_euclid(2);
         }
+        result
+    }
+}
+
+impl<const N: usize, const M: usize> Mul<N, M> for BitArray<N> {
+    type Output = BitArray<M>;
 
-        if carry != 0 {
-            result.0.as_mut_slice().rotate_right(1);
-            result.0[0] = carry;
+    /// For each bit set in `rhs`, `self` shifted by the position of that
+    /// bit is added to the result. `M = 2N` holds every product while
+    /// the high bits are dropped for smaller M.
+    fn mul(&self, rhs: Self) -> Self::Output {
+        let mut result = BitArray([0; M]);
+        for (shift, bit) in rhs.0.iter().rev().enumerate() {
+            if *bit == 0 {
+                continue;
+            }
+            let mut carry = 0;
+            for position in shift..M {
+                let operand = N
+                    .checked_sub(position - shift + 1)
+                    .map_or(0, |index| self.0[index]);
+                let sum = result.0[M - 1 - position] + operand + carry;
+                result.0[M - 1 - position] = sum % 2;
+                carry = sum.div_euclid(2);
+            }
         }
         result
     }
 }
 
+impl<const N: usize> TryFrom<u64> for BitArray<N> {
+    type Error = Error;
+
+    /// Fails with [`ErrorKind::Overflow`] if the value needs more than N bits.
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if N < u64::BITS as usize && value >> N != 0 {
+            return Err(Error::new(ErrorKind::Overflow));
+        }
+        let mut bits = [0; N];
+        for (position, bit) in bits.iter_mut().rev().enumerate().take(u64::BITS as usize) {
+            *bit = (value >> position & 1) as usize;
+        }
+        Ok(Self(bits))
+    }
+}
+
+impl<const N: usize> TryFrom<BitArray<N>> for u64 {
+    type Error = Error;
+
+    /// Fails with [`ErrorKind::Overflow`] if the value needs more than 64 bits.
+    fn try_from(value: BitArray<N>) -> Result<Self, Self::Error> {
+        let mut result = 0;
+        for (position, bit) in value.0.iter().rev().enumerate() {
+            if *bit == 0 {
+                continue;
+            }
+            if position >= u64::BITS as usize {
+                return Err(Error::new(ErrorKind::Overflow));
+            }
+            result |= 1 << position;
+        }
+        Ok(result)
+    }
+}
+
+/// Returns the number of bits set in `x`.
+///
+/// The bits are counted in parallel: first the number of bits set in
+/// each pair of bits, then in each nibble, then in each byte, and the
+/// byte counts are finally summed up by a multiplication.
+pub const fn popcount(x: u64) -> u32 {
+    let x = x - ((x >> 1) & 0x5555_5555_5555_5555);
+    let x = (x & 0x3333_3333_3333_3333) + ((x >> 2) & 0x3333_3333_3333_3333);
+    let x = (x + (x >> 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    (x.wrapping_mul(0x0101_0101_0101_0101) >> 56) as u32
+}
+
+/// Returns true if the number of bits set in `x` is odd.
+///
+/// Each step folds the upper half of the remaining bits onto the lower
+/// half, so that the lowest bit ends up being the xor of all the bits.
+pub const fn parity(x: u64) -> bool {
+    let mut x = x;
+    x ^= x >> 32;
+    x ^= x >> 16;
+    x ^= x >> 8;
+    x ^= x >> 4;
+    x ^= x >> 2;
+    x ^= x >> 1;
+    x & 1 == 1
+}
+
+/// Returns the position of the lowest bit set in `x`.
+///
+/// `x & -x` isolates the lowest bit set and subtracting one from it sets
+/// exactly the bits below it, which are then counted.
+pub const fn lowest_set_bit(x: u64) -> Option<u32> {
+    if x == 0 {
+        return None;
+    }
+    Some(popcount((x & x.wrapping_neg()) - 1))
+}
+
+/// Returns the position of the highest bit set in `x`.
+///
+/// The highest bit set is first smeared to all the bits below it, which
+/// are then counted.
+pub const fn highest_set_bit(x: u64) -> Option<u32> {
+    if x == 0 {
+        return None;
+    }
+    Some(popcount(smear_right(x)) - 1)
+}
+
+/// Returns true if `x` is a power of two.
+///
+/// Subtracting one from a power of two clears its only bit set and sets
+/// all the bits below it, so the two numbers have no bit in common.
+pub const fn is_power_of_two(x: u64) -> bool {
+    x != 0 && x & (x - 1) == 0
+}
+
+/// Returns the smallest power of two greater than or equal to `x`.
+///
+/// It returns `None` if that power of two does not fit into a `u64`.
+pub const fn next_power_of_two(x: u64) -> Option<u64> {
+    if x <= 1 {
+        return Some(1);
+    }
+    smear_right(x - 1).checked_add(1)
+}
+
+/// Returns `x` with the order of its bits reversed.
+///
+/// Adjacent bits are swapped, then adjacent pairs of bits, then nibbles
+/// and so on until the two halves are swapped.
+pub const fn reverse_bits(x: u64) -> u64 {
+    let x = ((x >> 1) & 0x5555_5555_5555_5555) | ((x & 0x5555_5555_5555_5555) << 1);
+    let x = ((x >> 2) & 0x3333_3333_3333_3333) | ((x & 0x3333_3333_3333_3333) << 2);
+    let x = ((x >> 4) & 0x0f0f_0f0f_0f0f_0f0f) | ((x & 0x0f0f_0f0f_0f0f_0f0f) << 4);
+    let x = ((x >> 8) & 0x00ff_00ff_00ff_00ff) | ((x & 0x00ff_00ff_00ff_00ff) << 8);
+    let x = ((x >> 16) & 0x0000_ffff_0000_ffff) | ((x & 0x0000_ffff_0000_ffff) << 16);
+    x.rotate_left(32)
+}
+
+/// Returns the reflected binary Gray code of `x`.
+///
+/// Consecutive integers have Gray codes which differ by exactly one bit.
+pub const fn gray_encode(x: u64) -> u64 {
+    x ^ (x >> 1)
+}
+
+/// Returns the integer whose reflected binary Gray code is `code`.
+///
+/// Each bit of the integer is the xor of all the bits of the code above
+/// and including it, which is computed with a logarithmic number of shifts.
+pub const fn gray_decode(code: u64) -> u64 {
+    let mut x = code;
+    x ^= x >> 1;
+    x ^= x >> 2;
+    x ^= x >> 4;
+    x ^= x >> 8;
+    x ^= x >> 16;
+    x ^= x >> 32;
+    x
+}
+
+/// BitVec is a growable sequence of bits.
+///
+/// The bits are packed 64 to a word, the first bit in the lowest bit of
+/// the first word.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Creates new empty sequence.
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bits.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the sequence is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a bit to the sequence.
+    pub fn push(&mut self, bit: bool) {
+        let (word, position) = (self.len / 64, self.len % 64);
+        if word == self.words.len() {
+            self.words.push(0);
+        }
+        self.words[word] |= u64::from(bit) << position;
+        self.len += 1;
+    }
+
+    /// Returns the bit at the index, or `None` if the index is out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.len).then(|| self.words[index / 64] >> (index % 64) & 1 == 1)
+    }
+
+    /// Creates an iterator over the bits.
+    pub const fn iter(&self) -> Bits<'_> {
+        Bits {
+            bits: self,
+            index: 0,
+        }
+    }
+}
+
+impl Extend<bool> for BitVec {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for bit in iter {
+            self.push(bit);
+        }
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = Self::new();
+        bits.extend(iter);
+        bits
+    }
+}
+
+impl fmt::Display for BitVec {
+    /// Formats the bits as a string of `0` and `1`, the first bit first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter()
+            .try_for_each(|bit| f.write_str(if bit { "1" } else { "0" }))
+    }
+}
+
+/// Iterator over the bits of a [`BitVec`].
+#[derive(Debug, Clone)]
+pub struct Bits<'a> {
+    bits: &'a BitVec,
+    index: usize,
+}
+
+impl<'a> Iterator for Bits<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.bits.get(self.index)?;
+        self.index += 1;
+        Some(bit)
+    }
+}
+
+/// Sets all the bits below the highest bit set in `x`.
+const fn smear_right(x: u64) -> u64 {
+    let mut x = x;
+    x |= x >> 1;
+    x |= x >> 2;
+    x |= x >> 4;
+    x |= x >> 8;
+    x |= x >> 16;
+    x |= x >> 32;
+    x
+}
+
 #[cfg(test)]
 mod tests {
+    use quickcheck_macros::quickcheck;
+
     use super::*;
 
     #[test]
@@ -48,4 +370,164 @@ mod tests {
         let res = arr1.add(arr2);
         assert_eq!(res, BitArray([1, 0, 1, 0, 1, 0]))
     }
+
+    #[test]
+    fn add_two_bit_array_without_carry_into_larger_length() {
+        let arr1 = BitArray([0, 0, 0, 1, 1]);
+        let arr2 = BitArray([0, 1, 0, 1, 0]);
+        let res = arr1.add(arr2);
+        assert_eq!(res, BitArray([0, 0, 1, 1, 0, 1]))
+    }
+
+    #[test]
+    fn sub_two_bit_array() {
+        let arr1 = BitArray([0, 1, 0, 1, 1]);
+        let arr2 = BitArray([0, 0, 1, 1, 0]);
+        assert_eq!(arr1.sub(arr2), BitArray([0, 0, 1, 0, 1]));
+
+        // The difference wraps around in two's complement.
+        assert_eq!(arr2.sub(arr1), BitArray([1, 1, 0, 1, 1]));
+    }
+
+    #[test]
+    fn mul_two_bit_array() {
+        let arr1 = BitArray([1, 1, 1]);
+        let arr2 = BitArray([1, 0, 1]);
+        let res: BitArray<6> = arr1.mul(arr2);
+        assert_eq!(res, BitArray([1, 0, 0, 0, 1, 1]));
+    }
+
+    #[test]
+    fn compare_bit_array() {
+        assert!(BitArray([0, 1, 1]) < BitArray([1, 0, 0]));
+        assert!(BitArray([1, 0, 1]) > BitArray([1, 0, 0]));
+    }
+
+    #[test]
+    fn convert_bit_array() {
+        assert_eq!(BitArray::<5>::try_from(11), Ok(BitArray([0, 1, 0, 1, 1])));
+        assert_eq!(u64::try_from(BitArray([0, 1, 0, 1, 1])), Ok(11));
+        assert_eq!(
+            BitArray::<3>::try_from(8),
+            Err(Error::new(ErrorKind::Overflow))
+        );
+
+        let mut bits = [0; 70];
+        bits[70 - 65] = 1;
+        assert_eq!(
+            u64::try_from(BitArray(bits)),
+            Err(Error::new(ErrorKind::Overflow))
+        );
+        bits[70 - 65] = 0;
+        bits[69] = 1;
+        assert_eq!(u64::try_from(BitArray(bits)), Ok(1));
+        assert_eq!(BitArray::<70>::try_from(1), Ok(BitArray(bits)));
+    }
+
+    fn bits(value: u16) -> BitArray<16> {
+        BitArray::try_from(u64::from(value)).expect("value fits in 16 bits")
+    }
+
+    #[quickcheck]
+    fn arithmetic_agrees_with_integers(lhs: u16, rhs: u16) -> bool {
+        let sum: BitArray<17> = bits(lhs).add(bits(rhs));
+        let product: BitArray<32> = bits(lhs).mul(bits(rhs));
+        let wrapped_product: BitArray<16> = bits(lhs).mul(bits(rhs));
+        u64::try_from(sum) == Ok(u64::from(lhs) + u64::from(rhs))
+            && bits(lhs).sub(bits(rhs)) == bits(lhs.wrapping_sub(rhs))
+            && u64::try_from(product) == Ok(u64::from(lhs) * u64::from(rhs))
+            && wrapped_product == bits(lhs.wrapping_mul(rhs))
+            && bits(lhs).cmp(&bits(rhs)) == lhs.cmp(&rhs)
+    }
+
+    fn naive_popcount(x: u64) -> u32 {
+        (0..64).filter(|position| x >> position & 1 == 1).count() as u32
+    }
+
+    fn naive_highest_set_bit(x: u64) -> Option<u32> {
+        (0..64).rev().find(|position| x >> position & 1 == 1)
+    }
+
+    fn naive_lowest_set_bit(x: u64) -> Option<u32> {
+        (0..64).find(|position| x >> position & 1 == 1)
+    }
+
+    fn naive_next_power_of_two(x: u64) -> Option<u64> {
+        (0..64)
+            .map(|position| 1 << position)
+            .find(|power| *power >= x)
+    }
+
+    fn naive_reverse_bits(x: u64) -> u64 {
+        (0..64).fold(0, |reversed, position| {
+            reversed | (x >> position & 1) << (63 - position)
+        })
+    }
+
+    fn naive_gray_decode(code: u64) -> u64 {
+        (0..64)
+            .rev()
+            .fold((0, 0), |(x, bit), position| {
+                let bit = bit ^ (code >> position & 1);
+                (x | bit << position, bit)
+            })
+            .0
+    }
+
+    #[test]
+    fn bit_tricks() {
+        assert_eq!(popcount(0b1011_0001), 4);
+        assert!(parity(0b111));
+        assert!(!parity(0b1001));
+        assert_eq!(lowest_set_bit(0b1011_0000), Some(4));
+        assert_eq!(highest_set_bit(0b1011_0000), Some(7));
+        assert_eq!(lowest_set_bit(0), None);
+        assert_eq!(highest_set_bit(0), None);
+        assert!(is_power_of_two(64));
+        assert!(!is_power_of_two(0));
+        assert!(!is_power_of_two(96));
+        assert_eq!(next_power_of_two(0), Some(1));
+        assert_eq!(next_power_of_two(65), Some(128));
+        assert_eq!(next_power_of_two(1 << 63), Some(1 << 63));
+        assert_eq!(next_power_of_two((1 << 63) + 1), None);
+        assert_eq!(reverse_bits(1), 1 << 63);
+        let codes: Vec<_> = (0..8).map(gray_encode).collect();
+        assert_eq!(codes, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+    }
+
+    #[quickcheck]
+    fn bit_tricks_agree_with_naive_versions(x: u64) -> bool {
+        popcount(x) == naive_popcount(x)
+            && parity(x) == (naive_popcount(x) % 2 == 1)
+            && lowest_set_bit(x) == naive_lowest_set_bit(x)
+            && highest_set_bit(x) == naive_highest_set_bit(x)
+            && is_power_of_two(x) == (naive_popcount(x) == 1)
+            && next_power_of_two(x) == naive_next_power_of_two(x)
+            && reverse_bits(x) == naive_reverse_bits(x)
+            && gray_decode(x) == naive_gray_decode(x)
+            && gray_decode(gray_encode(x)) == x
+            && popcount(gray_encode(x) ^ gray_encode(x.wrapping_add(1))) == 1
+    }
+
+    #[quickcheck]
+    fn bit_vec_holds_the_bits(bits: Vec<bool>) -> bool {
+        let vec: BitVec = bits.iter().copied().collect();
+        let text: String = bits
+            .iter()
+            .map(|&bit| if bit { '1' } else { '0' })
+            .collect();
+        vec.len() == bits.len()
+            && vec.iter().eq(bits.iter().copied())
+            && vec.get(bits.len()).is_none()
+            && vec.to_string() == text
+    }
+
+    #[quickcheck]
+    fn power_of_two_tricks(shift: u8, offset: u64) -> bool {
+        let power = 1_u64 << (shift % 64);
+        let x = power.saturating_sub(offset % power);
+        is_power_of_two(power)
+            && next_power_of_two(power) == Some(power)
+            && next_power_of_two(x) == naive_next_power_of_two(x)
+    }
 }
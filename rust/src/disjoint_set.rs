@@ -0,0 +1,344 @@
+//! Disjoint sets.
+//!
+//! A disjoint-set structure maintains a partition of elements into sets,
+//! each identified by one of its elements, its representative. This module
+//! defines the [`DisjointSet`] trait and its two representations of CLRS
+//! chapter 21: linked lists with the weighted-union heuristic, and forests
+//! with union by rank and path compression.
+
+/// DisjointSet is a partition of the elements `0..len` into sets.
+pub trait DisjointSet {
+    /// Adds a new element alone in its set, and returns it.
+    fn make_set(&mut self) -> usize;
+
+    /// Returns the representative of the set containing the element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element was not made.
+    fn find(&mut self, element: usize) -> usize;
+
+    /// Merges the sets containing the two elements.
+    ///
+    /// It returns false if they were already in the same set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either element was not made.
+    fn union(&mut self, x: usize, y: usize) -> bool;
+
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns true if there is no element.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of sets.
+    fn set_count(&self) -> usize;
+
+    /// Returns the number of elements of the set containing the element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element was not made.
+    fn set_size(&mut self, element: usize) -> usize;
+
+    /// Returns true if the two elements are in the same set.
+    fn same_set(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+}
+
+/// DisjointSetList stores each set as a linked list of its elements.
+///
+/// Each element points to its representative, the head of its list, so
+/// `find` takes O(1) time. A union appends the shorter list to the longer
+/// one and updates the representative of the elements moved, so each
+/// element moves O(lg n) times and `m` operations on `n` elements take
+/// O(m + n lg n) time.
+#[derive(Debug, Clone, Default)]
+pub struct DisjointSetList {
+    /// `representatives[x]` is the head of the list of the element `x`.
+    representatives: Vec<usize>,
+
+    /// `next[x]` is the element after `x` in its list.
+    next: Vec<Option<usize>>,
+
+    /// `tails[r]` is the last element of the list whose head is `r`.
+    tails: Vec<usize>,
+
+    /// `sizes[r]` is the length of the list whose head is `r`.
+    sizes: Vec<usize>,
+
+    count: usize,
+}
+
+impl DisjointSetList {
+    /// Creates new structure without elements.
+    pub const fn new() -> Self {
+        Self {
+            representatives: Vec::new(),
+            next: Vec::new(),
+            tails: Vec::new(),
+            sizes: Vec::new(),
+            count: 0,
+        }
+    }
+
+    /// Creates an iterator over the elements of the set containing the
+    /// element, starting with its representative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element was not made.
+    pub fn members(&self, element: usize) -> impl Iterator<Item = usize> + '_ {
+        let head = self.representatives[element];
+        std::iter::successors(Some(head), |&member| self.next[member])
+    }
+}
+
+impl DisjointSet for DisjointSetList {
+    fn make_set(&mut self) -> usize {
+        let element = self.representatives.len();
+        self.representatives.push(element);
+        self.next.push(None);
+        self.tails.push(element);
+        self.sizes.push(1);
+        self.count += 1;
+        element
+    }
+
+    fn find(&mut self, element: usize) -> usize {
+        self.representatives[element]
+    }
+
+    fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut long, mut short) = (self.representatives[x], self.representatives[y]);
+        if long == short {
+            return false;
+        }
+        if self.sizes[long] < self.sizes[short] {
+            std::mem::swap(&mut long, &mut short);
+        }
+
+        let mut member = Some(short);
+        while let Some(element) = member {
+            self.representatives[element] = long;
+            member = self.next[element];
+        }
+        self.next[self.tails[long]] = Some(short);
+        self.tails[long] = self.tails[short];
+        self.sizes[long] += self.sizes[short];
+        self.count -= 1;
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.representatives.len()
+    }
+
+    fn set_count(&self) -> usize {
+        self.count
+    }
+
+    fn set_size(&mut self, element: usize) -> usize {
+        self.sizes[self.representatives[element]]
+    }
+}
+
+/// DisjointSetForest stores each set as a rooted tree whose root is the
+/// representative.
+///
+/// A union makes the root of lower rank, an upper bound of the height of
+/// its tree, a child of the other root, and `find` makes every node on the
+/// path to the root a child of the root. With both heuristics `m`
+/// operations on `n` elements take O(m α(n)) time, where the inverse
+/// Ackermann function α is at most 4 in practice.
+#[derive(Debug, Clone, Default)]
+pub struct DisjointSetForest {
+    /// `parents[x]` is the parent of the element `x`, a root being its own
+    /// parent.
+    parents: Vec<usize>,
+
+    ranks: Vec<u8>,
+
+    /// `sizes[r]` is the number of elements of the tree whose root is `r`.
+    sizes: Vec<usize>,
+
+    count: usize,
+}
+
+impl DisjointSetForest {
+    /// Creates new structure without elements.
+    pub const fn new() -> Self {
+        Self {
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            sizes: Vec::new(),
+            count: 0,
+        }
+    }
+
+    /// Creates new structure with the elements `0..len`, each alone in its
+    /// set.
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            count: len,
+        }
+    }
+}
+
+impl DisjointSet for DisjointSetForest {
+    fn make_set(&mut self) -> usize {
+        let element = self.parents.len();
+        self.parents.push(element);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.count += 1;
+        element
+    }
+
+    fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut node = element;
+        while node != root {
+            node = std::mem::replace(&mut self.parents[node], root);
+        }
+        root
+    }
+
+    fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut high, mut low) = (self.find(x), self.find(y));
+        if high == low {
+            return false;
+        }
+        if self.ranks[high] < self.ranks[low] {
+            std::mem::swap(&mut high, &mut low);
+        }
+        self.parents[low] = high;
+        if self.ranks[high] == self.ranks[low] {
+            self.ranks[high] += 1;
+        }
+        self.sizes[high] += self.sizes[low];
+        self.count -= 1;
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    fn set_count(&self) -> usize {
+        self.count
+    }
+
+    fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Applies the unions to the structure and to a labeling of the
+    /// elements, and returns true if they always agree.
+    fn agrees_with_labels(sets: &mut impl DisjointSet, unions: &[(u8, u8)]) -> bool {
+        let mut labels: Vec<_> = (0..16).collect();
+        while sets.len() < labels.len() {
+            sets.make_set();
+        }
+        unions.iter().all(|&(x, y)| {
+            let (x, y) = (usize::from(x % 16), usize::from(y % 16));
+            let (old, new) = (labels[y], labels[x]);
+            labels
+                .iter_mut()
+                .filter(|label| **label == old)
+                .for_each(|label| *label = new);
+            let mut distinct = labels.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+
+            sets.union(x, y) == (old != new)
+                && sets.set_count() == distinct.len()
+                && (0..16).all(|element| {
+                    let size = labels
+                        .iter()
+                        .filter(|&&label| label == labels[element])
+                        .count();
+                    sets.same_set(x, element) == (labels[x] == labels[element])
+                        && sets.set_size(element) == size
+                })
+        })
+    }
+
+    #[test]
+    fn clrs_connected_components() {
+        // CLRS figure 21.1, with the vertices a to j numbered from 0.
+        let edges = [(1, 3), (4, 6), (0, 2), (7, 8), (0, 1), (4, 5), (1, 2)];
+        let mut list = DisjointSetList::new();
+        let mut forest = DisjointSetForest::new();
+        for _ in 0..10 {
+            list.make_set();
+            forest.make_set();
+        }
+        for (u, v) in edges {
+            list.union(u, v);
+            forest.union(u, v);
+        }
+        assert_eq!(list.set_count(), 4);
+        assert_eq!(forest.set_count(), 4);
+        assert_eq!(list.set_size(3), 4);
+        assert_eq!(forest.set_size(5), 3);
+        assert!(forest.same_set(0, 3));
+        assert!(!list.same_set(0, 9));
+
+        let mut members: Vec<_> = list.members(6).collect();
+        members.sort_unstable();
+        assert_eq!(members, vec![4, 5, 6]);
+        assert_eq!(list.members(9).collect::<Vec<_>>(), vec![9]);
+    }
+
+    #[test]
+    fn union_by_rank_and_path_compression() {
+        let mut forest = DisjointSetForest::with_len(8);
+        for (x, y) in [(0, 1), (2, 3), (0, 2), (4, 5), (6, 7), (4, 6), (0, 4)] {
+            assert!(forest.union(x, y));
+        }
+        assert!(!forest.union(3, 7));
+        assert_eq!(forest.ranks.iter().max(), Some(&3));
+        let root = forest.find(7);
+        assert!(forest
+            .parents
+            .iter()
+            .all(|&parent| parent == root || forest.parents[parent] == root));
+        assert_eq!(forest.set_size(1), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn find_unknown_element() {
+        DisjointSetForest::with_len(2).find(2);
+    }
+
+    #[quickcheck]
+    fn list_agrees_with_labels(unions: Vec<(u8, u8)>) -> bool {
+        agrees_with_labels(&mut DisjointSetList::new(), &unions)
+    }
+
+    #[quickcheck]
+    fn forest_agrees_with_labels(unions: Vec<(u8, u8)>) -> bool {
+        agrees_with_labels(&mut DisjointSetForest::new(), &unions)
+    }
+}
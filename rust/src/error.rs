@@ -3,6 +3,8 @@
 use std::error::Error as StdError;
 use std::fmt;
 
+use crate::graph::VertexId;
+
 /// Error type.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Error {
@@ -15,6 +17,11 @@ impl Error {
     pub const fn new(kind: ErrorKind) -> Self {
         Self { kind }
     }
+
+    /// Returns the type of error.
+    pub const fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
 }
 
 impl StdError for Error {
@@ -37,6 +44,44 @@ pub enum ErrorKind {
     QueueUnderflow,
     /// This error type occurs when adding an item to a full queue.
     QueueOverflow,
+    /// This error type occurs when decoding a malformed tree encoding.
+    InvalidTreeEncoding,
+    /// This error type occurs when the result of an arithmetic operation
+    /// does not fit into its type.
+    Overflow,
+    /// This error type occurs when dividing by zero.
+    DivisionByZero,
+    /// This error type occurs when parsing a malformed number.
+    InvalidNumber,
+    /// This error type occurs when a key is given more than once.
+    DuplicateKey,
+    /// This error type occurs when a key is outside the supported range.
+    InvalidKey,
+    /// This error type occurs when accessing an index past the end of a
+    /// container.
+    IndexOutOfBounds,
+    /// This error type occurs when the start of a range is after its end.
+    InvalidRange,
+    /// This error type occurs when parsing or evaluating a malformed
+    /// expression.
+    InvalidExpression,
+    /// This error type occurs when an algorithm does not finish within the
+    /// maximum number of iterations it was given.
+    IterationLimit,
+    /// This error type occurs when a graph has a cycle where none is
+    /// allowed.
+    CycleDetected,
+    /// This error type occurs when a graph has a cycle of negative weight,
+    /// so some shortest paths are undefined. It holds the vertices of the
+    /// cycle in order.
+    NegativeCycle(Vec<VertexId>),
+    /// This error type occurs when parsing a malformed edge list.
+    InvalidEdgeList,
+    /// This error type occurs when parsing a malformed adjacency list.
+    InvalidAdjacencyList,
+    /// This error type occurs when decoding bits which are not a sequence
+    /// of codewords of a prefix code.
+    InvalidCode,
 }
 
 impl fmt::Display for ErrorKind {
@@ -44,6 +89,21 @@ impl fmt::Display for ErrorKind {
         let s = match self {
             Self::QueueOverflow => "failed to enqueue a new element to already full queue.",
             Self::QueueUnderflow => "cannot dequeue element from an empty queue",
+            Self::InvalidTreeEncoding => "invalid binary tree encoding",
+            Self::Overflow => "arithmetic operation overflowed",
+            Self::DivisionByZero => "attempt to divide by zero",
+            Self::InvalidNumber => "invalid number literal",
+            Self::DuplicateKey => "duplicate key",
+            Self::InvalidKey => "key is out of range",
+            Self::IndexOutOfBounds => "index out of bounds",
+            Self::InvalidRange => "range start is greater than range end",
+            Self::InvalidExpression => "invalid expression",
+            Self::IterationLimit => "iteration limit exceeded",
+            Self::CycleDetected => "graph has a cycle",
+            Self::NegativeCycle(_) => "graph has a negative-weight cycle",
+            Self::InvalidEdgeList => "invalid edge list",
+            Self::InvalidAdjacencyList => "invalid adjacency list",
+            Self::InvalidCode => "invalid prefix code",
         };
         write!(f, "{}", s)
     }
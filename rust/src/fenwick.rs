@@ -0,0 +1,286 @@
+//! Fenwick trees.
+//!
+//! A Fenwick tree, or binary indexed tree, maintains the prefix sums of an
+//! array under point updates. The entry at the 1-based index `i` holds the
+//! sum of the `i & -i` items ending at `i`, so both an update and a prefix
+//! sum query touch O(log n) entries.
+
+use std::ops::{Add, Range, Sub};
+
+use crate::matrix::Matrix;
+
+/// Returns the value of the lowest set bit of the index.
+const fn lowest_bit(index: usize) -> usize {
+    index & index.wrapping_neg()
+}
+
+/// FenwickTree maintains the prefix sums of an array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FenwickTree<T> {
+    /// The partial sums, indexed from 1.
+    tree: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates new tree over `len` items equal to `Default::default()`.
+    pub fn new(len: usize) -> Self {
+        Self {
+            tree: vec![T::default(); len + 1],
+        }
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// Returns true if there is no item.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `delta` to the item at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn add(&mut self, index: usize, delta: T) {
+        assert!(index < self.len(), "index is out of bounds");
+        let mut index = index + 1;
+        while index < self.tree.len() {
+            self.tree[index] = self.tree[index] + delta;
+            index += lowest_bit(index);
+        }
+    }
+
+    /// Returns the sum of the first `end` items.
+    pub fn prefix_sum(&self, end: usize) -> T {
+        let mut index = end.min(self.len());
+        let mut sum = T::default();
+        while index > 0 {
+            sum = sum + self.tree[index];
+            index -= lowest_bit(index);
+        }
+        sum
+    }
+
+    /// Returns the sum of the items in the given range.
+    pub fn range_sum(&self, range: Range<usize>) -> T {
+        if range.start >= range.end {
+            return T::default();
+        }
+        self.prefix_sum(range.end) - self.prefix_sum(range.start)
+    }
+}
+
+impl<T> From<&[T]> for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates new tree over the items in linear time.
+    ///
+    /// Each entry is pushed to the next entry which covers it, instead of
+    /// adding every item one by one.
+    fn from(items: &[T]) -> Self {
+        let mut tree = vec![T::default(); items.len() + 1];
+        tree[1..].copy_from_slice(items);
+        for index in 1..tree.len() {
+            let parent = index + lowest_bit(index);
+            if parent < tree.len() {
+                tree[parent] = tree[parent] + tree[index];
+            }
+        }
+        Self { tree }
+    }
+}
+
+/// FenwickTree2D maintains the prefix sums of a matrix.
+///
+/// The entry at `(i, j)` holds the sum of the items in the rectangle of
+/// `i & -i` rows and `j & -j` columns ending at `(i, j)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FenwickTree2D<T> {
+    /// The partial sums, indexed from 1 in both dimensions.
+    tree: Matrix<T>,
+}
+
+impl<T> FenwickTree2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates new tree over a matrix of items equal to `Default::default()`.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            tree: Matrix::new(rows + 1, columns + 1),
+        }
+    }
+
+    /// Returns the number of rows.
+    pub const fn rows(&self) -> usize {
+        self.tree.rows() - 1
+    }
+
+    /// Returns the number of columns.
+    pub const fn columns(&self) -> usize {
+        self.tree.columns() - 1
+    }
+
+    /// Adds `delta` to the item at the given row and column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row or the column is out of bounds.
+    pub fn add(&mut self, row: usize, column: usize, delta: T) {
+        assert!(
+            row < self.rows() && column < self.columns(),
+            "index is out of bounds"
+        );
+        let mut i = row + 1;
+        while i < self.tree.rows() {
+            let mut j = column + 1;
+            while j < self.tree.columns() {
+                self.tree[(i, j)] = self.tree[(i, j)] + delta;
+                j += lowest_bit(j);
+            }
+            i += lowest_bit(i);
+        }
+    }
+
+    /// Returns the sum of the items in the first `rows` rows and the first
+    /// `columns` columns.
+    pub fn prefix_sum(&self, rows: usize, columns: usize) -> T {
+        let mut sum = T::default();
+        let mut i = rows.min(self.rows());
+        while i > 0 {
+            let mut j = columns.min(self.columns());
+            while j > 0 {
+                sum = sum + self.tree[(i, j)];
+                j -= lowest_bit(j);
+            }
+            i -= lowest_bit(i);
+        }
+        sum
+    }
+
+    /// Returns the sum of the items in the given rectangle.
+    ///
+    /// The sum is obtained from four prefix sums by inclusion-exclusion.
+    pub fn rect_sum(&self, rows: Range<usize>, columns: Range<usize>) -> T {
+        if rows.start >= rows.end || columns.start >= columns.end {
+            return T::default();
+        }
+        self.prefix_sum(rows.end, columns.end)
+            - self.prefix_sum(rows.start, columns.end)
+            - self.prefix_sum(rows.end, columns.start)
+            + self.prefix_sum(rows.start, columns.start)
+    }
+}
+
+impl<T> From<&Matrix<T>> for FenwickTree2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(items: &Matrix<T>) -> Self {
+        let mut tree = Self::new(items.rows(), items.columns());
+        for row in 0..items.rows() {
+            for (column, &item) in items.row(row).iter().enumerate() {
+                tree.add(row, column, item);
+            }
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn prefix_sums() {
+        let mut tree = FenwickTree::from(&[3, 2, -1, 6, 5, 4, -3, 3][..]);
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.prefix_sum(0), 0);
+        assert_eq!(tree.prefix_sum(4), 10);
+        assert_eq!(tree.prefix_sum(100), 19);
+        assert_eq!(tree.range_sum(2..6), 14);
+        assert_eq!(tree.range_sum(6..6), 0);
+
+        tree.add(2, 10);
+        assert_eq!(tree.range_sum(2..6), 24);
+        assert_eq!(tree, {
+            let mut other = FenwickTree::new(8);
+            for (index, item) in [3, 2, 9, 6, 5, 4, -3, 3].into_iter().enumerate() {
+                other.add(index, item);
+            }
+            other
+        });
+    }
+
+    #[test]
+    fn rectangle_sums() {
+        let image = Matrix::from_rows(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12],
+        ])
+        .expect("rows have the same length");
+        let mut tree = FenwickTree2D::from(&image);
+        assert_eq!((tree.rows(), tree.columns()), (3, 4));
+        assert_eq!(tree.prefix_sum(3, 4), 78);
+        assert_eq!(tree.rect_sum(1..3, 1..3), 34);
+        assert_eq!(tree.rect_sum(0..1, 0..4), 10);
+        assert_eq!(tree.rect_sum(2..2, 0..4), 0);
+
+        tree.add(1, 2, -7);
+        assert_eq!(tree.rect_sum(1..3, 1..3), 27);
+        assert_eq!(tree.prefix_sum(2, 3), 17);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn add_out_of_bounds() {
+        FenwickTree2D::<i32>::new(2, 2).add(0, 2, 1);
+    }
+
+    #[quickcheck]
+    fn rect_sum_agrees_with_naive_sum(
+        items: Vec<i8>,
+        updates: Vec<(u8, u8, i8)>,
+        rows: (u8, u8),
+        columns: (u8, u8),
+    ) -> bool {
+        const COLUMNS: usize = 5;
+        let height = items.len() / COLUMNS;
+        let mut matrix = Matrix::from_rows(
+            items
+                .chunks_exact(COLUMNS)
+                .map(|row| row.iter().map(|&item| i64::from(item)).collect())
+                .collect(),
+        )
+        .expect("rows have the same length");
+        let mut tree = FenwickTree2D::from(&matrix);
+        if height == 0 {
+            return tree.prefix_sum(10, 10) == 0;
+        }
+
+        for (row, column, delta) in updates {
+            let (row, column) = (usize::from(row) % height, usize::from(column) % COLUMNS);
+            matrix[(row, column)] += i64::from(delta);
+            tree.add(row, column, i64::from(delta));
+        }
+
+        let rows = usize::from(rows.0) % height..usize::from(rows.1) % (height + 1);
+        let columns = usize::from(columns.0) % COLUMNS..usize::from(columns.1) % (COLUMNS + 1);
+        let naive: i64 = rows
+            .clone()
+            .flat_map(|row| columns.clone().map(move |column| (row, column)))
+            .map(|index| matrix[index])
+            .sum();
+        tree.rect_sum(rows, columns) == naive
+    }
+}
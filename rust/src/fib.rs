@@ -0,0 +1,345 @@
+//! Fibonacci numbers.
+//!
+//! The Fibonacci numbers are defined by `F(0) = 0`, `F(1) = 1` and
+//! `F(n) = F(n - 1) + F(n - 2)`.
+
+#[cfg(feature = "bigint")]
+use crate::numbers::BigUint;
+use crate::{error::ErrorKind, Error};
+
+/// Rank of the largest Fibonacci number which fits into a `u64`.
+const MAX_RANK: usize = 93;
+
+/// The `Fib` type computes Fibonacci numbers.
+///
+/// The numbers are computed bottom-up and cached, so computing a rank
+/// only costs the steps beyond the largest rank computed so far.
+#[derive(Debug, Clone)]
+pub struct Fib {
+    /// `cache[n]` is `F(n)`, wrapped around beyond `F(93)`.
+    cache: Vec<u64>,
+}
+
+impl Fib {
+    /// Creates new Fibonacci calculator.
+    pub fn new() -> Self {
+        Self { cache: vec![0, 1] }
+    }
+
+    /// Returns the n-th Fibonacci number.
+    ///
+    /// `F(93)` is the largest Fibonacci number which fits into a `u64` and
+    /// larger ranks wrap around. Use [`Fib::checked_compute`] to detect
+    /// the overflow, or `Fib::compute_big` from the `bigint` feature to
+    /// compute larger ranks. Every rank up to `n` is cached, so it takes
+    /// O(n) space.
+    pub fn compute(&mut self, n: usize) -> u64 {
+        while self.cache.len() <= n {
+            let len = self.cache.len();
+            let next = self.cache[len - 2].wrapping_add(self.cache[len - 1]);
+            self.cache.push(next);
+        }
+        self.cache[n]
+    }
+
+    /// Returns the n-th Fibonacci number.
+    ///
+    /// It fails with [`ErrorKind::Overflow`] if the number does not fit
+    /// into a `u64`.
+    pub fn checked_compute(&mut self, n: usize) -> Result<u64, Error> {
+        if n > MAX_RANK {
+            return Err(Error::new(ErrorKind::Overflow));
+        }
+        Ok(self.compute(n))
+    }
+
+    /// Creates an infinite iterator over the Fibonacci numbers.
+    ///
+    /// The iterator yields `F(0)`, `F(1)`, ... and like [`Fib::compute`],
+    /// the numbers beyond `F(93)` wrap around. Use `Fib::big_iter` from the
+    /// `bigint` feature to iterate over the exact numbers.
+    pub const fn iter() -> FibIter {
+        FibIter {
+            previous: 1,
+            current: 0,
+        }
+    }
+
+    /// Creates an infinite iterator over the Fibonacci numbers with
+    /// arbitrary precision.
+    #[cfg(feature = "bigint")]
+    pub fn big_iter() -> BigFibIter {
+        BigFibIter {
+            previous: BigUint::from(1_u64),
+            current: BigUint::zero(),
+        }
+    }
+
+    /// Returns the n-th Fibonacci number by following the recurrence
+    /// naively.
+    ///
+    /// Both subproblems are solved again at each call, so it takes time
+    /// proportional to `F(n)` itself. It fails with [`ErrorKind::Overflow`]
+    /// if the number does not fit into a `u64`.
+    pub fn compute_naive(n: usize) -> Result<u64, Error> {
+        if n > MAX_RANK {
+            return Err(Error::new(ErrorKind::Overflow));
+        }
+        fn naive(n: usize) -> u64 {
+            if n < 2 {
+                return n as u64;
+            }
+            naive(n - 1) + naive(n - 2)
+        }
+        Ok(naive(n))
+    }
+
+    /// Returns the n-th Fibonacci number by memoized recursion.
+    ///
+    /// This is the top-down counterpart of [`Fib::checked_compute`]: each
+    /// subproblem is solved once, then looked up, so it takes O(n) time.
+    /// It fails with [`ErrorKind::Overflow`] if the number does not fit
+    /// into a `u64`.
+    pub fn compute_memoized(n: usize) -> Result<u64, Error> {
+        if n > MAX_RANK {
+            return Err(Error::new(ErrorKind::Overflow));
+        }
+        fn memoized(n: usize, memo: &mut [Option<u64>]) -> u64 {
+            if let Some(value) = memo[n] {
+                return value;
+            }
+            let value = if n < 2 {
+                n as u64
+            } else {
+                memoized(n - 1, memo) + memoized(n - 2, memo)
+            };
+            memo[n] = Some(value);
+            value
+        }
+        Ok(memoized(n, &mut vec![None; n + 1]))
+    }
+
+    /// Returns the n-th Fibonacci number by matrix exponentiation.
+    ///
+    /// The matrix `[[1, 1], [1, 0]]` raised to the power `k` is
+    /// `[[F(k + 1), F(k)], [F(k), F(k - 1)]]`, and raising it to the power
+    /// `n - 1` by repeated squaring takes O(log n) matrix products. No
+    /// intermediate entry exceeds `F(n)`, so it fails with
+    /// [`ErrorKind::Overflow`] exactly when the number does not fit into a
+    /// `u128`, that is beyond `F(186)`.
+    pub fn compute_matrix(n: usize) -> Result<u128, Error> {
+        type Matrix = [[u128; 2]; 2];
+        fn multiply(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+            let mut product = [[0; 2]; 2];
+            for (i, row) in product.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
+                    *entry = a[i][0]
+                        .checked_mul(b[0][j])
+                        .zip(a[i][1].checked_mul(b[1][j]))
+                        .and_then(|(x, y)| x.checked_add(y))
+                        .ok_or_else(|| Error::new(ErrorKind::Overflow))?;
+                }
+            }
+            Ok(product)
+        }
+
+        let Some(mut exponent) = n.checked_sub(1) else {
+            return Ok(0);
+        };
+        let mut power: Matrix = [[1, 0], [0, 1]];
+        let mut base: Matrix = [[1, 1], [1, 0]];
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = multiply(&power, &base)?;
+            }
+            exponent >>= 1;
+            // The last square would not be used, and may overflow.
+            if exponent > 0 {
+                base = multiply(&base, &base)?;
+            }
+        }
+        Ok(power[0][0])
+    }
+
+    /// Computes the n-th Fibonacci number with arbitrary precision.
+    #[cfg(feature = "bigint")]
+    pub fn compute_big(n: usize) -> BigUint {
+        let (mut previous, mut current) = (BigUint::from(1_u64), BigUint::zero());
+        for _ in 0..n {
+            let next = &previous + &current;
+            previous = current;
+            current = next;
+        }
+        current
+    }
+}
+
+impl Default for Fib {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the Fibonacci numbers, wrapped around beyond `F(93)`.
+#[derive(Debug, Clone)]
+pub struct FibIter {
+    /// The number preceding `current`.
+    previous: u64,
+
+    /// The next number to yield.
+    current: u64,
+}
+
+impl Iterator for FibIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current;
+        self.current = self.previous.wrapping_add(current);
+        self.previous = current;
+        Some(current)
+    }
+}
+
+/// Iterator over the Fibonacci numbers with arbitrary precision.
+#[cfg(feature = "bigint")]
+#[derive(Debug, Clone)]
+pub struct BigFibIter {
+    /// The number preceding `current`.
+    previous: BigUint,
+
+    /// The next number to yield.
+    current: BigUint,
+}
+
+#[cfg(feature = "bigint")]
+impl Iterator for BigFibIter {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = &self.previous + &self.current;
+        self.previous = std::mem::replace(&mut self.current, next);
+        Some(self.previous.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_fibonacci() {
+        let mut fib = Fib::new();
+        let values: Vec<_> = (0..10).map(|n| fib.compute(n)).collect();
+        assert_eq!(values, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+        assert_eq!(fib.compute(5), 5);
+        assert_eq!(fib.compute(94), 1_293_530_146_158_671_551);
+        assert_eq!(fib.cache.len(), 95);
+        assert_eq!(fib.compute(94), 1_293_530_146_158_671_551);
+        assert_eq!(fib.cache.len(), 95);
+    }
+
+    #[test]
+    fn checked_compute_detects_overflow() {
+        let mut fib = Fib::new();
+        assert_eq!(fib.checked_compute(0), Ok(0));
+        assert_eq!(fib.checked_compute(93), Ok(12_200_160_415_121_876_738));
+        assert_eq!(
+            fib.checked_compute(94),
+            Err(Error::new(ErrorKind::Overflow))
+        );
+        assert_eq!(fib.checked_compute(92), Ok(7_540_113_804_746_346_429));
+    }
+
+    #[test]
+    fn iterate_fibonacci() {
+        let values: Vec<_> = Fib::iter().take(10).collect();
+        assert_eq!(values, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+
+        let even_sum: u64 = Fib::iter()
+            .take_while(|n| *n < 4_000_000)
+            .filter(|n| n % 2 == 0)
+            .sum();
+        assert_eq!(even_sum, 4_613_732);
+
+        let mut fib = Fib::new();
+        assert!(Fib::iter()
+            .take(300)
+            .enumerate()
+            .all(|(n, value)| fib.compute(n) == value));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn iterate_big_fibonacci() {
+        assert!(Fib::big_iter()
+            .take(300)
+            .enumerate()
+            .all(|(n, value)| Fib::compute_big(n) == value));
+        assert_eq!(
+            Fib::big_iter().nth(94).map(|value| value.to_string()),
+            Some("19740274219868223167".to_string())
+        );
+    }
+
+    #[test]
+    fn recursive_and_matrix_variants() {
+        assert!(Fib::iter().take(25).enumerate().all(|(n, value)| {
+            Fib::compute_naive(n) == Ok(value) && Fib::compute_memoized(n) == Ok(value)
+        }));
+        assert!(Fib::iter()
+            .take(MAX_RANK + 1)
+            .enumerate()
+            .all(|(n, value)| Fib::compute_matrix(n) == Ok(u128::from(value))));
+        assert_eq!(
+            Fib::compute_memoized(MAX_RANK),
+            Ok(12_200_160_415_121_876_738)
+        );
+        assert_eq!(
+            Fib::compute_naive(MAX_RANK + 1),
+            Err(Error::new(ErrorKind::Overflow))
+        );
+        assert_eq!(
+            Fib::compute_memoized(MAX_RANK + 1),
+            Err(Error::new(ErrorKind::Overflow))
+        );
+    }
+
+    #[test]
+    fn matrix_variant_fits_into_u128() {
+        assert_eq!(
+            Fib::compute_matrix(186),
+            Ok(332_825_110_087_067_562_321_196_029_789_634_457_848)
+        );
+        assert_eq!(
+            Fib::compute_matrix(187),
+            Err(Error::new(ErrorKind::Overflow))
+        );
+        assert_eq!(
+            Fib::compute_matrix(150),
+            Ok(9_969_216_677_189_303_386_214_405_760_200)
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn compute_big_fibonacci() {
+        assert_eq!(Fib::compute_big(0), BigUint::zero());
+        assert_eq!(Fib::compute_big(1), BigUint::from(1_u64));
+        assert_eq!(Fib::compute_big(93).to_string(), "12200160415121876738");
+        assert_eq!(Fib::compute_big(94).to_string(), "19740274219868223167");
+        assert_eq!(
+            Fib::compute_big(200).to_string(),
+            "280571172992510140037611932413038677189525"
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn compute_big_agrees_with_compute() {
+        let mut fib = Fib::new();
+        for n in 0..=MAX_RANK {
+            assert_eq!(Fib::compute_big(n), BigUint::from(fib.compute(n)));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Computational geometry.
+//!
+//! This module defines points and axis-aligned rectangles in a
+//! k-dimensional space, and the spatial data structures which index them.
+
+pub mod kd_tree;
+pub mod quadtree;
+
+/// Point is a point in a K-dimensional space.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Point<const K: usize>(pub [f64; K]);
+
+impl<const K: usize> Point<K> {
+    /// Creates new point from its coordinates.
+    pub const fn new(coordinates: [f64; K]) -> Self {
+        Self(coordinates)
+    }
+
+    /// Returns the origin of the space.
+    pub const fn origin() -> Self {
+        Self([0.0; K])
+    }
+
+    /// Returns the squared Euclidean distance between the two points.
+    ///
+    /// Comparing squared distances gives the same order as comparing
+    /// distances, without computing any square root.
+    pub fn distance_squared(&self, other: &Self) -> f64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum()
+    }
+
+    /// Returns the Euclidean distance between the two points.
+    pub fn distance(&self, other: &Self) -> f64 {
+        self.distance_squared(other).sqrt()
+    }
+}
+
+impl<const K: usize> Default for Point<K> {
+    fn default() -> Self {
+        Self::origin()
+    }
+}
+
+/// Rect is an axis-aligned rectangle in a K-dimensional space.
+///
+/// The rectangle is closed: it contains the points on its boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect<const K: usize> {
+    /// The corner with the smallest coordinates.
+    pub min: Point<K>,
+
+    /// The corner with the largest coordinates.
+    pub max: Point<K>,
+}
+
+impl<const K: usize> Rect<K> {
+    /// Creates new rectangle from two opposite corners.
+    pub fn new(a: Point<K>, b: Point<K>) -> Self {
+        let mut min = a;
+        let mut max = b;
+        for axis in 0..K {
+            min.0[axis] = a.0[axis].min(b.0[axis]);
+            max.0[axis] = a.0[axis].max(b.0[axis]);
+        }
+        Self { min, max }
+    }
+
+    /// Returns true if the point lies in the rectangle.
+    pub fn contains(&self, point: &Point<K>) -> bool {
+        (0..K).all(|axis| self.min.0[axis] <= point.0[axis] && point.0[axis] <= self.max.0[axis])
+    }
+
+    /// Returns true if the two rectangles have at least one point in common.
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..K).all(|axis| {
+            self.min.0[axis] <= other.max.0[axis] && other.min.0[axis] <= self.max.0[axis]
+        })
+    }
+
+    /// Returns the squared distance between the point and the closest point
+    /// of the rectangle.
+    pub fn distance_squared(&self, point: &Point<K>) -> f64 {
+        (0..K)
+            .map(|axis| {
+                let closest = point.0[axis].clamp(self.min.0[axis], self.max.0[axis]);
+                (point.0[axis] - closest) * (point.0[axis] - closest)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_distance() {
+        let a = Point::new([0.0, 0.0]);
+        let b = Point::new([3.0, 4.0]);
+        assert_eq!(a.distance_squared(&b), 25.0);
+        assert_eq!(b.distance(&a), 5.0);
+    }
+
+    #[test]
+    fn rect_queries() {
+        let rect = Rect::new(Point::new([2.0, 0.0]), Point::new([0.0, 2.0]));
+        assert_eq!(rect.min, Point::new([0.0, 0.0]));
+        assert!(rect.contains(&Point::new([2.0, 1.0])));
+        assert!(!rect.contains(&Point::new([2.5, 1.0])));
+        assert_eq!(rect.distance_squared(&Point::new([1.0, 1.0])), 0.0);
+        assert_eq!(rect.distance_squared(&Point::new([5.0, 6.0])), 25.0);
+        assert!(rect.intersects(&Rect::new(Point::new([2.0, 2.0]), Point::new([3.0, 3.0]))));
+        assert!(!rect.intersects(&Rect::new(Point::new([2.1, 0.0]), Point::new([3.0, 3.0]))));
+    }
+}
@@ -0,0 +1,222 @@
+//! K-dimensional tree.
+//!
+//! A k-d tree is a binary search tree over points where the nodes at depth
+//! `d` split the space along the axis `d mod K`: the points of the left
+//! subtree have a smaller coordinate on that axis than the node, and the
+//! points of the right subtree have a larger or equal one.
+
+use super::{Point, Rect};
+use crate::stack::DummyStack;
+
+/// Node of a [`KdTree`].
+#[derive(Debug, Clone)]
+struct KdNode<const K: usize> {
+    point: Point<K>,
+    left: Option<Box<KdNode<K>>>,
+    right: Option<Box<KdNode<K>>>,
+}
+
+/// KdTree indexes points of a K-dimensional space.
+#[derive(Debug, Clone, Default)]
+pub struct KdTree<const K: usize> {
+    root: Option<Box<KdNode<K>>>,
+    len: usize,
+}
+
+impl<const K: usize> KdTree<K> {
+    /// Creates new empty tree.
+    pub const fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// Returns the number of points in the tree.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a point into the tree.
+    pub fn insert(&mut self, point: Point<K>) {
+        let mut link = &mut self.root;
+        let mut axis = 0;
+        while let Some(node) = link {
+            link = if point.0[axis] < node.point.0[axis] {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+            axis = (axis + 1) % K;
+        }
+        *link = Some(Box::new(KdNode {
+            point,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+    }
+
+    /// Returns the point of the tree closest to the given point.
+    ///
+    /// The subtree on the side of the splitting plane containing the point
+    /// is searched first. The other subtree is only searched if the plane
+    /// is closer than the best point found so far.
+    pub fn nearest(&self, point: &Point<K>) -> Option<Point<K>> {
+        fn search<const K: usize>(
+            node: Option<&KdNode<K>>,
+            point: &Point<K>,
+            axis: usize,
+            best: &mut Option<(f64, Point<K>)>,
+        ) {
+            let Some(node) = node else {
+                return;
+            };
+            let distance = node.point.distance_squared(point);
+            if best.map_or(true, |(best, _)| distance < best) {
+                *best = Some((distance, node.point));
+            }
+
+            let offset = point.0[axis] - node.point.0[axis];
+            let (near, far) = if offset < 0.0 {
+                (&node.left, &node.right)
+            } else {
+                (&node.right, &node.left)
+            };
+            let next = (axis + 1) % K;
+            search(near.as_deref(), point, next, best);
+            if best.map_or(true, |(best, _)| offset * offset < best) {
+                search(far.as_deref(), point, next, best);
+            }
+        }
+
+        let mut best = None;
+        search(self.root.as_deref(), point, 0, &mut best);
+        best.map(|(_, point)| point)
+    }
+
+    /// Returns the points of the tree which lie in the rectangle.
+    ///
+    /// A subtree is skipped when the rectangle lies entirely on the other
+    /// side of its splitting plane.
+    pub fn range_search(&self, rect: &Rect<K>) -> Vec<Point<K>> {
+        let mut points = Vec::new();
+        let mut stack = DummyStack::new();
+        stack.push((self.root.as_deref(), 0));
+        while let Some((node, axis)) = stack.pop() {
+            let Some(node) = node else {
+                continue;
+            };
+            if rect.contains(&node.point) {
+                points.push(node.point);
+            }
+            let next = (axis + 1) % K;
+            if rect.min.0[axis] < node.point.0[axis] {
+                stack.push((node.left.as_deref(), next));
+            }
+            if rect.max.0[axis] >= node.point.0[axis] {
+                stack.push((node.right.as_deref(), next));
+            }
+        }
+        points
+    }
+}
+
+impl<const K: usize> FromIterator<Point<K>> for KdTree<K> {
+    fn from_iter<I: IntoIterator<Item = Point<K>>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for point in iter {
+            tree.insert(point);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn points(coordinates: &[(i8, i8)]) -> Vec<Point<2>> {
+        coordinates
+            .iter()
+            .map(|&(x, y)| Point::new([f64::from(x), f64::from(y)]))
+            .collect()
+    }
+
+    fn sorted(mut points: Vec<Point<2>>) -> Vec<Point<2>> {
+        points.sort_by(|a, b| a.partial_cmp(b).expect("coordinates are not NaN"));
+        points
+    }
+
+    #[test]
+    fn nearest_neighbor() {
+        let tree: KdTree<2> = points(&[(2, 3), (5, 4), (9, 6), (4, 7), (8, 1), (7, 2)])
+            .into_iter()
+            .collect();
+        assert_eq!(tree.len(), 6);
+        assert_eq!(
+            tree.nearest(&Point::new([9.0, 2.0])),
+            Some(Point::new([8.0, 1.0]))
+        );
+        assert_eq!(
+            tree.nearest(&Point::new([3.0, 6.0])),
+            Some(Point::new([4.0, 7.0]))
+        );
+        assert_eq!(KdTree::<3>::new().nearest(&Point::default()), None);
+    }
+
+    #[test]
+    fn range_search() {
+        let tree: KdTree<2> = points(&[(2, 3), (5, 4), (9, 6), (4, 7), (8, 1), (7, 2)])
+            .into_iter()
+            .collect();
+        let rect = Rect::new(Point::new([4.0, 1.0]), Point::new([8.0, 4.0]));
+        assert_eq!(
+            sorted(tree.range_search(&rect)),
+            points(&[(5, 4), (7, 2), (8, 1)])
+        );
+    }
+
+    #[test]
+    fn three_dimensional_points() {
+        let tree: KdTree<3> = [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [0.0, 0.0, 5.0]]
+            .into_iter()
+            .map(Point::new)
+            .collect();
+        assert_eq!(
+            tree.nearest(&Point::new([0.0, 0.0, 4.0])),
+            Some(Point::new([0.0, 0.0, 5.0]))
+        );
+    }
+
+    #[quickcheck]
+    fn queries_agree_with_linear_scan(
+        coordinates: Vec<(i8, i8)>,
+        target: (i8, i8),
+        corner: (i8, i8),
+    ) -> bool {
+        let all = points(&coordinates);
+        let tree: KdTree<2> = all.iter().copied().collect();
+        let target = points(&[target])[0];
+        let rect = Rect::new(target, points(&[corner])[0]);
+
+        let nearest = tree
+            .nearest(&target)
+            .map(|point| point.distance_squared(&target));
+        let expected = all
+            .iter()
+            .map(|point| point.distance_squared(&target))
+            .min_by(|a, b| a.total_cmp(b));
+        let inside = all
+            .iter()
+            .copied()
+            .filter(|point| rect.contains(point))
+            .collect();
+
+        nearest == expected && sorted(tree.range_search(&rect)) == sorted(inside)
+    }
+}
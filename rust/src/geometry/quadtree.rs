@@ -0,0 +1,273 @@
+//! Region quadtree.
+//!
+//! A quadtree recursively divides a rectangle of the plane into four equal
+//! quadrants. A node stores its points until there are more than the
+//! capacity of the tree, then it subdivides and moves them to its children,
+//! so dense regions are divided finely and sparse regions coarsely.
+
+use super::{Point, Rect};
+use crate::stack::DummyStack;
+
+/// Maximum depth of a node.
+///
+/// Subdividing cannot separate equal points, so nodes at this depth keep
+/// all their points regardless of the capacity.
+const MAX_DEPTH: usize = 16;
+
+/// Node of a [`Quadtree`].
+#[derive(Debug, Clone)]
+struct QuadNode {
+    /// The region covered by the node.
+    bounds: Rect<2>,
+
+    /// The points of a leaf.
+    points: Vec<Point<2>>,
+
+    /// The south-west, south-east, north-west and north-east quadrants.
+    children: Option<Box<[QuadNode; 4]>>,
+}
+
+impl QuadNode {
+    /// Creates new leaf covering the given region.
+    const fn new(bounds: Rect<2>) -> Self {
+        Self {
+            bounds,
+            points: Vec::new(),
+            children: None,
+        }
+    }
+
+    /// Returns the center of the region.
+    fn center(&self) -> Point<2> {
+        let [min_x, min_y] = self.bounds.min.0;
+        let [max_x, max_y] = self.bounds.max.0;
+        Point::new([(min_x + max_x) / 2.0, (min_y + max_y) / 2.0])
+    }
+
+    /// Returns the index of the quadrant containing the point.
+    ///
+    /// Points on the dividing lines belong to the north or east quadrant.
+    fn quadrant(&self, point: &Point<2>) -> usize {
+        let center = self.center();
+        usize::from(point.0[0] >= center.0[0]) + 2 * usize::from(point.0[1] >= center.0[1])
+    }
+
+    /// Inserts a point into the leaf of the subtree covering it.
+    fn insert(&mut self, point: Point<2>, depth: usize, capacity: usize) {
+        let quadrant = self.quadrant(&point);
+        match self.children.as_deref_mut() {
+            Some(children) => children[quadrant].insert(point, depth + 1, capacity),
+            None => {
+                self.points.push(point);
+                self.subdivide(depth, capacity);
+            }
+        }
+    }
+
+    /// Divides the region into four quadrants and moves the points to them
+    /// if the node holds more than `capacity` points.
+    fn subdivide(&mut self, depth: usize, capacity: usize) {
+        if self.points.len() <= capacity || depth >= MAX_DEPTH {
+            return;
+        }
+        let [min_x, min_y] = self.bounds.min.0;
+        let [max_x, max_y] = self.bounds.max.0;
+        let [center_x, center_y] = self.center().0;
+        let rect =
+            |x0, y0, x1, y1| QuadNode::new(Rect::new(Point::new([x0, y0]), Point::new([x1, y1])));
+        let mut children = Box::new([
+            rect(min_x, min_y, center_x, center_y),
+            rect(center_x, min_y, max_x, center_y),
+            rect(min_x, center_y, center_x, max_y),
+            rect(center_x, center_y, max_x, max_y),
+        ]);
+        for point in std::mem::take(&mut self.points) {
+            children[self.quadrant(&point)].points.push(point);
+        }
+        for child in children.iter_mut() {
+            child.subdivide(depth + 1, capacity);
+        }
+        self.children = Some(children);
+    }
+}
+
+/// Quadtree indexes points of a rectangular region of the plane.
+#[derive(Debug, Clone)]
+pub struct Quadtree {
+    root: QuadNode,
+    capacity: usize,
+    len: usize,
+}
+
+impl Quadtree {
+    /// Creates new empty tree covering the given region.
+    ///
+    /// A node is subdivided when it holds more than `capacity` points.
+    /// The capacity is at least one.
+    pub fn new(bounds: Rect<2>, capacity: usize) -> Self {
+        Self {
+            root: QuadNode::new(bounds),
+            capacity: capacity.max(1),
+            len: 0,
+        }
+    }
+
+    /// Returns the region covered by the tree.
+    pub const fn bounds(&self) -> &Rect<2> {
+        &self.root.bounds
+    }
+
+    /// Returns the number of points in the tree.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a point into the tree.
+    ///
+    /// It returns false, leaving the tree unchanged, if the point lies
+    /// outside the region covered by the tree.
+    pub fn insert(&mut self, point: Point<2>) -> bool {
+        if !self.root.bounds.contains(&point) {
+            return false;
+        }
+
+        self.root.insert(point, 0, self.capacity);
+        self.len += 1;
+        true
+    }
+
+    /// Returns true if the point is in the tree.
+    pub fn contains(&self, point: &Point<2>) -> bool {
+        if !self.root.bounds.contains(point) {
+            return false;
+        }
+        let mut node = &self.root;
+        while let Some(children) = node.children.as_deref() {
+            node = &children[node.quadrant(point)];
+        }
+        node.points.contains(point)
+    }
+
+    /// Returns the points of the tree which lie in the rectangle.
+    ///
+    /// Only the nodes whose region intersects the rectangle are visited.
+    pub fn query(&self, rect: &Rect<2>) -> Vec<Point<2>> {
+        let mut points = Vec::new();
+        let mut stack = DummyStack::new();
+        stack.push(&self.root);
+        while let Some(node) = stack.pop() {
+            if !node.bounds.intersects(rect) {
+                continue;
+            }
+            points.extend(node.points.iter().filter(|point| rect.contains(point)));
+            if let Some(children) = node.children.as_deref() {
+                children.iter().for_each(|child| stack.push(child));
+            }
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn square(size: f64) -> Rect<2> {
+        Rect::new(Point::new([0.0, 0.0]), Point::new([size, size]))
+    }
+
+    fn sorted(mut points: Vec<Point<2>>) -> Vec<Point<2>> {
+        points.sort_by(|a, b| a.partial_cmp(b).expect("coordinates are not NaN"));
+        points
+    }
+
+    #[test]
+    fn insert_and_find_points() {
+        let mut tree = Quadtree::new(square(100.0), 2);
+        for (x, y) in [(10.0, 10.0), (90.0, 90.0), (50.0, 50.0), (12.0, 11.0)] {
+            assert!(tree.insert(Point::new([x, y])));
+        }
+        assert!(!tree.insert(Point::new([101.0, 0.0])));
+        assert_eq!(tree.len(), 4);
+        assert!(tree.root.children.is_some());
+        assert!(tree.contains(&Point::new([50.0, 50.0])));
+        assert!(!tree.contains(&Point::new([50.0, 51.0])));
+        assert!(!tree.contains(&Point::new([-1.0, 0.0])));
+
+        let near_origin = tree.query(&square(20.0));
+        assert_eq!(
+            sorted(near_origin),
+            vec![Point::new([10.0, 10.0]), Point::new([12.0, 11.0])]
+        );
+    }
+
+    #[test]
+    fn equal_points_stop_subdividing() {
+        let mut tree = Quadtree::new(square(1.0), 1);
+        for _ in 0..100 {
+            tree.insert(Point::new([0.25, 0.25]));
+        }
+        assert_eq!(tree.len(), 100);
+        assert_eq!(tree.query(&square(1.0)).len(), 100);
+    }
+
+    #[test]
+    fn collision_candidates() {
+        // Circles of radius 1 collide when their centers are closer than 2.
+        let centers = [[1.0, 1.0], [2.5, 1.0], [8.0, 8.0], [9.0, 9.5], [5.0, 1.0]];
+        let mut tree = Quadtree::new(square(10.0), 1);
+        for center in centers {
+            tree.insert(Point::new(center));
+        }
+
+        let mut collisions = 0;
+        for center in centers.map(Point::new) {
+            let around = Rect::new(
+                Point::new([center.0[0] - 2.0, center.0[1] - 2.0]),
+                Point::new([center.0[0] + 2.0, center.0[1] + 2.0]),
+            );
+            collisions += tree
+                .query(&around)
+                .iter()
+                .filter(|other| **other != center && other.distance(&center) < 2.0)
+                .count();
+        }
+        assert_eq!(collisions / 2, 2);
+    }
+
+    #[quickcheck]
+    fn queries_agree_with_linear_scan(
+        coordinates: Vec<(u8, u8)>,
+        corners: ((u8, u8), (u8, u8)),
+        capacity: u8,
+    ) -> bool {
+        let all: Vec<_> = coordinates
+            .iter()
+            .map(|&(x, y)| Point::new([f64::from(x), f64::from(y)]))
+            .collect();
+        let mut tree = Quadtree::new(square(255.0), usize::from(capacity % 8));
+        let inserted = all.iter().all(|point| tree.insert(*point));
+
+        let ((x0, y0), (x1, y1)) = corners;
+        let rect = Rect::new(
+            Point::new([f64::from(x0), f64::from(y0)]),
+            Point::new([f64::from(x1), f64::from(y1)]),
+        );
+        let inside = all
+            .iter()
+            .copied()
+            .filter(|point| rect.contains(point))
+            .collect();
+        inserted
+            && tree.len() == all.len()
+            && all.iter().all(|point| tree.contains(point))
+            && sorted(tree.query(&rect)) == sorted(inside)
+    }
+}
@@ -0,0 +1,521 @@
+//! Graphs.
+//!
+//! This module defines the [`Graph`] type on which the graph algorithms of
+//! the crate operate. A graph is directed or undirected, and stores its
+//! edges either in adjacency lists or in an adjacency matrix. Adjacency
+//! lists take O(V + E) space and are suited to sparse graphs, while the
+//! matrix takes O(V²) space but tells in O(1) time if two vertices are
+//! adjacent. See CLRS section 22.1.
+
+use std::iter;
+
+pub mod adjacency_list;
+pub mod allpairs;
+pub mod bfs;
+pub mod dfs;
+pub mod edge_list;
+pub mod flow;
+pub mod mst;
+pub mod scc;
+pub mod shortest_paths;
+
+/// Direction tells whether the edges of a graph are directed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// An edge `(u, v)` goes from `u` to `v` only.
+    Directed,
+
+    /// An edge `(u, v)` also goes from `v` to `u`.
+    Undirected,
+}
+
+/// Representation is the way a graph stores its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Representation {
+    /// Each vertex has the list of its outgoing edges.
+    #[default]
+    AdjacencyList,
+
+    /// The weight of the edge `(u, v)` is the entry at row `u` and column
+    /// `v` of a matrix.
+    AdjacencyMatrix,
+}
+
+/// VertexId identifies a vertex of a [`Graph`].
+///
+/// Identifiers are given by [`Graph::add_vertex`] and are never reused, so
+/// the identifier of a removed vertex does not designate any other vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertexId(usize);
+
+impl VertexId {
+    /// Returns the index of the vertex, which is smaller than
+    /// [`Graph::vertex_bound`].
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Edges of a [`Graph`], in one of the two representations.
+#[derive(Debug, Clone)]
+enum Adjacency<E> {
+    List(Vec<Vec<(VertexId, E)>>),
+    Matrix(Vec<Vec<Option<E>>>),
+}
+
+/// Graph is a graph with data of type `V` on its vertices and of type `E`
+/// on its edges.
+///
+/// Between two vertices there is at most one edge in each direction, and
+/// an edge may go from a vertex to itself.
+#[derive(Debug, Clone)]
+pub struct Graph<V, E> {
+    direction: Direction,
+
+    /// `vertices[i]` is the data of the vertex `i`, or `None` if it was
+    /// removed.
+    vertices: Vec<Option<V>>,
+
+    adjacency: Adjacency<E>,
+
+    /// The number of vertices which were not removed.
+    vertex_count: usize,
+
+    /// The number of edges, each undirected edge counting once.
+    edge_count: usize,
+}
+
+impl<V, E> Graph<V, E> {
+    /// Creates new empty graph stored in adjacency lists.
+    pub const fn new(direction: Direction) -> Self {
+        Self::with_representation(direction, Representation::AdjacencyList)
+    }
+
+    /// Creates new empty graph with the given representation.
+    pub const fn with_representation(direction: Direction, representation: Representation) -> Self {
+        Self {
+            direction,
+            vertices: Vec::new(),
+            adjacency: match representation {
+                Representation::AdjacencyList => Adjacency::List(Vec::new()),
+                Representation::AdjacencyMatrix => Adjacency::Matrix(Vec::new()),
+            },
+            vertex_count: 0,
+            edge_count: 0,
+        }
+    }
+
+    /// Returns the direction of the edges.
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns true if the edges are directed.
+    pub const fn is_directed(&self) -> bool {
+        matches!(self.direction, Direction::Directed)
+    }
+
+    /// Returns the representation of the edges.
+    pub const fn representation(&self) -> Representation {
+        match self.adjacency {
+            Adjacency::List(_) => Representation::AdjacencyList,
+            Adjacency::Matrix(_) => Representation::AdjacencyMatrix,
+        }
+    }
+
+    /// Returns the number of vertices.
+    pub const fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Returns the number of edges.
+    pub const fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns an upper bound of the indices of the vertices.
+    ///
+    /// Algorithms use it to size the tables they index by vertex.
+    pub fn vertex_bound(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Adds a vertex with the given data and returns its identifier.
+    pub fn add_vertex(&mut self, data: V) -> VertexId {
+        let id = VertexId(self.vertices.len());
+        self.vertices.push(Some(data));
+        match &mut self.adjacency {
+            Adjacency::List(lists) => lists.push(Vec::new()),
+            Adjacency::Matrix(rows) => {
+                for row in rows.iter_mut() {
+                    row.push(None);
+                }
+                rows.push(iter::repeat_with(|| None).take(id.0 + 1).collect());
+            }
+        }
+        self.vertex_count += 1;
+        id
+    }
+
+    /// Removes the vertex and its edges, and returns its data.
+    ///
+    /// It returns `None` if the vertex is not in the graph.
+    pub fn remove_vertex(&mut self, vertex: VertexId) -> Option<V> {
+        let data = self.vertices.get_mut(vertex.0)?.take()?;
+        let others: Vec<_> = self.vertices().collect();
+        for other in others {
+            self.remove_edge(vertex, other);
+            self.remove_edge(other, vertex);
+        }
+        self.remove_edge(vertex, vertex);
+        self.vertex_count -= 1;
+        Some(data)
+    }
+
+    /// Returns true if the vertex is in the graph.
+    pub fn contains_vertex(&self, vertex: VertexId) -> bool {
+        self.vertex(vertex).is_some()
+    }
+
+    /// Returns a reference to the data of the vertex.
+    pub fn vertex(&self, vertex: VertexId) -> Option<&V> {
+        self.vertices.get(vertex.0)?.as_ref()
+    }
+
+    /// Returns a mutable reference to the data of the vertex.
+    pub fn vertex_mut(&mut self, vertex: VertexId) -> Option<&mut V> {
+        self.vertices.get_mut(vertex.0)?.as_mut()
+    }
+
+    /// Creates an iterator over the vertices, by increasing identifier.
+    pub fn vertices(&self) -> impl Iterator<Item = VertexId> + '_ {
+        self.vertices
+            .iter()
+            .enumerate()
+            .filter(|(_, data)| data.is_some())
+            .map(|(index, _)| VertexId(index))
+    }
+
+    /// Returns a reference to the data of the edge from `from` to `to`.
+    pub fn edge(&self, from: VertexId, to: VertexId) -> Option<&E> {
+        match &self.adjacency {
+            Adjacency::List(lists) => lists
+                .get(from.0)?
+                .iter()
+                .find(|(vertex, _)| *vertex == to)
+                .map(|(_, data)| data),
+            Adjacency::Matrix(rows) => rows.get(from.0)?.get(to.0)?.as_ref(),
+        }
+    }
+
+    /// Returns true if there is an edge from `from` to `to`.
+    pub fn has_edge(&self, from: VertexId, to: VertexId) -> bool {
+        self.edge(from, to).is_some()
+    }
+
+    /// Removes the edge from `from` to `to` and returns its data.
+    ///
+    /// The edge is removed in both directions in an undirected graph. It
+    /// returns `None` if there is no such edge.
+    pub fn remove_edge(&mut self, from: VertexId, to: VertexId) -> Option<E> {
+        let data = self.unlink(from, to)?;
+        if !self.is_directed() && from != to {
+            self.unlink(to, from);
+        }
+        self.edge_count -= 1;
+        Some(data)
+    }
+
+    /// Creates an iterator over the vertices adjacent to the vertex, with
+    /// the data of the edges leading to them.
+    ///
+    /// The iterator is empty if the vertex is not in the graph.
+    pub fn neighbors(&self, vertex: VertexId) -> Neighbors<'_, E> {
+        match &self.adjacency {
+            Adjacency::List(lists) => {
+                Neighbors::List(lists.get(vertex.0).map_or(&[][..], Vec::as_slice).iter())
+            }
+            Adjacency::Matrix(rows) => Neighbors::Matrix(
+                rows.get(vertex.0)
+                    .map_or(&[][..], Vec::as_slice)
+                    .iter()
+                    .enumerate(),
+            ),
+        }
+    }
+
+    /// Returns the number of edges leaving the vertex.
+    ///
+    /// In an undirected graph it is the number of edges incident to the
+    /// vertex, a self-loop counting once.
+    pub fn degree(&self, vertex: VertexId) -> usize {
+        self.neighbors(vertex).count()
+    }
+
+    /// Returns the number of edges entering the vertex.
+    ///
+    /// It takes O(V + E) time with adjacency lists and O(V) time with a
+    /// matrix.
+    pub fn in_degree(&self, vertex: VertexId) -> usize {
+        if !self.is_directed() {
+            return self.degree(vertex);
+        }
+        self.vertices()
+            .filter(|&other| self.has_edge(other, vertex))
+            .count()
+    }
+
+    /// Creates an iterator over the edges, as `(from, to, data)` triples.
+    ///
+    /// Each edge of an undirected graph is given once, with `from <= to`.
+    pub fn edges(&self) -> impl Iterator<Item = (VertexId, VertexId, &E)> + '_ {
+        self.vertices().flat_map(move |from| {
+            self.neighbors(from)
+                .filter(move |(to, _)| self.is_directed() || from <= *to)
+                .map(move |(to, data)| (from, to, data))
+        })
+    }
+
+    /// Returns a graph in adjacency lists with the same vertex identifiers,
+    /// without data, and without edges.
+    fn skeleton<F>(&self, direction: Direction) -> Graph<(), F> {
+        Graph {
+            direction,
+            vertices: self
+                .vertices
+                .iter()
+                .map(|data| data.as_ref().map(|_| ()))
+                .collect(),
+            adjacency: Adjacency::List(
+                iter::repeat_with(Vec::new)
+                    .take(self.vertex_bound())
+                    .collect(),
+            ),
+            vertex_count: self.vertex_count,
+            edge_count: 0,
+        }
+    }
+
+    /// Removes the edge from `from` to `to` in one direction only.
+    fn unlink(&mut self, from: VertexId, to: VertexId) -> Option<E> {
+        match &mut self.adjacency {
+            Adjacency::List(lists) => {
+                let list = lists.get_mut(from.0)?;
+                let index = list.iter().position(|(vertex, _)| *vertex == to)?;
+                Some(list.swap_remove(index).1)
+            }
+            Adjacency::Matrix(rows) => rows.get_mut(from.0)?.get_mut(to.0)?.take(),
+        }
+    }
+
+    /// Sets the edge from `from` to `to` in one direction only, and returns
+    /// the previous data of the edge.
+    fn link(&mut self, from: VertexId, to: VertexId, data: E) -> Option<E> {
+        match &mut self.adjacency {
+            Adjacency::List(lists) => {
+                let list = &mut lists[from.0];
+                match list.iter_mut().find(|(vertex, _)| *vertex == to) {
+                    Some((_, previous)) => Some(std::mem::replace(previous, data)),
+                    None => {
+                        list.push((to, data));
+                        None
+                    }
+                }
+            }
+            Adjacency::Matrix(rows) => rows[from.0][to.0].replace(data),
+        }
+    }
+}
+
+impl<V, E: Clone> Graph<V, E> {
+    /// Adds an edge from `from` to `to` with the given data.
+    ///
+    /// It returns the previous data of the edge if there was already one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either vertex is not in the graph.
+    pub fn add_edge(&mut self, from: VertexId, to: VertexId, data: E) -> Option<E> {
+        assert!(
+            self.contains_vertex(from) && self.contains_vertex(to),
+            "vertex is not in the graph"
+        );
+        if !self.is_directed() && from != to {
+            self.link(to, from, data.clone());
+        }
+        let previous = self.link(from, to, data);
+        if previous.is_none() {
+            self.edge_count += 1;
+        }
+        previous
+    }
+}
+
+/// Iterator over the neighbors of a vertex of a [`Graph`].
+#[derive(Debug)]
+pub enum Neighbors<'a, E> {
+    /// Neighbors in an adjacency list.
+    List(std::slice::Iter<'a, (VertexId, E)>),
+
+    /// Neighbors in a row of an adjacency matrix.
+    Matrix(iter::Enumerate<std::slice::Iter<'a, Option<E>>>),
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (VertexId, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::List(edges) => edges.next().map(|(vertex, data)| (*vertex, data)),
+            Self::Matrix(row) => {
+                row.find_map(|(index, data)| Some((VertexId(index), data.as_ref()?)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    const REPRESENTATIONS: [Representation; 2] = [
+        Representation::AdjacencyList,
+        Representation::AdjacencyMatrix,
+    ];
+
+    fn sorted_neighbors<V, E: Copy>(graph: &Graph<V, E>, vertex: VertexId) -> Vec<(usize, E)> {
+        let mut neighbors: Vec<_> = graph
+            .neighbors(vertex)
+            .map(|(vertex, data)| (vertex.index(), *data))
+            .collect();
+        neighbors.sort_unstable_by_key(|(vertex, _)| *vertex);
+        neighbors
+    }
+
+    #[test]
+    fn directed_graph() {
+        for representation in REPRESENTATIONS {
+            let mut graph = Graph::with_representation(Direction::Directed, representation);
+            let [a, b, c] = ["a", "b", "c"].map(|name| graph.add_vertex(name));
+            assert_eq!(graph.add_edge(a, b, 1), None);
+            assert_eq!(graph.add_edge(a, c, 2), None);
+            assert_eq!(graph.add_edge(c, a, 3), None);
+            assert_eq!(graph.add_edge(a, b, 4), Some(1));
+            assert_eq!(graph.representation(), representation);
+            assert_eq!(graph.edge_count(), 3);
+            assert_eq!(graph.edge(a, b), Some(&4));
+            assert!(!graph.has_edge(b, a));
+            assert_eq!(sorted_neighbors(&graph, a), vec![(1, 4), (2, 2)]);
+            assert_eq!(graph.degree(a), 2);
+            assert_eq!(graph.in_degree(a), 1);
+            assert_eq!(graph.in_degree(b), 1);
+            assert_eq!(graph.edges().count(), 3);
+
+            assert_eq!(graph.remove_edge(b, a), None);
+            assert_eq!(graph.remove_vertex(a), Some("a"));
+            assert_eq!(graph.remove_vertex(a), None);
+            assert_eq!(graph.vertex_count(), 2);
+            assert_eq!(graph.edge_count(), 0);
+            assert_eq!(graph.degree(c), 0);
+            assert_eq!(graph.vertices().collect::<Vec<_>>(), vec![b, c]);
+            assert_eq!(graph.vertex(c), Some(&"c"));
+            assert_eq!(graph.vertex_bound(), 3);
+        }
+    }
+
+    #[test]
+    fn undirected_graph() {
+        for representation in REPRESENTATIONS {
+            let mut graph = Graph::with_representation(Direction::Undirected, representation);
+            let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+            graph.add_edge(a, b, 7);
+            graph.add_edge(c, b, 8);
+            graph.add_edge(c, c, 9);
+            assert!(!graph.is_directed());
+            assert_eq!(graph.edge_count(), 3);
+            assert_eq!(graph.edge(b, a), Some(&7));
+            assert_eq!(sorted_neighbors(&graph, b), vec![(0, 7), (2, 8)]);
+            assert_eq!(graph.degree(c), 2);
+            assert_eq!(graph.in_degree(b), 2);
+
+            let mut edges: Vec<_> = graph
+                .edges()
+                .map(|(from, to, data)| (from.index(), to.index(), *data))
+                .collect();
+            edges.sort_unstable();
+            assert_eq!(edges, vec![(0, 1, 7), (1, 2, 8), (2, 2, 9)]);
+
+            assert_eq!(graph.remove_edge(b, c), Some(8));
+            assert!(!graph.has_edge(c, b));
+            assert_eq!(graph.edge_count(), 2);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex is not in the graph")]
+    fn add_edge_to_removed_vertex() {
+        let mut graph = Graph::new(Direction::Directed);
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.remove_vertex(b);
+        graph.add_edge(a, b, ());
+    }
+
+    #[quickcheck]
+    fn representations_agree(operations: Vec<(u8, u8, u8)>, directed: bool) -> bool {
+        let direction = if directed {
+            Direction::Directed
+        } else {
+            Direction::Undirected
+        };
+        let mut list = Graph::new(direction);
+        let mut matrix = Graph::with_representation(direction, Representation::AdjacencyMatrix);
+        let mut ids = Vec::new();
+        for (operation, from, to) in operations {
+            let agree = match operation % 4 {
+                0 => {
+                    let id = list.add_vertex(());
+                    ids.push(id);
+                    matrix.add_vertex(()) == id
+                }
+                1 if !ids.is_empty() => {
+                    let id = ids[usize::from(from) % ids.len()];
+                    list.remove_vertex(id) == matrix.remove_vertex(id)
+                }
+                2 if !ids.is_empty() => {
+                    let (from, to) = (
+                        ids[usize::from(from) % ids.len()],
+                        ids[usize::from(to) % ids.len()],
+                    );
+                    if list.contains_vertex(from) && list.contains_vertex(to) {
+                        let data = u16::from(operation);
+                        list.add_edge(from, to, data) == matrix.add_edge(from, to, data)
+                    } else {
+                        true
+                    }
+                }
+                3 if !ids.is_empty() => {
+                    let (from, to) = (
+                        ids[usize::from(from) % ids.len()],
+                        ids[usize::from(to) % ids.len()],
+                    );
+                    list.remove_edge(from, to) == matrix.remove_edge(from, to)
+                }
+                _ => true,
+            };
+            if !agree
+                || list.vertex_count() != matrix.vertex_count()
+                || list.edge_count() != matrix.edge_count()
+                || list.edges().count() != list.edge_count()
+            {
+                return false;
+            }
+        }
+        ids.iter().all(|&id| {
+            sorted_neighbors(&list, id) == sorted_neighbors(&matrix, id)
+                && list.in_degree(id) == matrix.in_degree(id)
+        })
+    }
+}
@@ -0,0 +1,282 @@
+//! Adjacency-list text format.
+//!
+//! An adjacency list describes a graph one vertex at a time. It starts
+//! with the same header as an [edge list](super::edge_list), giving the
+//! direction of the edges and the number of vertices, numbered from `0`.
+//! Each following line is a vertex, a colon, and the vertices adjacent to
+//! it, each followed by a slash and the weight of the edge in a weighted
+//! adjacency list:
+//!
+//! ```text
+//! # The triangle of CLRS figure 23.4, with an isolated vertex.
+//! undirected 4
+//! 0: 1/4 2/11
+//! 1: 0/4 2/8
+//! 2: 0/11 1/8
+//! 3:
+//! ```
+//!
+//! An edge of an undirected graph is listed by both its endpoints, like in
+//! the adjacency lists of the [`Graph`] itself. A vertex without a line has
+//! no edges leaving it. Blank lines and lines starting with `#` are
+//! skipped.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use super::edge_list::read_header;
+use super::{Graph, Representation, VertexId};
+use crate::{error::ErrorKind, Error};
+
+impl Graph<(), ()> {
+    /// Reads a graph from an unweighted adjacency list.
+    ///
+    /// It fails with an error of kind [`io::ErrorKind::InvalidData`],
+    /// wrapping an [`ErrorKind::InvalidAdjacencyList`], if the header is
+    /// missing, or a line is not a vertex of the graph followed by vertices
+    /// of the graph.
+    pub fn from_adjacency_list(
+        reader: impl BufRead,
+        representation: Representation,
+    ) -> io::Result<Self> {
+        read_adjacency_list(reader, representation, |weight| {
+            weight.is_none().then_some(())
+        })
+    }
+}
+
+impl<W: FromStr + Clone> Graph<(), W> {
+    /// Reads a graph from a weighted adjacency list.
+    ///
+    /// It fails like [`Graph::from_adjacency_list`], and also if a weight
+    /// is missing or cannot be parsed.
+    pub fn from_weighted_adjacency_list(
+        reader: impl BufRead,
+        representation: Representation,
+    ) -> io::Result<Self> {
+        read_adjacency_list(reader, representation, |weight| weight?.parse().ok())
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Writes the graph as an unweighted adjacency list.
+    ///
+    /// The data of the vertices and of the edges is not written. The
+    /// vertices are numbered by their index, and a removed vertex has no
+    /// line, so it is read back as an isolated vertex.
+    pub fn to_adjacency_list(&self, mut writer: impl Write) -> io::Result<()> {
+        self.write_header(&mut writer)?;
+        for vertex in self.vertices() {
+            write!(writer, "{}:", vertex.index())?;
+            for (neighbor, _) in self.neighbors(vertex) {
+                write!(writer, " {}", neighbor.index())?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<V, E: Display> Graph<V, E> {
+    /// Writes the graph as a weighted adjacency list, the data of each
+    /// edge being its weight.
+    ///
+    /// Vertices are numbered as by [`Graph::to_adjacency_list`].
+    pub fn to_weighted_adjacency_list(&self, mut writer: impl Write) -> io::Result<()> {
+        self.write_header(&mut writer)?;
+        for vertex in self.vertices() {
+            write!(writer, "{}:", vertex.index())?;
+            for (neighbor, weight) in self.neighbors(vertex) {
+                write!(writer, " {}/{}", neighbor.index(), weight)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads an adjacency list, the data of each edge being parsed from what
+/// follows the slash of its entry.
+fn read_adjacency_list<E: Clone>(
+    reader: impl BufRead,
+    representation: Representation,
+    parse: impl Fn(Option<&str>) -> Option<E>,
+) -> io::Result<Graph<(), E>> {
+    let mut graph: Option<Graph<(), E>> = None;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(graph) = &mut graph else {
+            graph = Some(read_header(line, representation).ok_or_else(invalid_adjacency_list)?);
+            continue;
+        };
+        let (from, neighbors) = line.split_once(':').ok_or_else(invalid_adjacency_list)?;
+        let from = parse_vertex(graph, from)?;
+        for entry in neighbors.split_whitespace() {
+            let (to, weight) = match entry.split_once('/') {
+                Some((to, weight)) => (to, Some(weight)),
+                None => (entry, None),
+            };
+            let to = parse_vertex(graph, to)?;
+            let data = parse(weight).ok_or_else(invalid_adjacency_list)?;
+            graph.add_edge(from, to, data);
+        }
+    }
+    graph.ok_or_else(invalid_adjacency_list)
+}
+
+/// Parses the index of a vertex of the graph.
+fn parse_vertex<E>(graph: &Graph<(), E>, field: &str) -> io::Result<VertexId> {
+    field
+        .trim()
+        .parse()
+        .ok()
+        .map(VertexId)
+        .filter(|&vertex| graph.contains_vertex(vertex))
+        .ok_or_else(invalid_adjacency_list)
+}
+
+fn invalid_adjacency_list() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        Error::new(ErrorKind::InvalidAdjacencyList),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::graph::Direction;
+
+    const REPRESENTATIONS: [Representation; 2] = [
+        Representation::AdjacencyList,
+        Representation::AdjacencyMatrix,
+    ];
+
+    /// Returns the edges of the graph as sorted index triples.
+    fn sorted_edges<V, E: Ord + Clone>(graph: &Graph<V, E>) -> Vec<(usize, usize, E)> {
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|(from, to, data)| (from.index(), to.index(), data.clone()))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn read_and_write_adjacency_lists() {
+        let weighted =
+            "# CLRS figure 23.4\nundirected 4\n\n0: 1/4 2/11\n1: 0/4 2/8\n2: 0/11 1/8\n3:\n";
+        let unweighted = "directed 3\n0: 1\n1: 2\n2: 0\n";
+        for representation in REPRESENTATIONS {
+            let graph: Graph<(), u32> =
+                Graph::from_weighted_adjacency_list(weighted.as_bytes(), representation)
+                    .expect("the adjacency list is valid");
+            assert_eq!(graph.representation(), representation);
+            assert!(!graph.is_directed());
+            assert_eq!(graph.vertex_count(), 4);
+            assert_eq!(graph.edge_count(), 3);
+            assert_eq!(graph.edge(VertexId(2), VertexId(1)), Some(&8));
+            let mut output = Vec::new();
+            graph
+                .to_weighted_adjacency_list(&mut output)
+                .expect("writing to a vector cannot fail");
+            assert_eq!(
+                String::from_utf8(output).expect("adjacency lists are valid UTF-8"),
+                weighted
+                    .replace("# CLRS figure 23.4\n", "")
+                    .replace("\n\n", "\n")
+            );
+
+            let graph = Graph::from_adjacency_list(unweighted.as_bytes(), representation)
+                .expect("the adjacency list is valid");
+            assert!(graph.is_directed());
+            assert!(graph.has_edge(VertexId(2), VertexId(0)));
+            assert!(!graph.has_edge(VertexId(0), VertexId(2)));
+            let mut output = Vec::new();
+            graph
+                .to_adjacency_list(&mut output)
+                .expect("writing to a vector cannot fail");
+            assert_eq!(output, unweighted.as_bytes());
+        }
+    }
+
+    #[test]
+    fn invalid_adjacency_lists() {
+        let invalid = [
+            "",
+            "# no header\n",
+            "sideways 2\n",
+            "directed\n",
+            "directed 2 3\n",
+            "directed 2\n0 1\n",
+            "directed 2\n2: 0\n",
+            "directed 2\n0: 2\n",
+            "directed 2\n0: 1/5\n",
+            "directed 2\nzero: one\n",
+        ];
+        for input in invalid {
+            let error = Graph::from_adjacency_list(input.as_bytes(), Representation::AdjacencyList)
+                .expect_err("the adjacency list is invalid");
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(
+                error.into_inner().map(|error| error.to_string()),
+                Some(Error::new(ErrorKind::InvalidAdjacencyList).to_string())
+            );
+        }
+        let missing_weight = "directed 2\n0: 1\n";
+        assert!(Graph::<(), i32>::from_weighted_adjacency_list(
+            missing_weight.as_bytes(),
+            Representation::AdjacencyList
+        )
+        .is_err());
+    }
+
+    #[quickcheck]
+    fn round_trip(edges: Vec<(u8, u8, i16)>, directed: bool, size: u8) -> bool {
+        let direction = if directed {
+            Direction::Directed
+        } else {
+            Direction::Undirected
+        };
+        let size = usize::from(size % 16) + 1;
+        REPRESENTATIONS.iter().all(|&representation| {
+            let mut graph = Graph::with_representation(direction, representation);
+            let vertices: Vec<_> = (0..size).map(|_| graph.add_vertex(())).collect();
+            for &(from, to, weight) in &edges {
+                let (from, to) = (usize::from(from) % size, usize::from(to) % size);
+                graph.add_edge(vertices[from], vertices[to], weight);
+            }
+
+            let mut weighted = Vec::new();
+            let mut unweighted = Vec::new();
+            graph
+                .to_weighted_adjacency_list(&mut weighted)
+                .expect("writing to a vector cannot fail");
+            graph
+                .to_adjacency_list(&mut unweighted)
+                .expect("writing to a vector cannot fail");
+            let read: Graph<(), i16> =
+                Graph::from_weighted_adjacency_list(&weighted[..], representation)
+                    .expect("the written adjacency list is valid");
+            let read_unweighted = Graph::from_adjacency_list(&unweighted[..], representation)
+                .expect("the written adjacency list is valid");
+
+            read.direction() == direction
+                && read.vertex_count() == size
+                && read.edge_count() == graph.edge_count()
+                && sorted_edges(&read) == sorted_edges(&graph)
+                && sorted_edges(&read_unweighted)
+                    == sorted_edges(&graph)
+                        .into_iter()
+                        .map(|(from, to, _)| (from, to, ()))
+                        .collect::<Vec<_>>()
+        })
+    }
+}
@@ -0,0 +1,316 @@
+//! All-pairs shortest paths.
+//!
+//! The Floyd-Warshall algorithm is a dynamic program over the adjacency
+//! matrix of the weights: after considering the vertices `0..k`, the entry
+//! `(i, j)` is the weight of a lightest path from `i` to `j` whose
+//! intermediate vertices are all in `0..k`. It takes O(V³) time. Johnson's
+//! algorithm reweights the edges so that they are all nonnegative without
+//! changing the lightest paths, using potentials computed by Bellman-Ford,
+//! then runs Dijkstra's algorithm from every vertex, which is faster on
+//! sparse graphs. See CLRS sections 25.2 and 25.3.
+
+use super::{Direction, Graph, VertexId};
+use crate::matrix::Matrix;
+use crate::Error;
+
+/// AllPairs holds the lightest paths between every pair of vertices.
+///
+/// The rows and columns of its matrices are indexed by
+/// [`VertexId::index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairs {
+    /// The entry `(u, v)` is the weight of a lightest path from `u` to `v`,
+    /// or `None` if there is no path.
+    distances: Matrix<Option<i64>>,
+
+    /// The entry `(u, v)` is the vertex before `v` on a lightest path from
+    /// `u` to `v`.
+    predecessors: Matrix<Option<VertexId>>,
+}
+
+impl AllPairs {
+    /// Creates new result where no vertex is reached.
+    fn new(bound: usize) -> Self {
+        Self {
+            distances: Matrix::new(bound, bound),
+            predecessors: Matrix::new(bound, bound),
+        }
+    }
+
+    /// Returns the matrix of the distances.
+    pub const fn distances(&self) -> &Matrix<Option<i64>> {
+        &self.distances
+    }
+
+    /// Returns the weight of a lightest path from `from` to `to`, or `None`
+    /// if there is no such path.
+    pub fn distance(&self, from: VertexId, to: VertexId) -> Option<i64> {
+        *self.distances.get(from.index(), to.index())?
+    }
+
+    /// Returns the vertex before `to` on a lightest path from `from`.
+    pub fn predecessor(&self, from: VertexId, to: VertexId) -> Option<VertexId> {
+        *self.predecessors.get(from.index(), to.index())?
+    }
+
+    /// Returns the vertices of a lightest path from `from` to `to`, both
+    /// included, or `None` if there is no such path.
+    pub fn path(&self, from: VertexId, to: VertexId) -> Option<Vec<VertexId>> {
+        self.distance(from, to)?;
+        let mut path: Vec<_> =
+            std::iter::successors(Some(to), |&vertex| self.predecessor(from, vertex)).collect();
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<V, E: Copy + Into<i64>> Graph<V, E> {
+    /// Returns the lightest paths between all pairs of vertices, computed by
+    /// the Floyd-Warshall algorithm.
+    ///
+    /// The weights are first copied into a matrix, whatever the
+    /// representation of the graph. It fails with
+    /// [`ErrorKind::NegativeCycle`](crate::error::ErrorKind::NegativeCycle)
+    /// if the graph has a cycle of negative weight, which shows as a
+    /// negative entry on the diagonal.
+    pub fn floyd_warshall(&self) -> Result<AllPairs, Error> {
+        let mut paths = AllPairs::new(self.vertex_bound());
+        let vertices: Vec<_> = self.vertices().map(VertexId::index).collect();
+        for &vertex in &vertices {
+            paths.distances[(vertex, vertex)] = Some(0);
+        }
+        for (from, to, weight) in self.arcs() {
+            if from == to {
+                let distance = &mut paths.distances[(from.index(), from.index())];
+                *distance = (*distance).min(Some(weight));
+            } else {
+                paths.distances[(from.index(), to.index())] = Some(weight);
+                paths.predecessors[(from.index(), to.index())] = Some(from);
+            }
+        }
+
+        for &k in &vertices {
+            for &i in &vertices {
+                let Some(to_k) = paths.distances[(i, k)] else {
+                    continue;
+                };
+                for &j in &vertices {
+                    let Some(from_k) = paths.distances[(k, j)] else {
+                        continue;
+                    };
+                    let through_k = to_k + from_k;
+                    if paths.distances[(i, j)].map_or(true, |distance| through_k < distance) {
+                        paths.distances[(i, j)] = Some(through_k);
+                        paths.predecessors[(i, j)] = paths.predecessors[(k, j)];
+                    }
+                }
+            }
+
+            // Stopping at the first negative cycle keeps the entries from
+            // decreasing without bound.
+            if let Some(&vertex) = vertices
+                .iter()
+                .find(|&&vertex| paths.distances[(vertex, vertex)] < Some(0))
+            {
+                return Err(self.negative_cycle_through(vertex));
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Returns the lightest paths between all pairs of vertices, computed by
+    /// Johnson's algorithm.
+    ///
+    /// A new vertex is joined to every vertex by an edge of weight zero, and
+    /// its distances `h` given by Bellman-Ford reweight each edge `(u, v)`
+    /// to `w(u, v) + h(u) - h(v)`, which is nonnegative. A path from `u` to
+    /// `v` is then heavier by exactly `h(u) - h(v)`, so Dijkstra's
+    /// algorithm finds the same lightest paths. It fails with
+    /// [`ErrorKind::NegativeCycle`](crate::error::ErrorKind::NegativeCycle)
+    /// if the graph has a cycle of negative weight.
+    pub fn johnson(&self) -> Result<AllPairs, Error> {
+        let mut augmented = self.skeleton(Direction::Directed);
+        for (from, to, weight) in self.arcs() {
+            augmented.add_edge(from, to, weight);
+        }
+        let source = augmented.add_vertex(());
+        for vertex in self.vertices() {
+            augmented.add_edge(source, vertex, 0);
+        }
+        let potentials = augmented.bellman_ford(source)?;
+        let potential = |vertex| {
+            potentials
+                .distance(vertex)
+                .expect("every vertex is reachable from the new vertex")
+        };
+
+        let mut reweighted = self.skeleton(Direction::Directed);
+        for (from, to, &weight) in augmented.edges() {
+            if from != source {
+                reweighted.add_edge(from, to, weight + potential(from) - potential(to));
+            }
+        }
+        let mut paths = AllPairs::new(self.vertex_bound());
+        for from in self.vertices() {
+            let tree = reweighted.dijkstra(from);
+            for to in self.vertices() {
+                let Some(distance) = tree.distance(to) else {
+                    continue;
+                };
+                paths.distances[(from.index(), to.index())] =
+                    Some(distance - potential(from) + potential(to));
+                paths.predecessors[(from.index(), to.index())] = tree.predecessor(to);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Creates an iterator over the edges as directed arcs with their
+    /// weight, an undirected edge giving an arc in each direction.
+    fn arcs(&self) -> impl Iterator<Item = (VertexId, VertexId, i64)> + '_ {
+        self.edges().flat_map(move |(from, to, &weight)| {
+            let weight = weight.into();
+            let reverse = (!self.is_directed() && from != to).then_some((to, from, weight));
+            std::iter::once((from, to, weight)).chain(reverse)
+        })
+    }
+
+    /// Returns the error reporting a negative cycle through the vertex.
+    fn negative_cycle_through(&self, vertex: usize) -> Error {
+        match self.bellman_ford(VertexId(vertex)) {
+            Err(error) => error,
+            Ok(_) => unreachable!("the vertex is on a negative cycle"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::Representation;
+    use super::*;
+    use crate::error::ErrorKind;
+
+    /// Returns the directed graph of CLRS figure 25.1, with the vertices
+    /// numbered from 0.
+    fn clrs_graph(representation: Representation) -> (Graph<(), i32>, Vec<VertexId>) {
+        let mut graph = Graph::with_representation(Direction::Directed, representation);
+        let ids: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        for (from, to, weight) in [
+            (1, 2, 3),
+            (1, 3, 8),
+            (1, 5, -4),
+            (2, 4, 1),
+            (2, 5, 7),
+            (3, 2, 4),
+            (4, 1, 2),
+            (4, 3, -5),
+            (5, 4, 6),
+        ] {
+            graph.add_edge(ids[from - 1], ids[to - 1], weight);
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn clrs_all_pairs() {
+        let expected = [
+            [0, 1, -3, 2, -4],
+            [3, 0, -4, 1, -1],
+            [7, 4, 0, 5, 3],
+            [2, -1, -5, 0, -2],
+            [8, 5, 1, 6, 0],
+        ];
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let (graph, ids) = clrs_graph(representation);
+            let floyd_warshall = graph.floyd_warshall().expect("no negative cycle");
+            let johnson = graph.johnson().expect("no negative cycle");
+            for paths in [&floyd_warshall, &johnson] {
+                for (row, distances) in expected.iter().enumerate() {
+                    for (column, &distance) in distances.iter().enumerate() {
+                        assert_eq!(paths.distance(ids[row], ids[column]), Some(distance));
+                    }
+                }
+                assert_eq!(
+                    paths.path(ids[0], ids[1]),
+                    Some(vec![ids[0], ids[4], ids[3], ids[2], ids[1]])
+                );
+                assert_eq!(paths.path(ids[2], ids[2]), Some(vec![ids[2]]));
+            }
+            assert_eq!(floyd_warshall.distances(), johnson.distances());
+        }
+    }
+
+    #[test]
+    fn negative_cycles() {
+        let mut graph = Graph::new(Direction::Directed);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, 2);
+        graph.add_edge(b, a, -3);
+        graph.add_edge(b, c, 1);
+        for result in [graph.floyd_warshall(), graph.johnson()] {
+            let error = result.expect_err("a, b is a negative cycle");
+            assert!(matches!(error.kind(), ErrorKind::NegativeCycle(cycle) if cycle.len() == 2));
+        }
+
+        graph.remove_edge(b, a);
+        graph.add_edge(c, c, -1);
+        assert_eq!(
+            graph.floyd_warshall().map_err(|error| error.kind().clone()),
+            Err(ErrorKind::NegativeCycle(vec![c]))
+        );
+    }
+
+    #[test]
+    fn removed_vertices() {
+        let mut graph = Graph::new(Direction::Undirected);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, c, 4_u8);
+        graph.remove_vertex(b);
+        let paths = graph.johnson().expect("weights are nonnegative");
+        assert_eq!(paths.distance(c, a), Some(4));
+        assert_eq!(paths.distance(a, b), None);
+        assert_eq!(paths.path(b, b), None);
+        assert_eq!(Ok(paths), graph.floyd_warshall());
+    }
+
+    #[quickcheck]
+    fn algorithms_agree(edges: Vec<(u8, u8, i8)>, directed: bool) -> bool {
+        let direction = if directed {
+            Direction::Directed
+        } else {
+            Direction::Undirected
+        };
+        let mut graph = Graph::new(direction);
+        let ids: Vec<_> = (0..8).map(|_| graph.add_vertex(())).collect();
+        for (from, to, weight) in edges {
+            graph.add_edge(ids[usize::from(from % 8)], ids[usize::from(to % 8)], weight);
+        }
+        match (graph.floyd_warshall(), graph.johnson()) {
+            (Ok(floyd_warshall), Ok(johnson)) => {
+                floyd_warshall.distances() == johnson.distances()
+                    && ids.iter().all(|&from| {
+                        let tree = graph.bellman_ford(from).expect("no negative cycle");
+                        ids.iter().all(|&to| {
+                            let weight = |path: Vec<VertexId>| {
+                                path.windows(2)
+                                    .map(|pair| {
+                                        i64::from(*graph.edge(pair[0], pair[1]).expect("edge"))
+                                    })
+                                    .sum::<i64>()
+                            };
+                            tree.distance(to) == johnson.distance(from, to)
+                                && floyd_warshall.path(from, to).map(weight) == tree.distance(to)
+                                && johnson.path(from, to).map(weight) == tree.distance(to)
+                        })
+                    })
+            }
+            (Err(_), Err(_)) => ids.iter().any(|&from| graph.bellman_ford(from).is_err()),
+            _ => false,
+        }
+    }
+}
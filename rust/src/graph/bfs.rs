@@ -0,0 +1,220 @@
+//! Breadth-first search.
+//!
+//! Breadth-first search discovers the vertices in order of their distance
+//! from a source, counted in edges. A vertex is white until it is
+//! discovered, gray while it waits in the queue and black once its
+//! neighbors are discovered. The predecessors of the discovered vertices
+//! form a tree of shortest paths from the source. See CLRS section 22.2.
+
+use super::{Graph, VertexId};
+use crate::queue::BoundedQueue;
+
+/// State of a vertex during the search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// BreadthFirstTree is the result of a breadth-first search.
+///
+/// It holds the distance from the source of each vertex reachable from it,
+/// and its predecessor on a shortest path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreadthFirstTree {
+    source: VertexId,
+
+    /// `distances[v]` is the distance of the vertex `v` from the source, or
+    /// `None` if it is unreachable.
+    distances: Vec<Option<usize>>,
+
+    /// `predecessors[v]` is the vertex from which `v` was discovered.
+    predecessors: Vec<Option<VertexId>>,
+}
+
+impl BreadthFirstTree {
+    /// Returns the source of the search.
+    pub const fn source(&self) -> VertexId {
+        self.source
+    }
+
+    /// Returns the number of edges of a shortest path from the source to
+    /// the vertex, or `None` if there is no such path.
+    pub fn distance(&self, vertex: VertexId) -> Option<usize> {
+        *self.distances.get(vertex.index())?
+    }
+
+    /// Returns the predecessor of the vertex in the tree.
+    ///
+    /// The source and the unreachable vertices have no predecessor.
+    pub fn predecessor(&self, vertex: VertexId) -> Option<VertexId> {
+        *self.predecessors.get(vertex.index())?
+    }
+
+    /// Returns the vertices of a shortest path from the source to the
+    /// vertex, both included, or `None` if there is no such path.
+    pub fn path_to(&self, vertex: VertexId) -> Option<Vec<VertexId>> {
+        self.distance(vertex)?;
+        let mut path: Vec<_> =
+            std::iter::successors(Some(vertex), |&vertex| self.predecessor(vertex)).collect();
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Searches the graph breadth-first from the source.
+    ///
+    /// It takes O(V + E) time with adjacency lists and O(V²) time with a
+    /// matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source is not in the graph.
+    pub fn bfs(&self, source: VertexId) -> BreadthFirstTree {
+        assert!(self.contains_vertex(source), "vertex is not in the graph");
+        let bound = self.vertex_bound();
+        let mut colors = vec![Color::White; bound];
+        let mut distances = vec![None; bound];
+        let mut predecessors = vec![None; bound];
+
+        // Each vertex is enqueued at most once.
+        let mut queue = BoundedQueue::with_capacity(self.vertex_count());
+        colors[source.index()] = Color::Gray;
+        distances[source.index()] = Some(0);
+        queue.enqueue(source).expect("the queue holds every vertex");
+        while let Ok(vertex) = queue.dequeue() {
+            let distance = distances[vertex.index()].map(|distance| distance + 1);
+            for (neighbor, _) in self.neighbors(vertex) {
+                if colors[neighbor.index()] == Color::White {
+                    colors[neighbor.index()] = Color::Gray;
+                    distances[neighbor.index()] = distance;
+                    predecessors[neighbor.index()] = Some(vertex);
+                    queue
+                        .enqueue(neighbor)
+                        .expect("the queue holds every vertex");
+                }
+            }
+            colors[vertex.index()] = Color::Black;
+        }
+
+        BreadthFirstTree {
+            source,
+            distances,
+            predecessors,
+        }
+    }
+
+    /// Returns the vertices of a path with the fewest edges from `from` to
+    /// `to`, both included, or `None` if `to` is unreachable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is not in the graph.
+    pub fn shortest_path(&self, from: VertexId, to: VertexId) -> Option<Vec<VertexId>> {
+        self.bfs(from).path_to(to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::{Direction, Representation};
+    use super::*;
+
+    /// Returns the undirected graph of CLRS figure 22.3, with the vertices
+    /// `r, s, t, u, v, w, x, y`.
+    fn clrs_graph(representation: Representation) -> (Graph<char, ()>, Vec<VertexId>) {
+        let mut graph = Graph::with_representation(Direction::Undirected, representation);
+        let ids: Vec<_> = "rstuvwxy"
+            .chars()
+            .map(|name| graph.add_vertex(name))
+            .collect();
+        let id = |name| ids["rstuvwxy".find(name).expect("vertex exists")];
+        for (from, to) in [
+            ('r', 's'),
+            ('r', 'v'),
+            ('s', 'w'),
+            ('w', 't'),
+            ('w', 'x'),
+            ('t', 'x'),
+            ('t', 'u'),
+            ('x', 'u'),
+            ('x', 'y'),
+            ('u', 'y'),
+        ] {
+            graph.add_edge(id(from), id(to), ());
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn clrs_distances() {
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let (graph, ids) = clrs_graph(representation);
+            let tree = graph.bfs(ids[1]);
+            let distances: Vec<_> = ids.iter().map(|&id| tree.distance(id)).collect();
+            assert_eq!(
+                distances,
+                [1, 0, 2, 3, 2, 1, 2, 3].map(Some).to_vec(),
+                "{representation:?}"
+            );
+            assert_eq!(tree.source(), ids[1]);
+            assert_eq!(tree.predecessor(ids[1]), None);
+            assert_eq!(tree.predecessor(ids[4]), Some(ids[0]));
+
+            let path = graph.shortest_path(ids[4], ids[7]).expect("y is reachable");
+            let names: String = path
+                .iter()
+                .map(|&id| *graph.vertex(id).expect("vertex exists"))
+                .collect();
+            assert_eq!(names.len(), 6);
+            assert!(names.starts_with("vrsw") && names.ends_with('y'));
+        }
+    }
+
+    #[test]
+    fn unreachable_vertices() {
+        let mut graph = Graph::new(Direction::Directed);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, ());
+        assert_eq!(graph.shortest_path(a, b), Some(vec![a, b]));
+        assert_eq!(graph.shortest_path(b, a), None);
+        assert_eq!(graph.shortest_path(a, c), None);
+        assert_eq!(graph.shortest_path(c, c), Some(vec![c]));
+        assert_eq!(graph.bfs(a).distance(c), None);
+    }
+
+    #[quickcheck]
+    fn distances_satisfy_edges(edges: Vec<(u8, u8)>) -> bool {
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = (0..16).map(|_| graph.add_vertex(())).collect();
+        for (from, to) in edges {
+            graph.add_edge(ids[usize::from(from % 16)], ids[usize::from(to % 16)], ());
+        }
+        let tree = graph.bfs(ids[0]);
+        // An edge shortens a distance by at most one, and every path found
+        // has as many edges as its distance.
+        graph
+            .edges()
+            .all(|(from, to, _)| match tree.distance(from) {
+                Some(distance) => tree
+                    .distance(to)
+                    .map_or(false, |other| other <= distance + 1),
+                None => true,
+            })
+            && ids.iter().all(|&id| match tree.path_to(id) {
+                Some(path) => {
+                    path.len() == tree.distance(id).map_or(0, |distance| distance + 1)
+                        && path.windows(2).all(|pair| graph.has_edge(pair[0], pair[1]))
+                        && path[0] == ids[0]
+                }
+                None => tree.distance(id).is_none(),
+            })
+    }
+}
@@ -0,0 +1,330 @@
+//! Depth-first search.
+//!
+//! Depth-first search explores the edges of the most recently discovered
+//! vertex first, and backtracks once they are all explored. It timestamps
+//! each vertex when it is discovered and when it is finished, and the
+//! predecessors of the vertices form a forest of depth-first trees. An
+//! edge to a vertex which is discovered but not yet finished leads back to
+//! an ancestor, so it closes a cycle. See CLRS sections 22.3 and 22.4.
+
+use super::{Graph, Neighbors, VertexId};
+use crate::stack::DummyStack;
+use crate::{error::ErrorKind, Error};
+
+/// DepthFirstForest is the result of a depth-first search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthFirstForest {
+    /// `discovered[v]` is the time at which the vertex `v` was discovered.
+    discovered: Vec<Option<usize>>,
+
+    /// `finished[v]` is the time at which the neighbors of the vertex `v`
+    /// were all explored.
+    finished: Vec<Option<usize>>,
+
+    predecessors: Vec<Option<VertexId>>,
+
+    /// The vertices by increasing finishing time.
+    finish_order: Vec<VertexId>,
+
+    /// The first edge found leading back to an ancestor.
+    back_edge: Option<(VertexId, VertexId)>,
+
+    /// The clock giving the timestamps.
+    time: usize,
+}
+
+impl DepthFirstForest {
+    /// Returns the time at which the vertex was discovered, or `None` if it
+    /// was not reached.
+    ///
+    /// Timestamps range from 1 to twice the number of vertices reached.
+    pub fn discovery(&self, vertex: VertexId) -> Option<usize> {
+        *self.discovered.get(vertex.index())?
+    }
+
+    /// Returns the time at which the vertex was finished, or `None` if it
+    /// was not reached.
+    pub fn finish(&self, vertex: VertexId) -> Option<usize> {
+        *self.finished.get(vertex.index())?
+    }
+
+    /// Returns the parent of the vertex in its depth-first tree.
+    ///
+    /// The roots of the trees have no parent.
+    pub fn predecessor(&self, vertex: VertexId) -> Option<VertexId> {
+        *self.predecessors.get(vertex.index())?
+    }
+
+    /// Returns the vertices reached, by increasing finishing time.
+    pub fn finish_order(&self) -> &[VertexId] {
+        &self.finish_order
+    }
+
+    /// Returns an edge from a vertex to one of its ancestors, if the
+    /// search found one.
+    ///
+    /// In an undirected graph the edge back to the parent is not counted.
+    /// There is such an edge if and only if the part of the graph reached
+    /// has a cycle.
+    pub const fn back_edge(&self) -> Option<(VertexId, VertexId)> {
+        self.back_edge
+    }
+
+    /// Returns true if the vertex is discovered but not finished.
+    fn is_gray(&self, vertex: VertexId) -> bool {
+        self.discovered[vertex.index()].is_some() && self.finished[vertex.index()].is_none()
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Searches the whole graph depth-first, starting the trees from the
+    /// vertices by increasing identifier.
+    ///
+    /// It takes O(V + E) time with adjacency lists and O(V²) time with a
+    /// matrix.
+    pub fn dfs(&self) -> DepthFirstForest {
+        self.dfs_from(self.vertices())
+    }
+
+    /// Searches the graph depth-first, starting a new tree from each root
+    /// which is not yet discovered, in the given order.
+    ///
+    /// The roots which are not in the graph are skipped, and the vertices
+    /// unreachable from the roots are not discovered.
+    pub fn dfs_from(&self, roots: impl IntoIterator<Item = VertexId>) -> DepthFirstForest {
+        let bound = self.vertex_bound();
+        let mut forest = DepthFirstForest {
+            discovered: vec![None; bound],
+            finished: vec![None; bound],
+            predecessors: vec![None; bound],
+            finish_order: Vec::with_capacity(self.vertex_count()),
+            back_edge: None,
+            time: 0,
+        };
+        for root in roots {
+            if self.contains_vertex(root) && forest.discovered[root.index()].is_none() {
+                self.visit(root, &mut forest);
+            }
+        }
+        forest
+    }
+
+    /// Returns the vertices in an order in which every edge goes from a
+    /// vertex to a later one.
+    ///
+    /// The vertices are sorted by decreasing finishing time of a
+    /// depth-first search. It fails with [`ErrorKind::CycleDetected`] if
+    /// the graph has a cycle, in which case there is no such order. An
+    /// undirected edge is a cycle between its two ends.
+    pub fn topological_sort(&self) -> Result<Vec<VertexId>, Error> {
+        if !self.is_directed() && self.edge_count() > 0 {
+            return Err(Error::new(ErrorKind::CycleDetected));
+        }
+        let forest = self.dfs();
+        if forest.back_edge().is_some() {
+            return Err(Error::new(ErrorKind::CycleDetected));
+        }
+        let mut order = forest.finish_order;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Returns true if the graph has no cycle.
+    pub fn is_acyclic(&self) -> bool {
+        self.dfs().back_edge().is_none()
+    }
+
+    /// Explores the vertices reachable from the root which are not yet
+    /// discovered.
+    ///
+    /// The stack holds the gray vertices with the iterator over their
+    /// neighbors left to explore, the top being the most recently
+    /// discovered.
+    fn visit(&self, root: VertexId, forest: &mut DepthFirstForest) {
+        let mut stack: DummyStack<(VertexId, Neighbors<'_, E>)> = DummyStack::new();
+        forest.time += 1;
+        forest.discovered[root.index()] = Some(forest.time);
+        stack.push((root, self.neighbors(root)));
+        while let Some((vertex, mut neighbors)) = stack.pop() {
+            let parent = forest.predecessors[vertex.index()];
+            let white = neighbors.find_map(|(neighbor, _)| {
+                if forest.discovered[neighbor.index()].is_none() {
+                    return Some(neighbor);
+                }
+                let to_parent = !self.is_directed() && Some(neighbor) == parent;
+                if forest.back_edge.is_none() && forest.is_gray(neighbor) && !to_parent {
+                    forest.back_edge = Some((vertex, neighbor));
+                }
+                None
+            });
+            match white {
+                Some(neighbor) => {
+                    forest.time += 1;
+                    forest.discovered[neighbor.index()] = Some(forest.time);
+                    forest.predecessors[neighbor.index()] = Some(vertex);
+                    stack.push((vertex, neighbors));
+                    stack.push((neighbor, self.neighbors(neighbor)));
+                }
+                None => {
+                    forest.time += 1;
+                    forest.finished[vertex.index()] = Some(forest.time);
+                    forest.finish_order.push(vertex);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::{Direction, Representation};
+    use super::*;
+
+    /// Returns the directed graph of CLRS figure 22.7, the dependencies
+    /// between the garments of professor Bumstead.
+    fn getting_dressed(representation: Representation) -> Graph<&'static str, ()> {
+        let mut graph = Graph::with_representation(Direction::Directed, representation);
+        let garments = [
+            "undershorts",
+            "pants",
+            "belt",
+            "shirt",
+            "tie",
+            "jacket",
+            "socks",
+            "shoes",
+            "watch",
+        ];
+        let ids = garments.map(|garment| graph.add_vertex(garment));
+        for (from, to) in [
+            (0, 1),
+            (0, 7),
+            (1, 2),
+            (1, 7),
+            (2, 5),
+            (3, 2),
+            (3, 4),
+            (4, 5),
+            (6, 7),
+        ] {
+            graph.add_edge(ids[from], ids[to], ());
+        }
+        graph
+    }
+
+    /// Returns true if every edge goes forward in the order.
+    fn is_topological<V, E>(graph: &Graph<V, E>, order: &[VertexId]) -> bool {
+        let mut position = vec![usize::MAX; graph.vertex_bound()];
+        for (index, vertex) in order.iter().enumerate() {
+            position[vertex.index()] = index;
+        }
+        order.len() == graph.vertex_count()
+            && graph
+                .edges()
+                .all(|(from, to, _)| position[from.index()] < position[to.index()])
+    }
+
+    #[test]
+    fn parenthesis_structure() {
+        let graph = getting_dressed(Representation::AdjacencyList);
+        let forest = graph.dfs();
+        let interval = |vertex| {
+            (
+                forest.discovery(vertex).expect("every vertex is reached"),
+                forest.finish(vertex).expect("every vertex is reached"),
+            )
+        };
+        for u in graph.vertices() {
+            let (start, end) = interval(u);
+            assert!(start < end && end <= 2 * graph.vertex_count());
+            for v in graph.vertices() {
+                let (other_start, other_end) = interval(v);
+                let disjoint = end < other_start || other_end < start;
+                let nested = (start < other_start && other_end < end)
+                    || (other_start < start && end < other_end);
+                assert!(u == v || disjoint || nested);
+            }
+        }
+        assert_eq!(forest.finish_order().len(), graph.vertex_count());
+        assert_eq!(forest.back_edge(), None);
+    }
+
+    #[test]
+    fn getting_dressed_order() {
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let graph = getting_dressed(representation);
+            let order = graph.topological_sort().expect("the graph is acyclic");
+            assert!(is_topological(&graph, &order));
+            assert!(graph.is_acyclic());
+        }
+    }
+
+    #[test]
+    fn detect_cycles() {
+        let mut graph = Graph::new(Direction::Directed);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        assert_eq!(graph.topological_sort(), Ok(vec![a, b, c]));
+        graph.add_edge(c, a, ());
+        assert_eq!(
+            graph.topological_sort(),
+            Err(Error::new(ErrorKind::CycleDetected))
+        );
+        assert_eq!(graph.dfs().back_edge(), Some((c, a)));
+
+        let mut graph = Graph::new(Direction::Undirected);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        assert!(graph.is_acyclic());
+        assert!(graph.topological_sort().is_err());
+        graph.add_edge(a, c, ());
+        assert!(!graph.is_acyclic());
+    }
+
+    #[test]
+    fn search_from_roots() {
+        let mut graph = Graph::new(Direction::Directed);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(b, a, ());
+        let forest = graph.dfs_from([b]);
+        assert_eq!(forest.finish_order(), [a, b]);
+        assert_eq!(forest.predecessor(a), Some(b));
+        assert_eq!(forest.discovery(c), None);
+    }
+
+    #[quickcheck]
+    fn sorts_random_dags(edges: Vec<(u8, u8)>) -> bool {
+        // Edges from a smaller to a larger vertex never close a cycle.
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = (0..16).map(|_| graph.add_vertex(())).collect();
+        for (from, to) in edges {
+            let (from, to) = (usize::from(from % 16), usize::from(to % 16));
+            if from < to {
+                graph.add_edge(ids[to], ids[from], ());
+            }
+        }
+        graph
+            .topological_sort()
+            .map_or(false, |order| is_topological(&graph, &order))
+    }
+
+    #[quickcheck]
+    fn cycle_detection_agrees_with_sort(edges: Vec<(u8, u8)>) -> bool {
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = (0..8).map(|_| graph.add_vertex(())).collect();
+        for (from, to) in edges {
+            graph.add_edge(ids[usize::from(from % 8)], ids[usize::from(to % 8)], ());
+        }
+        match graph.topological_sort() {
+            Ok(order) => graph.is_acyclic() && is_topological(&graph, &order),
+            Err(error) => !graph.is_acyclic() && error == Error::new(ErrorKind::CycleDetected),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Edge-list text format.
+//!
+//! An edge list describes a graph one line at a time, like the DIMACS
+//! files used by graph benchmarks. A header gives the direction of the
+//! edges and the number of vertices, numbered from `0`, and each following
+//! line is an edge given by the indices of its endpoints, and its weight in
+//! a weighted edge list:
+//!
+//! ```text
+//! # The triangle of CLRS figure 23.4, with an isolated vertex.
+//! undirected 4
+//! 0 1 4
+//! 1 2 8
+//! 0 2 11
+//! ```
+//!
+//! Blank lines and lines starting with `#` are skipped.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use super::{Direction, Graph, Representation, VertexId};
+use crate::{error::ErrorKind, Error};
+
+impl Graph<(), ()> {
+    /// Reads a graph from an unweighted edge list.
+    ///
+    /// It fails with an error of kind [`io::ErrorKind::InvalidData`],
+    /// wrapping an [`ErrorKind::InvalidEdgeList`], if the header is
+    /// missing, or a line is not an edge between two vertices of the graph.
+    pub fn from_edge_list(
+        reader: impl BufRead,
+        representation: Representation,
+    ) -> io::Result<Self> {
+        read_edge_list(reader, representation, |weight| {
+            weight.is_none().then_some(())
+        })
+    }
+}
+
+impl<W: FromStr + Clone> Graph<(), W> {
+    /// Reads a graph from a weighted edge list.
+    ///
+    /// It fails like [`Graph::from_edge_list`], and also if a weight is
+    /// missing or cannot be parsed.
+    pub fn from_weighted_edge_list(
+        reader: impl BufRead,
+        representation: Representation,
+    ) -> io::Result<Self> {
+        read_edge_list(reader, representation, |weight| weight?.parse().ok())
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Writes the graph as an unweighted edge list.
+    ///
+    /// The data of the vertices and of the edges is not written. The vertices
+    /// are numbered by their index, so a removed vertex is read back as an
+    /// isolated vertex.
+    pub fn to_edge_list(&self, mut writer: impl Write) -> io::Result<()> {
+        self.write_header(&mut writer)?;
+        for (from, to, _) in self.edges() {
+            writeln!(writer, "{} {}", from.index(), to.index())?;
+        }
+        Ok(())
+    }
+
+    /// Writes the direction of the edges and the number of vertices.
+    pub(super) fn write_header(&self, writer: &mut impl Write) -> io::Result<()> {
+        let direction = if self.is_directed() {
+            "directed"
+        } else {
+            "undirected"
+        };
+        writeln!(writer, "{} {}", direction, self.vertex_bound())
+    }
+}
+
+impl<V, E: Display> Graph<V, E> {
+    /// Writes the graph as a weighted edge list, the data of each edge
+    /// being its weight.
+    ///
+    /// Vertices are numbered as by [`Graph::to_edge_list`].
+    pub fn to_weighted_edge_list(&self, mut writer: impl Write) -> io::Result<()> {
+        self.write_header(&mut writer)?;
+        for (from, to, weight) in self.edges() {
+            writeln!(writer, "{} {} {}", from.index(), to.index(), weight)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads an edge list, the data of each edge being parsed from the third
+/// field of its line.
+fn read_edge_list<E: Clone>(
+    reader: impl BufRead,
+    representation: Representation,
+    parse: impl Fn(Option<&str>) -> Option<E>,
+) -> io::Result<Graph<(), E>> {
+    let mut graph: Option<Graph<(), E>> = None;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(graph) = &mut graph else {
+            graph = Some(read_header(line, representation).ok_or_else(invalid_edge_list)?);
+            continue;
+        };
+        let mut fields = line.split_whitespace();
+        let from = VertexId(parse_field(fields.next())?);
+        let to = VertexId(parse_field(fields.next())?);
+        let data = parse(fields.next()).ok_or_else(invalid_edge_list)?;
+        if fields.next().is_some() || !graph.contains_vertex(from) || !graph.contains_vertex(to) {
+            return Err(invalid_edge_list());
+        }
+        graph.add_edge(from, to, data);
+    }
+    graph.ok_or_else(invalid_edge_list)
+}
+
+/// Parses the header line giving the direction of the edges and the number
+/// of vertices into a graph without edges.
+pub(super) fn read_header<E>(line: &str, representation: Representation) -> Option<Graph<(), E>> {
+    let mut fields = line.split_whitespace();
+    let direction = match fields.next()? {
+        "directed" => Direction::Directed,
+        "undirected" => Direction::Undirected,
+        _ => return None,
+    };
+    let count: usize = fields.next()?.parse().ok()?;
+    if fields.next().is_some() {
+        return None;
+    }
+    let mut graph = Graph::with_representation(direction, representation);
+    for _ in 0..count {
+        graph.add_vertex(());
+    }
+    Some(graph)
+}
+
+/// Parses a field of a line of an edge list.
+fn parse_field<T: FromStr>(field: Option<&str>) -> io::Result<T> {
+    field
+        .and_then(|field| field.parse().ok())
+        .ok_or_else(invalid_edge_list)
+}
+
+fn invalid_edge_list() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        Error::new(ErrorKind::InvalidEdgeList),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    const REPRESENTATIONS: [Representation; 2] = [
+        Representation::AdjacencyList,
+        Representation::AdjacencyMatrix,
+    ];
+
+    /// Returns the edges of the graph as sorted index triples.
+    fn sorted_edges<V, E: Ord + Clone>(graph: &Graph<V, E>) -> Vec<(usize, usize, E)> {
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|(from, to, data)| (from.index(), to.index(), data.clone()))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn read_and_write_edge_lists() {
+        let weighted = "# CLRS figure 23.4\nundirected 4\n\n0 1 4\n0 2 11\n1 2 8\n";
+        let unweighted = "directed 3\n0 1\n1 2\n2 0\n";
+        for representation in REPRESENTATIONS {
+            let graph: Graph<(), u32> =
+                Graph::from_weighted_edge_list(weighted.as_bytes(), representation)
+                    .expect("the edge list is valid");
+            assert_eq!(graph.representation(), representation);
+            assert!(!graph.is_directed());
+            assert_eq!(graph.vertex_count(), 4);
+            assert_eq!(graph.edge(VertexId(2), VertexId(1)), Some(&8));
+            let mut output = Vec::new();
+            graph
+                .to_weighted_edge_list(&mut output)
+                .expect("writing to a vector cannot fail");
+            assert_eq!(
+                String::from_utf8(output).expect("edge lists are valid UTF-8"),
+                weighted
+                    .replace("# CLRS figure 23.4\n", "")
+                    .replace("\n\n", "\n")
+            );
+
+            let graph = Graph::from_edge_list(unweighted.as_bytes(), representation)
+                .expect("the edge list is valid");
+            assert!(graph.is_directed());
+            assert!(graph.has_edge(VertexId(2), VertexId(0)));
+            assert!(!graph.has_edge(VertexId(0), VertexId(2)));
+            let mut output = Vec::new();
+            graph
+                .to_edge_list(&mut output)
+                .expect("writing to a vector cannot fail");
+            assert_eq!(output, unweighted.as_bytes());
+        }
+    }
+
+    #[test]
+    fn invalid_edge_lists() {
+        let invalid = [
+            "",
+            "# no header\n",
+            "sideways 2\n",
+            "directed\n",
+            "directed 2 3\n",
+            "directed 2\n0\n",
+            "directed 2\n0 2\n",
+            "directed 2\n0 1 5\n",
+            "directed 2\nzero one\n",
+        ];
+        for input in invalid {
+            let error = Graph::from_edge_list(input.as_bytes(), Representation::AdjacencyList)
+                .expect_err("the edge list is invalid");
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(
+                error.into_inner().map(|error| error.to_string()),
+                Some(Error::new(ErrorKind::InvalidEdgeList).to_string())
+            );
+        }
+        let missing_weight = "directed 2\n0 1\n";
+        assert!(Graph::<(), i32>::from_weighted_edge_list(
+            missing_weight.as_bytes(),
+            Representation::AdjacencyList
+        )
+        .is_err());
+    }
+
+    #[quickcheck]
+    fn round_trip(edges: Vec<(u8, u8, i16)>, directed: bool, size: u8) -> bool {
+        let direction = if directed {
+            Direction::Directed
+        } else {
+            Direction::Undirected
+        };
+        let size = usize::from(size % 16) + 1;
+        REPRESENTATIONS.iter().all(|&representation| {
+            let mut graph = Graph::with_representation(direction, representation);
+            let vertices: Vec<_> = (0..size).map(|_| graph.add_vertex(())).collect();
+            for &(from, to, weight) in &edges {
+                let (from, to) = (usize::from(from) % size, usize::from(to) % size);
+                graph.add_edge(vertices[from], vertices[to], weight);
+            }
+
+            let mut weighted = Vec::new();
+            let mut unweighted = Vec::new();
+            graph
+                .to_weighted_edge_list(&mut weighted)
+                .expect("writing to a vector cannot fail");
+            graph
+                .to_edge_list(&mut unweighted)
+                .expect("writing to a vector cannot fail");
+            let read: Graph<(), i16> =
+                Graph::from_weighted_edge_list(&weighted[..], representation)
+                    .expect("the written edge list is valid");
+            let read_unweighted = Graph::from_edge_list(&unweighted[..], representation)
+                .expect("the written edge list is valid");
+
+            read.direction() == direction
+                && read.vertex_count() == size
+                && sorted_edges(&read) == sorted_edges(&graph)
+                && sorted_edges(&read_unweighted)
+                    == sorted_edges(&graph)
+                        .into_iter()
+                        .map(|(from, to, _)| (from, to, ()))
+                        .collect::<Vec<_>>()
+        })
+    }
+}
@@ -0,0 +1,434 @@
+//! Maximum flow.
+//!
+//! In a flow network each edge has a capacity, and a flow assigns to each
+//! edge an amount no larger than its capacity, such that the amount
+//! entering a vertex equals the amount leaving it, except at the source
+//! and the sink. The residual network has an arc wherever the flow can
+//! still be increased, either along an edge which is not saturated or by
+//! cancelling flow on the opposite edge. The Ford-Fulkerson method
+//! augments the flow along paths of the residual network from the source
+//! to the sink until there is none, and the Edmonds-Karp algorithm picks a
+//! path with the fewest arcs by breadth-first search, which bounds the
+//! number of augmentations by O(V E). See CLRS sections 26.2 and 26.3.
+
+use super::{Direction, Graph, VertexId};
+use crate::queue::BoundedQueue;
+
+/// MaxFlow is a maximum flow from a source to a sink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxFlow {
+    value: i64,
+    direction: Direction,
+
+    /// The flow along each edge, in the order of [`Graph::edges`].
+    flows: Vec<(VertexId, VertexId, i64)>,
+
+    /// The vertices reachable from the source in the final residual
+    /// network.
+    source_side: Vec<VertexId>,
+}
+
+impl MaxFlow {
+    /// Returns the amount of flow leaving the source.
+    pub const fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Returns the flow along each edge, in the order of [`Graph::edges`].
+    ///
+    /// The flow along an undirected edge is negative if it goes from the
+    /// second vertex to the first.
+    pub fn flows(&self) -> &[(VertexId, VertexId, i64)] {
+        &self.flows
+    }
+
+    /// Returns the flow along the edge from `from` to `to`, or `None` if
+    /// there is no such edge.
+    ///
+    /// The flow along an undirected edge is negative if it goes from `to`
+    /// to `from`. It takes O(E) time.
+    pub fn flow(&self, from: VertexId, to: VertexId) -> Option<i64> {
+        self.flows.iter().find_map(|&(u, v, flow)| {
+            if (u, v) == (from, to) {
+                Some(flow)
+            } else if self.direction == Direction::Undirected && (v, u) == (from, to) {
+                Some(-flow)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the source side of a minimum cut, the vertices still
+    /// reachable from the source in the residual network.
+    ///
+    /// The edges leaving this set are saturated, and their total capacity
+    /// equals the value of the flow.
+    pub fn min_cut(&self) -> &[VertexId] {
+        &self.source_side
+    }
+}
+
+/// Residual network of a flow network.
+///
+/// Arcs are stored in pairs: the arc `a ^ 1` is the reverse of the arc
+/// `a`, and carries the opposite flow.
+#[derive(Debug, Clone)]
+struct ResidualNetwork {
+    heads: Vec<usize>,
+    capacities: Vec<i64>,
+    flows: Vec<i64>,
+
+    /// `arcs[v]` holds the indices of the arcs leaving the vertex `v`.
+    arcs: Vec<Vec<usize>>,
+}
+
+impl ResidualNetwork {
+    /// Creates new network without arcs.
+    fn new(bound: usize) -> Self {
+        Self {
+            heads: Vec::new(),
+            capacities: Vec::new(),
+            flows: Vec::new(),
+            arcs: vec![Vec::new(); bound],
+        }
+    }
+
+    /// Adds an arc with the given capacity and its reverse with the
+    /// capacity `reverse`, and returns the index of the arc.
+    fn add_arc(&mut self, from: usize, to: usize, capacity: i64, reverse: i64) -> usize {
+        let arc = self.heads.len();
+        for (tail, head, capacity) in [(from, to, capacity), (to, from, reverse)] {
+            self.arcs[tail].push(self.heads.len());
+            self.heads.push(head);
+            self.capacities.push(capacity);
+            self.flows.push(0);
+        }
+        arc
+    }
+
+    /// Returns the amount by which the flow along the arc can increase.
+    fn residual(&self, arc: usize) -> i64 {
+        self.capacities[arc] - self.flows[arc]
+    }
+
+    /// Searches the residual network breadth-first from the source, and
+    /// returns the arc through which each vertex was reached.
+    ///
+    /// The source is marked as reached through no arc.
+    fn search(&self, source: usize) -> Vec<Option<Option<usize>>> {
+        let mut via = vec![None; self.arcs.len()];
+        let mut queue = BoundedQueue::with_capacity(self.arcs.len());
+        via[source] = Some(None);
+        queue.enqueue(source).expect("the queue holds every vertex");
+        while let Ok(vertex) = queue.dequeue() {
+            for &arc in &self.arcs[vertex] {
+                let head = self.heads[arc];
+                if via[head].is_none() && self.residual(arc) > 0 {
+                    via[head] = Some(Some(arc));
+                    queue.enqueue(head).expect("the queue holds every vertex");
+                }
+            }
+        }
+        via
+    }
+
+    /// Augments the flow along shortest paths until the sink is
+    /// unreachable, and returns the value of the flow.
+    fn edmonds_karp(&mut self, source: usize, sink: usize) -> i64 {
+        let mut value = 0;
+        loop {
+            let via = self.search(source);
+            if via[sink].is_none() {
+                return value;
+            }
+            let mut path = Vec::new();
+            let mut vertex = sink;
+            while let Some(Some(arc)) = via[vertex] {
+                path.push(arc);
+                vertex = self.heads[arc ^ 1];
+            }
+            let bottleneck = path
+                .iter()
+                .map(|&arc| self.residual(arc))
+                .min()
+                .expect("the source and the sink are distinct");
+            for arc in path {
+                self.flows[arc] += bottleneck;
+                self.flows[arc ^ 1] -= bottleneck;
+            }
+            value += bottleneck;
+        }
+    }
+}
+
+impl<V, E: Copy + Into<i64>> Graph<V, E> {
+    /// Returns a maximum flow from the source to the sink, computed by the
+    /// Edmonds-Karp algorithm, where the data of an edge is its capacity.
+    ///
+    /// An undirected edge can carry flow in either direction. It takes
+    /// O(V E²) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source or the sink is not in the graph, if they are
+    /// the same vertex, or if an edge has a negative capacity.
+    pub fn max_flow(&self, source: VertexId, sink: VertexId) -> MaxFlow {
+        assert!(
+            self.contains_vertex(source) && self.contains_vertex(sink),
+            "vertex is not in the graph"
+        );
+        assert_ne!(source, sink, "the source is the sink");
+        let mut network = ResidualNetwork::new(self.vertex_bound());
+        let edges: Vec<_> = self
+            .edges()
+            .map(|(from, to, &capacity)| {
+                let capacity = capacity.into();
+                assert!(capacity >= 0, "edge capacity is negative");
+                let reverse = if self.is_directed() { 0 } else { capacity };
+                (
+                    from,
+                    to,
+                    network.add_arc(from.index(), to.index(), capacity, reverse),
+                )
+            })
+            .collect();
+
+        let value = network.edmonds_karp(source.index(), sink.index());
+        let reached = network.search(source.index());
+        MaxFlow {
+            value,
+            direction: self.direction(),
+            flows: edges
+                .into_iter()
+                .map(|(from, to, arc)| (from, to, network.flows[arc]))
+                .collect(),
+            source_side: self
+                .vertices()
+                .filter(|vertex| reached[vertex.index()].is_some())
+                .collect(),
+        }
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Returns a maximum matching of a bipartite graph, a largest set of
+    /// edges without common vertex.
+    ///
+    /// The vertices of `left` form one side of the graph and the other
+    /// vertices the other side. Each edge becomes an arc of capacity one
+    /// from the left to the right, a new source feeds every left vertex
+    /// and every right vertex drains into a new sink, so an integral
+    /// maximum flow selects a maximum matching. The edges inside a side are
+    /// ignored, and so are the edges from the right to the left in a
+    /// directed graph. The pairs are given as `(left, right)`. See CLRS section
+    /// 26.3.
+    pub fn bipartite_matching(&self, left: &[VertexId]) -> Vec<(VertexId, VertexId)> {
+        let mut is_left = vec![false; self.vertex_bound()];
+        for vertex in left {
+            if self.contains_vertex(*vertex) {
+                is_left[vertex.index()] = true;
+            }
+        }
+
+        let mut network = self.skeleton(Direction::Directed);
+        for (from, to, _) in self.edges() {
+            match (is_left[from.index()], is_left[to.index()]) {
+                (true, false) => network.add_edge(from, to, 1),
+                (false, true) if !self.is_directed() => network.add_edge(to, from, 1),
+                _ => None,
+            };
+        }
+        let source = network.add_vertex(());
+        let sink = network.add_vertex(());
+        for vertex in self.vertices() {
+            if is_left[vertex.index()] {
+                network.add_edge(source, vertex, 1);
+            } else {
+                network.add_edge(vertex, sink, 1);
+            }
+        }
+
+        network
+            .max_flow(source, sink)
+            .flows()
+            .iter()
+            .filter(|&&(from, to, flow)| flow > 0 && from != source && to != sink)
+            .map(|&(from, to, _)| (from, to))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::Representation;
+    use super::*;
+
+    /// Returns the flow network of CLRS figure 26.1, with the vertices
+    /// `s, v1, v2, v3, v4, t`.
+    fn clrs_network(representation: Representation) -> (Graph<(), u8>, Vec<VertexId>) {
+        let mut graph = Graph::with_representation(Direction::Directed, representation);
+        let ids: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        for (from, to, capacity) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ] {
+            graph.add_edge(ids[from], ids[to], capacity);
+        }
+        (graph, ids)
+    }
+
+    /// Returns true if the flow respects the capacities and is conserved
+    /// at every vertex but the source and the sink.
+    fn is_feasible<V>(
+        graph: &Graph<V, u8>,
+        flow: &MaxFlow,
+        source: VertexId,
+        sink: VertexId,
+    ) -> bool {
+        let mut excess = vec![0; graph.vertex_bound()];
+        let respects_capacities = flow.flows().iter().all(|&(from, to, amount)| {
+            excess[from.index()] -= amount;
+            excess[to.index()] += amount;
+            let capacity = i64::from(*graph.edge(from, to).expect("edge exists"));
+            let lower = if graph.is_directed() { 0 } else { -capacity };
+            lower <= amount && amount <= capacity
+        });
+        respects_capacities
+            && excess[sink.index()] == flow.value()
+            && excess[source.index()] == -flow.value()
+            && graph
+                .vertices()
+                .filter(|&vertex| vertex != source && vertex != sink)
+                .all(|vertex| excess[vertex.index()] == 0)
+    }
+
+    #[test]
+    fn clrs_max_flow() {
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let (graph, ids) = clrs_network(representation);
+            let flow = graph.max_flow(ids[0], ids[5]);
+            assert_eq!(flow.value(), 23);
+            assert!(is_feasible(&graph, &flow, ids[0], ids[5]));
+            assert_eq!(flow.flow(ids[3], ids[5]), Some(19));
+            assert_eq!(flow.flow(ids[5], ids[3]), None);
+            assert_eq!(flow.min_cut(), [ids[0], ids[1], ids[2], ids[4]]);
+        }
+    }
+
+    #[test]
+    fn undirected_network() {
+        let mut graph = Graph::new(Direction::Undirected);
+        let [s, a, b, t] = [(); 4].map(|()| graph.add_vertex(()));
+        graph.add_edge(s, a, 3_u8);
+        graph.add_edge(s, b, 2);
+        graph.add_edge(b, a, 5);
+        graph.add_edge(a, t, 4);
+        let flow = graph.max_flow(s, t);
+        assert_eq!(flow.value(), 4);
+        assert_eq!(flow.flow(b, a), Some(1));
+        assert!(is_feasible(&graph, &flow, s, t));
+        assert_eq!(graph.max_flow(t, s).value(), 4);
+    }
+
+    #[test]
+    fn bipartite_matching() {
+        // CLRS figure 26.8, with the left vertices 0 to 4 and the right
+        // vertices 5 to 8.
+        let mut graph = Graph::new(Direction::Undirected);
+        let ids: Vec<_> = (0..9).map(|_| graph.add_vertex(())).collect();
+        for (left, right) in [
+            (0, 5),
+            (1, 5),
+            (1, 7),
+            (2, 6),
+            (2, 7),
+            (2, 8),
+            (3, 7),
+            (4, 7),
+        ] {
+            graph.add_edge(ids[right], ids[left], ());
+        }
+        let matching = graph.bipartite_matching(&ids[..5]);
+        assert_eq!(matching.len(), 3);
+        assert!(matching
+            .iter()
+            .all(|&(left, right)| left.index() < 5 && graph.has_edge(left, right)));
+    }
+
+    #[quickcheck]
+    fn max_flow_equals_min_cut(edges: Vec<(u8, u8, u8)>) -> bool {
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = (0..8).map(|_| graph.add_vertex(())).collect();
+        for (from, to, capacity) in edges {
+            graph.add_edge(
+                ids[usize::from(from % 8)],
+                ids[usize::from(to % 8)],
+                capacity,
+            );
+        }
+        let (source, sink) = (ids[0], ids[7]);
+        let flow = graph.max_flow(source, sink);
+        let mut in_cut = vec![false; graph.vertex_bound()];
+        for vertex in flow.min_cut() {
+            in_cut[vertex.index()] = true;
+        }
+        let cut: i64 = graph
+            .edges()
+            .filter(|(from, to, _)| in_cut[from.index()] && !in_cut[to.index()])
+            .map(|(_, _, &capacity)| i64::from(capacity))
+            .sum();
+        is_feasible(&graph, &flow, source, sink)
+            && in_cut[source.index()]
+            && !in_cut[sink.index()]
+            && cut == flow.value()
+    }
+
+    #[quickcheck]
+    fn matching_is_maximum(edges: Vec<(u8, u8)>) -> bool {
+        let mut graph = Graph::new(Direction::Undirected);
+        let ids: Vec<_> = (0..8).map(|_| graph.add_vertex(())).collect();
+        for (left, right) in edges {
+            graph.add_edge(
+                ids[usize::from(left % 4)],
+                ids[4 + usize::from(right % 4)],
+                (),
+            );
+        }
+        let matching = graph.bipartite_matching(&ids[..4]);
+
+        // Compare the size with the best of all the subsets of edges.
+        let edges: Vec<_> = graph.edges().map(|(from, to, _)| (from, to)).collect();
+        let best = (0..1_u32 << edges.len())
+            .filter(|subset| {
+                let mut used = [false; 8];
+                edges.iter().enumerate().all(|(index, &(from, to))| {
+                    subset & (1 << index) == 0
+                        || !std::mem::replace(&mut used[from.index()], true)
+                            && !std::mem::replace(&mut used[to.index()], true)
+                })
+            })
+            .map(u32::count_ones)
+            .max()
+            .unwrap_or(0);
+        let mut used = [false; 8];
+        matching.len() == best as usize
+            && matching.iter().all(|&(left, right)| {
+                graph.has_edge(left, right)
+                    && !std::mem::replace(&mut used[left.index()], true)
+                    && !std::mem::replace(&mut used[right.index()], true)
+            })
+    }
+}
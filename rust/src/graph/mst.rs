@@ -0,0 +1,234 @@
+//! Minimum spanning trees.
+//!
+//! A spanning tree of a connected undirected graph is a subset of its edges
+//! joining all the vertices without cycle, and a minimum spanning tree is
+//! one of least total weight. Both algorithms grow a set of edges which is
+//! always part of a minimum spanning tree, adding a lightest edge crossing a
+//! cut. Kruskal's algorithm considers the edges by increasing weight and
+//! keeps those joining two different trees of the forest built so far.
+//! Prim's algorithm grows a single tree from a root, always adding the
+//! lightest edge leaving it. See CLRS chapter 23.
+
+use super::{Graph, VertexId};
+use crate::disjoint_set::{DisjointSet, DisjointSetForest};
+use crate::heap::priority_queue::PriorityQueue;
+
+/// SpanningTree is a minimum spanning forest of a graph.
+///
+/// It has a tree per connected component of the graph, and it is a
+/// spanning tree if the graph is connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningTree {
+    edges: Vec<(VertexId, VertexId, i64)>,
+    weight: i64,
+}
+
+impl SpanningTree {
+    /// Returns the edges of the tree with their weight, in the order they
+    /// were added.
+    pub fn edges(&self) -> &[(VertexId, VertexId, i64)] {
+        &self.edges
+    }
+
+    /// Returns the total weight of the edges of the tree.
+    pub const fn weight(&self) -> i64 {
+        self.weight
+    }
+
+    /// Adds an edge to the tree.
+    fn push(&mut self, from: VertexId, to: VertexId, weight: i64) {
+        self.edges.push((from, to, weight));
+        self.weight += weight;
+    }
+}
+
+impl<V, E: Copy + Into<i64>> Graph<V, E> {
+    /// Returns a minimum spanning forest computed by Kruskal's algorithm.
+    ///
+    /// The trees of the forest built so far are kept in a disjoint-set
+    /// forest. It takes O(E lg E) time to sort the edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph is directed.
+    pub fn kruskal(&self) -> SpanningTree {
+        assert!(!self.is_directed(), "graph is directed");
+        let mut edges: Vec<_> = self
+            .edges()
+            .map(|(from, to, &weight)| (weight.into(), from, to))
+            .collect();
+        edges.sort_unstable();
+
+        let mut forest = DisjointSetForest::with_len(self.vertex_bound());
+        let mut tree = SpanningTree {
+            edges: Vec::new(),
+            weight: 0,
+        };
+        for (weight, from, to) in edges {
+            if forest.union(from.index(), to.index()) {
+                tree.push(from, to, weight);
+            }
+        }
+        tree
+    }
+
+    /// Returns a minimum spanning forest computed by Prim's algorithm.
+    ///
+    /// The vertices outside the tree wait in a priority queue keyed by the
+    /// weight of the lightest edge joining them to the tree, decreased
+    /// through the handle of the vertex, so it takes O(E log V) time. A
+    /// tree is grown from each vertex which is not yet in a tree, by
+    /// increasing identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph is directed.
+    pub fn prim(&self) -> SpanningTree {
+        assert!(!self.is_directed(), "graph is directed");
+        let bound = self.vertex_bound();
+        let mut keys: Vec<Option<i64>> = vec![None; bound];
+        let mut parents = vec![None; bound];
+        let mut in_tree = vec![false; bound];
+        // The keys are pairs of an edge weight and a vertex index.
+        let mut queue = PriorityQueue::with_capacity(self.vertex_count());
+        let mut handles = vec![None; bound];
+        let mut tree = SpanningTree {
+            edges: Vec::new(),
+            weight: 0,
+        };
+
+        for root in self.vertices() {
+            if in_tree[root.index()] {
+                continue;
+            }
+            keys[root.index()] = Some(0);
+            handles[root.index()] = Some(queue.push((0, root.index())));
+            while let Some((_, (key, index))) = queue.pop() {
+                let vertex = VertexId(index);
+                in_tree[index] = true;
+                if let Some(parent) = parents[index] {
+                    tree.push(parent, vertex, key);
+                }
+                for (neighbor, &weight) in self.neighbors(vertex) {
+                    let weight = weight.into();
+                    let key = &mut keys[neighbor.index()];
+                    if in_tree[neighbor.index()] || key.map_or(false, |key| key <= weight) {
+                        continue;
+                    }
+                    *key = Some(weight);
+                    parents[neighbor.index()] = Some(vertex);
+                    match handles[neighbor.index()] {
+                        Some(handle) => {
+                            queue.decrease_key(handle, (weight, neighbor.index()));
+                        }
+                        None => {
+                            handles[neighbor.index()] = Some(queue.push((weight, neighbor.index())))
+                        }
+                    }
+                }
+            }
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::{Direction, Representation};
+    use super::*;
+
+    /// Returns the undirected graph of CLRS figure 23.1, with the vertices
+    /// `a` to `i`.
+    fn clrs_graph(representation: Representation) -> Graph<char, u8> {
+        let mut graph = Graph::with_representation(Direction::Undirected, representation);
+        let ids: Vec<_> = ('a'..='i').map(|name| graph.add_vertex(name)).collect();
+        let id = |name: char| ids[usize::from(name as u8 - b'a')];
+        for (from, to, weight) in [
+            ('a', 'b', 4),
+            ('a', 'h', 8),
+            ('b', 'c', 8),
+            ('b', 'h', 11),
+            ('c', 'd', 7),
+            ('c', 'f', 4),
+            ('c', 'i', 2),
+            ('d', 'e', 9),
+            ('d', 'f', 14),
+            ('e', 'f', 10),
+            ('f', 'g', 2),
+            ('g', 'h', 1),
+            ('g', 'i', 6),
+            ('h', 'i', 7),
+        ] {
+            graph.add_edge(id(from), id(to), weight);
+        }
+        graph
+    }
+
+    /// Returns true if the edges of the tree are edges of the graph with
+    /// the same weight, and do not close a cycle.
+    fn is_forest(graph: &Graph<(), u8>, tree: &SpanningTree) -> bool {
+        let mut forest = DisjointSetForest::with_len(graph.vertex_bound());
+        tree.edges().iter().all(|&(from, to, weight)| {
+            graph.edge(from, to).map(|&weight| i64::from(weight)) == Some(weight)
+                && forest.union(from.index(), to.index())
+        }) && tree.edges().iter().map(|edge| edge.2).sum::<i64>() == tree.weight()
+    }
+
+    #[test]
+    fn clrs_spanning_tree() {
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let graph = clrs_graph(representation);
+            let kruskal = graph.kruskal();
+            let prim = graph.prim();
+            assert_eq!(kruskal.weight(), 37);
+            assert_eq!(prim.weight(), 37);
+            assert_eq!(kruskal.edges().len(), 8);
+            assert_eq!(prim.edges().len(), 8);
+            assert_eq!(kruskal.edges()[0].2, 1);
+            assert_eq!(prim.edges()[0], (VertexId(0), VertexId(1), 4));
+        }
+    }
+
+    #[test]
+    fn spanning_forest() {
+        let mut graph = Graph::new(Direction::Undirected);
+        let [a, b, c, d] = [(); 4].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, -2);
+        graph.add_edge(c, d, 5);
+        graph.add_edge(d, d, 1);
+        assert_eq!(graph.kruskal().weight(), 3);
+        assert_eq!(graph.prim().edges(), [(a, b, -2), (c, d, 5)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "graph is directed")]
+    fn directed_graph() {
+        Graph::<(), u8>::new(Direction::Directed).prim();
+    }
+
+    #[quickcheck]
+    fn algorithms_agree(edges: Vec<(u8, u8, u8)>) -> bool {
+        let mut graph = Graph::new(Direction::Undirected);
+        let ids: Vec<_> = (0..10).map(|_| graph.add_vertex(())).collect();
+        for (from, to, weight) in edges {
+            graph.add_edge(
+                ids[usize::from(from % 10)],
+                ids[usize::from(to % 10)],
+                weight,
+            );
+        }
+        let kruskal = graph.kruskal();
+        let prim = graph.prim();
+        let trees = graph.vertex_count() - graph.tarjan().len();
+        kruskal.weight() == prim.weight()
+            && kruskal.edges().len() == trees
+            && prim.edges().len() == trees
+            && is_forest(&graph, &kruskal)
+            && is_forest(&graph, &prim)
+    }
+}
@@ -0,0 +1,326 @@
+//! Strongly connected components.
+//!
+//! Two vertices are in the same strongly connected component if each is
+//! reachable from the other. Kosaraju's algorithm runs a depth-first search
+//! on the graph, then a second one on the transposed graph which starts the
+//! trees by decreasing finishing time: each tree of the second search is a
+//! component. See CLRS section 22.5. Tarjan's algorithm needs a single
+//! search: it keeps the vertices on a stack and tracks the smallest
+//! discovery index reachable from each vertex, and a vertex reaching no
+//! earlier vertex is the root of a component made of the vertices above it
+//! on the stack.
+
+use super::{Graph, Neighbors, VertexId};
+use crate::stack::DummyStack;
+
+/// Components is a partition of the vertices of a graph into strongly
+/// connected components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// The vertices of each component.
+    components: Vec<Vec<VertexId>>,
+
+    /// `component_of[v]` is the index of the component of the vertex `v`.
+    component_of: Vec<Option<usize>>,
+}
+
+impl Components {
+    /// Creates new partition into the given components.
+    fn new(bound: usize, components: Vec<Vec<VertexId>>) -> Self {
+        let mut component_of = vec![None; bound];
+        for (index, component) in components.iter().enumerate() {
+            for vertex in component {
+                component_of[vertex.index()] = Some(index);
+            }
+        }
+        Self {
+            components,
+            component_of,
+        }
+    }
+
+    /// Returns the number of components.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns true if there is no component, that is if the graph is
+    /// empty.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Returns the index of the component of the vertex, or `None` if it is
+    /// not in the graph.
+    pub fn component(&self, vertex: VertexId) -> Option<usize> {
+        *self.component_of.get(vertex.index())?
+    }
+
+    /// Returns true if the two vertices are in the same component.
+    pub fn connected(&self, u: VertexId, v: VertexId) -> bool {
+        self.component(u).is_some() && self.component(u) == self.component(v)
+    }
+
+    /// Returns the vertices of each component, indexed by component.
+    pub fn components(&self) -> &[Vec<VertexId>] {
+        &self.components
+    }
+
+    /// Returns the component graph of the graph, which has a vertex per
+    /// component and an edge between two components when an edge of the
+    /// graph joins them.
+    ///
+    /// The vertex of the component `i` has the index `i` and holds the
+    /// vertices of the component. The component graph of a directed graph
+    /// is acyclic.
+    pub fn condensation<V, E>(&self, graph: &Graph<V, E>) -> Graph<Vec<VertexId>, ()> {
+        let mut condensation = Graph::new(graph.direction());
+        let ids: Vec<_> = self
+            .components
+            .iter()
+            .map(|component| condensation.add_vertex(component.clone()))
+            .collect();
+        for (from, to, _) in graph.edges() {
+            if let (Some(from), Some(to)) = (self.component(from), self.component(to)) {
+                if from != to {
+                    condensation.add_edge(ids[from], ids[to], ());
+                }
+            }
+        }
+        condensation
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Returns the strongly connected components of the graph, computed by
+    /// Kosaraju's algorithm.
+    ///
+    /// The components are in topological order: an edge between two
+    /// components goes to a later one. It takes O(V + E) time with
+    /// adjacency lists.
+    pub fn kosaraju(&self) -> Components {
+        let first = self.dfs();
+        let forest = self
+            .reversed()
+            .dfs_from(first.finish_order().iter().rev().copied());
+
+        // The vertices of a depth-first tree are finished one after the
+        // other, the root last.
+        let mut components = Vec::new();
+        let mut component = Vec::new();
+        for &vertex in forest.finish_order() {
+            component.push(vertex);
+            if forest.predecessor(vertex).is_none() {
+                components.push(std::mem::take(&mut component));
+            }
+        }
+        Components::new(self.vertex_bound(), components)
+    }
+
+    /// Returns the strongly connected components of the graph, computed by
+    /// Tarjan's algorithm.
+    ///
+    /// The components are in reverse topological order: an edge between
+    /// two components goes to an earlier one. It takes O(V + E) time with
+    /// adjacency lists.
+    pub fn tarjan(&self) -> Components {
+        let bound = self.vertex_bound();
+        let mut indices = vec![None; bound];
+        let mut lowlinks = vec![0; bound];
+        let mut on_stack = vec![false; bound];
+        let mut stack = DummyStack::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in self.vertices() {
+            if indices[root.index()].is_some() {
+                continue;
+            }
+            // The call stack of the recursive formulation, holding the
+            // neighbors left to explore.
+            let mut calls: DummyStack<(VertexId, Neighbors<'_, E>)> = DummyStack::new();
+            indices[root.index()] = Some(next_index);
+            lowlinks[root.index()] = next_index;
+            on_stack[root.index()] = true;
+            stack.push(root);
+            next_index += 1;
+            calls.push((root, self.neighbors(root)));
+
+            while let Some((vertex, mut neighbors)) = calls.pop() {
+                let white = neighbors.find_map(|(neighbor, _)| match indices[neighbor.index()] {
+                    None => Some(neighbor),
+                    Some(index) => {
+                        if on_stack[neighbor.index()] {
+                            let lowlink = &mut lowlinks[vertex.index()];
+                            *lowlink = (*lowlink).min(index);
+                        }
+                        None
+                    }
+                });
+                if let Some(neighbor) = white {
+                    indices[neighbor.index()] = Some(next_index);
+                    lowlinks[neighbor.index()] = next_index;
+                    on_stack[neighbor.index()] = true;
+                    stack.push(neighbor);
+                    next_index += 1;
+                    calls.push((vertex, neighbors));
+                    calls.push((neighbor, self.neighbors(neighbor)));
+                    continue;
+                }
+
+                if Some(lowlinks[vertex.index()]) == indices[vertex.index()] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member.index()] = false;
+                        component.push(member);
+                        if member == vertex {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+                if let Some((parent, _)) = calls.peek() {
+                    lowlinks[parent.index()] =
+                        lowlinks[parent.index()].min(lowlinks[vertex.index()]);
+                }
+            }
+        }
+        Components::new(bound, components)
+    }
+
+    /// Returns the transpose of the graph, without the data of the
+    /// vertices and edges.
+    fn reversed(&self) -> Graph<(), ()> {
+        let mut reversed = self.skeleton(self.direction);
+        for (from, to, _) in self.edges() {
+            reversed.add_edge(to, from, ());
+        }
+        reversed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::{Direction, Representation};
+    use super::*;
+
+    /// Returns the components as sorted lists of vertex indices, in no
+    /// particular order.
+    fn normalized(components: &Components) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<_>> = components
+            .components()
+            .iter()
+            .map(|component| {
+                let mut set: Vec<_> = component.iter().map(|vertex| vertex.index()).collect();
+                set.sort_unstable();
+                set
+            })
+            .collect();
+        sets.sort_unstable();
+        sets
+    }
+
+    /// Returns the directed graph of CLRS figure 22.9, with the vertices
+    /// `a` to `h`.
+    fn clrs_graph(representation: Representation) -> Graph<char, ()> {
+        let mut graph = Graph::with_representation(Direction::Directed, representation);
+        let ids: Vec<_> = ('a'..='h').map(|name| graph.add_vertex(name)).collect();
+        let id = |name: char| ids[usize::from(name as u8 - b'a')];
+        for (from, to) in [
+            ('a', 'b'),
+            ('b', 'c'),
+            ('b', 'e'),
+            ('b', 'f'),
+            ('c', 'd'),
+            ('c', 'g'),
+            ('d', 'c'),
+            ('d', 'h'),
+            ('e', 'a'),
+            ('e', 'f'),
+            ('f', 'g'),
+            ('g', 'f'),
+            ('g', 'h'),
+            ('h', 'h'),
+        ] {
+            graph.add_edge(id(from), id(to), ());
+        }
+        graph
+    }
+
+    #[test]
+    fn clrs_components() {
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let graph = clrs_graph(representation);
+            let expected = vec![vec![0, 1, 4], vec![2, 3], vec![5, 6], vec![7]];
+            let kosaraju = graph.kosaraju();
+            let tarjan = graph.tarjan();
+            assert_eq!(normalized(&kosaraju), expected);
+            assert_eq!(normalized(&tarjan), expected);
+
+            let [a, b, c, .., h] = graph.vertices().collect::<Vec<_>>()[..] else {
+                panic!("the graph has 8 vertices");
+            };
+            assert!(kosaraju.connected(a, b));
+            assert!(!tarjan.connected(a, c));
+            assert_eq!(kosaraju.component(h), Some(3));
+            assert_eq!(tarjan.component(h), Some(0));
+
+            for components in [kosaraju, tarjan] {
+                let condensation = components.condensation(&graph);
+                assert_eq!(condensation.vertex_count(), 4);
+                assert_eq!(condensation.edge_count(), 5);
+                assert!(condensation.is_acyclic());
+            }
+        }
+    }
+
+    #[test]
+    fn undirected_components() {
+        let mut graph = Graph::new(Direction::Undirected);
+        let ids: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(ids[0], ids[3], ());
+        graph.add_edge(ids[3], ids[1], ());
+        graph.remove_vertex(ids[4]);
+        let expected = vec![vec![0, 1, 3], vec![2]];
+        assert_eq!(normalized(&graph.kosaraju()), expected);
+        assert_eq!(normalized(&graph.tarjan()), expected);
+        assert_eq!(graph.tarjan().component(ids[4]), None);
+        assert!(Graph::<(), ()>::new(Direction::Directed)
+            .tarjan()
+            .is_empty());
+    }
+
+    #[quickcheck]
+    fn algorithms_agree(edges: Vec<(u8, u8)>) -> bool {
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = (0..12).map(|_| graph.add_vertex(())).collect();
+        for (from, to) in edges {
+            graph.add_edge(ids[usize::from(from % 12)], ids[usize::from(to % 12)], ());
+        }
+        let kosaraju = graph.kosaraju();
+        let tarjan = graph.tarjan();
+
+        // Components are in topological order for Kosaraju's algorithm,
+        // and in reverse topological order for Tarjan's.
+        let ordered = graph.edges().all(|(from, to, _)| {
+            kosaraju.component(from) <= kosaraju.component(to)
+                && tarjan.component(from) >= tarjan.component(to)
+        });
+        let mutually_reachable = ids.iter().all(|&u| {
+            let reached = graph.bfs(u);
+            ids.iter().all(|&v| {
+                let both = reached.distance(v).is_some() && graph.bfs(v).distance(u).is_some();
+                both == kosaraju.connected(u, v)
+            })
+        });
+        normalized(&kosaraju) == normalized(&tarjan)
+            && ordered
+            && mutually_reachable
+            && kosaraju.condensation(&graph).is_acyclic()
+    }
+}
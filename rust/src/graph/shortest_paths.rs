@@ -0,0 +1,425 @@
+//! Single-source shortest paths.
+//!
+//! The weight of a path is the sum of the weights of its edges. These
+//! algorithms compute the weight of a lightest path from a source to every
+//! vertex by relaxing edges: the estimate of the distance of `v` improves
+//! when a path to `u` followed by the edge `(u, v)` is lighter. See CLRS
+//! chapter 24.
+
+use super::{Graph, VertexId};
+use crate::heap::priority_queue::PriorityQueue;
+use crate::{error::ErrorKind, Error};
+
+/// ShortestPaths is a tree of lightest paths from a source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths {
+    source: VertexId,
+
+    /// `distances[v]` is the weight of a lightest path from the source to
+    /// the vertex `v`, or `None` if it is unreachable.
+    distances: Vec<Option<i64>>,
+
+    /// `predecessors[v]` is the vertex before `v` on a lightest path.
+    predecessors: Vec<Option<VertexId>>,
+}
+
+impl ShortestPaths {
+    /// Creates new tree where only the source is reached.
+    fn new(bound: usize, source: VertexId) -> Self {
+        let mut distances = vec![None; bound];
+        distances[source.index()] = Some(0);
+        Self {
+            source,
+            distances,
+            predecessors: vec![None; bound],
+        }
+    }
+
+    /// Returns the source of the paths.
+    pub const fn source(&self) -> VertexId {
+        self.source
+    }
+
+    /// Returns the weight of a lightest path from the source to the vertex,
+    /// or `None` if there is no such path.
+    pub fn distance(&self, vertex: VertexId) -> Option<i64> {
+        *self.distances.get(vertex.index())?
+    }
+
+    /// Returns the predecessor of the vertex on a lightest path.
+    ///
+    /// The source and the unreachable vertices have no predecessor.
+    pub fn predecessor(&self, vertex: VertexId) -> Option<VertexId> {
+        *self.predecessors.get(vertex.index())?
+    }
+
+    /// Returns the vertices of a lightest path from the source to the
+    /// vertex, both included, or `None` if there is no such path.
+    pub fn path_to(&self, vertex: VertexId) -> Option<Vec<VertexId>> {
+        self.distance(vertex)?;
+        let mut path: Vec<_> =
+            std::iter::successors(Some(vertex), |&vertex| self.predecessor(vertex)).collect();
+        path.reverse();
+        Some(path)
+    }
+
+    /// Lowers the distance of `to` if the edge `(from, to)` leads to a
+    /// lighter path, and returns true if it did.
+    fn relax(&mut self, from: VertexId, to: VertexId, weight: i64) -> bool {
+        let Some(distance) = self.distances[from.index()] else {
+            return false;
+        };
+        let distance = distance + weight;
+        if self.distances[to.index()].map_or(true, |current| distance < current) {
+            self.distances[to.index()] = Some(distance);
+            self.predecessors[to.index()] = Some(from);
+            return true;
+        }
+        false
+    }
+
+    /// Returns the vertices of the negative cycle leading to `to`, where
+    /// `(from, to)` can still be relaxed after `V - 1` passes.
+    ///
+    /// After relaxing the edge, walking `V` predecessors back from `to`
+    /// ends on the cycle, whose vertices are then collected by walking it
+    /// once more.
+    fn negative_cycle(&mut self, from: VertexId, to: VertexId, count: usize) -> Vec<VertexId> {
+        self.predecessors[to.index()] = Some(from);
+        let mut start = to;
+        for _ in 0..count {
+            start = self.predecessor(start).expect("the vertex is reached");
+        }
+        let mut cycle = vec![start];
+        let mut vertex = self.predecessor(start).expect("the vertex is reached");
+        while vertex != start {
+            cycle.push(vertex);
+            vertex = self.predecessor(vertex).expect("the vertex is reached");
+        }
+        cycle.reverse();
+        cycle
+    }
+}
+
+impl<V, E: Copy + Into<i64>> Graph<V, E> {
+    /// Returns the lightest paths from the source, computed by Dijkstra's
+    /// algorithm.
+    ///
+    /// The vertices are finished by increasing distance. The unfinished
+    /// vertices with a known distance wait in a priority queue keyed by
+    /// distance, whose key is decreased through the handle of the vertex
+    /// when a lighter path is found, so the algorithm takes O((V + E) log V)
+    /// time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source is not in the graph, or if an edge reached has
+    /// a negative weight.
+    pub fn dijkstra(&self, source: VertexId) -> ShortestPaths {
+        assert!(self.contains_vertex(source), "vertex is not in the graph");
+        let mut paths = ShortestPaths::new(self.vertex_bound(), source);
+        let mut finished = vec![false; self.vertex_bound()];
+        // The keys are pairs of a distance and a vertex index.
+        let mut queue = PriorityQueue::with_capacity(self.vertex_count());
+        let mut handles = vec![None; self.vertex_bound()];
+        handles[source.index()] = Some(queue.push((0, source.index())));
+
+        while let Some((_, (_, index))) = queue.pop() {
+            let vertex = VertexId(index);
+            finished[index] = true;
+            for (neighbor, &weight) in self.neighbors(vertex) {
+                let weight = weight.into();
+                assert!(weight >= 0, "edge weight is negative");
+                if finished[neighbor.index()] || !paths.relax(vertex, neighbor, weight) {
+                    continue;
+                }
+                let key = (
+                    paths.distances[neighbor.index()].expect("the vertex is reached"),
+                    neighbor.index(),
+                );
+                match handles[neighbor.index()] {
+                    Some(handle) => {
+                        queue.decrease_key(handle, key);
+                    }
+                    None => handles[neighbor.index()] = Some(queue.push(key)),
+                }
+            }
+        }
+        paths
+    }
+
+    /// Returns the lightest paths from the source, computed by the
+    /// Bellman-Ford algorithm.
+    ///
+    /// Edges may have negative weights. Every edge is relaxed `V - 1`
+    /// times, which is enough unless a cycle of negative weight is
+    /// reachable from the source: it then fails with
+    /// [`ErrorKind::NegativeCycle`] holding such a cycle. An undirected
+    /// edge of negative weight is a negative cycle. It takes O(V E) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source is not in the graph.
+    pub fn bellman_ford(&self, source: VertexId) -> Result<ShortestPaths, Error> {
+        assert!(self.contains_vertex(source), "vertex is not in the graph");
+        let mut paths = ShortestPaths::new(self.vertex_bound(), source);
+        for _ in 1..self.vertex_count() {
+            if !self.relax_all(&mut paths) {
+                return Ok(paths);
+            }
+        }
+        match self.relaxable_edge(&paths) {
+            None => Ok(paths),
+            Some((from, to)) => Err(Error::new(ErrorKind::NegativeCycle(paths.negative_cycle(
+                from,
+                to,
+                self.vertex_count(),
+            )))),
+        }
+    }
+
+    /// Relaxes every edge once, and returns true if a distance changed.
+    fn relax_all(&self, paths: &mut ShortestPaths) -> bool {
+        let mut changed = false;
+        for (from, to, &weight) in self.edges() {
+            changed |= paths.relax(from, to, weight.into());
+            if !self.is_directed() {
+                changed |= paths.relax(to, from, weight.into());
+            }
+        }
+        changed
+    }
+
+    /// Returns an edge which can still be relaxed.
+    fn relaxable_edge(&self, paths: &ShortestPaths) -> Option<(VertexId, VertexId)> {
+        let improves = |from: VertexId, to: VertexId, weight: i64| match paths.distance(from) {
+            Some(distance) => paths
+                .distance(to)
+                .map_or(true, |current| distance + weight < current),
+            None => false,
+        };
+        self.edges().find_map(|(from, to, &weight)| {
+            if improves(from, to, weight.into()) {
+                Some((from, to))
+            } else if !self.is_directed() && improves(to, from, weight.into()) {
+                Some((to, from))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::super::{Direction, Representation};
+    use super::*;
+
+    /// Returns the directed graph of CLRS figure 24.6, with the vertices
+    /// `s, t, x, y, z`.
+    fn clrs_graph(representation: Representation) -> (Graph<char, u8>, Vec<VertexId>) {
+        let mut graph = Graph::with_representation(Direction::Directed, representation);
+        let ids: Vec<_> = "stxyz".chars().map(|name| graph.add_vertex(name)).collect();
+        let id = |name| ids["stxyz".find(name).expect("vertex exists")];
+        for (from, to, weight) in [
+            ('s', 't', 10),
+            ('s', 'y', 5),
+            ('t', 'x', 1),
+            ('t', 'y', 2),
+            ('x', 'z', 4),
+            ('y', 't', 3),
+            ('y', 'x', 9),
+            ('y', 'z', 2),
+            ('z', 's', 7),
+            ('z', 'x', 6),
+        ] {
+            graph.add_edge(id(from), id(to), weight);
+        }
+        (graph, ids)
+    }
+
+    /// Returns the distances from the source, computed by relaxing every
+    /// edge until none changes.
+    fn relax_until_stable(graph: &Graph<(), u8>, source: VertexId) -> Vec<Option<i64>> {
+        let mut paths = ShortestPaths::new(graph.vertex_bound(), source);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (from, to, &weight) in graph.edges() {
+                changed |= paths.relax(from, to, weight.into());
+                if !graph.is_directed() {
+                    changed |= paths.relax(to, from, weight.into());
+                }
+            }
+        }
+        paths.distances
+    }
+
+    #[test]
+    fn clrs_dijkstra() {
+        for representation in [
+            Representation::AdjacencyList,
+            Representation::AdjacencyMatrix,
+        ] {
+            let (graph, ids) = clrs_graph(representation);
+            let paths = graph.dijkstra(ids[0]);
+            let distances: Vec<_> = ids.iter().map(|&id| paths.distance(id)).collect();
+            assert_eq!(distances, [0, 8, 9, 5, 7].map(Some).to_vec());
+            assert_eq!(paths.source(), ids[0]);
+            assert_eq!(
+                paths.path_to(ids[2]),
+                Some(vec![ids[0], ids[3], ids[1], ids[2]])
+            );
+        }
+    }
+
+    #[test]
+    fn clrs_bellman_ford() {
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = "stxyz".chars().map(|name| graph.add_vertex(name)).collect();
+        let id = |name| ids["stxyz".find(name).expect("vertex exists")];
+        for (from, to, weight) in [
+            ('s', 't', 6),
+            ('s', 'y', 7),
+            ('t', 'x', 5),
+            ('t', 'y', 8),
+            ('t', 'z', -4),
+            ('x', 't', -2),
+            ('y', 'x', -3),
+            ('y', 'z', 9),
+            ('z', 's', 2),
+            ('z', 'x', 7),
+        ] {
+            graph.add_edge(id(from), id(to), weight);
+        }
+        let paths = graph.bellman_ford(id('s')).expect("no negative cycle");
+        let distances: Vec<_> = ids.iter().map(|&id| paths.distance(id)).collect();
+        assert_eq!(distances, [0, 2, 4, 7, -2].map(Some).to_vec());
+        assert_eq!(
+            paths.path_to(id('z')),
+            Some(vec![id('s'), id('y'), id('x'), id('t'), id('z')])
+        );
+
+        let (graph, ids) = clrs_graph(Representation::AdjacencyMatrix);
+        assert_eq!(graph.bellman_ford(ids[0]), Ok(graph.dijkstra(ids[0])));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = Graph::new(Direction::Directed);
+        let [a, b, c, d] = [(); 4].map(|()| graph.add_vertex(()));
+        graph.add_edge(d, a, 5);
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, -3);
+        graph.add_edge(c, a, 1);
+        let error = graph
+            .bellman_ford(d)
+            .expect_err("a, b, c is a negative cycle");
+        let ErrorKind::NegativeCycle(cycle) = error.kind() else {
+            panic!("unexpected error {error}");
+        };
+        let start = cycle
+            .iter()
+            .position(|&vertex| vertex == a)
+            .expect("a is on the cycle");
+        let mut cycle = cycle.clone();
+        cycle.rotate_left(start);
+        assert_eq!(cycle, vec![a, b, c]);
+
+        graph.remove_edge(c, a);
+        assert!(graph.bellman_ford(d).is_ok());
+
+        let mut graph = Graph::new(Direction::Undirected);
+        let [a, b] = [(); 2].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, -1);
+        let error = graph
+            .bellman_ford(a)
+            .expect_err("an undirected edge is a cycle");
+        assert!(matches!(error.kind(), ErrorKind::NegativeCycle(cycle) if cycle.len() == 2));
+    }
+
+    #[test]
+    fn unreachable_vertex() {
+        let mut graph = Graph::new(Direction::Undirected);
+        let [a, b, c] = [(); 3].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, 3_u32);
+        let paths = graph.dijkstra(b);
+        assert_eq!(paths.distance(a), Some(3));
+        assert_eq!(paths.distance(c), None);
+        assert_eq!(paths.path_to(c), None);
+        assert_eq!(paths.predecessor(b), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "edge weight is negative")]
+    fn negative_weight() {
+        let mut graph = Graph::new(Direction::Directed);
+        let [a, b] = [(); 2].map(|()| graph.add_vertex(()));
+        graph.add_edge(a, b, -1);
+        graph.dijkstra(a);
+    }
+
+    #[quickcheck]
+    fn bellman_ford_finds_paths_or_cycles(edges: Vec<(u8, u8, i8)>) -> bool {
+        let mut graph = Graph::new(Direction::Directed);
+        let ids: Vec<_> = (0..8).map(|_| graph.add_vertex(())).collect();
+        for (from, to, weight) in edges {
+            graph.add_edge(ids[usize::from(from % 8)], ids[usize::from(to % 8)], weight);
+        }
+        let weight = |from, to| i64::from(*graph.edge(from, to).expect("edge exists"));
+        match graph.bellman_ford(ids[0]) {
+            Ok(paths) => graph.edges().all(|(from, to, _)| {
+                match (paths.distance(from), paths.distance(to)) {
+                    (Some(from_distance), Some(to_distance)) => {
+                        to_distance <= from_distance + weight(from, to)
+                    }
+                    (Some(_), None) => false,
+                    _ => true,
+                }
+            }),
+            Err(error) => match error.kind() {
+                ErrorKind::NegativeCycle(cycle) => {
+                    let total: i64 = cycle
+                        .iter()
+                        .zip(cycle.iter().cycle().skip(1))
+                        .map(|(&from, &to)| weight(from, to))
+                        .sum();
+                    total < 0
+                }
+                _ => false,
+            },
+        }
+    }
+
+    #[quickcheck]
+    fn dijkstra_agrees_with_relaxation(edges: Vec<(u8, u8, u8)>, directed: bool) -> bool {
+        let direction = if directed {
+            Direction::Directed
+        } else {
+            Direction::Undirected
+        };
+        let mut graph = Graph::new(direction);
+        let ids: Vec<_> = (0..10).map(|_| graph.add_vertex(())).collect();
+        for (from, to, weight) in edges {
+            graph.add_edge(
+                ids[usize::from(from % 10)],
+                ids[usize::from(to % 10)],
+                weight,
+            );
+        }
+        let paths = graph.dijkstra(ids[0]);
+        paths.distances == relax_until_stable(&graph, ids[0])
+            && ids.iter().all(|&id| match paths.path_to(id) {
+                Some(path) => {
+                    let weight: i64 = path
+                        .windows(2)
+                        .map(|pair| i64::from(*graph.edge(pair[0], pair[1]).expect("edge exists")))
+                        .sum();
+                    Some(weight) == paths.distance(id)
+                }
+                None => paths.distance(id).is_none(),
+            })
+    }
+}
@@ -0,0 +1,123 @@
+//! Hash tables.
+//!
+//! A hash table stores each entry in the slot its hash function computes
+//! from the key, so that searching, inserting and deleting take O(1)
+//! expected time when the hash function spreads the keys well. Two keys may
+//! collide in the same slot, which the tables of this module resolve either
+//! by chaining the entries of a slot in a list, see
+//! [`ChainedHashTable`](chaining::ChainedHashTable), or by probing other
+//! slots of the table, see
+//! [`OpenAddressingTable`](open_addressing::OpenAddressingTable). See CLRS
+//! chapter 11. The [`CuckooHashTable`](cuckoo::CuckooHashTable) gives every
+//! key one slot in each of two tables, and moves the entries in the way.
+//!
+//! Keys are first interpreted as natural numbers by the [`Key`] trait, then
+//! mapped to a slot by a [`HashFn`], or by the random hash functions of the
+//! cuckoo table. The tables double their number of slots when their load
+//! factor, the number of entries per slot, exceeds a maximum, so their
+//! number of slots is always a power of two.
+
+pub mod chaining;
+pub mod cuckoo;
+pub mod open_addressing;
+
+/// The initial number of slots of a table.
+const INITIAL_SLOTS: usize = 8;
+
+/// Key is a key of a hash table interpreted as a natural number.
+pub trait Key: Eq {
+    /// Returns the natural number interpretation of the key.
+    fn to_natural(&self) -> u64;
+}
+
+macro_rules! impl_key_for_integers {
+    ($($integer:ty),*) => {
+        $(
+            impl Key for $integer {
+                fn to_natural(&self) -> u64 {
+                    *self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_key_for_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, char);
+
+impl Key for str {
+    /// Interprets the string as an integer in radix 128, as in CLRS
+    /// section 11.3, wrapping around on overflow.
+    fn to_natural(&self) -> u64 {
+        self.bytes().fold(0, |number: u64, byte| {
+            number.wrapping_mul(128).wrapping_add(u64::from(byte))
+        })
+    }
+}
+
+impl Key for String {
+    fn to_natural(&self) -> u64 {
+        self.as_str().to_natural()
+    }
+}
+
+/// HashFn maps the natural number interpretation of a key to a slot.
+pub trait HashFn {
+    /// Returns the slot of the key in a table of `slots` slots.
+    fn hash(&self, key: u64, slots: usize) -> usize;
+}
+
+/// DivisionMethod hashes the key `k` into the slot `k mod m`.
+///
+/// It is fast, but only uses the low-order bits of the key when `m` is a
+/// power of two, as in the tables of this module, so it suits keys whose
+/// low-order bits are evenly distributed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DivisionMethod;
+
+impl HashFn for DivisionMethod {
+    fn hash(&self, key: u64, slots: usize) -> usize {
+        (key % slots as u64) as usize
+    }
+}
+
+/// MultiplicationMethod hashes the key `k` into the slot `⌊m (k A mod 1)⌋`
+/// with Knuth's constant `A = (√5 - 1) / 2`.
+///
+/// The fractional part of `k A` is computed in 64-bit fixed point, so every
+/// bit of the key contributes to the slot whatever the number of slots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MultiplicationMethod;
+
+impl MultiplicationMethod {
+    /// The constant `A` scaled by `2^64`.
+    const A: u64 = 0x9e37_79b9_7f4a_7c15;
+}
+
+impl HashFn for MultiplicationMethod {
+    fn hash(&self, key: u64, slots: usize) -> usize {
+        let fraction = key.wrapping_mul(Self::A);
+        ((u128::from(fraction) * slots as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn hash_methods() {
+        assert_eq!(DivisionMethod.hash(100, 8), 4);
+        // CLRS section 11.3.2: k = 123456 and m = 2^14 give slot 67.
+        assert_eq!(MultiplicationMethod.hash(123_456, 1 << 14), 67);
+        assert_eq!("ab".to_natural(), 97 * 128 + 98);
+        assert_eq!(String::from("ab").to_natural(), "ab".to_natural());
+    }
+
+    #[quickcheck]
+    fn hashes_into_the_table(key: u64, slots: u16) -> bool {
+        let slots = usize::from(slots) + 1;
+        DivisionMethod.hash(key, slots) < slots && MultiplicationMethod.hash(key, slots) < slots
+    }
+}
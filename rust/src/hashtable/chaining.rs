@@ -0,0 +1,265 @@
+//! Hash table with separate chaining.
+
+use super::{HashFn, Key, MultiplicationMethod, INITIAL_SLOTS};
+use crate::list::{DoublyIter, DoublyLinkedList};
+use crate::map::Map;
+
+/// ChainedHashTable is a hash table whose slots hold the list of the
+/// entries hashed there.
+///
+/// The lists are doubly linked, so an entry is unlinked in O(1) time once
+/// found. See CLRS section 11.2.
+#[derive(Debug, Clone)]
+pub struct ChainedHashTable<K, V, H = MultiplicationMethod> {
+    slots: Vec<DoublyLinkedList<(K, V)>>,
+    hash_fn: H,
+    len: usize,
+}
+
+impl<K, V> ChainedHashTable<K, V> {
+    /// Creates new empty table using the multiplication method.
+    pub fn new() -> Self {
+        Self::with_hash_fn(MultiplicationMethod)
+    }
+}
+
+impl<K, V, H> ChainedHashTable<K, V, H> {
+    /// The maximum load factor, above which the number of slots doubles.
+    pub const MAX_LOAD_FACTOR: f64 = 1.0;
+
+    /// Creates new empty table using the given hash function.
+    pub fn with_hash_fn(hash_fn: H) -> Self {
+        Self {
+            slots: std::iter::repeat_with(DoublyLinkedList::new)
+                .take(INITIAL_SLOTS)
+                .collect(),
+            hash_fn,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the table is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of slots.
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the average number of entries per slot.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots.len() as f64
+    }
+
+    /// Returns the length of the longest chain.
+    pub fn longest_chain(&self) -> usize {
+        self.slots
+            .iter()
+            .map(DoublyLinkedList::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Creates an iterator over the entries, in no particular order.
+    pub fn iter(&self) -> ChainedIter<'_, K, V> {
+        ChainedIter {
+            slots: self.slots.iter(),
+            chain: None,
+        }
+    }
+}
+
+impl<K: Key, V, H: HashFn> ChainedHashTable<K, V, H> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let slot = self.slot(&key);
+        let chain = &mut self.slots[slot];
+        let mut cursor = chain.cursor_front();
+        while let Some(current) = cursor {
+            if let Some((other, old)) = chain.get_mut(current) {
+                if *other == key {
+                    return Some(std::mem::replace(old, value));
+                }
+            }
+            cursor = chain.next(current);
+        }
+
+        chain.push_front((key, value));
+        self.len += 1;
+        if self.load_factor() > Self::MAX_LOAD_FACTOR {
+            self.rehash(2 * self.slots.len());
+        }
+        None
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.slots[self.slot(key)]
+            .iter()
+            .find(|(other, _)| other == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Removes the key from the table and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let slot = self.slot(key);
+        let chain = &mut self.slots[slot];
+        let mut cursor = chain.cursor_front();
+        while let Some(current) = cursor {
+            if chain.get(current).map_or(false, |(other, _)| other == key) {
+                self.len -= 1;
+                return chain.delete(current).map(|(_, value)| value);
+            }
+            cursor = chain.next(current);
+        }
+        None
+    }
+
+    /// Returns the slot of the key.
+    fn slot(&self, key: &K) -> usize {
+        self.hash_fn.hash(key.to_natural(), self.slots.len())
+    }
+
+    /// Moves every entry into a table of the given number of slots.
+    fn rehash(&mut self, slot_count: usize) {
+        let slots = std::iter::repeat_with(DoublyLinkedList::new)
+            .take(slot_count)
+            .collect();
+        for mut chain in std::mem::replace(&mut self.slots, slots) {
+            while let Some((key, value)) = chain.pop_front() {
+                let slot = self.slot(&key);
+                self.slots[slot].push_front((key, value));
+            }
+        }
+    }
+}
+
+impl<K, V> Default for ChainedHashTable<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key, V, H: HashFn> Map<K, V> for ChainedHashTable<K, V, H> {
+    type Iter<'a>
+        = ChainedIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        H: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        ChainedHashTable::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        ChainedHashTable::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        ChainedHashTable::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        ChainedHashTable::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ChainedHashTable::iter(self)
+    }
+}
+
+/// Iterator over the entries of a [`ChainedHashTable`].
+#[derive(Debug)]
+pub struct ChainedIter<'a, K, V> {
+    /// The slots not visited yet.
+    slots: std::slice::Iter<'a, DoublyLinkedList<(K, V)>>,
+
+    /// The chain being visited.
+    chain: Option<DoublyIter<'a, (K, V)>>,
+}
+
+impl<'a, K, V> Iterator for ChainedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, value)) = self.chain.as_mut().and_then(Iterator::next) {
+                return Some((key, value));
+            }
+            self.chain = Some(self.slots.next()?.iter());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::hashtable::DivisionMethod;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    #[test]
+    fn insert_and_remove() {
+        let mut table = ChainedHashTable::new();
+        for key in 0..100u32 {
+            assert_eq!(table.insert(key, key * 2), None);
+        }
+        assert_eq!(table.insert(7, 0), Some(14));
+        assert_eq!(table.get(&7), Some(&0));
+        assert_eq!(table.len(), 100);
+        assert_eq!(table.slot_count(), 128);
+        assert!(table.load_factor() <= ChainedHashTable::<u32, u32>::MAX_LOAD_FACTOR);
+
+        for key in (0..100).step_by(2) {
+            assert!(table.remove(&key).is_some());
+        }
+        assert_eq!(table.remove(&0), None);
+        assert_eq!(table.get(&3), Some(&6));
+        assert_eq!(table.len(), 50);
+        assert_eq!(table.iter().count(), 50);
+    }
+
+    #[test]
+    fn colliding_keys_share_a_chain() {
+        // Multiples of the number of slots all land in slot 0.
+        let mut table = ChainedHashTable::with_hash_fn(DivisionMethod);
+        for key in [0u64, 8, 16, 24] {
+            table.insert(key, ());
+        }
+        assert_eq!(table.slot_count(), 8);
+        assert_eq!(table.longest_chain(), 4);
+        assert_eq!(table.remove(&8), Some(()));
+        assert_eq!(table.longest_chain(), 3);
+        assert!(table.get(&24).is_some());
+    }
+
+    #[test]
+    fn string_keys() {
+        let mut table = ChainedHashTable::new();
+        for word in ["chaining", "probing", "hashing"] {
+            table.insert(word.to_string(), word.len());
+        }
+        assert_eq!(table.get(&"probing".to_string()), Some(&7));
+        assert_eq!(table.get(&"sorting".to_string()), None);
+    }
+
+    #[quickcheck]
+    fn chained_table_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut ChainedHashTable::new(), &operations)
+            && agrees_with_model::<u8, _>(
+                &mut ChainedHashTable::with_hash_fn(DivisionMethod),
+                &operations,
+            )
+    }
+}
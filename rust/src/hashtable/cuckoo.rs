@@ -0,0 +1,342 @@
+//! Cuckoo hash table.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{Key, INITIAL_SLOTS};
+use crate::map::Map;
+
+/// Slot of a [`CuckooHashTable`].
+type Slot<K, V> = Option<(K, V)>;
+
+/// Iterator over the slots of one of the tables of a [`CuckooHashTable`].
+type SlotIter<'a, K, V> = std::slice::Iter<'a, Slot<K, V>>;
+
+/// CuckooHashTable is a hash table where every key has exactly two
+/// candidate slots, one in each of its two tables.
+///
+/// A search probes at most two slots, so searching and removing take O(1)
+/// worst-case time. An insertion into a taken slot evicts its entry to the
+/// other slot of the evicted key, which may evict another entry in turn.
+/// When the evictions go on for too long, the table picks new hash
+/// functions and reinserts every entry, so inserting takes O(1) expected
+/// amortized time. See Pagh and Rodler, "Cuckoo hashing", 2001.
+///
+/// Each table hashes the natural number interpretation of the keys with
+/// the multiply-shift method, whose odd multipliers are drawn at random.
+///
+/// # Panics
+///
+/// Keys with the same natural number interpretation have the same two
+/// slots, so at most two of them fit: inserting a third one panics.
+#[derive(Debug, Clone)]
+pub struct CuckooHashTable<K, V> {
+    /// The two tables, of the same power of two number of slots.
+    tables: [Vec<Slot<K, V>>; 2],
+
+    /// The multiplier of the hash function of each table.
+    multipliers: [u64; 2],
+    len: usize,
+
+    /// The source of the multipliers.
+    rng: StdRng,
+}
+
+impl<K, V> CuckooHashTable<K, V> {
+    /// The maximum load factor, above which the number of slots doubles.
+    ///
+    /// With two tables, insertions succeed with high probability as long
+    /// as the load factor stays below one half.
+    pub const MAX_LOAD_FACTOR: f64 = 0.4;
+
+    /// The number of rehashes which fail at a given number of slots before
+    /// the number of slots doubles.
+    const MAX_REHASHES: usize = 8;
+
+    /// Creates new empty table whose hash functions are seeded from the
+    /// operating system.
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates new empty table with a fixed seed, so that the hash
+    /// functions drawn, and hence the layout of the table, are
+    /// reproducible.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(mut rng: StdRng) -> Self {
+        Self {
+            tables: [
+                Self::empty_slots(INITIAL_SLOTS),
+                Self::empty_slots(INITIAL_SLOTS),
+            ],
+            multipliers: [rng.gen::<u64>() | 1, rng.gen::<u64>() | 1],
+            len: 0,
+            rng,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the table is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of slots of both tables.
+    pub fn slot_count(&self) -> usize {
+        2 * self.tables[0].len()
+    }
+
+    /// Returns the number of entries per slot.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slot_count() as f64
+    }
+
+    /// Creates an iterator over the entries, in no particular order.
+    pub fn iter(&self) -> CuckooIter<'_, K, V> {
+        CuckooIter {
+            slots: self.tables[0].iter().chain(self.tables[1].iter()),
+        }
+    }
+
+    fn empty_slots(slot_count: usize) -> Vec<Slot<K, V>> {
+        std::iter::repeat_with(|| None).take(slot_count).collect()
+    }
+
+    /// Removes every entry from the tables, which keep their number of
+    /// slots, and returns them.
+    fn drain(&mut self) -> impl Iterator<Item = (K, V)> + '_ {
+        let [first, second] = &mut self.tables;
+        first
+            .iter_mut()
+            .chain(second.iter_mut())
+            .filter_map(Option::take)
+    }
+}
+
+impl<K: Key, V> CuckooHashTable<K, V> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some((table, index)) = self.find(&key) {
+            let (_, old) = self.tables[table][index]
+                .as_mut()
+                .expect("the slot holds the key");
+            return Some(std::mem::replace(old, value));
+        }
+
+        self.len += 1;
+        let slot_count = self.tables[0].len();
+        if self.len as f64 > Self::MAX_LOAD_FACTOR * self.slot_count() as f64 {
+            self.rehash(2 * slot_count, None);
+        }
+        if let Err(homeless) = self.place((key, value)) {
+            self.rehash(self.tables[0].len(), Some(homeless));
+        }
+        None
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let (table, index) = self.find(key)?;
+        self.tables[table][index].as_ref().map(|(_, value)| value)
+    }
+
+    /// Removes the key from the table and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (table, index) = self.find(key)?;
+        let (_, value) = self.tables[table][index].take()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Returns the slot of the key in the table.
+    fn slot(&self, table: usize, key: &K) -> usize {
+        let hash = key.to_natural().wrapping_mul(self.multipliers[table]);
+        ((u128::from(hash) * self.tables[table].len() as u128) >> 64) as usize
+    }
+
+    /// Returns the table and the index of the slot holding the key, if any.
+    fn find(&self, key: &K) -> Option<(usize, usize)> {
+        (0..2).find_map(|table| {
+            let index = self.slot(table, key);
+            match &self.tables[table][index] {
+                Some((other, _)) if other == key => Some((table, index)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Stores the entry, evicting the entries in its way to their other
+    /// slot, and returns the entry left without a slot if the evictions go
+    /// on for too long.
+    fn place(&mut self, mut entry: (K, V)) -> Result<(), (K, V)> {
+        // An insertion evicts O(log n) entries with high probability.
+        let max_evictions = 4 * self.tables[0].len().trailing_zeros() as usize + 8;
+        for eviction in 0..max_evictions {
+            let table = eviction % 2;
+            let index = self.slot(table, &entry.0);
+            match self.tables[table][index].replace(entry) {
+                None => return Ok(()),
+                Some(evicted) => entry = evicted,
+            }
+        }
+        Err(entry)
+    }
+
+    /// Moves every entry, and the given one, into tables of the given
+    /// number of slots with new hash functions.
+    ///
+    /// It draws new hash functions until every entry finds a slot, and
+    /// doubles the number of slots after too many failures.
+    fn rehash(&mut self, mut slot_count: usize, entry: Option<(K, V)>) {
+        let mut entries: Vec<(K, V)> = self.drain().chain(entry).collect();
+        let mut failures = 0;
+        loop {
+            self.tables = [Self::empty_slots(slot_count), Self::empty_slots(slot_count)];
+            self.multipliers = [self.rng.gen::<u64>() | 1, self.rng.gen::<u64>() | 1];
+            let homeless =
+                std::iter::from_fn(|| entries.pop()).find_map(|entry| self.place(entry).err());
+            let Some(homeless) = homeless else {
+                return;
+            };
+            entries.push(homeless);
+            entries.extend(self.drain());
+            failures += 1;
+            if failures % Self::MAX_REHASHES == 0 {
+                assert!(
+                    slot_count <= 2 * entries.len().max(INITIAL_SLOTS),
+                    "more than two keys have the same natural number interpretation"
+                );
+                slot_count *= 2;
+            }
+        }
+    }
+}
+
+impl<K, V> Default for CuckooHashTable<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key, V> Map<K, V> for CuckooHashTable<K, V> {
+    type Iter<'a>
+        = CuckooIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        CuckooHashTable::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        CuckooHashTable::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        CuckooHashTable::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        CuckooHashTable::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        CuckooHashTable::iter(self)
+    }
+}
+
+/// Iterator over the entries of a [`CuckooHashTable`].
+#[derive(Debug)]
+pub struct CuckooIter<'a, K, V> {
+    slots: std::iter::Chain<SlotIter<'a, K, V>, SlotIter<'a, K, V>>,
+}
+
+impl<'a, K, V> Iterator for CuckooIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots
+            .find_map(|slot| slot.as_ref().map(|(key, value)| (key, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    /// Returns true if every entry is in one of the two slots of its key.
+    fn is_valid<K: Key, V>(table: &CuckooHashTable<K, V>) -> bool {
+        table.tables.iter().enumerate().all(|(side, slots)| {
+            slots.iter().enumerate().all(|(index, slot)| {
+                slot.as_ref()
+                    .map_or(true, |(key, _)| table.slot(side, key) == index)
+            })
+        }) && table.iter().count() == table.len()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut table = CuckooHashTable::with_seed(7);
+        for key in 0..100u32 {
+            assert_eq!(table.insert(key, key * 2), None);
+            assert!(is_valid(&table));
+        }
+        assert_eq!(table.insert(7, 0), Some(14));
+        assert_eq!(table.get(&7), Some(&0));
+        assert_eq!(table.len(), 100);
+        assert!(table.load_factor() <= CuckooHashTable::<u32, u32>::MAX_LOAD_FACTOR);
+
+        for key in (0..100).step_by(2) {
+            assert!(table.remove(&key).is_some());
+        }
+        assert_eq!(table.remove(&0), None);
+        assert_eq!(table.get(&3), Some(&6));
+        assert_eq!(table.len(), 50);
+        assert_eq!(table.iter().count(), 50);
+        assert!(is_valid(&table));
+    }
+
+    #[test]
+    fn same_seed_same_layout() {
+        let layout = |seed| {
+            let mut table = CuckooHashTable::with_seed(seed);
+            for key in 0..50u64 {
+                table.insert(key * 1_000_003, ());
+            }
+            table.iter().map(|(key, _)| *key).collect::<Vec<_>>()
+        };
+        assert_eq!(layout(3), layout(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "same natural number interpretation")]
+    fn three_keys_with_the_same_natural() {
+        // The first of eleven characters is shifted out of the 64 bits.
+        let keys = ["a", "b", "c"].map(|first| format!("{first}0123456789"));
+        assert!(keys
+            .iter()
+            .all(|key| key.to_natural() == keys[0].to_natural()));
+        let mut table = CuckooHashTable::with_seed(0);
+        for key in keys {
+            table.insert(key, ());
+        }
+    }
+
+    #[quickcheck]
+    fn cuckoo_agrees_with_model(operations: Vec<Operation>, seed: u64) -> bool {
+        agrees_with_model::<u8, _>(&mut CuckooHashTable::with_seed(seed), &operations)
+    }
+}
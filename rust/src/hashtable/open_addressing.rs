@@ -0,0 +1,365 @@
+//! Hash table with open addressing.
+
+use super::{HashFn, Key, MultiplicationMethod, INITIAL_SLOTS};
+use crate::map::Map;
+
+/// Probing is the way an [`OpenAddressingTable`] looks for another slot
+/// when the slot of a key is taken.
+///
+/// The `i`-th probe of the key `k` in a table of `m` slots is at
+/// `(h(k) + f(i)) mod m`, where `h` is the hash function of the table. As
+/// `m` is a power of two, each probe sequence visits every slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probing {
+    /// `f(i) = i`: the probes scan the table, so long runs of taken slots
+    /// build up and slow down all the keys hashed into them.
+    Linear,
+
+    /// `f(i) = (i + i²) / 2`: the probes spread out, but keys hashed into
+    /// the same slot still follow the same sequence.
+    Quadratic,
+
+    /// `f(i) = i h₂(k)`, where the odd step `h₂(k)` is taken from the
+    /// quotient of the key by the number of slots, so keys colliding under
+    /// the division method follow different sequences.
+    Double,
+}
+
+/// Slot of an [`OpenAddressingTable`].
+#[derive(Debug, Clone)]
+enum Slot<K, V> {
+    Empty,
+
+    /// A slot whose entry was removed, which searches must probe past.
+    Deleted,
+    Occupied(K, V),
+}
+
+/// OpenAddressingTable is a hash table storing its entries in the slots
+/// themselves.
+///
+/// Removed entries leave a marker behind, so that the searches for the
+/// keys probed past them go on. The markers count towards the load factor
+/// until the next rehashing clears them. See CLRS section 11.4.
+#[derive(Debug, Clone)]
+pub struct OpenAddressingTable<K, V, H = MultiplicationMethod> {
+    slots: Vec<Slot<K, V>>,
+    probing: Probing,
+    hash_fn: H,
+    len: usize,
+
+    /// The number of slots holding a removed entry marker.
+    deleted: usize,
+}
+
+impl<K, V> OpenAddressingTable<K, V> {
+    /// Creates new empty table with the given probing, using the
+    /// multiplication method.
+    pub fn new(probing: Probing) -> Self {
+        Self::with_hash_fn(probing, MultiplicationMethod)
+    }
+}
+
+impl<K, V, H> OpenAddressingTable<K, V, H> {
+    /// The maximum load factor, above which the table is rehashed.
+    ///
+    /// An unsuccessful search makes at most `1 / (1 - α)` probes in
+    /// expectation with uniform hashing, so two at this load factor.
+    pub const MAX_LOAD_FACTOR: f64 = 0.5;
+
+    /// Creates new empty table with the given probing and hash function.
+    pub fn with_hash_fn(probing: Probing, hash_fn: H) -> Self {
+        Self {
+            slots: Self::empty_slots(INITIAL_SLOTS),
+            probing,
+            hash_fn,
+            len: 0,
+            deleted: 0,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the table is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the probing of the table.
+    pub const fn probing(&self) -> Probing {
+        self.probing
+    }
+
+    /// Returns the number of slots.
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of entries per slot.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots.len() as f64
+    }
+
+    /// Creates an iterator over the entries, in no particular order.
+    pub fn iter(&self) -> OpenAddressingIter<'_, K, V> {
+        OpenAddressingIter {
+            slots: self.slots.iter(),
+        }
+    }
+
+    fn empty_slots(slot_count: usize) -> Vec<Slot<K, V>> {
+        std::iter::repeat_with(|| Slot::Empty)
+            .take(slot_count)
+            .collect()
+    }
+}
+
+impl<K: Key, V, H: HashFn> OpenAddressingTable<K, V, H> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (found, vacant) = self.search(&key);
+        if let Some(index) = found {
+            if let Slot::Occupied(_, old) = &mut self.slots[index] {
+                return Some(std::mem::replace(old, value));
+            }
+        }
+
+        let index = vacant.expect("the load factor leaves a vacant slot");
+        if matches!(self.slots[index], Slot::Deleted) {
+            self.deleted -= 1;
+        }
+        self.slots[index] = Slot::Occupied(key, value);
+        self.len += 1;
+        if (self.len + self.deleted) as f64 > Self::MAX_LOAD_FACTOR * self.slots.len() as f64 {
+            // Only grow if the entries themselves fill the table; otherwise
+            // rehashing in place clears the removed entry markers.
+            let slot_count = if self.load_factor() > Self::MAX_LOAD_FACTOR / 2.0 {
+                2 * self.slots.len()
+            } else {
+                self.slots.len()
+            };
+            self.rehash(slot_count);
+        }
+        None
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match &self.slots[self.search(key).0?] {
+            Slot::Occupied(_, value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Removes the key from the table and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.search(key).0?;
+        match std::mem::replace(&mut self.slots[index], Slot::Deleted) {
+            Slot::Occupied(_, value) => {
+                self.len -= 1;
+                self.deleted += 1;
+                Some(value)
+            }
+            slot => {
+                self.slots[index] = slot;
+                None
+            }
+        }
+    }
+
+    /// Returns the number of probes a search for the key makes, including
+    /// the probe which finds it or finds an empty slot.
+    pub fn probe_count(&self, key: &K) -> usize {
+        let mut count = 0;
+        for index in self.probes(key) {
+            count += 1;
+            match &self.slots[index] {
+                Slot::Empty => break,
+                Slot::Occupied(other, _) if other == key => break,
+                _ => {}
+            }
+        }
+        count
+    }
+
+    /// Returns the index of the slot holding the key, if any, and the index
+    /// of the first vacant slot of its probe sequence, if any.
+    fn search(&self, key: &K) -> (Option<usize>, Option<usize>) {
+        let mut vacant = None;
+        for index in self.probes(key) {
+            match &self.slots[index] {
+                Slot::Empty => return (None, vacant.or(Some(index))),
+                Slot::Deleted => {
+                    vacant = vacant.or(Some(index));
+                }
+                Slot::Occupied(other, _) if other == key => return (Some(index), vacant),
+                Slot::Occupied(..) => {}
+            }
+        }
+        (None, vacant)
+    }
+
+    /// Returns the probe sequence of the key.
+    fn probes(&self, key: &K) -> impl Iterator<Item = usize> {
+        let natural = key.to_natural();
+        let slot_count = self.slots.len();
+        let mask = slot_count - 1;
+        let start = self.hash_fn.hash(natural, slot_count);
+        let step = (natural / slot_count as u64 % slot_count as u64) as usize | 1;
+        let probing = self.probing;
+        (0..slot_count).map(move |i| {
+            let offset = match probing {
+                Probing::Linear => i,
+                Probing::Quadratic => i * (i + 1) / 2,
+                Probing::Double => i.wrapping_mul(step),
+            };
+            start.wrapping_add(offset) & mask
+        })
+    }
+
+    /// Moves every entry into a table of the given number of slots.
+    fn rehash(&mut self, slot_count: usize) {
+        let slots = std::mem::replace(&mut self.slots, Self::empty_slots(slot_count));
+        self.deleted = 0;
+        for slot in slots {
+            if let Slot::Occupied(key, value) = slot {
+                let index = self.search(&key).1.expect("the new table has room");
+                self.slots[index] = Slot::Occupied(key, value);
+            }
+        }
+    }
+}
+
+impl<K: Key, V, H: HashFn> Map<K, V> for OpenAddressingTable<K, V, H> {
+    type Iter<'a>
+        = OpenAddressingIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        H: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        OpenAddressingTable::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        OpenAddressingTable::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        OpenAddressingTable::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        OpenAddressingTable::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        OpenAddressingTable::iter(self)
+    }
+}
+
+/// Iterator over the entries of an [`OpenAddressingTable`].
+#[derive(Debug)]
+pub struct OpenAddressingIter<'a, K, V> {
+    slots: std::slice::Iter<'a, Slot<K, V>>,
+}
+
+impl<'a, K, V> Iterator for OpenAddressingIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|slot| match slot {
+            Slot::Occupied(key, value) => Some((key, value)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::hashtable::DivisionMethod;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    const PROBINGS: [Probing; 3] = [Probing::Linear, Probing::Quadratic, Probing::Double];
+
+    #[test]
+    fn insert_and_remove() {
+        for probing in PROBINGS {
+            let mut table = OpenAddressingTable::new(probing);
+            for key in 0..100u32 {
+                assert_eq!(table.insert(key, key * 2), None);
+            }
+            assert_eq!(table.insert(7, 0), Some(14));
+            assert_eq!(table.get(&7), Some(&0));
+            assert_eq!(table.len(), 100);
+            assert_eq!(table.slot_count(), 256);
+
+            for key in (0..100).step_by(2) {
+                assert!(table.remove(&key).is_some());
+            }
+            assert_eq!(table.remove(&0), None);
+            assert_eq!(table.get(&3), Some(&6));
+            assert_eq!(table.len(), 50);
+            assert_eq!(table.iter().count(), 50);
+        }
+    }
+
+    #[test]
+    fn probe_sequences_visit_every_slot() {
+        for probing in PROBINGS {
+            let table = OpenAddressingTable::<u64, ()>::new(probing);
+            for key in 0..64 {
+                let mut slots: Vec<_> = table.probes(&key).collect();
+                slots.sort_unstable();
+                assert!(slots.into_iter().eq(0..8), "{probing:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn double_hashing_breaks_clusters() {
+        // The keys all hash into slot 0 with the division method.
+        let keys: Vec<u64> = (0..4).map(|key| key * 8 + 64 * key).collect();
+        let probes = |probing| {
+            let mut table = OpenAddressingTable::with_hash_fn(probing, DivisionMethod);
+            for &key in &keys {
+                table.insert(key, ());
+            }
+            keys.iter().map(|key| table.probe_count(key)).sum::<usize>()
+        };
+        assert_eq!(probes(Probing::Linear), 1 + 2 + 3 + 4);
+        assert!(probes(Probing::Double) < probes(Probing::Linear));
+    }
+
+    #[test]
+    fn removed_markers_are_cleared() {
+        let mut table = OpenAddressingTable::new(Probing::Linear);
+        for round in 0..100u32 {
+            table.insert(round, ());
+            table.remove(&round);
+        }
+        assert!(table.is_empty());
+        assert_eq!(table.slot_count(), 8);
+        assert!(table.deleted <= 4);
+    }
+
+    #[quickcheck]
+    fn open_addressing_agrees_with_model(operations: Vec<Operation>) -> bool {
+        PROBINGS.into_iter().all(|probing| {
+            agrees_with_model::<u8, _>(&mut OpenAddressingTable::new(probing), &operations)
+                && agrees_with_model::<u8, _>(
+                    &mut OpenAddressingTable::with_hash_fn(probing, DivisionMethod),
+                    &operations,
+                )
+        })
+    }
+}
@@ -1,11 +1,25 @@
 //! Heap operations.
 //!
 //! This module implements various heap operations on the [`Container`](crate::Container) type.
+//!
+//! The [`PriorityQueue`](priority_queue::PriorityQueue) wraps a min heap and
+//! follows the swaps of its traced operations to keep track of the position
+//! of its keys, so they can be decreased or removed by handle.
+//! The [`BinomialHeap`](binomial::BinomialHeap) is a mergeable heap.
+//! The [`YoungTableau`](young_tableau::YoungTableau) is a matrix heap
+//! whose rows and columns are sorted.
 
 use std::mem;
 use std::ops::{Index, IndexMut};
 use std::{cmp::Ordering, marker::PhantomData};
 
+use crate::trace::{Event, Sink};
+use crate::Container;
+
+pub mod binomial;
+pub mod priority_queue;
+pub mod young_tableau;
+
 /// Heap type.
 #[derive(Debug, Clone, Default)]
 pub struct Heap<T, K> {
@@ -47,8 +61,89 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
-/// Max Heap type
+/// Iterator type which drains a heap in priority order.
 #[derive(Debug)]
+pub struct IntoSortedIter<T, K> {
+    heap: Heap<T, K>,
+}
+
+impl<T> Iterator for IntoSortedIter<T, MaxHeap>
+where
+    T: PartialEq + Eq + Ord + PartialOrd + Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.extract_max()
+    }
+}
+
+impl<T> Iterator for IntoSortedIter<T, MinHeap>
+where
+    T: PartialEq + Eq + Ord + PartialOrd + Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.extract_min()
+    }
+}
+
+/// Iterator type over the elements in a heap in priority order.
+#[derive(Debug)]
+pub struct SortedIter<'a, T, K> {
+    heap: &'a Heap<T, K>,
+
+    /// The values not yielded yet whose parent was, with their index.
+    frontier: Heap<(&'a T, usize), K>,
+}
+
+impl<'a, T, K> SortedIter<'a, T, K>
+where
+    T: PartialEq + Eq + Ord + PartialOrd + Clone,
+{
+    /// Returns the children of the value at the index, with their index.
+    fn children(&self, index: usize) -> impl Iterator<Item = (&'a T, usize)> {
+        let heap = self.heap;
+        [heap.left_child(index), heap.right_child(index)]
+            .into_iter()
+            .filter(move |&child| child < heap.size)
+            .map(move |child| (&heap.buffer[child], child))
+    }
+}
+
+impl<'a, T> Iterator for SortedIter<'a, T, MaxHeap>
+where
+    T: PartialEq + Eq + Ord + PartialOrd + Clone,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, index) = self.frontier.extract_max()?;
+        for child in self.children(index) {
+            self.frontier.insert(child);
+        }
+        Some(value)
+    }
+}
+
+impl<'a, T> Iterator for SortedIter<'a, T, MinHeap>
+where
+    T: PartialEq + Eq + Ord + PartialOrd + Clone,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, index) = self.frontier.extract_min()?;
+        for child in self.children(index) {
+            self.frontier.insert(child);
+        }
+        Some(value)
+    }
+}
+
+/// Max Heap type
+#[derive(Debug, Clone)]
 pub struct MaxHeap;
 
 impl<T, K> Heap<T, K>
@@ -56,11 +151,11 @@ where
     T: PartialEq + Eq + Ord + PartialOrd + Clone,
 {
     /// Creates new heap.
-    pub fn new(buffer: Vec<T>) -> Self {
+    pub const fn new(buffer: Vec<T>) -> Self {
         Self {
             buffer,
             size: 0,
-            marker: PhantomData::default(),
+            marker: PhantomData,
         }
     }
 
@@ -69,21 +164,71 @@ where
         Self {
             buffer: Vec::with_capacity(cap),
             size: 0,
-            marker: PhantomData::default(),
+            marker: PhantomData,
         }
     }
 
+    /// Returns a reference to the element at the index, or `None` if the
+    /// index is not below the size of the heap.
+    ///
+    /// The elements of the buffer past the size, such as those a sort moved
+    /// out of the heap, are not in the heap.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.buffer[..self.size].get(index)
+    }
+
+    /// Returns a mutable reference to the element at the index, or `None`
+    /// if the index is not below the size of the heap.
+    ///
+    /// Changing the element may break the heap property.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.buffer[..self.size].get_mut(index)
+    }
+
     /// Creates an iterator over the values in the heap.
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             inner: &self.buffer[..self.size],
             pos: 0,
         }
     }
 
+    /// Creates an iterator which drains the heap in priority order, from
+    /// the maximum of a max heap or the minimum of a min heap.
+    ///
+    /// Each element is extracted when it is needed, so taking the first `k`
+    /// elements takes O(k log n) time.
+    pub const fn into_sorted_iter(self) -> IntoSortedIter<T, K> {
+        IntoSortedIter { heap: self }
+    }
+
+    /// Creates an iterator over the values in the heap in priority order,
+    /// leaving the heap unchanged.
+    ///
+    /// The next value is the best one among the children of the values
+    /// yielded so far, which are kept in a heap of their own, so taking the
+    /// first `k` values takes O(k log k) time.
+    pub fn sorted_iter(&self) -> SortedIter<'_, T, K> {
+        let buffer = if self.size > 0 {
+            vec![(&self.buffer[0], 0)]
+        } else {
+            Vec::new()
+        };
+        SortedIter {
+            heap: self,
+            frontier: Heap {
+                size: buffer.len(),
+                buffer,
+                marker: PhantomData,
+            },
+        }
+    }
+
     /// Returns the index of the parent of the child at the specified index.
+    ///
+    /// The root is its own parent.
     pub const fn parent(&self, index: usize) -> usize {
-        index / 2
+        index.saturating_sub(1) / 2
     }
 
     /// Returns the index of the left child for the subtree rooted at the specified index.
@@ -95,6 +240,15 @@ where
     pub const fn right_child(&self, index: usize) -> usize {
         index * 2 + 2
     }
+
+    /// Swaps the elements at the two indices, and records the swap to the
+    /// sink unless the indices are equal.
+    fn swap(&mut self, i: usize, j: usize, sink: &mut impl Sink) {
+        if i != j {
+            sink.record(Event::Swap { i, j });
+            self.buffer.swap(i, j);
+        }
+    }
 }
 impl<T> Heap<T, MaxHeap>
 where
@@ -192,20 +346,25 @@ where
             Some(old)
         }
     }
-}
 
-impl Heap<i64, MaxHeap> {
-    /// Insert new key into the heap.
-    pub fn max_insert_key(&mut self, key: i64) {
-        let index = self.size;
-        self.buffer.push(i64::MIN);
-        self.increase_key(index, key);
+    /// Insert the key into the max heap.
+    ///
+    /// The key is appended to the heap, then swapped with its parent while
+    /// it is larger, which takes O(log n) time.
+    pub fn insert(&mut self, key: T) {
+        let mut index = self.size;
+        self.buffer.insert(index, key);
         self.size += 1;
+        while index > 0 && self[self.parent(index)] < self[index] {
+            let parent = self.parent(index);
+            self.buffer.swap(index, parent);
+            index = parent;
+        }
     }
 }
 
 /// Min Heap type.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MinHeap;
 
 impl<T> Heap<T, MinHeap>
@@ -215,22 +374,7 @@ where
     /// Re-arrange the element at the specified index so that the subtree
     /// rooted at the specified index satisfied the min heap property.
     pub fn min_heapify(&mut self, index: usize) {
-        let left = self.left_child(index);
-        let mut smallest = index;
-
-        if left < self.size && self[left] < self[index] {
-            smallest = left;
-        }
-
-        let right = self.right_child(index);
-        if right < self.size && self[right] < self[smallest] {
-            smallest = right;
-        }
-
-        if smallest != index {
-            self.buffer.swap(index, smallest);
-            self.min_heapify(smallest);
-        }
+        self.sift_down(index, &mut ());
     }
 
     /// Build min heap.
@@ -249,58 +393,118 @@ where
 
     /// Extract the minimum element in the heap.
     pub fn extract_min(&mut self) -> Option<T> {
+        self.traced_extract_min(&mut ())
+    }
+
+    /// Traced extraction of the minimum element.
+    ///
+    /// It is [`Heap::extract_min`], where each swap is recorded to the
+    /// sink. The minimum is swapped with the last element before it is
+    /// removed.
+    pub fn traced_extract_min(&mut self, sink: &mut impl Sink) -> Option<T> {
         if self.size == 0 {
             return None;
         }
-
-        let min = self.buffer.swap_remove(0);
+        self.swap(0, self.buffer.len() - 1, sink);
+        let min = self.buffer.pop();
         self.size -= 1;
-        self.min_heapify(0);
-        Some(min)
+        self.sift_down(0, sink);
+        min
     }
 
     /// Decrease the key at the specified index.
     /// On success, it returns the old value.
     pub fn decrease_key(&mut self, index: usize, key: T) -> Option<T> {
+        self.traced_decrease_key(index, key, &mut ())
+    }
+
+    /// Traced decrease of the key at the specified index.
+    ///
+    /// It is [`Heap::decrease_key`], where each swap is recorded to the
+    /// sink.
+    pub fn traced_decrease_key(&mut self, index: usize, key: T, sink: &mut impl Sink) -> Option<T> {
         if index >= self.size || self[index] < key {
             return None;
         }
         let prev = mem::replace(&mut self.buffer[index], key);
-        let mut index = index;
-        let mut parent = self.parent(index);
-        while index < self.size && self[index] < self[parent] {
-            self.buffer.swap(index, parent);
-            index = parent;
-            parent = self.parent(index);
-        }
+        self.sift_up(index, sink);
         Some(prev)
     }
 
     /// Delete min key
     pub fn delete_min(&mut self, index: usize) -> Option<T> {
+        self.traced_delete_min(index, &mut ())
+    }
+
+    /// Traced deletion of the key at the specified index.
+    ///
+    /// It is [`Heap::delete_min`], where each swap is recorded to the sink.
+    /// The key is swapped with the last one before it is removed, then the
+    /// key moved into its place goes up or down to restore the heap
+    /// property.
+    pub fn traced_delete_min(&mut self, index: usize, sink: &mut impl Sink) -> Option<T> {
         if self.size <= index {
             return None;
         }
-
-        if self[index] < self[self.size - 1] {
-            self.buffer.swap(index, self.size - 1);
-            self.size -= 1;
-            self.min_heapify(index);
-        } else {
-            self.decrease_key(index, self[self.size - 1].clone());
-            self.size -= 1;
+        let last = self.size - 1;
+        self.swap(index, last, sink);
+        self.size -= 1;
+        let key = self.buffer.pop();
+        if index < last {
+            let index = self.sift_up(index, sink);
+            self.sift_down(index, sink);
         }
-        self.buffer.pop()
+        key
     }
-}
 
-impl Heap<i64, MinHeap> {
     /// Insert the key into the min heap.
-    pub fn min_insert_key(&mut self, key: i64) {
+    ///
+    /// The key is appended to the heap, then swapped with its parent while
+    /// it is smaller, which takes O(log n) time.
+    pub fn insert(&mut self, key: T) {
+        self.traced_insert(key, &mut ());
+    }
+
+    /// Traced insertion of the key into the min heap.
+    ///
+    /// It is [`Heap::insert`], where each swap is recorded to the sink.
+    pub fn traced_insert(&mut self, key: T, sink: &mut impl Sink) {
         let index = self.size;
-        self.buffer.push(i64::MAX);
+        self.buffer.insert(index, key);
         self.size += 1;
-        self.decrease_key(index, key);
+        self.sift_up(index, sink);
+    }
+
+    /// Moves the key at the index up while it is smaller than its parent,
+    /// and returns its new index.
+    fn sift_up(&mut self, mut index: usize, sink: &mut impl Sink) -> usize {
+        while index > 0 && self[index] < self[self.parent(index)] {
+            let parent = self.parent(index);
+            self.swap(index, parent, sink);
+            index = parent;
+        }
+        index
+    }
+
+    /// Moves the key at the index down while it is greater than one of its
+    /// children.
+    fn sift_down(&mut self, index: usize, sink: &mut impl Sink) {
+        let left = self.left_child(index);
+        let mut smallest = index;
+
+        if left < self.size && self[left] < self[index] {
+            smallest = left;
+        }
+
+        let right = self.right_child(index);
+        if right < self.size && self[right] < self[smallest] {
+            smallest = right;
+        }
+
+        if smallest != index {
+            self.swap(index, smallest, sink);
+            self.sift_down(smallest, sink);
+        }
     }
 }
 
@@ -327,7 +531,7 @@ where
     T: PartialEq + Eq + Ord + PartialOrd + Clone,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.key.partial_cmp(&other.key)
+        Some(self.cmp(other))
     }
 }
 
@@ -342,18 +546,6 @@ where
 impl<T> Eq for Value<T> where T: PartialEq + Eq + Ord + PartialOrd + Clone {}
 
 impl Heap<Value<i64>, MinHeap> {
-    /// Insert the key into the min heap.
-    pub fn min_insert_key(&mut self, value: Value<i64>) {
-        self.buffer.push(Value {
-            key: i64::MAX,
-            index: value.index,
-        });
-
-        let index = self.size;
-        self.size += 1;
-        self.decrease_min_key(index, value);
-    }
-
     /// Decrease the key at the specified index.
     /// On success, it returns the old value.
     pub fn decrease_min_key(&mut self, index: usize, value: Value<i64>) -> Option<Value<i64>> {
@@ -371,9 +563,76 @@ impl Heap<Value<i64>, MinHeap> {
     }
 }
 
+impl<T> Container<T>
+where
+    T: PartialEq + Eq + Ord + PartialOrd + Clone,
+{
+    /// Returns the `k` largest elements in decreasing order, or every
+    /// element if there are fewer than `k`.
+    ///
+    /// The largest elements seen so far are kept in a min heap of size
+    /// `k`, whose minimum is replaced by any larger element, so it takes
+    /// O(n log k) time and O(k) memory.
+    pub fn top_k(&self, k: usize) -> Vec<T> {
+        let mut heap: Heap<T, MinHeap> = Heap::with_capacity(k);
+        for value in self.iter() {
+            if heap.size < k {
+                heap.insert(value.clone());
+            } else if heap.min().map_or(false, |min| min < value) {
+                heap[0] = value.clone();
+                heap.min_heapify(0);
+            }
+        }
+        let mut top: Vec<T> = heap.into_sorted_iter().collect();
+        top.reverse();
+        top
+    }
+
+    /// Returns the `k` smallest elements in increasing order, or every
+    /// element if there are fewer than `k`.
+    ///
+    /// It is the counterpart of [`Container::top_k`], keeping the smallest
+    /// elements seen so far in a max heap of size `k`.
+    pub fn bottom_k(&self, k: usize) -> Vec<T> {
+        let mut heap: Heap<T, MaxHeap> = Heap::with_capacity(k);
+        for value in self.iter() {
+            if heap.size < k {
+                heap.insert(value.clone());
+            } else if heap.max().map_or(false, |max| value < max) {
+                heap[0] = value.clone();
+                heap.max_heapify(0);
+            }
+        }
+        let mut bottom: Vec<T> = heap.into_sorted_iter().collect();
+        bottom.reverse();
+        bottom
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Heap;
+    use std::collections::BinaryHeap;
+
+    use quickcheck_macros::quickcheck;
+
+    use super::{Heap, MaxHeap, MinHeap};
+    use crate::Container;
+
+    #[test]
+    fn get_is_bounded_by_the_size() {
+        let mut heap: Heap<_, MaxHeap> = Heap::new(vec![3, 1, 5, 2, 4]);
+        assert_eq!(heap.get(0), None);
+        heap.build_max_heap();
+        assert_eq!(heap.get(0), Some(&5));
+        assert_eq!(heap.get(5), None);
+
+        // The sort leaves a single element in the heap.
+        heap.sort();
+        assert_eq!(heap.iter().count(), 1);
+        assert_eq!(heap.get(0), Some(&1));
+        assert_eq!(heap.get(4), None);
+        assert_eq!(heap.get_mut(4), None);
+    }
 
     #[test]
     fn max_heapify() {
@@ -423,4 +682,119 @@ mod tests {
         assert_eq!(prev, Some(14));
         assert_eq!(heap.buffer, vec![25, 16, 10, 8, 7, 9, 3, 2, 4, 1])
     }
+
+    #[test]
+    fn decrease_key() {
+        let mut heap: Heap<_, MinHeap> = Heap::new(vec![1, 5, 3, 7, 8, 4, 6]);
+        heap.build_min_heap();
+        assert_eq!(heap.parent(5), 2);
+        assert_eq!(heap.decrease_key(5, 0), Some(4));
+        assert_eq!(heap.buffer, vec![0, 5, 1, 7, 8, 3, 6]);
+        assert_eq!(heap.decrease_key(1, 9), None);
+    }
+
+    #[test]
+    fn insert_keys() {
+        let mut heap: Heap<_, MaxHeap> = Heap::with_capacity(4);
+        for key in [3, 9, 1, 7] {
+            heap.insert(key);
+        }
+        assert_eq!(heap.buffer, vec![9, 7, 1, 3]);
+        assert_eq!(heap.extract_max(), Some(9));
+
+        let mut heap: Heap<_, MinHeap> = Heap::new(vec![]);
+        for key in ["c", "a", "b"] {
+            heap.insert(key);
+        }
+        assert_eq!(heap.min(), Some(&"a"));
+    }
+
+    #[test]
+    fn sorted_iterators() {
+        let mut heap: Heap<_, MaxHeap> = Heap::new(vec![16, 4, 10, 14, 7, 9, 3, 2, 8, 1]);
+        heap.build_max_heap();
+        let top: Vec<&i32> = heap.sorted_iter().take(3).collect();
+        assert_eq!(top, vec![&16, &14, &10]);
+        assert_eq!(heap.buffer, vec![16, 14, 10, 8, 7, 9, 3, 2, 4, 1]);
+        let drained: Vec<i32> = heap.into_sorted_iter().collect();
+        assert_eq!(drained, vec![16, 14, 10, 9, 8, 7, 4, 3, 2, 1]);
+
+        let mut heap: Heap<_, MinHeap> = Heap::new(vec![5, 1, 4, 1]);
+        heap.build_min_heap();
+        assert_eq!(
+            heap.sorted_iter().copied().collect::<Vec<_>>(),
+            [1, 1, 4, 5]
+        );
+        assert_eq!(heap.into_sorted_iter().take(2).collect::<Vec<_>>(), [1, 1]);
+        assert_eq!(
+            Heap::<i32, MinHeap>::new(vec![1]).sorted_iter().next(),
+            None
+        );
+    }
+
+    #[quickcheck]
+    fn sorted_iterators_sort(keys: Vec<i16>) -> bool {
+        let mut ascending = keys.clone();
+        ascending.sort_unstable();
+        let mut min_heap: Heap<_, MinHeap> = Heap::new(keys.clone());
+        min_heap.build_min_heap();
+        let mut max_heap: Heap<_, MaxHeap> = Heap::new(keys);
+        max_heap.build_max_heap();
+
+        min_heap.sorted_iter().eq(ascending.iter())
+            && max_heap.sorted_iter().eq(ascending.iter().rev())
+            && min_heap.into_sorted_iter().eq(ascending.iter().copied())
+            && max_heap.into_sorted_iter().eq(ascending.into_iter().rev())
+    }
+
+    #[test]
+    fn top_and_bottom_k() {
+        let container = Container::new(vec![16, 4, 10, 14, 7, 9, 3, 2, 8, 1, 14]);
+        assert_eq!(container.top_k(3), vec![16, 14, 14]);
+        assert_eq!(container.bottom_k(4), vec![1, 2, 3, 4]);
+        assert_eq!(container.top_k(0), Vec::<i32>::new());
+        assert_eq!(Container::new(vec![2, 1]).bottom_k(5), vec![1, 2]);
+    }
+
+    #[quickcheck]
+    fn top_k_agrees_with_sort(keys: Vec<i16>, k: u8) -> bool {
+        let k = usize::from(k);
+        let container = Container::new(keys.clone());
+        let mut sorted = keys;
+        sorted.sort_unstable();
+        let bottom: Vec<i16> = sorted.iter().copied().take(k).collect();
+        let top: Vec<i16> = sorted.into_iter().rev().take(k).collect();
+        container.top_k(k) == top && container.bottom_k(k) == bottom
+    }
+
+    /// Operation is an insertion of the key, or an extraction.
+    type Operation = Option<i16>;
+
+    #[quickcheck]
+    fn max_heap_agrees_with_binary_heap(operations: Vec<Operation>) -> bool {
+        let mut heap: Heap<_, MaxHeap> = Heap::new(vec![]);
+        let mut model = BinaryHeap::new();
+        operations.into_iter().all(|operation| match operation {
+            Some(key) => {
+                heap.insert(key);
+                model.push(key);
+                heap.max() == model.peek()
+            }
+            None => heap.extract_max() == model.pop(),
+        })
+    }
+
+    #[quickcheck]
+    fn min_heap_agrees_with_binary_heap(operations: Vec<Operation>) -> bool {
+        let mut heap: Heap<_, MinHeap> = Heap::new(vec![]);
+        let mut model = BinaryHeap::new();
+        operations.into_iter().all(|operation| match operation {
+            Some(key) => {
+                heap.insert(key);
+                model.push(std::cmp::Reverse(key));
+                heap.min() == model.peek().map(|key| &key.0)
+            }
+            None => heap.extract_min() == model.pop().map(|key| key.0),
+        })
+    }
 }
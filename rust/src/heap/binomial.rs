@@ -0,0 +1,516 @@
+//! Binomial heap.
+//!
+//! A binomial tree `B(k)` is made of two `B(k - 1)` trees, the root of one
+//! being the leftmost child of the root of the other, so it has `2^k` nodes
+//! and its root has `k` children. A binomial heap is a list of heap-ordered
+//! binomial trees of distinct degrees, one per bit set in its number of
+//! keys. Merging two heaps adds their lists like binary numbers, linking
+//! two trees of the same degree into one of the next degree, so mergeable
+//! heaps are supported in O(log n) time per operation. See CLRS chapter 19
+//! of the second edition.
+
+use super::priority_queue::Handle;
+
+/// Node of a [`BinomialHeap`].
+#[derive(Debug, Clone)]
+struct BinomialNode<T> {
+    key: T,
+    handle: Handle,
+
+    /// The number of children.
+    degree: usize,
+    parent: Option<usize>,
+
+    /// The leftmost child, whose degree is the largest.
+    child: Option<usize>,
+
+    /// The next sibling, or the next root for a root.
+    sibling: Option<usize>,
+}
+
+/// BinomialHeap is a mergeable min-heap.
+///
+/// The nodes are stored in an arena, and the keys are reached through the
+/// handle returned when they were inserted, so they can be decreased or
+/// deleted. A key moving up its tree takes its handle along.
+#[derive(Debug, Clone)]
+pub struct BinomialHeap<T> {
+    nodes: Vec<Option<BinomialNode<T>>>,
+
+    /// The slots of the removed nodes, to be reused.
+    free: Vec<usize>,
+
+    /// The first root, roots being listed by increasing degree.
+    head: Option<usize>,
+
+    /// The slot of the node of each handle, if its key is still in the
+    /// heap.
+    positions: Vec<Option<usize>>,
+    len: usize,
+}
+
+impl<T> BinomialHeap<T> {
+    /// Creates new empty heap.
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: None,
+            positions: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of keys.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the heap is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the key of the handle is in the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slot(handle).is_some()
+    }
+
+    /// Returns the key of the handle, if it is in the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.slot(handle).map(|slot| &self.node(slot).key)
+    }
+
+    fn slot(&self, handle: Handle) -> Option<usize> {
+        self.positions.get(handle.0).copied().flatten()
+    }
+
+    fn node(&self, slot: usize) -> &BinomialNode<T> {
+        self.nodes[slot].as_ref().expect("the slot holds a node")
+    }
+
+    fn node_mut(&mut self, slot: usize) -> &mut BinomialNode<T> {
+        self.nodes[slot].as_mut().expect("the slot holds a node")
+    }
+
+    /// Returns the roots, by increasing degree.
+    fn roots(&self) -> Vec<usize> {
+        self.siblings(self.head)
+    }
+
+    /// Returns the list of siblings starting at the node.
+    fn siblings(&self, mut current: Option<usize>) -> Vec<usize> {
+        let mut siblings = Vec::new();
+        while let Some(slot) = current {
+            siblings.push(slot);
+            current = self.node(slot).sibling;
+        }
+        siblings
+    }
+
+    /// Stores the node in a free slot, and returns the slot.
+    fn allocate(&mut self, node: BinomialNode<T>) -> usize {
+        let handle = node.handle;
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = Some(node);
+                slot
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.positions[handle.0] = Some(slot);
+        slot
+    }
+
+    /// Swaps the keys of the two nodes, with their handles.
+    fn swap_keys(&mut self, a: usize, b: usize) {
+        let (low, high) = (a.min(b), a.max(b));
+        let (left, right) = self.nodes.split_at_mut(high);
+        let x = left[low].as_mut().expect("the slot holds a node");
+        let y = right[0].as_mut().expect("the slot holds a node");
+        std::mem::swap(&mut x.key, &mut y.key);
+        std::mem::swap(&mut x.handle, &mut y.handle);
+        self.positions[x.handle.0] = Some(low);
+        self.positions[y.handle.0] = Some(high);
+    }
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    /// Inserts the key and returns its handle.
+    pub fn insert(&mut self, key: T) -> Handle {
+        let handle = Handle(self.positions.len());
+        self.positions.push(None);
+        let slot = self.allocate(BinomialNode {
+            key,
+            handle,
+            degree: 0,
+            parent: None,
+            child: None,
+            sibling: None,
+        });
+        let mut roots = self.roots();
+        roots.push(slot);
+        self.consolidate(roots);
+        self.len += 1;
+        handle
+    }
+
+    /// Returns the minimum key with its handle.
+    ///
+    /// The minimum is one of the roots, so it takes O(log n) time.
+    pub fn minimum(&self) -> Option<(Handle, &T)> {
+        self.min_root().map(|slot| {
+            let node = self.node(slot);
+            (node.handle, &node.key)
+        })
+    }
+
+    /// Removes the minimum key and returns it with its handle.
+    pub fn extract_min(&mut self) -> Option<(Handle, T)> {
+        let root = self.min_root()?;
+        Some(self.remove_root(root))
+    }
+
+    /// Moves every key of the other heap into this heap.
+    ///
+    /// The keys of the other heap are given new handles, so its handles
+    /// are not valid for this heap. Linking the trees takes O(log n) time,
+    /// while moving the nodes into the arena of this heap takes time
+    /// proportional to the size of the other heap.
+    pub fn union(&mut self, other: Self) {
+        let mut slots = vec![0; other.nodes.len()];
+        for (old, node) in other.nodes.iter().enumerate() {
+            if node.is_some() {
+                slots[old] = match self.free.pop() {
+                    Some(slot) => slot,
+                    None => {
+                        self.nodes.push(None);
+                        self.nodes.len() - 1
+                    }
+                };
+            }
+        }
+        for (old, node) in other.nodes.into_iter().enumerate() {
+            let Some(node) = node else {
+                continue;
+            };
+            let slot = slots[old];
+            let handle = Handle(self.positions.len());
+            self.positions.push(Some(slot));
+            self.nodes[slot] = Some(BinomialNode {
+                key: node.key,
+                handle,
+                degree: node.degree,
+                parent: node.parent.map(|old| slots[old]),
+                child: node.child.map(|old| slots[old]),
+                sibling: node.sibling.map(|old| slots[old]),
+            });
+        }
+
+        let mut roots = self.roots();
+        roots.extend(
+            other
+                .head
+                .map(|old| self.siblings(Some(slots[old])))
+                .unwrap_or_default(),
+        );
+        self.len += other.len;
+        self.consolidate(roots);
+    }
+
+    /// Replaces the key of the handle by a key which is not greater.
+    ///
+    /// The key moves up its tree while it is smaller than its parent. On
+    /// success, it returns the previous key. It returns `None`, leaving the
+    /// heap unchanged, if the key of the handle is not in the heap or is
+    /// smaller than the new key.
+    pub fn decrease_key(&mut self, handle: Handle, key: T) -> Option<T> {
+        let slot = self.slot(handle)?;
+        if self.node(slot).key < key {
+            return None;
+        }
+        let previous = std::mem::replace(&mut self.node_mut(slot).key, key);
+        let mut current = slot;
+        while let Some(parent) = self.node(current).parent {
+            if self.node(parent).key <= self.node(current).key {
+                break;
+            }
+            self.swap_keys(current, parent);
+            current = parent;
+        }
+        Some(previous)
+    }
+
+    /// Removes the key of the handle and returns it, if it is in the heap.
+    ///
+    /// The key moves up to the root of its tree as if it were decreased to
+    /// minus infinity, then it is extracted.
+    pub fn delete(&mut self, handle: Handle) -> Option<T> {
+        let mut current = self.slot(handle)?;
+        while let Some(parent) = self.node(current).parent {
+            self.swap_keys(current, parent);
+            current = parent;
+        }
+        Some(self.remove_root(current).1)
+    }
+
+    /// Returns the root of minimum key.
+    fn min_root(&self) -> Option<usize> {
+        self.roots()
+            .into_iter()
+            .min_by(|&a, &b| self.node(a).key.cmp(&self.node(b).key))
+    }
+
+    /// Removes the root from the heap, merging its children with the other
+    /// roots, and returns its key with its handle.
+    fn remove_root(&mut self, root: usize) -> (Handle, T) {
+        let mut roots = self.roots();
+        roots.retain(|&slot| slot != root);
+        let children = self.siblings(self.node(root).child);
+        for &child in &children {
+            self.node_mut(child).parent = None;
+        }
+        roots.extend(children);
+        self.consolidate(roots);
+
+        let node = self.nodes[root].take().expect("the slot holds a node");
+        self.free.push(root);
+        self.positions[node.handle.0] = None;
+        self.len -= 1;
+        (node.handle, node.key)
+    }
+
+    /// Links the trees of the same degree together, as in a binary
+    /// addition, and makes the resulting trees the roots.
+    fn consolidate(&mut self, roots: Vec<usize>) {
+        // `trees[k]` is the tree of degree `k`, if any.
+        let mut trees: Vec<Option<usize>> = Vec::new();
+        for root in roots {
+            let mut carry = root;
+            loop {
+                let degree = self.node(carry).degree;
+                if trees.len() <= degree {
+                    trees.resize(degree + 1, None);
+                }
+                match trees[degree].take() {
+                    Some(tree) => carry = self.link(tree, carry),
+                    None => {
+                        trees[degree] = Some(carry);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.head = None;
+        for root in trees.into_iter().rev().flatten() {
+            self.node_mut(root).sibling = self.head;
+            self.head = Some(root);
+        }
+    }
+
+    /// Links two trees of the same degree, the root of larger key becoming
+    /// the leftmost child of the other one, and returns the new root.
+    fn link(&mut self, a: usize, b: usize) -> usize {
+        let (parent, child) = if self.node(b).key < self.node(a).key {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let first = self.node(parent).child;
+        let node = self.node_mut(child);
+        node.parent = Some(parent);
+        node.sibling = first;
+        let node = self.node_mut(parent);
+        node.child = Some(child);
+        node.degree += 1;
+        parent
+    }
+}
+
+impl<T> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        for key in iter {
+            heap.insert(key);
+        }
+        heap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if the roots have increasing degrees, every tree is a
+    /// heap-ordered binomial tree, and the handles track the nodes.
+    fn is_valid<T: Ord>(heap: &BinomialHeap<T>) -> bool {
+        // Returns the size of the tree if it is valid.
+        fn check<T: Ord>(heap: &BinomialHeap<T>, slot: usize) -> Option<usize> {
+            let node = heap.node(slot);
+            let children = heap.siblings(node.child);
+            let degrees: Vec<usize> = children.iter().map(|&c| heap.node(c).degree).collect();
+            let expected: Vec<usize> = (0..node.degree).rev().collect();
+            if degrees != expected || heap.slot(node.handle) != Some(slot) {
+                return None;
+            }
+            children.iter().try_fold(1, |size, &child| {
+                let child_node = heap.node(child);
+                (child_node.parent == Some(slot) && node.key <= child_node.key)
+                    .then_some(())
+                    .and_then(|_| check(heap, child))
+                    .map(|child_size| size + child_size)
+            })
+        }
+
+        let roots = heap.roots();
+        let sizes: Option<Vec<usize>> = roots
+            .iter()
+            .map(|&root| {
+                (heap.node(root).parent.is_none())
+                    .then_some(())
+                    .and_then(|_| check(heap, root))
+            })
+            .collect();
+        let Some(sizes) = sizes else {
+            return false;
+        };
+        roots
+            .windows(2)
+            .all(|pair| heap.node(pair[0]).degree < heap.node(pair[1]).degree)
+            && sizes.iter().sum::<usize>() == heap.len()
+            && heap.positions.iter().flatten().count() == heap.len()
+    }
+
+    fn drain<T: Ord>(heap: &mut BinomialHeap<T>) -> Vec<T> {
+        std::iter::from_fn(|| heap.extract_min().map(|(_, key)| key)).collect()
+    }
+
+    #[test]
+    fn mergeable_heap_operations() {
+        let mut heap: BinomialHeap<i32> = [37, 41, 10, 28, 13, 77, 1, 6, 16, 12, 25]
+            .into_iter()
+            .collect();
+        assert!(is_valid(&heap));
+        // 11 keys make trees of degrees 0, 1 and 3.
+        let degrees: Vec<usize> = heap
+            .roots()
+            .into_iter()
+            .map(|root| heap.node(root).degree)
+            .collect();
+        assert_eq!(degrees, vec![0, 1, 3]);
+        assert_eq!(heap.minimum().map(|(_, key)| *key), Some(1));
+
+        let handle = heap.insert(26);
+        assert_eq!(heap.decrease_key(handle, 0), Some(26));
+        assert_eq!(heap.decrease_key(handle, 5), None);
+        assert_eq!(heap.minimum(), Some((handle, &0)));
+        let handle = heap.insert(8);
+        assert_eq!(heap.delete(handle), Some(8));
+        assert_eq!(heap.delete(handle), None);
+        assert!(!heap.contains(handle));
+        assert!(is_valid(&heap));
+
+        let other: BinomialHeap<i32> = [11, 3, 50].into_iter().collect();
+        heap.union(other);
+        assert!(is_valid(&heap));
+        assert_eq!(
+            drain(&mut heap),
+            vec![0, 1, 3, 6, 10, 11, 12, 13, 16, 25, 28, 37, 41, 50, 77]
+        );
+        assert!(heap.is_empty());
+    }
+
+    #[quickcheck]
+    fn union_preserves_keys(lhs: Vec<i16>, rhs: Vec<i16>, extracted: u8) -> bool {
+        let mut heap: BinomialHeap<i16> = lhs.iter().copied().collect();
+        let mut other: BinomialHeap<i16> = rhs.iter().copied().collect();
+        // Leave some free slots in the arena of the other heap.
+        let extracted: Vec<i16> = (0..extracted % 4)
+            .filter_map(|_| other.extract_min().map(|(_, key)| key))
+            .collect();
+        heap.union(other);
+
+        let mut expected: Vec<i16> = lhs.into_iter().chain(rhs).collect();
+        expected.sort_unstable();
+        for key in extracted {
+            let index = expected
+                .iter()
+                .position(|&other| other == key)
+                .expect("the key was in the heap");
+            expected.remove(index);
+        }
+        is_valid(&heap) && drain(&mut heap) == expected
+    }
+
+    /// Operation is a kind, a key and a target among the handles inserted
+    /// so far: an insertion of the key, an extraction of the minimum, a
+    /// decrease of the target key by the magnitude of the key, or a
+    /// deletion of the target key.
+    type Operation = (u8, i16, u8);
+
+    #[quickcheck]
+    fn agrees_with_multiset(operations: Vec<Operation>) -> bool {
+        let mut heap = BinomialHeap::new();
+        let mut model: BTreeMap<i16, usize> = BTreeMap::new();
+        let mut handles = Vec::new();
+        let remove = |model: &mut BTreeMap<i16, usize>, key: i16| {
+            let count = model.get_mut(&key).expect("the key is in the model");
+            *count -= 1;
+            if *count == 0 {
+                model.remove(&key);
+            }
+        };
+        for (kind, key, target) in operations {
+            let target =
+                (!handles.is_empty()).then(|| handles[usize::from(target) % handles.len()]);
+            let agree = match (kind % 4, target) {
+                (0, _) | (_, None) => {
+                    handles.push(heap.insert(key));
+                    *model.entry(key).or_default() += 1;
+                    true
+                }
+                (1, _) => {
+                    let expected = model.keys().next().copied();
+                    if let Some(min) = expected {
+                        remove(&mut model, min);
+                    }
+                    heap.extract_min().map(|(_, key)| key) == expected
+                }
+                (2, Some(handle)) => match heap.get(handle).copied() {
+                    Some(current) => {
+                        let decreased = current.saturating_sub(key.saturating_abs());
+                        remove(&mut model, current);
+                        *model.entry(decreased).or_default() += 1;
+                        heap.decrease_key(handle, decreased) == Some(current)
+                    }
+                    None => heap.decrease_key(handle, key).is_none(),
+                },
+                (_, Some(handle)) => {
+                    let current = heap.get(handle).copied();
+                    if let Some(current) = current {
+                        remove(&mut model, current);
+                    }
+                    heap.delete(handle) == current
+                }
+            };
+            if !agree || !is_valid(&heap) || heap.len() != model.values().sum::<usize>() {
+                return false;
+            }
+        }
+        true
+    }
+}
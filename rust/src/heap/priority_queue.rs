@@ -0,0 +1,263 @@
+//! Priority queue with handles.
+
+use crate::trace::{Event, Sink};
+
+use super::{Heap, MinHeap};
+
+/// Handle to a key pushed onto a [`PriorityQueue`].
+///
+/// A handle stays valid until its key leaves the queue, and is never given
+/// to another key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(pub(super) usize);
+
+/// PriorityQueue is a min-priority queue whose keys can be changed or
+/// removed through the handle returned when they were pushed.
+///
+/// The keys are stored in a [`Heap`] with the [`MinHeap`] property, and the
+/// position of each key in the heap is tracked by handle. The traced heap
+/// operations record every swap, which the queue replays on its handles, so
+/// a key is found without scanning the heap. Pushing, popping, decreasing
+/// and removing a key take O(log n) time. See CLRS section 6.5.
+#[derive(Debug, Clone)]
+pub struct PriorityQueue<T> {
+    /// The keys in heap order.
+    heap: Heap<T, MinHeap>,
+
+    /// The handle of the key at each position in the heap.
+    handles: Vec<Handle>,
+
+    /// The position in the heap of the key of each handle, if it is still
+    /// in the queue.
+    positions: Vec<Option<usize>>,
+}
+
+/// Sink which moves the handles along with the keys swapped in the heap.
+struct Tracker<'a> {
+    handles: &'a mut Vec<Handle>,
+    positions: &'a mut Vec<Option<usize>>,
+}
+
+impl Sink for Tracker<'_> {
+    fn record(&mut self, event: Event) {
+        if let Event::Swap { i, j } = event {
+            self.handles.swap(i, j);
+            self.positions[self.handles[i].0] = Some(i);
+            self.positions[self.handles[j].0] = Some(j);
+        }
+    }
+}
+
+impl<T: Ord + Clone> PriorityQueue<T> {
+    /// Creates new empty queue.
+    pub const fn new() -> Self {
+        Self {
+            heap: Heap::new(Vec::new()),
+            handles: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Creates new empty queue with room for the given number of keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: Heap::with_capacity(capacity),
+            handles: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns true if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Returns true if the key of the handle is in the queue.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Returns the key of the handle, if it is in the queue.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position(handle)
+            .and_then(|position| self.heap.get(position))
+    }
+
+    /// Returns the minimum key with its handle.
+    pub fn peek(&self) -> Option<(Handle, &T)> {
+        Some((*self.handles.first()?, self.heap.min()?))
+    }
+
+    /// Inserts the key and returns its handle.
+    pub fn push(&mut self, key: T) -> Handle {
+        let handle = Handle(self.positions.len());
+        self.positions.push(Some(self.handles.len()));
+        self.handles.push(handle);
+        let (heap, mut tracker) = self.tracked_heap();
+        heap.traced_insert(key, &mut tracker);
+        handle
+    }
+
+    /// Removes the minimum key and returns it with its handle.
+    pub fn pop(&mut self) -> Option<(Handle, T)> {
+        let handle = *self.handles.first()?;
+        self.remove(handle).map(|key| (handle, key))
+    }
+
+    /// Replaces the key of the handle by a key which is not greater.
+    ///
+    /// On success, it returns the previous key. It returns `None`, leaving
+    /// the queue unchanged, if the key of the handle is not in the queue or
+    /// is smaller than the new key.
+    pub fn decrease_key(&mut self, handle: Handle, key: T) -> Option<T> {
+        let position = self.position(handle)?;
+        let (heap, mut tracker) = self.tracked_heap();
+        heap.traced_decrease_key(position, key, &mut tracker)
+    }
+
+    /// Removes the key of the handle and returns it, if it is in the queue.
+    ///
+    /// The last key of the heap takes its place, and then moves up or down
+    /// to restore the heap property.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let position = self.position(handle)?;
+        let (heap, mut tracker) = self.tracked_heap();
+        let key = heap.traced_delete_min(position, &mut tracker);
+        // The key was swapped to the end of the heap before it was removed.
+        self.handles.pop();
+        self.positions[handle.0] = None;
+        key
+    }
+
+    fn position(&self, handle: Handle) -> Option<usize> {
+        self.positions.get(handle.0).copied().flatten()
+    }
+
+    /// Returns the heap with the sink which keeps the handles in step with
+    /// it.
+    fn tracked_heap(&mut self) -> (&mut Heap<T, MinHeap>, Tracker<'_>) {
+        let tracker = Tracker {
+            handles: &mut self.handles,
+            positions: &mut self.positions,
+        };
+        (&mut self.heap, tracker)
+    }
+}
+
+impl<T: Ord + Clone> Default for PriorityQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if every key is at its tracked position and no parent
+    /// is greater than its children.
+    fn is_valid<T: Ord + Clone>(queue: &PriorityQueue<T>) -> bool {
+        queue.heap.iter().count() == queue.len()
+            && queue
+                .heap
+                .iter()
+                .zip(&queue.handles)
+                .enumerate()
+                .all(|(position, (key, handle))| {
+                    queue.position(*handle) == Some(position)
+                        && (position == 0 || queue.heap[(position - 1) / 2] <= *key)
+                })
+            && queue.positions.iter().flatten().count() == queue.len()
+    }
+
+    #[test]
+    fn decrease_and_remove_by_handle() {
+        let mut queue = PriorityQueue::new();
+        let handles: Vec<Handle> = [16, 4, 10, 14, 7, 9, 3, 2, 8, 1]
+            .into_iter()
+            .map(|key| queue.push(key))
+            .collect();
+        assert_eq!(queue.peek(), Some((handles[9], &1)));
+
+        assert_eq!(queue.decrease_key(handles[0], 0), Some(16));
+        assert_eq!(queue.decrease_key(handles[1], 5), None);
+        assert_eq!(queue.get(handles[0]), Some(&0));
+        assert_eq!(queue.remove(handles[2]), Some(10));
+        assert_eq!(queue.remove(handles[2]), None);
+        assert!(!queue.contains(handles[2]));
+        assert!(is_valid(&queue));
+
+        let mut keys = Vec::new();
+        while let Some((handle, key)) = queue.pop() {
+            assert!(!queue.contains(handle));
+            keys.push(key);
+        }
+        assert_eq!(keys, vec![0, 1, 2, 3, 4, 7, 8, 9, 14]);
+        assert_eq!(queue.decrease_key(handles[3], 0), None);
+        assert!(queue.is_empty());
+    }
+
+    /// Operation is a kind, a key and a target among the handles pushed
+    /// so far: a push of the key, a pop, a decrease of the target key by
+    /// the magnitude of the key, or a removal of the target key.
+    type Operation = (u8, i16, u8);
+
+    #[quickcheck]
+    fn agrees_with_ordered_set(operations: Vec<Operation>) -> bool {
+        let mut queue = PriorityQueue::new();
+        // The keys are made distinct by pairing them with their handle.
+        let mut model = BTreeSet::new();
+        let mut handles = Vec::new();
+        for (kind, key, target) in operations {
+            let target =
+                (!handles.is_empty()).then(|| handles[usize::from(target) % handles.len()]);
+            let agree = match (kind % 4, target) {
+                (0, _) | (_, None) => {
+                    let handle = queue.push((key, handles.len()));
+                    model.insert((key, handles.len()));
+                    handles.push(handle);
+                    true
+                }
+                (1, _) => {
+                    let expected = model.iter().next().copied();
+                    if let Some(min) = expected {
+                        model.remove(&min);
+                    }
+                    queue.pop().map(|(_, key)| key) == expected
+                }
+                (2, Some(handle)) => {
+                    let current = queue.get(handle).copied();
+                    let decreased =
+                        current.map(|(old, id)| (old.saturating_sub(key.saturating_abs()), id));
+                    if let (Some(current), Some(decreased)) = (current, decreased) {
+                        model.remove(&current);
+                        model.insert(decreased);
+                    }
+                    decreased.map_or(true, |decreased| {
+                        queue.decrease_key(handle, decreased) == current
+                    })
+                }
+                (_, Some(handle)) => {
+                    let current = queue.get(handle).copied();
+                    if let Some(current) = current {
+                        model.remove(&current);
+                    }
+                    queue.remove(handle) == current
+                }
+            };
+            if !agree || !is_valid(&queue) || queue.len() != model.len() {
+                return false;
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,282 @@
+//! Young tableau.
+//!
+//! A Young tableau is an `m × n` matrix whose rows and columns are sorted
+//! in increasing order, its empty cells being thought of as holding `∞`,
+//! so they come last in their row and column. Like a heap, its minimum is
+//! its top left entry, and keys move along a row or column to restore the
+//! order, so the operations take O(m + n) time. See CLRS problem 6-3.
+
+use crate::matrix::Matrix;
+use crate::{error::ErrorKind, Error};
+
+/// YoungTableau is a matrix of keys sorted along its rows and columns.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct YoungTableau<T> {
+    /// The cells of the tableau, `None` standing for an empty cell.
+    cells: Matrix<Option<T>>,
+    len: usize,
+}
+
+impl<T> YoungTableau<T> {
+    /// Creates new empty tableau of the given dimensions.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let cells = (0..rows)
+            .map(|_| (0..columns).map(|_| None).collect())
+            .collect();
+        Self {
+            cells: Matrix::from_rows(cells).expect("the rows have the same length"),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub const fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    /// Returns the number of columns.
+    pub const fn columns(&self) -> usize {
+        self.cells.columns()
+    }
+
+    /// Returns the number of keys.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tableau is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if every cell of the tableau holds a key.
+    pub const fn is_full(&self) -> bool {
+        self.len == self.rows() * self.columns()
+    }
+
+    /// Returns the key at the given row and column, if the cell is not
+    /// empty.
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        self.cells.get(row, column)?.as_ref()
+    }
+
+    /// Returns the minimum key.
+    pub fn minimum(&self) -> Option<&T> {
+        self.get(0, 0)
+    }
+}
+
+impl<T: Ord> YoungTableau<T> {
+    /// Inserts the key.
+    ///
+    /// The key is put in the bottom right cell, which is empty unless the
+    /// tableau is full, and then swapped with the greater of its upper and
+    /// left neighbors until neither is greater.
+    ///
+    /// It fails with [`ErrorKind::QueueOverflow`] if the tableau is full.
+    pub fn insert(&mut self, key: T) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::new(ErrorKind::QueueOverflow));
+        }
+        let (mut row, mut column) = (self.rows() - 1, self.columns() - 1);
+        self.cells[(row, column)] = Some(key);
+        self.len += 1;
+        loop {
+            let mut largest = (row, column);
+            if row > 0 && self.is_greater((row - 1, column), largest) {
+                largest = (row - 1, column);
+            }
+            if column > 0 && self.is_greater((row, column - 1), largest) {
+                largest = (row, column - 1);
+            }
+            if largest == (row, column) {
+                break;
+            }
+            self.swap((row, column), largest);
+            (row, column) = largest;
+        }
+        Ok(())
+    }
+
+    /// Removes the minimum key and returns it.
+    ///
+    /// The top left cell is emptied, and the hole is then swapped with the
+    /// smaller of its lower and right neighbors until both are empty.
+    pub fn extract_min(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let min = self.cells[(0, 0)].take()?;
+        self.len -= 1;
+        let (mut row, mut column) = (0, 0);
+        loop {
+            let mut smallest = (row, column);
+            if row + 1 < self.rows() && self.is_greater(smallest, (row + 1, column)) {
+                smallest = (row + 1, column);
+            }
+            if column + 1 < self.columns() && self.is_greater(smallest, (row, column + 1)) {
+                smallest = (row, column + 1);
+            }
+            if smallest == (row, column) {
+                break;
+            }
+            self.swap((row, column), smallest);
+            (row, column) = smallest;
+        }
+        Some(min)
+    }
+
+    /// Returns true if the key is in the tableau.
+    ///
+    /// The search starts from the top right cell: a greater key or an
+    /// empty cell rules out the rest of its column, and a smaller key the
+    /// rest of its row.
+    pub fn contains(&self, key: &T) -> bool {
+        let (mut row, mut column) = (0, self.columns());
+        while row < self.rows() && column > 0 {
+            match &self.cells[(row, column - 1)] {
+                Some(current) if current == key => return true,
+                Some(current) if current < key => row += 1,
+                _ => column -= 1,
+            }
+        }
+        false
+    }
+
+    /// Returns true if the key of the first cell is greater than the key of
+    /// the second one, empty cells holding `∞`.
+    fn is_greater(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        match (&self.cells[a], &self.cells[b]) {
+            (Some(a), Some(b)) => a > b,
+            (None, b) => b.is_some(),
+            (Some(_), None) => false,
+        }
+    }
+
+    fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let key = self.cells[a].take();
+        self.cells[a] = std::mem::replace(&mut self.cells[b], key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if the rows and columns are sorted, empty cells coming
+    /// last, and the number of keys is tracked.
+    fn is_valid<T: Ord>(tableau: &YoungTableau<T>) -> bool {
+        let (rows, columns) = (tableau.rows(), tableau.columns());
+        let cells = (0..rows).flat_map(|row| (0..columns).map(move |column| (row, column)));
+        let sorted = |(row, column): (usize, usize)| {
+            let below = (row + 1 < rows).then_some((row + 1, column));
+            let right = (column + 1 < columns).then_some((row, column + 1));
+            [below, right]
+                .into_iter()
+                .flatten()
+                .all(|next| !tableau.is_greater((row, column), next))
+        };
+        cells.clone().all(sorted)
+            && cells
+                .filter(|&(row, column)| tableau.get(row, column).is_some())
+                .count()
+                == tableau.len()
+    }
+
+    /// Sorts the keys by inserting them into a square tableau and then
+    /// extracting them, in O(n³) time for `n²` keys.
+    fn tableau_sort<T: Ord>(keys: Vec<T>) -> Vec<T> {
+        let mut n = 0;
+        while n * n < keys.len() {
+            n += 1;
+        }
+        let mut tableau = YoungTableau::new(n, n);
+        for key in keys {
+            tableau
+                .insert(key)
+                .expect("the tableau has room for the keys");
+        }
+        std::iter::from_fn(|| tableau.extract_min()).collect()
+    }
+
+    #[test]
+    fn clrs_tableau() {
+        // CLRS exercise 6-3(a).
+        let mut tableau = YoungTableau::new(4, 4);
+        for key in [9, 16, 3, 2, 4, 8, 5, 14, 12] {
+            assert_eq!(tableau.insert(key), Ok(()));
+            assert!(is_valid(&tableau));
+        }
+        assert_eq!(tableau.len(), 9);
+        assert_eq!(tableau.minimum(), Some(&2));
+        assert!(tableau.contains(&14));
+        assert!(!tableau.contains(&7));
+        assert!(!tableau.contains(&17));
+
+        assert_eq!(tableau.extract_min(), Some(2));
+        assert_eq!(tableau.extract_min(), Some(3));
+        assert!(is_valid(&tableau));
+        assert!(!tableau.contains(&2));
+        assert_eq!(tableau.len(), 7);
+    }
+
+    #[test]
+    fn full_and_empty_tableaux() {
+        let mut tableau = YoungTableau::new(2, 1);
+        assert_eq!(tableau.extract_min(), None);
+        assert_eq!(tableau.insert(2), Ok(()));
+        assert_eq!(tableau.insert(1), Ok(()));
+        assert!(tableau.is_full());
+        assert_eq!(tableau.insert(0), Err(Error::new(ErrorKind::QueueOverflow)));
+        assert_eq!(tableau.get(0, 0), Some(&1));
+        assert_eq!(tableau.get(1, 0), Some(&2));
+
+        let mut degenerate = YoungTableau::new(0, 3);
+        assert!(degenerate.is_full() && degenerate.is_empty());
+        assert_eq!(
+            degenerate.insert(0),
+            Err(Error::new(ErrorKind::QueueOverflow))
+        );
+        assert!(!degenerate.contains(&0));
+    }
+
+    #[test]
+    fn sort_square() {
+        let keys: Vec<i32> = (0..16).map(|i| (i * 7) % 16 - 8).collect();
+        let mut expected = keys.clone();
+        expected.sort_unstable();
+        assert_eq!(tableau_sort(keys), expected);
+    }
+
+    #[quickcheck]
+    fn sorts_like_std(keys: Vec<i32>) -> bool {
+        let mut expected = keys.clone();
+        expected.sort_unstable();
+        tableau_sort(keys) == expected
+    }
+
+    #[quickcheck]
+    fn finds_inserted_keys(keys: Vec<u8>, probes: Vec<u8>, extracted: u8) -> bool {
+        let mut tableau = YoungTableau::new(4, 8);
+        let keys: Vec<u8> = keys.into_iter().take(32).collect();
+        for &key in &keys {
+            if tableau.insert(key).is_err() {
+                return false;
+            }
+        }
+        let mut remaining = keys;
+        remaining.sort_unstable();
+        for _ in 0..usize::from(extracted).min(remaining.len()) {
+            if tableau.extract_min() != Some(remaining.remove(0)) {
+                return false;
+            }
+        }
+        is_valid(&tableau)
+            && probes
+                .iter()
+                .chain(&remaining)
+                .all(|key| tableau.contains(key) == remaining.contains(key))
+    }
+}
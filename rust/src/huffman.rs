@@ -0,0 +1,277 @@
+//! Huffman codes.
+//!
+//! A prefix code assigns each symbol a codeword such that no codeword is a
+//! prefix of another one, so a sequence of codewords is decoded without
+//! separators. Huffman's greedy algorithm builds the prefix code which
+//! encodes a text in the fewest bits, given the frequency of each of its
+//! symbols: it repeatedly merges the two least frequent trees into one,
+//! whose frequency is the sum of theirs. See CLRS section 16.3.
+
+use crate::bits::BitVec;
+use crate::heap::{Heap, MinHeap, Value};
+use crate::{error::ErrorKind, Error};
+
+/// Node of the tree of a [`HuffmanCode`].
+///
+/// The path from the root to a leaf spells the codeword of its symbol, a
+/// left edge standing for 0 and a right edge for 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HuffmanNode {
+    Leaf(u8),
+    Internal(usize, usize),
+}
+
+/// HuffmanCode is an optimal prefix code for bytes.
+#[derive(Debug, Clone)]
+pub struct HuffmanCode {
+    /// The nodes of the tree, the root being the last one.
+    nodes: Vec<HuffmanNode>,
+
+    /// The codeword of each byte, if it has one.
+    codewords: Vec<Option<BitVec>>,
+}
+
+impl HuffmanCode {
+    /// Creates new code for the symbols with the given frequencies.
+    ///
+    /// The trees are kept in a min heap by frequency, so building the code
+    /// of `n` symbols takes O(n log n) time. A single symbol is given the
+    /// codeword `0`, so that its occurrences can be counted.
+    ///
+    /// It fails with [`ErrorKind::DuplicateKey`] if a symbol is given more
+    /// than once, and with [`ErrorKind::Overflow`] if the sum of the
+    /// frequencies does not fit into an `i64`.
+    pub fn new(frequencies: &[(u8, u64)]) -> Result<Self, Error> {
+        let mut seen = [false; 256];
+        let mut nodes = Vec::with_capacity(2 * frequencies.len());
+        let mut trees = Vec::with_capacity(frequencies.len());
+        let mut total: i64 = 0;
+        for &(symbol, frequency) in frequencies {
+            if std::mem::replace(&mut seen[usize::from(symbol)], true) {
+                return Err(Error::new(ErrorKind::DuplicateKey));
+            }
+            let key = i64::try_from(frequency).map_err(|_| Error::new(ErrorKind::Overflow))?;
+            total = total
+                .checked_add(key)
+                .ok_or_else(|| Error::new(ErrorKind::Overflow))?;
+            trees.push(Value {
+                key,
+                index: nodes.len(),
+            });
+            nodes.push(HuffmanNode::Leaf(symbol));
+        }
+
+        let mut heap = Heap::<_, MinHeap>::new(trees);
+        heap.build_min_heap();
+        while let Some(x) = heap.extract_min() {
+            let Some(y) = heap.extract_min() else {
+                break;
+            };
+            heap.insert(Value {
+                key: x.key + y.key,
+                index: nodes.len(),
+            });
+            nodes.push(HuffmanNode::Internal(x.index, y.index));
+        }
+
+        let mut code = Self {
+            nodes,
+            codewords: vec![None; 256],
+        };
+        code.assign_codewords();
+        Ok(code)
+    }
+
+    /// Creates new code for the bytes of the data, with their number of
+    /// occurrences as frequencies.
+    pub fn from_data(data: &[u8]) -> Self {
+        let mut counts = [0; 256];
+        for &byte in data {
+            counts[usize::from(byte)] += 1;
+        }
+        let frequencies: Vec<(u8, u64)> = (0..=u8::MAX)
+            .zip(counts)
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        Self::new(&frequencies).expect("the counts sum up to the data length")
+    }
+
+    /// Returns the codeword of the symbol, if it has one.
+    pub fn codeword(&self, symbol: u8) -> Option<&BitVec> {
+        self.codewords[usize::from(symbol)].as_ref()
+    }
+
+    /// Encodes the data as the concatenation of the codewords of its bytes.
+    ///
+    /// It fails with [`ErrorKind::InvalidKey`] if a byte has no codeword.
+    pub fn encode(&self, data: &[u8]) -> Result<BitVec, Error> {
+        let mut bits = BitVec::new();
+        for &byte in data {
+            let codeword = self
+                .codeword(byte)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidKey))?;
+            bits.extend(codeword.iter());
+        }
+        Ok(bits)
+    }
+
+    /// Decodes bits encoded by [`HuffmanCode::encode`].
+    ///
+    /// Each codeword is read by walking the tree down from the root to a
+    /// leaf. It fails with [`ErrorKind::InvalidCode`] if the bits do not
+    /// end with a whole codeword.
+    pub fn decode(&self, bits: &BitVec) -> Result<Vec<u8>, Error> {
+        let invalid = || Error::new(ErrorKind::InvalidCode);
+        let root = self.nodes.len().checked_sub(1);
+        let mut data = Vec::new();
+        let mut current = root;
+        for bit in bits.iter() {
+            let node = current.ok_or_else(invalid)?;
+            current = match (self.nodes[node], bit) {
+                (HuffmanNode::Internal(left, _), false) => Some(left),
+                (HuffmanNode::Internal(_, right), true) => Some(right),
+                // The only symbol has the codeword `0`.
+                (HuffmanNode::Leaf(symbol), false) => {
+                    data.push(symbol);
+                    continue;
+                }
+                (HuffmanNode::Leaf(_), true) => return Err(invalid()),
+            };
+            if let Some(HuffmanNode::Leaf(symbol)) = current.map(|node| self.nodes[node]) {
+                data.push(symbol);
+                current = root;
+            }
+        }
+        if current != root {
+            return Err(invalid());
+        }
+        Ok(data)
+    }
+
+    /// Sets the codeword of each leaf from its path.
+    fn assign_codewords(&mut self) {
+        let Some(root) = self.nodes.len().checked_sub(1) else {
+            return;
+        };
+        let mut stack = vec![(root, BitVec::new())];
+        while let Some((node, path)) = stack.pop() {
+            match self.nodes[node] {
+                HuffmanNode::Leaf(symbol) => {
+                    let codeword = if path.is_empty() {
+                        std::iter::once(false).collect()
+                    } else {
+                        path
+                    };
+                    self.codewords[usize::from(symbol)] = Some(codeword);
+                }
+                HuffmanNode::Internal(left, right) => {
+                    let mut right_path = path.clone();
+                    right_path.push(true);
+                    let mut left_path = path;
+                    left_path.push(false);
+                    stack.push((right, right_path));
+                    stack.push((left, left_path));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if no codeword is a prefix of another one.
+    fn is_prefix_free(code: &HuffmanCode) -> bool {
+        let codewords: Vec<String> = (0..=u8::MAX)
+            .filter_map(|symbol| code.codeword(symbol).map(BitVec::to_string))
+            .collect();
+        codewords.iter().enumerate().all(|(i, a)| {
+            codewords
+                .iter()
+                .enumerate()
+                .all(|(j, b)| i == j || !b.starts_with(a.as_str()))
+        })
+    }
+
+    #[test]
+    fn clrs_code() {
+        // CLRS figure 16.3, with frequencies in thousands.
+        let frequencies = [
+            (b'a', 45),
+            (b'b', 13),
+            (b'c', 12),
+            (b'd', 16),
+            (b'e', 9),
+            (b'f', 5),
+        ];
+        let code = HuffmanCode::new(&frequencies).expect("the symbols are distinct");
+        let lengths: Vec<usize> = frequencies
+            .iter()
+            .map(|&(symbol, _)| code.codeword(symbol).map_or(0, BitVec::len))
+            .collect();
+        assert_eq!(lengths, vec![1, 3, 3, 3, 4, 4]);
+        assert!(is_prefix_free(&code));
+        assert_eq!(code.codeword(b'z'), None);
+
+        let text = b"facade";
+        let bits = code.encode(text).expect("the symbols have a codeword");
+        assert_eq!(bits.len(), 4 + 1 + 3 + 1 + 3 + 4);
+        assert_eq!(code.decode(&bits), Ok(text.to_vec()));
+        assert_eq!(
+            code.encode(b"zebra"),
+            Err(Error::new(ErrorKind::InvalidKey))
+        );
+
+        let mut truncated: BitVec = bits.iter().collect();
+        truncated.push(true);
+        assert_eq!(
+            code.decode(&truncated),
+            Err(Error::new(ErrorKind::InvalidCode))
+        );
+        assert_eq!(
+            HuffmanCode::new(&[(b'a', 1), (b'a', 2)]).map(|_| ()),
+            Err(Error::new(ErrorKind::DuplicateKey))
+        );
+    }
+
+    #[test]
+    fn degenerate_codes() {
+        let single = HuffmanCode::from_data(b"aaa");
+        assert_eq!(
+            single.codeword(b'a').map(BitVec::to_string),
+            Some("0".into())
+        );
+        let bits = single.encode(b"aaa").expect("the symbol has a codeword");
+        assert_eq!(single.decode(&bits), Ok(b"aaa".to_vec()));
+
+        let empty = HuffmanCode::from_data(b"");
+        assert_eq!(empty.encode(b""), Ok(BitVec::new()));
+        assert_eq!(empty.decode(&BitVec::new()), Ok(Vec::new()));
+        assert_eq!(
+            empty.decode(&std::iter::once(false).collect()),
+            Err(Error::new(ErrorKind::InvalidCode))
+        );
+    }
+
+    #[quickcheck]
+    fn round_trips(data: Vec<u8>) -> bool {
+        let code = HuffmanCode::from_data(&data);
+        let bits = code.encode(&data).expect("every byte has a codeword");
+        // Huffman codes are never longer than a fixed-length code, whose
+        // codewords have one bit per level of a complete tree of the
+        // symbols, or a single bit.
+        let symbols = (0..=u8::MAX)
+            .filter(|&symbol| code.codeword(symbol).is_some())
+            .count();
+        let mut width = 1;
+        while 1 << width < symbols {
+            width += 1;
+        }
+        code.decode(&bits) == Ok(data.clone())
+            && is_prefix_free(&code)
+            && bits.len() <= width * data.len()
+    }
+}
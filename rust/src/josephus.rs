@@ -0,0 +1,116 @@
+//! The Josephus problem.
+//!
+//! `n` people stand in a circle, numbered from 1 to `n`. Starting from the
+//! first one, people count off and every `k`-th person leaves the circle,
+//! until a single one remains. The problem is to find the survivor. See
+//! CLRS problem 14-2.
+//!
+//! This module solves it three ways: by simulating the circle with a
+//! doubly linked list, by rotating a queue, and with a recurrence.
+
+use crate::list::{Cursor, DoublyLinkedList};
+use crate::queue::BoundedQueue;
+
+/// Returns the survivor of the circle of `n` people where every `k`-th
+/// person leaves, or `None` if `n` or `k` is zero.
+///
+/// Once the first person left, the `n - 1` remaining people form the same
+/// problem with the numbers shifted by `k`, hence the recurrence
+/// `J(1) = 0` and `J(n) = (J(n - 1) + k) mod n` on zero-based positions.
+/// The step is reduced modulo `n` before the addition, so it does not
+/// overflow whatever `k`. It takes O(n) time and O(1) space.
+pub fn josephus(n: usize, k: usize) -> Option<usize> {
+    if n == 0 || k == 0 {
+        return None;
+    }
+    let survivor = (2..=n).fold(0, |survivor, size| (survivor + k % size) % size);
+    Some(survivor + 1)
+}
+
+/// Returns the survivor by simulating the circle with a linked list.
+///
+/// The list is walked from node to node, wrapping around from the back to
+/// the front, and every `k`-th node is deleted. It takes O(nk) time.
+pub fn josephus_list(n: usize, k: usize) -> Option<usize> {
+    if k == 0 {
+        return None;
+    }
+    let mut circle = DoublyLinkedList::new();
+    for person in 1..=n {
+        circle.push_back(person);
+    }
+    let mut current = circle.cursor_front()?;
+    while circle.len() > 1 {
+        for _ in 1..k {
+            current = next_in_circle(&circle, current);
+        }
+        let next = next_in_circle(&circle, current);
+        circle.delete(current);
+        current = next;
+    }
+    circle.front().copied()
+}
+
+/// Returns the survivor by rotating a queue.
+///
+/// The people counted are moved from the front to the back of the queue,
+/// and the `k`-th one is dropped. It takes O(nk) time.
+pub fn josephus_queue(n: usize, k: usize) -> Option<usize> {
+    if n == 0 || k == 0 {
+        return None;
+    }
+    let mut circle = BoundedQueue::with_capacity(n);
+    for person in 1..=n {
+        circle.enqueue(person).expect("the queue holds everyone");
+    }
+    for _ in 1..n {
+        for _ in 1..k {
+            let person = circle.dequeue().expect("the circle is not empty");
+            circle
+                .enqueue(person)
+                .expect("the person was just dequeued");
+        }
+        circle.dequeue().expect("the circle is not empty");
+    }
+    circle.dequeue().ok()
+}
+
+/// Returns a cursor to the node after the given one, wrapping around from
+/// the back to the front of the list.
+fn next_in_circle(circle: &DoublyLinkedList<usize>, cursor: Cursor) -> Cursor {
+    circle
+        .next(cursor)
+        .or_else(|| circle.cursor_front())
+        .expect("the circle is not empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn known_survivors() {
+        for solve in [josephus, josephus_list, josephus_queue] {
+            assert_eq!(solve(7, 3), Some(4));
+            assert_eq!(solve(41, 3), Some(31));
+            assert_eq!(solve(10, 2), Some(5));
+            assert_eq!(solve(5, 1), Some(5));
+            assert_eq!(solve(1, 9), Some(1));
+            assert_eq!(solve(0, 3), None);
+            assert_eq!(solve(3, 0), None);
+        }
+        // Only the step modulo the size of the circle matters, and
+        // `usize::MAX` is 3 modulo 6.
+        assert_eq!(josephus(3, usize::MAX), Some(2));
+        assert_eq!(josephus(3, usize::MAX), josephus_list(3, 3));
+    }
+
+    #[quickcheck]
+    fn solutions_agree(n: u8, k: u8) -> bool {
+        let (n, k) = (usize::from(n), usize::from(k % 16));
+        let survivor = josephus(n, k);
+        josephus_list(n, k) == survivor && josephus_queue(n, k) == survivor
+    }
+}
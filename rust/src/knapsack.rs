@@ -0,0 +1,220 @@
+//! Knapsack problems.
+//!
+//! A thief robbing a store finds items of given weights and values, and can
+//! carry at most a given weight in their knapsack. The problem is to choose
+//! the items maximizing the total value. In the 0/1 knapsack problem each
+//! item is taken or left, while in the unbounded knapsack problem each item
+//! can be taken any number of times. See CLRS section 16.2.
+//!
+//! Unlike the fractional knapsack problem, neither problem has the greedy
+//! choice property, so both are solved by dynamic programming over the
+//! capacities, in pseudo-polynomial O(nW) time for `n` items and a capacity
+//! of `W`.
+
+/// Item is an item which can be put in the knapsack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Item {
+    /// The weight of the item.
+    pub weight: usize,
+
+    /// The value of the item.
+    pub value: u64,
+}
+
+impl Item {
+    /// Creates new item.
+    pub const fn new(weight: usize, value: u64) -> Self {
+        Self { weight, value }
+    }
+}
+
+/// Packing is an optimal choice of items.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Packing {
+    /// The total value of the items.
+    pub value: u64,
+
+    /// The indices of the items taken, in increasing order. An index occurs
+    /// once for each time the item is taken.
+    pub items: Vec<usize>,
+}
+
+/// Returns an optimal packing of the items, each taken at most once, in a
+/// knapsack of the given capacity.
+///
+/// `values[i][c]` is the maximum value of the first `i` items with a
+/// capacity of `c`, which either leaves the item `i` or takes it on top of
+/// the best packing of the previous items in the remaining capacity. The
+/// table is then walked back from the last item to find the items taken,
+/// which takes O(nW) time and memory.
+pub fn zero_one(items: &[Item], capacity: usize) -> Packing {
+    let mut values = vec![vec![0; capacity + 1]; items.len() + 1];
+    for (i, item) in items.iter().enumerate() {
+        for c in 0..=capacity {
+            values[i + 1][c] = values[i][c];
+            if item.weight <= c {
+                values[i + 1][c] = values[i + 1][c].max(values[i][c - item.weight] + item.value);
+            }
+        }
+    }
+
+    let mut taken = Vec::new();
+    let mut c = capacity;
+    for i in (0..items.len()).rev() {
+        if values[i + 1][c] != values[i][c] {
+            taken.push(i);
+            c -= items[i].weight;
+        }
+    }
+    taken.reverse();
+    Packing {
+        value: values[items.len()][capacity],
+        items: taken,
+    }
+}
+
+/// Returns an optimal packing of the items, each taken any number of
+/// times, in a knapsack of the given capacity.
+///
+/// `values[c]` is the maximum value with a capacity of `c`, which takes an
+/// item on top of the best packing in the remaining capacity, and
+/// `choices[c]` remembers that item. It takes O(nW) time and O(W) memory.
+///
+/// # Panics
+///
+/// Panics if an item has zero weight, as it could be taken infinitely many
+/// times.
+pub fn unbounded(items: &[Item], capacity: usize) -> Packing {
+    assert!(
+        items.iter().all(|item| item.weight > 0),
+        "item has zero weight"
+    );
+    let mut values = vec![0; capacity + 1];
+    let mut choices = vec![None; capacity + 1];
+    for c in 1..=capacity {
+        for (i, item) in items.iter().enumerate() {
+            if item.weight <= c && values[c - item.weight] + item.value > values[c] {
+                values[c] = values[c - item.weight] + item.value;
+                choices[c] = Some(i);
+            }
+        }
+    }
+
+    let mut taken = Vec::new();
+    let mut c = capacity;
+    while let Some(i) = choices[c] {
+        taken.push(i);
+        c -= items[i].weight;
+    }
+    taken.sort_unstable();
+    Packing {
+        value: values[capacity],
+        items: taken,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns the total weight and value of the items taken.
+    fn totals(items: &[Item], packing: &Packing) -> (usize, u64) {
+        packing.items.iter().fold((0, 0), |(weight, value), &i| {
+            (weight + items[i].weight, value + items[i].value)
+        })
+    }
+
+    /// Returns the maximum value of the 0/1 knapsack by trying every subset
+    /// of the items.
+    fn brute_force_zero_one(items: &[Item], capacity: usize) -> u64 {
+        (0..1_usize << items.len())
+            .filter_map(|subset| {
+                let (weight, value) = items
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| subset & (1 << i) != 0)
+                    .fold((0, 0), |(weight, value), (_, item)| {
+                        (weight + item.weight, value + item.value)
+                    });
+                (weight <= capacity).then_some(value)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the maximum value of the unbounded knapsack by trying every
+    /// item on top of every smaller packing, recursively.
+    fn brute_force_unbounded(items: &[Item], capacity: usize) -> u64 {
+        items
+            .iter()
+            .filter(|item| item.weight <= capacity)
+            .map(|item| item.value + brute_force_unbounded(items, capacity - item.weight))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Creates small items from arbitrary ones.
+    fn small_items(items: Vec<(u8, u8)>) -> Vec<Item> {
+        items
+            .into_iter()
+            .take(10)
+            .map(|(weight, value)| Item::new(usize::from(weight % 8) + 1, u64::from(value % 32)))
+            .collect()
+    }
+
+    #[test]
+    fn clrs_items() {
+        // CLRS figure 16.2.
+        let items = [Item::new(10, 60), Item::new(20, 100), Item::new(30, 120)];
+        assert_eq!(
+            zero_one(&items, 50),
+            Packing {
+                value: 220,
+                items: vec![1, 2]
+            }
+        );
+        assert_eq!(
+            unbounded(&items, 50),
+            Packing {
+                value: 300,
+                items: vec![0, 0, 0, 0, 0]
+            }
+        );
+        assert_eq!(zero_one(&items, 5), Packing::default());
+        assert_eq!(unbounded(&[], 5), Packing::default());
+        // A weightless item is free to take once.
+        assert_eq!(zero_one(&[Item::new(0, 3)], 0).items, vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "zero weight")]
+    fn unbounded_weightless_item() {
+        unbounded(&[Item::new(0, 1)], 10);
+    }
+
+    #[quickcheck]
+    fn zero_one_is_optimal(items: Vec<(u8, u8)>, capacity: u8) -> bool {
+        let items = small_items(items);
+        let capacity = usize::from(capacity % 32);
+        let packing = zero_one(&items, capacity);
+        let (weight, value) = totals(&items, &packing);
+        weight <= capacity
+            && value == packing.value
+            && packing.items.windows(2).all(|pair| pair[0] < pair[1])
+            && packing.value == brute_force_zero_one(&items, capacity)
+    }
+
+    #[quickcheck]
+    fn unbounded_is_optimal(items: Vec<(u8, u8)>, capacity: u8) -> bool {
+        let items: Vec<Item> = small_items(items).into_iter().take(4).collect();
+        let capacity = usize::from(capacity % 16);
+        let packing = unbounded(&items, capacity);
+        let (weight, value) = totals(&items, &packing);
+        weight <= capacity
+            && value == packing.value
+            && packing.value == brute_force_unbounded(&items, capacity)
+            && packing.value >= zero_one(&items, capacity).value
+    }
+}
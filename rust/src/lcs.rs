@@ -0,0 +1,492 @@
+//! Longest common subsequence.
+//!
+//! A subsequence of a string is obtained by deleting zero or more of its
+//! characters. The problem is to find a longest subsequence common to two
+//! strings. See CLRS section 15.4.
+
+use std::fmt;
+
+/// The `LongSubSequence` type computes the longest common subsequence of
+/// two strings.
+///
+/// The strings are compared character by character, that is by Unicode
+/// scalar value, so any UTF-8 string is supported. A character made of
+/// several scalar values, such as a letter followed by a combining accent,
+/// may therefore be only partially part of the subsequence.
+#[derive(Debug, Clone, Copy)]
+pub struct LongSubSequence<'a> {
+    lhs: &'a str,
+    rhs: &'a str,
+}
+
+/// The `Edit` type is an operation of an edit script.
+///
+/// Applying the `Keep` and `Delete` operations of a script gives the first
+/// sequence, while applying the `Keep` and `Insert` operations gives the
+/// second one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edit<T> {
+    /// The item is common to both sequences.
+    Keep(T),
+
+    /// The item is only in the second sequence.
+    Insert(T),
+
+    /// The item is only in the first sequence.
+    Delete(T),
+}
+
+impl<T: fmt::Display> fmt::Display for Edit<T> {
+    /// Formats the operation as a line of a unified diff.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keep(item) => write!(f, " {item}"),
+            Self::Insert(item) => write!(f, "+{item}"),
+            Self::Delete(item) => write!(f, "-{item}"),
+        }
+    }
+}
+
+impl<'a> LongSubSequence<'a> {
+    /// Creates new longest common subsequence problem.
+    pub const fn new(lhs: &'a str, rhs: &'a str) -> Self {
+        Self { lhs, rhs }
+    }
+
+    /// Returns a longest common subsequence.
+    ///
+    /// It fills the whole table of subproblems, then walks it back from
+    /// the bottom right corner, which takes O(mn) memory.
+    pub fn substr(&self) -> String {
+        let (lhs, rhs) = self.chars();
+        let table = Self::table(&lhs, &rhs);
+        let (mut i, mut j) = (lhs.len(), rhs.len());
+        let mut subsequence = Vec::with_capacity(table[i][j]);
+        while i > 0 && j > 0 {
+            if lhs[i - 1] == rhs[j - 1] {
+                subsequence.push(lhs[i - 1]);
+                i -= 1;
+                j -= 1;
+            } else if table[i - 1][j] >= table[i][j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+        subsequence.iter().rev().collect()
+    }
+
+    /// Returns the length of a longest common subsequence.
+    ///
+    /// Each row of the table only depends on the previous one, so only two
+    /// rows as long as the shortest string are kept, which takes
+    /// O(min(m, n)) memory.
+    pub fn length(&self) -> usize {
+        let (long, short) = self.by_length();
+        last_row(long.iter(), short.iter())[short.len()]
+    }
+
+    /// Returns a longest common subsequence with Hirschberg's algorithm.
+    ///
+    /// The longest string is split in half and the shortest string is split
+    /// where the lengths of the common subsequences of both halves add up
+    /// to the maximum. Both halves are then solved recursively. Only two
+    /// rows of the table are kept at once, which takes O(min(m, n))
+    /// memory, and the running time is still O(mn).
+    pub fn hirschberg(&self) -> String {
+        let (long, short) = self.by_length();
+        let mut subsequence = Vec::with_capacity(short.len());
+        hirschberg(&long, &short, &mut subsequence);
+        subsequence.iter().collect()
+    }
+
+    /// Returns the shortest edit script turning the first string into the
+    /// second one, character by character.
+    ///
+    /// The kept characters are a longest common subsequence. Within a
+    /// change, deletions come before insertions.
+    pub fn diff(&self) -> Vec<Edit<char>> {
+        let (lhs, rhs) = self.chars();
+        edit_script(&lhs, &rhs)
+    }
+
+    /// Returns the shortest edit script turning the first string into the
+    /// second one, line by line.
+    pub fn line_diff(&self) -> Vec<Edit<&'a str>> {
+        let lhs: Vec<_> = self.lhs.lines().collect();
+        let rhs: Vec<_> = self.rhs.lines().collect();
+        edit_script(&lhs, &rhs)
+    }
+
+    /// Returns the characters of the two strings.
+    fn chars(&self) -> (Vec<char>, Vec<char>) {
+        (self.lhs.chars().collect(), self.rhs.chars().collect())
+    }
+
+    /// Returns the characters of the two strings, the longest first.
+    fn by_length(&self) -> (Vec<char>, Vec<char>) {
+        let (lhs, rhs) = self.chars();
+        if lhs.len() < rhs.len() {
+            (rhs, lhs)
+        } else {
+            (lhs, rhs)
+        }
+    }
+
+    /// Computes the table of subproblems.
+    ///
+    /// `table[i][j]` is the length of a longest common subsequence of the
+    /// first `i` items of `lhs` and the first `j` items of `rhs`.
+    fn table<T: PartialEq>(lhs: &[T], rhs: &[T]) -> Vec<Vec<usize>> {
+        let mut table = vec![vec![0; rhs.len() + 1]; lhs.len() + 1];
+        for (i, x) in lhs.iter().enumerate() {
+            for (j, y) in rhs.iter().enumerate() {
+                table[i + 1][j + 1] = if x == y {
+                    table[i][j] + 1
+                } else {
+                    table[i][j + 1].max(table[i + 1][j])
+                };
+            }
+        }
+        table
+    }
+}
+
+/// Returns the length and a longest common subsequence of three strings.
+///
+/// It generalizes the table of [`LongSubSequence`] to three dimensions:
+/// `table[i][j][k]` is the length of a longest common subsequence of the
+/// first `i`, `j` and `k` characters of `a`, `b` and `c`. It takes
+/// O(|a||b||c|) time and memory.
+pub fn lcs3(a: &str, b: &str, c: &str) -> (usize, String) {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    let c: Vec<_> = c.chars().collect();
+
+    let mut table = vec![vec![vec![0; c.len() + 1]; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            for k in 1..=c.len() {
+                table[i][j][k] = if a[i - 1] == b[j - 1] && b[j - 1] == c[k - 1] {
+                    table[i - 1][j - 1][k - 1] + 1
+                } else {
+                    table[i - 1][j][k]
+                        .max(table[i][j - 1][k])
+                        .max(table[i][j][k - 1])
+                };
+            }
+        }
+    }
+
+    let (mut i, mut j, mut k) = (a.len(), b.len(), c.len());
+    let length = table[i][j][k];
+    let mut subsequence = Vec::with_capacity(length);
+    while i > 0 && j > 0 && k > 0 {
+        if a[i - 1] == b[j - 1] && b[j - 1] == c[k - 1] {
+            subsequence.push(a[i - 1]);
+            (i, j, k) = (i - 1, j - 1, k - 1);
+        } else if table[i][j][k] == table[i - 1][j][k] {
+            i -= 1;
+        } else if table[i][j][k] == table[i][j - 1][k] {
+            j -= 1;
+        } else {
+            k -= 1;
+        }
+    }
+    (length, subsequence.iter().rev().collect())
+}
+
+/// Returns a shortest common supersequence of two strings.
+///
+/// A shortest string containing both strings as subsequences is obtained
+/// by merging them around a longest common subsequence: the common
+/// characters are written once and the others are interleaved. Hence its
+/// length is `|a| + |b| - |lcs(a, b)|`. The characters are read off the
+/// edit script derived from the LCS table.
+pub fn scs(a: &str, b: &str) -> String {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    edit_script(&a, &b)
+        .into_iter()
+        .map(|edit| match edit {
+            Edit::Keep(c) | Edit::Insert(c) | Edit::Delete(c) => c,
+        })
+        .collect()
+}
+
+/// Formats an edit script as the body of a unified diff.
+///
+/// Each operation is written on its own line, prefixed with a space when
+/// the item is kept, `+` when it is inserted and `-` when it is deleted.
+pub fn unified<T: fmt::Display>(edits: &[Edit<T>]) -> String {
+    edits.iter().map(|edit| format!("{edit}\n")).collect()
+}
+
+/// Walks the table of subproblems back to list the edit operations.
+fn edit_script<T: PartialEq + Copy>(lhs: &[T], rhs: &[T]) -> Vec<Edit<T>> {
+    let table = LongSubSequence::table(lhs, rhs);
+    let (mut i, mut j) = (lhs.len(), rhs.len());
+    let mut edits = Vec::with_capacity(i + j - table[i][j]);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && lhs[i - 1] == rhs[j - 1] {
+            edits.push(Edit::Keep(lhs[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if j > 0 && (i == 0 || table[i][j - 1] >= table[i - 1][j]) {
+            edits.push(Edit::Insert(rhs[j - 1]));
+            j -= 1;
+        } else {
+            edits.push(Edit::Delete(lhs[i - 1]));
+            i -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+/// Pushes a longest common subsequence of `lhs` and `rhs`.
+fn hirschberg<T: PartialEq + Copy>(lhs: &[T], rhs: &[T], subsequence: &mut Vec<T>) {
+    match lhs {
+        [] => return,
+        [x] => {
+            if rhs.contains(x) {
+                subsequence.push(*x);
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let middle = lhs.len() / 2;
+    let forward = last_row(lhs[..middle].iter(), rhs.iter());
+    let backward = last_row(lhs[middle..].iter().rev(), rhs.iter().rev());
+    let split = (0..=rhs.len())
+        .max_by_key(|&j| forward[j] + backward[rhs.len() - j])
+        .unwrap_or_default();
+
+    hirschberg(&lhs[..middle], &rhs[..split], subsequence);
+    hirschberg(&lhs[middle..], &rhs[split..], subsequence);
+}
+
+/// Returns the last row of the table of subproblems.
+///
+/// `row[j]` is the length of a longest common subsequence of `lhs` and the
+/// first `j` items of `rhs`.
+fn last_row<'b, T, L, R>(lhs: L, rhs: R) -> Vec<usize>
+where
+    T: PartialEq + 'b,
+    L: Iterator<Item = &'b T>,
+    R: Iterator<Item = &'b T> + Clone,
+{
+    let len = rhs.clone().count();
+    let mut previous = vec![0; len + 1];
+    let mut current = vec![0; len + 1];
+    for x in lhs {
+        for (j, y) in rhs.clone().enumerate() {
+            current[j + 1] = if x == y {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if `subsequence` is a subsequence of `s`.
+    fn is_subsequence(subsequence: &str, s: &str) -> bool {
+        let mut chars = s.chars();
+        subsequence.chars().all(|c| chars.any(|other| other == c))
+    }
+
+    /// Applies the edit script, keeping either the deleted or the inserted
+    /// items.
+    fn apply(edits: &[Edit<char>], deleted: bool) -> String {
+        edits
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::Keep(c) => Some(c),
+                Edit::Delete(c) if deleted => Some(c),
+                Edit::Insert(c) if !deleted => Some(c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Maps bytes to a small alphabet mixing one to four bytes characters.
+    fn alphabet(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|byte| ['a', 'é', '語', '🦀'][usize::from(byte % 4)])
+            .collect()
+    }
+
+    #[test]
+    fn clrs_example() {
+        let lcs = LongSubSequence::new("ABCBDAB", "BDCABA");
+        assert_eq!(lcs.substr(), "BCBA");
+        assert_eq!(lcs.length(), 4);
+        assert_eq!(lcs.hirschberg().len(), 4);
+    }
+
+    #[test]
+    fn dna_strands() {
+        let lcs = LongSubSequence::new(
+            "ACCGGTCGAGTGCGCGGAAGCCGGCCGAA",
+            "GTCGTTCGGAATGCCGTTGCTCTGTAAA",
+        );
+        assert_eq!(lcs.substr(), "GTCGTCGGAAGCCGGCCGAA");
+        assert_eq!(lcs.length(), 20);
+    }
+
+    #[test]
+    fn empty_strings() {
+        assert_eq!(LongSubSequence::new("", "ABC").substr(), "");
+        assert_eq!(LongSubSequence::new("ABC", "").length(), 0);
+        assert_eq!(LongSubSequence::new("ABC", "DEF").length(), 0);
+        assert_eq!(LongSubSequence::new("", "").hirschberg(), "");
+        assert_eq!(LongSubSequence::new("A", "BAB").hirschberg(), "A");
+    }
+
+    #[test]
+    fn non_ascii_strings() {
+        let lcs = LongSubSequence::new("crème brûlée", "creme brulee");
+        assert_eq!(lcs.substr(), "crme brle");
+        assert_eq!(lcs.length(), 9);
+        assert_eq!(lcs.hirschberg(), "crme brle");
+
+        let lcs = LongSubSequence::new("日本語のテキスト", "日本のテスト");
+        assert_eq!(lcs.substr(), "日本のテスト");
+        assert_eq!(lcs.length(), 6);
+
+        let lcs = LongSubSequence::new("🦀🐍🦀", "🐍🦀");
+        assert_eq!(lcs.substr(), "🐍🦀");
+        assert_eq!(lcs.hirschberg(), "🐍🦀");
+    }
+
+    #[quickcheck]
+    fn length_agrees_with_substr(lhs: Vec<u8>, rhs: Vec<u8>) -> bool {
+        let (lhs, rhs) = (alphabet(&lhs), alphabet(&rhs));
+        let lcs = LongSubSequence::new(&lhs, &rhs);
+        let substr = lcs.substr();
+        substr.chars().count() == lcs.length()
+            && LongSubSequence::new(&rhs, &lhs).length() == lcs.length()
+            && is_subsequence(&substr, &lhs)
+            && is_subsequence(&substr, &rhs)
+    }
+
+    #[quickcheck]
+    fn hirschberg_is_a_longest_common_subsequence(lhs: Vec<u8>, rhs: Vec<u8>) -> bool {
+        let (lhs, rhs) = (alphabet(&lhs), alphabet(&rhs));
+        let lcs = LongSubSequence::new(&lhs, &rhs);
+        let subsequence = lcs.hirschberg();
+        subsequence.chars().count() == lcs.length()
+            && is_subsequence(&subsequence, &lhs)
+            && is_subsequence(&subsequence, &rhs)
+    }
+
+    #[test]
+    fn diff_strings() {
+        let edits = LongSubSequence::new("kitten", "sitting").diff();
+        assert_eq!(
+            edits,
+            vec![
+                Edit::Delete('k'),
+                Edit::Insert('s'),
+                Edit::Keep('i'),
+                Edit::Keep('t'),
+                Edit::Keep('t'),
+                Edit::Delete('e'),
+                Edit::Insert('i'),
+                Edit::Keep('n'),
+                Edit::Insert('g'),
+            ]
+        );
+        assert_eq!(LongSubSequence::new("", "").diff(), vec![]);
+    }
+
+    #[test]
+    fn diff_lines() {
+        let old = "fn main() {\n    println!(\"hello\");\n}\n";
+        let new = "fn main() {\n    let name = \"world\";\n    println!(\"hello {name}\");\n}\n";
+        let edits = LongSubSequence::new(old, new).line_diff();
+        assert_eq!(
+            unified(&edits),
+            " fn main() {\n-    println!(\"hello\");\n+    let name = \"world\";\n+    println!(\"hello {name}\");\n }\n"
+        );
+    }
+
+    #[quickcheck]
+    fn diff_is_a_shortest_edit_script(lhs: Vec<u8>, rhs: Vec<u8>) -> bool {
+        let (lhs, rhs) = (alphabet(&lhs), alphabet(&rhs));
+        let lcs = LongSubSequence::new(&lhs, &rhs);
+        let edits = lcs.diff();
+        let kept = edits
+            .iter()
+            .filter(|edit| matches!(edit, Edit::Keep(_)))
+            .count();
+        apply(&edits, true) == lhs && apply(&edits, false) == rhs && kept == lcs.length()
+    }
+
+    #[test]
+    fn lcs_of_three_strings() {
+        assert_eq!(
+            lcs3("geeks", "geeksfor", "geeksforgeeks"),
+            (5, "geeks".into())
+        );
+        assert_eq!(lcs3("abcd1e2", "bc12ea", "bd1ea"), (3, "b1e".into()));
+        assert_eq!(lcs3("ABCBDAB", "BDCABA", ""), (0, String::new()));
+        assert_eq!(lcs3("ça et là", "déjà là", "là"), (2, "là".into()));
+    }
+
+    #[quickcheck]
+    fn lcs3_is_a_longest_common_subsequence(a: Vec<u8>, b: Vec<u8>, c: Vec<u8>) -> bool {
+        let (a, b, c) = (alphabet(&a[..a.len().min(8)]), alphabet(&b), alphabet(&c));
+        let (length, subsequence) = lcs3(&a, &b, &c);
+
+        // Every subsequence of `a` is a candidate, which is few enough for
+        // short strings.
+        let chars: Vec<_> = a.chars().collect();
+        let longest = (0_u32..1 << chars.len())
+            .map(|mask| {
+                (0..chars.len())
+                    .filter(|index| mask >> index & 1 == 1)
+                    .map(|index| chars[index])
+                    .collect::<String>()
+            })
+            .filter(|candidate| is_subsequence(candidate, &b) && is_subsequence(candidate, &c))
+            .map(|candidate| candidate.chars().count())
+            .max();
+
+        subsequence.chars().count() == length
+            && longest == Some(length)
+            && is_subsequence(&subsequence, &a)
+            && is_subsequence(&subsequence, &b)
+            && is_subsequence(&subsequence, &c)
+    }
+
+    #[test]
+    fn shortest_common_supersequence() {
+        assert_eq!(scs("AGGTAB", "GXTXAYB").chars().count(), 9);
+        assert_eq!(scs("abac", "cab"), "cabac");
+        assert_eq!(scs("", "abc"), "abc");
+        assert_eq!(scs("été", "thé"), "éthé");
+    }
+
+    #[quickcheck]
+    fn scs_length_identity(lhs: Vec<u8>, rhs: Vec<u8>) -> bool {
+        let (lhs, rhs) = (alphabet(&lhs), alphabet(&rhs));
+        let supersequence = scs(&lhs, &rhs);
+        let length = LongSubSequence::new(&lhs, &rhs).length();
+        supersequence.chars().count() == lhs.chars().count() + rhs.chars().count() - length
+            && is_subsequence(&lhs, &supersequence)
+            && is_subsequence(&rhs, &supersequence)
+    }
+}
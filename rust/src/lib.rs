@@ -11,15 +11,41 @@
     clippy::missing_const_for_fn
 )]
 
+pub mod activity;
+pub mod analysis;
+pub mod binary;
 pub mod bits;
+pub mod disjoint_set;
 pub mod error;
+pub mod fenwick;
+pub mod fib;
+pub mod geometry;
+pub mod graph;
+pub mod hashtable;
 pub mod heap;
+pub mod huffman;
+pub mod josephus;
+pub mod knapsack;
+pub mod lcs;
+pub mod lis;
 pub mod list;
+pub mod map;
+pub mod matrix;
 pub mod maximum_subarray;
+pub mod numbers;
+pub mod perfect_hash;
+pub mod polynomial;
 pub mod queue;
+pub mod rod;
 pub mod search;
+pub mod select;
+pub mod skiplist;
 pub mod sort;
 pub mod stack;
+pub mod string_matching;
+pub mod trace;
+pub mod tree;
+pub mod trie;
 
 pub use self::error::Error;
 
@@ -33,12 +59,9 @@ pub struct Container<T> {
     pub(crate) data: Vec<T>,
 }
 
-impl<T> Container<T>
-where
-    T: PartialOrd + Clone,
-{
+impl<T> Container<T> {
     /// Creates new container instance.
-    pub fn new(data: Vec<T>) -> Self {
+    pub const fn new(data: Vec<T>) -> Self {
         Self { data }
     }
 
@@ -52,13 +75,32 @@ where
         self.data.len()
     }
 
+    /// Returns a reference to the element at the index, or `None` if the
+    /// index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.data.get(index)
+    }
+
+    /// Returns a mutable reference to the element at the index, or `None`
+    /// if the index is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.data.get_mut(index)
+    }
+
     /// Swaps two elements in the container.
-    fn swap(&mut self, i: usize, j: usize) {
+    ///
+    /// It fails with [`ErrorKind::IndexOutOfBounds`](error::ErrorKind::IndexOutOfBounds)
+    /// if either index is out of bounds, leaving the container unchanged.
+    pub fn try_swap(&mut self, i: usize, j: usize) -> Result<(), Error> {
+        if i >= self.len() || j >= self.len() {
+            return Err(Error::new(error::ErrorKind::IndexOutOfBounds));
+        }
         self.data.swap(i, j);
+        Ok(())
     }
 
     /// Creates a iterator over a container.
-    pub fn iter(&self) -> ContainerIterator<T> {
+    pub fn iter(&self) -> ContainerIterator<'_, T> {
         ContainerIterator {
             items: &self.data,
             pos: 0,
@@ -69,44 +111,8 @@ where
     pub fn inner(&self) -> &[T] {
         &self.data
     }
-
-    /// Merges two sorted containers.
-    ///
-    /// This methods creates a new container and merges in two sorted container.
-    /// The resulting container element are the elements with the index in the range
-    /// of start..end but in sorted order.
-    pub(crate) fn merge(&mut self, start: usize, middle: usize, end: usize) {
-        let lhs = self.data[start..middle].to_vec();
-        let rhs = self.data[middle..end].to_vec();
-
-        let mut i = 0;
-        let mut j = 0;
-        for k in start..end {
-            if i < lhs.len() && j < rhs.len() && lhs[i] <= rhs[j] || i < lhs.len() && j == rhs.len()
-            {
-                self[k] = lhs[i].clone();
-                i += 1;
-            } else {
-                self[k] = rhs[j].clone();
-                j += 1;
-            }
-        }
-    }
-
-    // Partition the container items in the specified bounds.
-    pub(crate) fn partition(&mut self, start: usize, end: usize) -> usize {
-        let Some(pivot) = self.iter().last().cloned() else {return 0;};
-        let mut last_smallest = start;
-        for index in start..end - 1 {
-            if self[index] <= pivot {
-                self.swap(last_smallest, index);
-                last_smallest += 1;
-            }
-        }
-        self.swap(last_smallest, end - 1);
-        last_smallest
-    }
 }
+
 impl<T> Index<usize> for Container<T> {
     type Output = T;
     fn index(&self, index: usize) -> &Self::Output {
@@ -157,7 +163,7 @@ pub fn merge_all_into(containers: &[&Container<i64>]) -> Container<i64> {
     while let Some(Value { key, index }) = heap.extract_min() {
         merged.push(key);
         if let Some(&key) = containers.get_mut(index).and_then(|i| i.next()) {
-            heap.min_insert_key(Value { key, index });
+            heap.insert(Value { key, index });
         }
     }
     Container::new(merged)
@@ -165,8 +171,28 @@ pub fn merge_all_into(containers: &[&Container<i64>]) -> Container<i64> {
 
 #[cfg(test)]
 mod tests {
+    use super::error::ErrorKind;
     use super::*;
 
+    #[test]
+    fn bounds_checked_access() {
+        let mut container = Container::new(vec![1, 2, 3]);
+        assert_eq!(container.get(2), Some(&3));
+        assert_eq!(container.get(3), None);
+        if let Some(first) = container.get_mut(0) {
+            *first = 7;
+        }
+        assert_eq!(container.get_mut(3), None);
+
+        assert_eq!(container.try_swap(0, 2), Ok(()));
+        assert_eq!(container.inner(), [3, 2, 7]);
+        assert_eq!(
+            container.try_swap(1, 3),
+            Err(Error::new(ErrorKind::IndexOutOfBounds))
+        );
+        assert_eq!(container.inner(), [3, 2, 7]);
+    }
+
     #[test]
     fn merge_sorted_list() {
         let list = &[
@@ -0,0 +1,104 @@
+//! Longest increasing subsequence.
+//!
+//! The problem is to find a longest subsequence of a sequence whose
+//! elements are strictly increasing. See CLRS exercises 15.4-5 and 15.4-6.
+//!
+//! Both algorithms of this module return the indices of the elements of
+//! the subsequence, in increasing order, so that the subsequence can be
+//! checked against the sequence.
+
+/// Returns the indices of a longest strictly increasing subsequence by
+/// dynamic programming.
+///
+/// The subproblem `j` is the longest increasing subsequence ending with the
+/// element `j`, which extends the longest one ending with a smaller element
+/// before it. It takes O(n²) time.
+pub fn quadratic<T: Ord>(data: &[T]) -> Vec<usize> {
+    let mut lengths = vec![1; data.len()];
+    let mut predecessors = vec![None; data.len()];
+    for j in 0..data.len() {
+        for i in 0..j {
+            if data[i] < data[j] && lengths[i] + 1 > lengths[j] {
+                lengths[j] = lengths[i] + 1;
+                predecessors[j] = Some(i);
+            }
+        }
+    }
+    let last = (0..data.len()).max_by_key(|&index| lengths[index]);
+    backtrack(&predecessors, last)
+}
+
+/// Returns the indices of a longest strictly increasing subsequence by
+/// patience sorting.
+///
+/// Only the top of each pile is kept: `tops[k]` is the index of the
+/// smallest element ending an increasing subsequence of length `k + 1`.
+/// The tops are increasing, so the pile of each element is found by binary
+/// search, which takes O(n log n) time. See also
+/// [`patience_sort`](crate::sort::patience::patience_sort), which sorts
+/// the sequence with the whole piles.
+pub fn patience<T: Ord>(data: &[T]) -> Vec<usize> {
+    let mut tops: Vec<usize> = Vec::new();
+    let mut predecessors = vec![None; data.len()];
+    for index in 0..data.len() {
+        let pile = tops.partition_point(|&top| data[top] < data[index]);
+        if pile > 0 {
+            predecessors[index] = Some(tops[pile - 1]);
+        }
+        if pile == tops.len() {
+            tops.push(index);
+        } else {
+            tops[pile] = index;
+        }
+    }
+    backtrack(&predecessors, tops.last().copied())
+}
+
+/// Follows the predecessors back from the last index of the subsequence.
+fn backtrack(predecessors: &[Option<usize>], mut last: Option<usize>) -> Vec<usize> {
+    let mut subsequence = Vec::new();
+    while let Some(index) = last {
+        subsequence.push(index);
+        last = predecessors[index];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if the indices are those of a strictly increasing
+    /// subsequence.
+    fn is_increasing<T: Ord>(data: &[T], subsequence: &[usize]) -> bool {
+        subsequence
+            .windows(2)
+            .all(|pair| pair[0] < pair[1] && data[pair[0]] < data[pair[1]])
+            && subsequence.iter().all(|&index| index < data.len())
+    }
+
+    #[test]
+    fn longest_increasing_subsequence() {
+        let data = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+        for subsequence in [quadratic(&data), patience(&data)] {
+            assert_eq!(subsequence.len(), 6);
+            assert!(is_increasing(&data, &subsequence));
+        }
+        assert_eq!(patience(&data), vec![0, 4, 6, 9, 13, 15]);
+        // Equal elements are not increasing.
+        assert_eq!(patience(&[3, 3, 3]).len(), 1);
+        assert_eq!(quadratic(&[3, 3, 3]).len(), 1);
+        assert!(patience::<i8>(&[]).is_empty());
+        assert!(quadratic::<i8>(&[]).is_empty());
+    }
+
+    #[quickcheck]
+    fn algorithms_agree(data: Vec<i8>) -> bool {
+        let slow = quadratic(&data);
+        let fast = patience(&data);
+        slow.len() == fast.len() && is_increasing(&data, &slow) && is_increasing(&data, &fast)
+    }
+}
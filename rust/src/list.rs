@@ -1,5 +1,7 @@
 //! Linked lists data structure
 
+use std::rc::Rc;
+
 /// A List is a single linked list data structure.
 #[derive(Debug, Clone)]
 pub struct List<T> {
@@ -41,6 +43,136 @@ impl<T> List<T> {
         self.head = Some(node);
         self
     }
+
+    /// Creates an iterator over the keys in the list.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+/// Returns the first element of the cycle reached by repeatedly applying
+/// `next` from `start`, or `None` if `next` returns `None` first.
+///
+/// It uses Floyd's tortoise and hare algorithm, where `same` tells if two
+/// elements are the same. A slow pointer moves one element at a time and
+/// a fast pointer two elements at a time, and they meet if and only if
+/// there is a cycle. The distance from the start to the first element of
+/// the cycle then equals the distance from the meeting point to it, so
+/// moving one pointer from each place at the same pace makes them meet
+/// there. It takes O(μ + λ) time and O(1) space, for a cycle of length λ
+/// reached after μ steps.
+///
+/// A [`List`] owns its nodes, so its links never form a cycle. The
+/// function is meant for sequences whose links are not owned, such as a
+/// table of successor indices or the iterates of a function.
+pub fn cycle_start_by<N: Copy>(
+    start: N,
+    next: impl Fn(N) -> Option<N>,
+    same: impl Fn(N, N) -> bool,
+) -> Option<N> {
+    let (mut slow, mut fast) = (start, start);
+    loop {
+        slow = next(slow)?;
+        fast = next(next(fast)?)?;
+        if same(slow, fast) {
+            break;
+        }
+    }
+    let mut first = start;
+    while !same(slow, first) {
+        slow = next(slow)?;
+        first = next(first)?;
+    }
+    Some(first)
+}
+
+impl<T> List<T>
+where
+    T: PartialOrd,
+{
+    /// Sorts the list using merge sort.
+    ///
+    /// The nodes are spliced into place so that no key is ever cloned
+    /// or moved out of its node. The sort is stable.
+    pub fn sort(&mut self) {
+        self.head = merge_sort(self.head.take());
+    }
+}
+
+/// Sorts a chain of nodes and returns the new head of the chain.
+fn merge_sort<T: PartialOrd>(mut head: Link<T>) -> Link<T> {
+    let len = {
+        let mut len = 0;
+        let mut cursor = head.as_deref();
+        while let Some(node) = cursor {
+            len += 1;
+            cursor = node.next.as_deref();
+        }
+        len
+    };
+    if len <= 1 {
+        return head;
+    }
+
+    let rhs = split_off(&mut head, len / 2);
+    merge(merge_sort(head), merge_sort(rhs))
+}
+
+/// Splits the chain after `at` nodes and returns the detached tail.
+fn split_off<T>(head: &mut Link<T>, at: usize) -> Link<T> {
+    let mut cursor = head;
+    for _ in 0..at {
+        match cursor {
+            Some(node) => cursor = &mut node.next,
+            None => break,
+        }
+    }
+    cursor.take()
+}
+
+/// Merges two sorted chains by splicing their nodes.
+fn merge<T: PartialOrd>(mut lhs: Link<T>, mut rhs: Link<T>) -> Link<T> {
+    let mut head = None;
+    let mut tail = &mut head;
+    loop {
+        let node = match (lhs.take(), rhs.take()) {
+            (Some(mut left), Some(right)) if left.key <= right.key => {
+                lhs = left.next.take();
+                rhs = Some(right);
+                left
+            }
+            (left, Some(mut right)) => {
+                rhs = right.next.take();
+                lhs = left;
+                right
+            }
+            (rest, None) => {
+                *tail = rest;
+                break;
+            }
+        };
+        tail = &mut tail.insert(node).next;
+    }
+    head
+}
+
+/// Iterator over the keys in a list.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.key
+        })
+    }
 }
 
 impl<T> Default for List<T> {
@@ -49,9 +181,555 @@ impl<T> Default for List<T> {
     }
 }
 
+impl<T> Drop for List<T> {
+    // The default drop glue recursively drops each boxed node, which
+    // overflows the stack on long lists. Instead we unlink the nodes
+    // one at a time so that each box is dropped with no successor.
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+/// Index of the sentinel node in a [`DoublyLinkedList`].
+const NIL: usize = 0;
+
+/// A doubly linked list with a sentinel.
+///
+/// This follows CLRS section 10.2: a dummy node `nil` sits between the
+/// head and the tail so that `nil.next` is the head and `nil.prev` is the
+/// tail, which removes every boundary case from insertion and deletion.
+///
+/// The nodes are stored in a vector and linked through their indices,
+/// so the list is implemented without any unsafe code.
+#[derive(Debug, Clone)]
+pub struct DoublyLinkedList<T> {
+    /// Node storage. The sentinel is always at index [`NIL`].
+    nodes: Vec<DoublyNode<T>>,
+
+    /// Indices of the deleted nodes which can be reused.
+    free: Vec<usize>,
+
+    /// The number of keys in the list.
+    len: usize,
+}
+
+/// Node of a [`DoublyLinkedList`].
+#[derive(Debug, Clone)]
+struct DoublyNode<T> {
+    /// The node key. It is `None` for the sentinel and deleted nodes.
+    key: Option<T>,
+
+    /// Index of the previous node.
+    prev: usize,
+
+    /// Index of the next node.
+    next: usize,
+
+    /// Incremented each time the node is deleted so that stale cursors
+    /// can be detected.
+    generation: usize,
+}
+
+/// A cursor points to a node in a [`DoublyLinkedList`].
+///
+/// A cursor stays valid until the node it points to is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    index: usize,
+    generation: usize,
+}
+
+impl<T> DoublyLinkedList<T> {
+    /// Creates new empty list.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![DoublyNode {
+                key: None,
+                prev: NIL,
+                next: NIL,
+                generation: 0,
+            }],
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of keys in the list.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts the key at the front of the list.
+    pub fn push_front(&mut self, key: T) -> Cursor {
+        self.insert_after(NIL, key)
+    }
+
+    /// Inserts the key at the back of the list.
+    pub fn push_back(&mut self, key: T) -> Cursor {
+        let tail = self.nodes[NIL].prev;
+        self.insert_after(tail, key)
+    }
+
+    /// Removes the key at the front of the list.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.nodes[NIL].next;
+        self.unlink(head)
+    }
+
+    /// Removes the key at the back of the list.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.nodes[NIL].prev;
+        self.unlink(tail)
+    }
+
+    /// Returns a reference to the key at the front of the list.
+    pub fn front(&self) -> Option<&T> {
+        self.nodes[self.nodes[NIL].next].key.as_ref()
+    }
+
+    /// Returns a reference to the key at the back of the list.
+    pub fn back(&self) -> Option<&T> {
+        self.nodes[self.nodes[NIL].prev].key.as_ref()
+    }
+
+    /// Returns a reference to the key the cursor points to.
+    pub fn get(&self, cursor: Cursor) -> Option<&T> {
+        self.resolve(cursor)
+            .and_then(|index| self.nodes[index].key.as_ref())
+    }
+
+    /// Returns a mutable reference to the key the cursor points to.
+    pub fn get_mut(&mut self, cursor: Cursor) -> Option<&mut T> {
+        let index = self.resolve(cursor)?;
+        self.nodes[index].key.as_mut()
+    }
+
+    /// Returns a cursor to the node at the front of the list.
+    pub fn cursor_front(&self) -> Option<Cursor> {
+        self.cursor(self.nodes[NIL].next)
+    }
+
+    /// Returns a cursor to the node after the one the cursor points to.
+    ///
+    /// It returns `None` at the back of the list, or if the node was
+    /// deleted.
+    pub fn next(&self, cursor: Cursor) -> Option<Cursor> {
+        let index = self.resolve(cursor)?;
+        self.cursor(self.nodes[index].next)
+    }
+
+    /// Deletes the node the cursor points to and returns its key.
+    ///
+    /// It returns `None` if the node was already deleted.
+    pub fn delete(&mut self, cursor: Cursor) -> Option<T> {
+        let index = self.resolve(cursor)?;
+        self.unlink(index)
+    }
+
+    /// Creates an iterator over the keys from the front to the back.
+    pub fn iter(&self) -> DoublyIter<'_, T> {
+        DoublyIter {
+            list: self,
+            front: self.nodes[NIL].next,
+            back: self.nodes[NIL].prev,
+            len: self.len,
+        }
+    }
+
+    /// Returns a cursor to the node at `index`, or `None` for the sentinel.
+    fn cursor(&self, index: usize) -> Option<Cursor> {
+        (index != NIL).then(|| Cursor {
+            index,
+            generation: self.nodes[index].generation,
+        })
+    }
+
+    /// Returns the index of the live node the cursor points to.
+    fn resolve(&self, cursor: Cursor) -> Option<usize> {
+        let node = self.nodes.get(cursor.index)?;
+        (cursor.index != NIL && node.generation == cursor.generation && node.key.is_some())
+            .then_some(cursor.index)
+    }
+
+    /// Splices a new node with the given key after the node at `index`.
+    fn insert_after(&mut self, index: usize, key: T) -> Cursor {
+        let next = self.nodes[index].next;
+        let node = match self.free.pop() {
+            Some(free) => {
+                let node = &mut self.nodes[free];
+                node.key = Some(key);
+                node.prev = index;
+                node.next = next;
+                free
+            }
+            None => {
+                self.nodes.push(DoublyNode {
+                    key: Some(key),
+                    prev: index,
+                    next,
+                    generation: 0,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        self.nodes[next].prev = node;
+        self.nodes[index].next = node;
+        self.len += 1;
+        Cursor {
+            index: node,
+            generation: self.nodes[node].generation,
+        }
+    }
+
+    /// Unlinks the node at `index` and returns its key.
+    fn unlink(&mut self, index: usize) -> Option<T> {
+        if index == NIL {
+            return None;
+        }
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+
+        let node = &mut self.nodes[index];
+        node.generation += 1;
+        self.free.push(index);
+        self.len -= 1;
+        node.key.take()
+    }
+}
+
+impl<T> DoublyLinkedList<T>
+where
+    T: PartialEq,
+{
+    /// Finds the first node with the given key.
+    pub fn search(&self, key: &T) -> Option<Cursor> {
+        let mut index = self.nodes[NIL].next;
+        while index != NIL && self.nodes[index].key.as_ref() != Some(key) {
+            index = self.nodes[index].next;
+        }
+        self.cursor(index)
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the keys in a doubly linked list.
+#[derive(Debug)]
+pub struct DoublyIter<'a, T> {
+    list: &'a DoublyLinkedList<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for DoublyIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = &self.list.nodes[self.front];
+        self.front = node.next;
+        self.len -= 1;
+        node.key.as_ref()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DoublyIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = &self.list.nodes[self.back];
+        self.back = node.prev;
+        self.len -= 1;
+        node.key.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod doubly_linked_list_tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::DoublyLinkedList;
+
+    #[test]
+    fn push_and_pop_at_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&3));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(list.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+    }
+
+    #[test]
+    fn search_and_delete() {
+        let mut list = DoublyLinkedList::new();
+        for key in [1, 4, 16, 9] {
+            list.push_front(key);
+        }
+        assert_eq!(list.search(&7), None);
+
+        let cursor = list.search(&4).expect("key is in the list");
+        assert_eq!(list.get(cursor), Some(&4));
+        if let Some(key) = list.get_mut(cursor) {
+            *key = 5;
+        }
+        assert_eq!(list.delete(cursor), Some(5));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&9, &16, &1]);
+        assert_eq!(list.search(&4), None);
+    }
+
+    #[test]
+    fn walk_with_cursors() {
+        let mut list = DoublyLinkedList::new();
+        assert_eq!(list.cursor_front(), None);
+        for key in 1..=3 {
+            list.push_back(key);
+        }
+
+        let first = list.cursor_front().expect("list is not empty");
+        let second = list.next(first).expect("first is not the back");
+        assert_eq!(list.get(second), Some(&2));
+        assert_eq!(list.delete(second), Some(2));
+        assert_eq!(list.next(second), None);
+
+        let third = list.next(first).expect("first is not the back");
+        assert_eq!(list.get(third), Some(&3));
+        assert_eq!(list.next(third), None);
+    }
+
+    #[test]
+    fn stale_cursor_is_rejected() {
+        let mut list = DoublyLinkedList::new();
+        let cursor = list.push_back(1);
+        assert_eq!(list.delete(cursor), Some(1));
+
+        // The node storage is reused by the next insertion.
+        let other = list.push_back(2);
+        assert_eq!(list.get(cursor), None);
+        assert_eq!(list.delete(cursor), None);
+        assert_eq!(list.get_mut(cursor), None);
+        assert_eq!(list.get(other), Some(&2));
+    }
+
+    #[quickcheck]
+    fn behaves_like_vec_deque(operations: Vec<(u8, i32)>) -> bool {
+        let mut list = DoublyLinkedList::new();
+        let mut model = std::collections::VecDeque::new();
+        for (operation, key) in operations {
+            let ok = match operation % 4 {
+                0 => {
+                    list.push_front(key);
+                    model.push_front(key);
+                    true
+                }
+                1 => {
+                    list.push_back(key);
+                    model.push_back(key);
+                    true
+                }
+                2 => list.pop_front() == model.pop_front(),
+                _ => list.pop_back() == model.pop_back(),
+            };
+            if !ok || list.len() != model.len() || !list.iter().eq(model.iter()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A persistent singly linked list.
+///
+/// Every operation leaves the list it is called on untouched and returns
+/// a new version instead. Versions share their common tail through
+/// reference counting, so cloning a list or pushing a key is O(1).
+#[derive(Debug)]
+pub struct PersistentList<T> {
+    head: Option<Rc<PersistentNode<T>>>,
+}
+
+/// Node of a [`PersistentList`].
+#[derive(Debug)]
+struct PersistentNode<T> {
+    key: T,
+    next: Option<Rc<PersistentNode<T>>>,
+}
+
+impl<T> PersistentList<T> {
+    /// Creates new empty list.
+    pub const fn new() -> Self {
+        Self { head: None }
+    }
+
+    /// Returns true if the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns a new list with the key in front of this list.
+    pub fn push_front(&self, key: T) -> Self {
+        Self {
+            head: Some(Rc::new(PersistentNode {
+                key,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    /// Returns a reference to the first key in the list.
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.key)
+    }
+
+    /// Returns the list without its first key.
+    ///
+    /// The tail of an empty list is `None`.
+    pub fn tail(&self) -> Option<Self> {
+        self.head.as_ref().map(|node| Self {
+            head: node.next.clone(),
+        })
+    }
+
+    /// Returns true if both lists are the same version.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(lhs), Some(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Creates an iterator over the keys in the list.
+    pub fn iter(&self) -> PersistentIter<'_, T> {
+        PersistentIter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for PersistentList<T> {
+    // Release the nodes iteratively, stopping at the first node which
+    // is still shared with another version.
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => link = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// Iterator over the keys in a persistent list.
+#[derive(Debug)]
+pub struct PersistentIter<'a, T> {
+    next: Option<&'a PersistentNode<T>>,
+}
+
+impl<'a, T> Iterator for PersistentIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.key
+        })
+    }
+}
+
+#[cfg(test)]
+mod persistent_list_tests {
+    use super::PersistentList;
+
+    #[test]
+    fn versions_are_preserved() {
+        let empty = PersistentList::new();
+        let one = empty.push_front(1);
+        let two = one.push_front(2);
+        let three = one.push_front(3);
+
+        assert!(empty.is_empty());
+        assert_eq!(one.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(two.iter().collect::<Vec<_>>(), vec![&2, &1]);
+        assert_eq!(three.iter().collect::<Vec<_>>(), vec![&3, &1]);
+        assert_eq!(three.head(), Some(&3));
+    }
+
+    #[test]
+    fn versions_share_their_tail() {
+        let one = PersistentList::new().push_front(1);
+        let two = one.push_front(2);
+        let three = one.push_front(3);
+
+        let tail = two.tail().expect("list is not empty");
+        assert!(tail.ptr_eq(&one));
+        assert!(tail.ptr_eq(&three.tail().expect("list is not empty")));
+        assert!(two.clone().ptr_eq(&two));
+        assert!(!two.ptr_eq(&three));
+        assert!(PersistentList::<i32>::new().tail().is_none());
+    }
+
+    #[test]
+    fn drop_long_shared_list() {
+        let mut list = PersistentList::new();
+        for key in 0..1_000_000 {
+            list = list.push_front(key);
+        }
+        let shared = list.tail();
+        drop(list);
+        assert_eq!(shared.and_then(|list| list.head().copied()), Some(999_998));
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::List;
+    use quickcheck_macros::quickcheck;
+
+    use super::{cycle_start_by, List};
 
     #[test]
     fn list_operations() {
@@ -60,5 +738,80 @@ mod tests {
         list.insert(2);
         list.insert(3);
         list.insert(4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+    }
+
+    #[quickcheck]
+    fn sort_list(keys: Vec<i32>) -> bool {
+        let mut list = List::new();
+        for key in keys.iter().rev() {
+            list.insert(*key);
+        }
+        list.sort();
+
+        let mut expected = keys;
+        expected.sort();
+        list.iter().eq(expected.iter())
+    }
+
+    #[test]
+    fn sort_list_is_stable() {
+        // Only the first field takes part in the ordering.
+        #[derive(Debug, PartialEq)]
+        struct Pair(i32, char);
+        impl PartialOrd for Pair {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.0.partial_cmp(&other.0)
+            }
+        }
+
+        let mut list = List::new();
+        list.insert(Pair(1, 'd'))
+            .insert(Pair(2, 'c'))
+            .insert(Pair(1, 'b'))
+            .insert(Pair(2, 'a'));
+        list.sort();
+        let keys: Vec<_> = list.iter().map(|pair| pair.1).collect();
+        assert_eq!(keys, vec!['b', 'd', 'a', 'c']);
+    }
+
+    #[test]
+    fn detect_cycle() {
+        // `successors[i]` is the element following `i`, like the links of a
+        // list whose last node points back to the node at `index`.
+        for len in 1..10 {
+            for index in 0..len {
+                let successors: Vec<_> = (1..len).chain([index]).collect();
+                let start = cycle_start_by(0, |i| Some(successors[i]), |a, b| a == b);
+                assert_eq!(start, Some(index));
+            }
+            let path: Vec<_> = (1..len).map(Some).chain([None]).collect();
+            assert_eq!(cycle_start_by(0, |i| path[i], |a, b| a == b), None);
+        }
+    }
+
+    #[quickcheck]
+    fn cycle_start_agrees_with_visited_set(successors: Vec<u8>) -> bool {
+        if successors.is_empty() {
+            return true;
+        }
+        let next = |i: usize| Some(usize::from(successors[i]) % successors.len());
+        // The first element visited twice starts the cycle.
+        let mut visited = vec![false; successors.len()];
+        let mut expected = 0;
+        while !visited[expected] {
+            visited[expected] = true;
+            expected = next(expected).expect("every element has a successor");
+        }
+        cycle_start_by(0, next, |a, b| a == b) == Some(expected)
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = List::new();
+        for key in 0..1_000_000 {
+            list.insert(key);
+        }
+        drop(list);
     }
 }
@@ -0,0 +1,244 @@
+//! Associative arrays.
+//!
+//! This module defines the [`Map`] trait shared by the dictionary data
+//! structures of the crate, so they can be benchmarked and tested
+//! interchangeably, and implements the direct-address table of CLRS
+//! section 11.1.
+
+use std::collections::BTreeMap;
+
+/// Map is a dictionary from keys to values.
+pub trait Map<K, V> {
+    /// Iterator over the entries of the map.
+    type Iter<'a>: Iterator<Item = (&'a K, &'a V)>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Returns a reference to the value associated with the key.
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Removes the key from the map and returns its value, if any.
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    /// Returns the number of entries.
+    fn len(&self) -> usize;
+
+    /// Returns true if the map is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the map contains the key.
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Creates an iterator over the entries, in no particular order.
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+impl<K: Ord, V> Map<K, V> for BTreeMap<K, V> {
+    type Iter<'a>
+        = std::collections::btree_map::Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        BTreeMap::iter(self)
+    }
+}
+
+/// DirectAddressTable is a map whose keys are drawn from a small universe
+/// `0..m`.
+///
+/// The table has one slot per key of the universe, so every operation
+/// takes O(1) worst-case time, at the price of O(m) memory.
+#[derive(Debug, Clone)]
+pub struct DirectAddressTable<V> {
+    /// `slots[k]` holds the entry with the key `k`.
+    slots: Vec<Option<(usize, V)>>,
+    len: usize,
+}
+
+impl<V> DirectAddressTable<V> {
+    /// Creates new empty table for the keys in `0..universe`.
+    pub fn new(universe: usize) -> Self {
+        Self {
+            slots: std::iter::repeat_with(|| None).take(universe).collect(),
+            len: 0,
+        }
+    }
+
+    /// Returns the size of the universe of keys.
+    pub fn universe(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+impl<V> Map<usize, V> for DirectAddressTable<V> {
+    type Iter<'a>
+        = DirectAddressIter<'a, V>
+    where
+        V: 'a;
+
+    /// Inserts a value with the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is outside the universe of the table.
+    fn insert(&mut self, key: usize, value: V) -> Option<V> {
+        assert!(key < self.universe(), "key is outside the universe");
+        let previous = self.slots[key].replace((key, value));
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous.map(|(_, value)| value)
+    }
+
+    fn get(&self, key: &usize) -> Option<&V> {
+        self.slots.get(*key)?.as_ref().map(|(_, value)| value)
+    }
+
+    fn remove(&mut self, key: &usize) -> Option<V> {
+        let (_, value) = self.slots.get_mut(*key)?.take()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    fn iter(&self) -> Self::Iter<'_> {
+        DirectAddressIter {
+            slots: self.slots.iter(),
+        }
+    }
+}
+
+/// Iterator over the entries of a [`DirectAddressTable`].
+#[derive(Debug)]
+pub struct DirectAddressIter<'a, V> {
+    slots: std::slice::Iter<'a, Option<(usize, V)>>,
+}
+
+impl<'a, V> Iterator for DirectAddressIter<'a, V> {
+    type Item = (&'a usize, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots
+            .find_map(|slot| slot.as_ref().map(|(key, value)| (key, value)))
+    }
+}
+
+/// Shared test suite of the [`Map`] implementations.
+#[cfg(test)]
+pub(crate) mod suite {
+    use std::collections::BTreeMap;
+
+    use super::Map;
+
+    /// An operation applied to both a map and the reference model.
+    pub(crate) type Operation = (bool, u8, u16);
+
+    /// Applies the operations to the map and to a `BTreeMap`, and returns
+    /// true if they always agree.
+    ///
+    /// Each operation inserts `(key, value)` if its flag is set, and
+    /// removes `key` otherwise.
+    pub(crate) fn agrees_with_model<K, M>(map: &mut M, operations: &[Operation]) -> bool
+    where
+        K: Ord + Copy + From<u8>,
+        M: Map<K, u16>,
+    {
+        let mut model = BTreeMap::new();
+        for &(insert, key, value) in operations {
+            let key = K::from(key);
+            let agree = if insert {
+                map.insert(key, value) == model.insert(key, value)
+            } else {
+                map.remove(&key) == model.remove(&key)
+            };
+            if !agree || map.len() != model.len() {
+                return false;
+            }
+        }
+
+        let mut entries: Vec<_> = map.iter().map(|(key, value)| (*key, *value)).collect();
+        entries.sort_unstable();
+        entries
+            .into_iter()
+            .eq(model.iter().map(|(key, value)| (*key, *value)))
+            && (0..=u8::MAX).all(|key| map.get(&K::from(key)) == model.get(&K::from(key)))
+            && map.is_empty() == model.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::suite::{agrees_with_model, Operation};
+    use super::*;
+
+    #[test]
+    fn direct_address_table() {
+        let mut table = DirectAddressTable::new(10);
+        assert_eq!(table.insert(3, "three"), None);
+        assert_eq!(table.insert(7, "seven"), None);
+        assert_eq!(table.insert(3, "drei"), Some("three"));
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(&3), Some(&"drei"));
+        assert_eq!(table.get(&42), None);
+        assert!(table.contains_key(&7));
+        assert_eq!(
+            table.iter().collect::<Vec<_>>(),
+            vec![(&3, &"drei"), (&7, &"seven")]
+        );
+        assert_eq!(table.remove(&3), Some("drei"));
+        assert_eq!(table.remove(&3), None);
+        assert_eq!(table.remove(&42), None);
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the universe")]
+    fn insert_outside_universe() {
+        DirectAddressTable::new(4).insert(4, ());
+    }
+
+    #[quickcheck]
+    fn direct_address_table_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<usize, _>(&mut DirectAddressTable::new(256), &operations)
+    }
+
+    #[quickcheck]
+    fn btree_map_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut BTreeMap::new(), &operations)
+    }
+}
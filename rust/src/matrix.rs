@@ -0,0 +1,111 @@
+//! Dense matrices.
+//!
+//! The [`chain`] module finds the optimal order to multiply a chain of
+//! matrices.
+
+use std::ops::{Index, IndexMut};
+
+pub mod chain;
+
+/// Matrix is a dense matrix stored in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Matrix<T> {
+    rows: usize,
+    columns: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// Creates new matrix with every entry set to `Default::default()`.
+    pub fn new(rows: usize, columns: usize) -> Self
+    where
+        T: Default + Clone,
+    {
+        Self {
+            rows,
+            columns,
+            data: vec![T::default(); rows * columns],
+        }
+    }
+
+    /// Creates new matrix from its rows.
+    ///
+    /// It returns `None` if the rows do not all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let columns = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != columns) {
+            return None;
+        }
+        Some(Self {
+            rows: rows.len(),
+            columns,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Returns the number of rows.
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub const fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the entry at the given row and column.
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        (row < self.rows && column < self.columns).then(|| &self.data[row * self.columns + column])
+    }
+
+    /// Returns the given row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.columns..(row + 1) * self.columns]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        assert!(column < self.columns, "column index is out of bounds");
+        &self.data[row * self.columns + column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        assert!(column < self.columns, "column index is out of bounds");
+        &mut self.data[row * self.columns + column]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_matrix() {
+        let mut matrix = Matrix::new(2, 3);
+        matrix[(1, 2)] = 5;
+        assert_eq!(matrix.row(1), &[0, 0, 5]);
+        assert_eq!(matrix.get(1, 2), Some(&5));
+        assert_eq!(matrix.get(2, 0), None);
+        assert_eq!(matrix.get(0, 3), None);
+
+        let from_rows = Matrix::from_rows(vec![vec![0, 0, 0], vec![0, 0, 5]]);
+        assert_eq!(from_rows, Some(matrix));
+        assert_eq!(Matrix::from_rows(vec![vec![1], vec![1, 2]]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn column_out_of_bounds() {
+        let matrix: Matrix<u8> = Matrix::new(2, 2);
+        let _ = matrix[(0, 2)];
+    }
+}
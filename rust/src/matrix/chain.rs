@@ -0,0 +1,168 @@
+//! Matrix-chain multiplication.
+//!
+//! Given a chain of matrices `A1 A2 ... An`, where `Ai` has `p[i - 1]` rows
+//! and `p[i]` columns, the problem is to parenthesize the product so that
+//! computing it takes the fewest scalar multiplications. Multiplying a
+//! `p × q` matrix by a `q × r` matrix takes `pqr` of them. See CLRS
+//! section 15.2.
+
+use std::fmt;
+
+/// Parenthesization is a full parenthesization of a matrix chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Parenthesization {
+    /// A single matrix, by its index in the chain counting from 0.
+    Matrix(usize),
+
+    /// The product of two parenthesized subchains.
+    Product(Box<Parenthesization>, Box<Parenthesization>),
+}
+
+impl Parenthesization {
+    /// Returns the number of scalar multiplications computing the product
+    /// takes with the given dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a matrix of the parenthesization has no dimensions.
+    pub fn cost(&self, dimensions: &[usize]) -> usize {
+        self.cost_and_range(dimensions).0
+    }
+
+    /// Returns the cost of the product and the indices of its first and
+    /// last matrices.
+    fn cost_and_range(&self, dimensions: &[usize]) -> (usize, usize, usize) {
+        match self {
+            Self::Matrix(index) => {
+                assert!(index + 1 < dimensions.len(), "matrix has no dimensions");
+                (0, *index, *index)
+            }
+            Self::Product(lhs, rhs) => {
+                let (lhs_cost, first, split) = lhs.cost_and_range(dimensions);
+                let (rhs_cost, _, last) = rhs.cost_and_range(dimensions);
+                let product = dimensions[first] * dimensions[split + 1] * dimensions[last + 1];
+                (lhs_cost + rhs_cost + product, first, last)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Parenthesization {
+    /// Formats the parenthesization as in CLRS, with the matrices counted
+    /// from 1, for example `((A1(A2A3))((A4A5)A6))`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Matrix(index) => write!(f, "A{}", index + 1),
+            Self::Product(lhs, rhs) => write!(f, "({lhs}{rhs})"),
+        }
+    }
+}
+
+/// ChainOrder is an optimal way to multiply a matrix chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChainOrder {
+    /// The minimum number of scalar multiplications.
+    pub cost: usize,
+
+    /// A parenthesization achieving the minimum.
+    pub parenthesization: Parenthesization,
+}
+
+/// Returns an optimal order to multiply the chain of matrices with the
+/// given dimensions.
+///
+/// The matrix `i` has `dimensions[i]` rows and `dimensions[i + 1]` columns,
+/// counting from 0. It fills the table of the costs of the subchains by
+/// increasing length, which takes O(n³) time and O(n²) memory. It returns
+/// `None` if the chain is empty, that is if there are fewer than two
+/// dimensions.
+pub fn optimal_order(dimensions: &[usize]) -> Option<ChainOrder> {
+    let n = dimensions.len().checked_sub(1).filter(|&n| n > 0)?;
+    // `costs[i][j]` is the minimum cost of the subchain from `i` to `j`,
+    // and `splits[i][j]` the index after which it is split.
+    let mut costs = vec![vec![0; n]; n];
+    let mut splits = vec![vec![0; n]; n];
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length - 1;
+            let (cost, split) = (i..j)
+                .map(|k| {
+                    let product = dimensions[i] * dimensions[k + 1] * dimensions[j + 1];
+                    (costs[i][k] + costs[k + 1][j] + product, k)
+                })
+                .min()
+                .expect("the subchain has two matrices");
+            costs[i][j] = cost;
+            splits[i][j] = split;
+        }
+    }
+    Some(ChainOrder {
+        cost: costs[0][n - 1],
+        parenthesization: parenthesize(&splits, 0, n - 1),
+    })
+}
+
+/// Builds the parenthesization of the subchain from `i` to `j` from the
+/// table of splits.
+fn parenthesize(splits: &[Vec<usize>], i: usize, j: usize) -> Parenthesization {
+    if i == j {
+        return Parenthesization::Matrix(i);
+    }
+    let split = splits[i][j];
+    Parenthesization::Product(
+        Box::new(parenthesize(splits, i, split)),
+        Box::new(parenthesize(splits, split + 1, j)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns the minimum cost of the subchain from `i` to `j` by trying
+    /// every split, in exponential time.
+    fn brute_force(dimensions: &[usize], i: usize, j: usize) -> usize {
+        (i..j)
+            .map(|k| {
+                brute_force(dimensions, i, k)
+                    + brute_force(dimensions, k + 1, j)
+                    + dimensions[i] * dimensions[k + 1] * dimensions[j + 1]
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn clrs_chain() {
+        // CLRS figure 15.5.
+        let dimensions = [30, 35, 15, 5, 10, 20, 25];
+        let order = optimal_order(&dimensions).expect("the chain is not empty");
+        assert_eq!(order.cost, 15_125);
+        assert_eq!(order.parenthesization.to_string(), "((A1(A2A3))((A4A5)A6))");
+        assert_eq!(order.parenthesization.cost(&dimensions), 15_125);
+
+        let single = optimal_order(&[10, 20]).expect("the chain is not empty");
+        assert_eq!(single.cost, 0);
+        assert_eq!(single.parenthesization, Parenthesization::Matrix(0));
+        assert_eq!(optimal_order(&[10]), None);
+        assert_eq!(optimal_order(&[]), None);
+    }
+
+    #[quickcheck]
+    fn optimal_among_all_orders(dimensions: Vec<u8>) -> bool {
+        let dimensions: Vec<usize> = dimensions
+            .into_iter()
+            .take(8)
+            .map(|dimension| usize::from(dimension % 16) + 1)
+            .collect();
+        match optimal_order(&dimensions) {
+            Some(order) => {
+                order.cost == brute_force(&dimensions, 0, dimensions.len() - 2)
+                    && order.parenthesization.cost(&dimensions) == order.cost
+            }
+            None => dimensions.len() < 2,
+        }
+    }
+}
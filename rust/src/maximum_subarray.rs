@@ -140,7 +140,6 @@ impl Container<i64> {
     }
 
     /// Iteratively find the maximum sub-array.
-
     pub fn iteratively_find_max_subarray(
         &self,
         low: usize,
@@ -0,0 +1,396 @@
+//! Number types.
+//!
+//! This module implements arbitrary precision arithmetic.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+use crate::{error::ErrorKind, Error};
+
+/// Largest power of ten which fits into a limb.
+const DECIMAL_BASE: u64 = 10_000_000_000_000_000_000;
+
+/// Number of decimal digits of [`DECIMAL_BASE`].
+const DECIMAL_DIGITS: usize = 19;
+
+/// BigUint is an arbitrary precision unsigned integer.
+///
+/// The number is stored as a vector of 64-bit limbs, from the least
+/// significant to the most significant one. The most significant limb
+/// is never zero, so zero is represented by an empty vector and every
+/// number has a unique representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    /// Creates new number equal to zero.
+    pub const fn zero() -> Self {
+        Self { limbs: Vec::new() }
+    }
+
+    /// Returns true if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns the number of bits needed to represent the number.
+    pub fn bits(&self) -> usize {
+        self.limbs.last().map_or(0, |last| {
+            self.limbs.len() * 64 - last.leading_zeros() as usize
+        })
+    }
+
+    /// Returns the difference of the two numbers, or `None` if `rhs` is
+    /// greater than `self`.
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        if *self < *rhs {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = false;
+        for (index, limb) in self.limbs.iter().enumerate() {
+            let (difference, first) = limb.overflowing_sub(*rhs.limbs.get(index).unwrap_or(&0));
+            let (difference, second) = difference.overflowing_sub(u64::from(borrow));
+            limbs.push(difference);
+            borrow = first || second;
+        }
+        Some(Self::from_limbs(limbs))
+    }
+
+    /// Returns the quotient and the remainder of the division of the two
+    /// numbers.
+    ///
+    /// It fails with [`ErrorKind::DivisionByZero`] if `rhs` is zero.
+    pub fn div_rem(&self, rhs: &Self) -> Result<(Self, Self), Error> {
+        match rhs.limbs.as_slice() {
+            [] => Err(Error::new(ErrorKind::DivisionByZero)),
+            [divisor] => {
+                let (quotient, remainder) = self.div_rem_limb(*divisor);
+                Ok((quotient, Self::from(remainder)))
+            }
+            _ => Ok(self.long_division(rhs)),
+        }
+    }
+
+    /// Divides the number by a single limb.
+    fn div_rem_limb(&self, divisor: u64) -> (Self, u64) {
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder = 0_u128;
+        for (index, limb) in self.limbs.iter().enumerate().rev() {
+            let dividend = remainder << 64 | u128::from(*limb);
+            quotient[index] = (dividend / u128::from(divisor)) as u64;
+            remainder = dividend % u128::from(divisor);
+        }
+        (Self::from_limbs(quotient), remainder as u64)
+    }
+
+    /// Divides the number bit by bit, from the most significant bit.
+    ///
+    /// The remainder is shifted left by one bit and the next bit of the
+    /// dividend is appended to it. Whenever the remainder reaches the
+    /// divisor, the divisor is subtracted and the quotient bit is set.
+    fn long_division(&self, divisor: &Self) -> (Self, Self) {
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder = Self::zero();
+        for position in (0..self.bits()).rev() {
+            remainder = remainder.shl_one(self.bit(position));
+            if let Some(difference) = remainder.checked_sub(divisor) {
+                remainder = difference;
+                quotient[position / 64] |= 1 << (position % 64);
+            }
+        }
+        (Self::from_limbs(quotient), remainder)
+    }
+
+    /// Returns the bit at the given position.
+    fn bit(&self, position: usize) -> bool {
+        self.limbs[position / 64] >> (position % 64) & 1 == 1
+    }
+
+    /// Shifts the number left by one bit and sets the lowest bit.
+    fn shl_one(mut self, bit: bool) -> Self {
+        let mut carry = u64::from(bit);
+        for limb in &mut self.limbs {
+            let next = *limb >> 63;
+            *limb = *limb << 1 | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+        self
+    }
+
+    /// Creates new number from its limbs, removing the leading zeros.
+    fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        Self::from_limbs(vec![value as u64, (value >> 64) as u64])
+    }
+}
+
+impl TryFrom<&BigUint> for u64 {
+    type Error = Error;
+
+    /// Fails with [`ErrorKind::Overflow`] if the number needs more than 64 bits.
+    fn try_from(value: &BigUint) -> Result<Self, Self::Error> {
+        match value.limbs.as_slice() {
+            [] => Ok(0),
+            [limb] => Ok(*limb),
+            _ => Err(Error::new(ErrorKind::Overflow)),
+        }
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Without leading zeros, a longer number is a larger number.
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let len = self.limbs.len().max(rhs.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = false;
+        for index in 0..len {
+            let lhs = *self.limbs.get(index).unwrap_or(&0);
+            let (sum, first) = lhs.overflowing_add(*rhs.limbs.get(index).unwrap_or(&0));
+            let (sum, second) = sum.overflowing_add(u64::from(carry));
+            limbs.push(sum);
+            carry = first || second;
+        }
+        limbs.push(u64::from(carry));
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Add for BigUint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    /// Panics if `rhs` is greater than `self`, like the subtraction of
+    /// primitive unsigned integers does; see [`BigUint::checked_sub`].
+    fn sub(self, rhs: Self) -> Self::Output {
+        match self.checked_sub(rhs) {
+            Some(difference) => difference,
+            None => panic!("attempt to subtract with overflow"),
+        }
+    }
+}
+
+impl Sub for BigUint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    /// Schoolbook multiplication: each limb of `self` is multiplied by
+    /// each limb of `rhs` and added at the sum of their positions.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut limbs = vec![0; self.limbs.len() + rhs.limbs.len()];
+        for (i, lhs) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, rhs) in rhs.limbs.iter().enumerate() {
+                // The product of two limbs plus two limbs fits into a u128.
+                let product =
+                    u128::from(*lhs) * u128::from(*rhs) + u128::from(limbs[i + j]) + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+            limbs[i + rhs.limbs.len()] = carry as u64;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Mul for BigUint {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = Error;
+
+    /// Parses a number in decimal notation.
+    ///
+    /// It fails with [`ErrorKind::InvalidNumber`] if the string is empty or
+    /// contains anything but decimal digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(Error::new(ErrorKind::InvalidNumber));
+        }
+
+        // Consume the digits by chunks which fit into a single limb.
+        let mut number = Self::zero();
+        let first = s.len() % DECIMAL_DIGITS;
+        let chunks = std::iter::once(&s[..first]).chain(
+            s.as_bytes()[first..]
+                .chunks(DECIMAL_DIGITS)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default()),
+        );
+        for chunk in chunks.filter(|chunk| !chunk.is_empty()) {
+            let value: u64 = chunk
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::InvalidNumber))?;
+            let scale = Self::from(10_u64.pow(chunk.len() as u32));
+            number = &(&number * &scale) + &Self::from(value);
+        }
+        Ok(number)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+
+        // Collect the digits by chunks, from the least significant one.
+        let mut chunks = Vec::new();
+        let mut number = self.clone();
+        while !number.is_zero() {
+            let (quotient, remainder) = number.div_rem_limb(DECIMAL_BASE);
+            chunks.push(remainder);
+            number = quotient;
+        }
+
+        let mut chunks = chunks.iter().rev();
+        let mut digits = chunks.next().map(u64::to_string).unwrap_or_default();
+        for chunk in chunks {
+            digits.push_str(&format!("{chunk:0width$}", width = DECIMAL_DIGITS));
+        }
+        f.pad(&digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().expect("valid number")
+    }
+
+    #[test]
+    fn parse_and_print() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(big("0"), BigUint::zero());
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(
+            big("340282366920938463463374607431768211456").to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(
+            big("340282366920938463463374607431768211455"),
+            BigUint::from(u128::MAX)
+        );
+        for invalid in ["", "12a", "-1", " 1"] {
+            assert_eq!(
+                invalid.parse::<BigUint>(),
+                Err(Error::new(ErrorKind::InvalidNumber))
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic_on_large_numbers() {
+        let factorial = (1..=30_u64).fold(BigUint::from(1_u64), |product, n| {
+            product * BigUint::from(n)
+        });
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+
+        let (quotient, remainder) = factorial
+            .div_rem(&big("10000000000000000000000"))
+            .expect("divisor is not zero");
+        assert_eq!(quotient, big("26525285981"));
+        assert_eq!(remainder, big("2191058636308480000000"));
+
+        let power = big("18446744073709551616");
+        assert_eq!(&power - &BigUint::from(1_u64), BigUint::from(u64::MAX));
+        assert_eq!(power.bits(), 65);
+        assert_eq!(BigUint::from(1_u64).checked_sub(&power), None);
+        assert_eq!(
+            power.div_rem(&BigUint::zero()),
+            Err(Error::new(ErrorKind::DivisionByZero))
+        );
+    }
+
+    #[quickcheck]
+    fn agrees_with_u128(lhs: u64, rhs: u64, other: u64) -> bool {
+        let (a, b, c) = (u128::from(lhs), u128::from(rhs), u128::from(other));
+        let big_a = BigUint::from(a);
+        let big_b = BigUint::from(b);
+        let product = BigUint::from(a * b);
+        let dividend = BigUint::from(a * b + c);
+
+        let quotient = (a * b + c).checked_div(a).map(BigUint::from);
+        let remainder = (a * b + c).checked_rem(a).map(BigUint::from);
+
+        &big_a * &big_b == product
+            && &big_a + &big_b == BigUint::from(a + b)
+            && big_a.checked_sub(&big_b) == a.checked_sub(b).map(BigUint::from)
+            && big_a.cmp(&big_b) == a.cmp(&b)
+            && dividend.div_rem(&big_a).ok() == quotient.zip(remainder)
+            && dividend.to_string() == (a * b + c).to_string()
+            && big(&dividend.to_string()) == dividend
+    }
+
+    #[quickcheck]
+    fn division_identity(lhs: Vec<u64>, rhs: Vec<u64>) -> bool {
+        let dividend = BigUint::from_limbs(lhs);
+        let divisor = BigUint::from_limbs(rhs);
+        match dividend.div_rem(&divisor) {
+            Ok((quotient, remainder)) => {
+                remainder < divisor && &(&quotient * &divisor) + &remainder == dividend
+            }
+            Err(_) => divisor.is_zero(),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Perfect hashing.
+//!
+//! When the set of keys is known in advance, a two-level hashing scheme
+//! gives O(1) worst-case lookups: a first universal hash function
+//! distributes the `n` keys into `n` buckets, and each bucket holding
+//! `n_j` keys is a secondary table of `n_j^2` slots whose own universal
+//! hash function is chosen without collision. See CLRS section 11.5.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{error::ErrorKind, Error};
+
+/// The prime `2^61 - 1` of the universal hash family.
+///
+/// Keys must be smaller than this prime.
+pub const PRIME: u64 = (1 << 61) - 1;
+
+/// A function of the universal hash family `((a k + b) mod p) mod m`.
+#[derive(Debug, Clone, Copy)]
+struct UniversalHash {
+    a: u64,
+    b: u64,
+    slots: usize,
+}
+
+impl UniversalHash {
+    /// Draws a random function of the family hashing into `slots` slots.
+    fn random(rng: &mut StdRng, slots: usize) -> Self {
+        Self {
+            a: rng.gen_range(1..PRIME),
+            b: rng.gen_range(0..PRIME),
+            slots,
+        }
+    }
+
+    /// Returns the slot of the key.
+    fn hash(&self, key: u64) -> usize {
+        let product = u128::from(self.a) * u128::from(key) + u128::from(self.b);
+        (product % u128::from(PRIME) % self.slots as u128) as usize
+    }
+}
+
+/// Secondary table of a [`PerfectHashTable`].
+#[derive(Debug, Clone)]
+struct Bucket<V> {
+    hash: UniversalHash,
+    slots: Vec<Option<(u64, V)>>,
+}
+
+/// PerfectHashTable is a static table with O(1) worst-case lookups.
+///
+/// It is created by a [`PerfectHashBuilder`] from a fixed set of entries.
+#[derive(Debug, Clone)]
+pub struct PerfectHashTable<V> {
+    hash: UniversalHash,
+    buckets: Vec<Bucket<V>>,
+    len: usize,
+}
+
+impl<V> PerfectHashTable<V> {
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the table is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the total number of slots of the secondary tables.
+    ///
+    /// The builder guarantees it is less than `4n`.
+    pub fn slots(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.slots.len()).sum()
+    }
+
+    /// Returns a reference to the value associated with the key.
+    ///
+    /// It computes two hashes and compares a single key.
+    pub fn get(&self, key: u64) -> Option<&V> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let bucket = &self.buckets[self.hash.hash(key)];
+        if bucket.slots.is_empty() {
+            return None;
+        }
+        match &bucket.slots[bucket.hash.hash(key)] {
+            Some((found, value)) if *found == key => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the table contains the key.
+    pub fn contains(&self, key: u64) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+/// PerfectHashBuilder builds [`PerfectHashTable`]s.
+///
+/// Hash functions are drawn at random until the primary table spreads the
+/// keys well enough and every secondary table is free of collisions. Each
+/// attempt succeeds with probability at least 1/2, so the expected number
+/// of attempts is at most two per table.
+#[derive(Debug, Clone)]
+pub struct PerfectHashBuilder {
+    rng: StdRng,
+}
+
+impl PerfectHashBuilder {
+    /// Creates new builder seeded from the operating system.
+    pub fn new() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Creates new builder with a fixed seed, so that the hash functions
+    /// drawn are reproducible.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Builds a table holding the given entries.
+    ///
+    /// It fails with [`ErrorKind::InvalidKey`] if a key is not smaller than
+    /// [`PRIME`], and with [`ErrorKind::DuplicateKey`] if a key is given
+    /// twice.
+    pub fn build<V>(&mut self, entries: Vec<(u64, V)>) -> Result<PerfectHashTable<V>, Error> {
+        if entries.iter().any(|(key, _)| *key >= PRIME) {
+            return Err(Error::new(ErrorKind::InvalidKey));
+        }
+        let mut keys: Vec<_> = entries.iter().map(|(key, _)| *key).collect();
+        keys.sort_unstable();
+        if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(Error::new(ErrorKind::DuplicateKey));
+        }
+
+        let len = entries.len();
+        if len == 0 {
+            return Ok(PerfectHashTable {
+                hash: UniversalHash::random(&mut self.rng, 1),
+                buckets: Vec::new(),
+                len,
+            });
+        }
+
+        // Retry until the sum of the squared bucket sizes is linear.
+        let (hash, groups) = loop {
+            let hash = UniversalHash::random(&mut self.rng, len);
+            let mut sizes = vec![0; len];
+            for key in &keys {
+                sizes[hash.hash(*key)] += 1;
+            }
+            if sizes.iter().map(|size| size * size).sum::<usize>() < 4 * len {
+                break (hash, sizes);
+            }
+        };
+
+        let mut grouped: Vec<Vec<(u64, V)>> = groups
+            .iter()
+            .map(|size| Vec::with_capacity(*size))
+            .collect();
+        for (key, value) in entries {
+            grouped[hash.hash(key)].push((key, value));
+        }
+        let buckets = grouped
+            .into_iter()
+            .map(|entries| self.bucket(entries))
+            .collect();
+        Ok(PerfectHashTable { hash, buckets, len })
+    }
+
+    /// Builds a secondary table without collision.
+    fn bucket<V>(&mut self, entries: Vec<(u64, V)>) -> Bucket<V> {
+        let slots = entries.len() * entries.len();
+        if slots == 0 {
+            return Bucket {
+                hash: UniversalHash::random(&mut self.rng, 1),
+                slots: Vec::new(),
+            };
+        }
+        let hash = loop {
+            let hash = UniversalHash::random(&mut self.rng, slots);
+            let mut used = vec![false; slots];
+            if entries
+                .iter()
+                .all(|(key, _)| !std::mem::replace(&mut used[hash.hash(*key)], true))
+            {
+                break hash;
+            }
+        };
+
+        let mut table: Vec<_> = std::iter::repeat_with(|| None).take(slots).collect();
+        for (key, value) in entries {
+            table[hash.hash(key)] = Some((key, value));
+        }
+        Bucket { hash, slots: table }
+    }
+}
+
+impl Default for PerfectHashBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn build_perfect_hash_table() {
+        let keys = [10, 22, 37, 40, 52, 60, 70, 72, 75];
+        let table = PerfectHashBuilder::with_seed(42)
+            .build(keys.iter().map(|&key| (key, key * 2)).collect())
+            .expect("keys are distinct");
+        assert_eq!(table.len(), keys.len());
+        assert!(table.slots() < 4 * keys.len());
+        for key in keys {
+            assert_eq!(table.get(key), Some(&(key * 2)));
+        }
+        assert_eq!(table.get(11), None);
+        assert!(!table.contains(PRIME));
+    }
+
+    #[test]
+    fn build_empty_table() {
+        let table = PerfectHashBuilder::new()
+            .build::<()>(vec![])
+            .expect("an empty key set is valid");
+        assert!(table.is_empty());
+        assert!(!table.contains(0));
+    }
+
+    #[test]
+    fn reject_invalid_keys() {
+        let mut builder = PerfectHashBuilder::with_seed(0);
+        assert_eq!(
+            builder
+                .build(vec![(1, ()), (1, ())])
+                .map(|table| table.len()),
+            Err(Error::new(ErrorKind::DuplicateKey))
+        );
+        assert_eq!(
+            builder.build(vec![(PRIME, ())]).map(|table| table.len()),
+            Err(Error::new(ErrorKind::InvalidKey))
+        );
+    }
+
+    #[quickcheck]
+    fn finds_exactly_the_keys(keys: Vec<u64>, others: Vec<u64>, seed: u64) -> bool {
+        let mut keys: Vec<_> = keys.into_iter().map(|key| key % PRIME).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let Ok(table) =
+            PerfectHashBuilder::with_seed(seed).build(keys.iter().map(|&key| (key, key)).collect())
+        else {
+            return false;
+        };
+        table.slots() < 4 * keys.len().max(1)
+            && keys.iter().all(|key| table.get(*key) == Some(key))
+            && others
+                .iter()
+                .all(|key| table.contains(*key) == keys.binary_search(key).is_ok())
+    }
+}
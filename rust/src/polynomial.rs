@@ -0,0 +1,212 @@
+//! Polynomials in one variable.
+//!
+//! A polynomial of degree `n` is represented by its coefficients
+//! `a[0], a[1], ..., a[n]`, that is `a[0] + a[1] x + ... + a[n] x^n`.
+//! See CLRS chapter 30.
+
+use std::ops::{Add, Mul};
+
+/// The `Polynomial` type is a polynomial in coefficient representation.
+///
+/// Coefficients can be of any numeric type, `Default::default()` being
+/// used as zero. The coefficients are stored from the lowest to the highest
+/// degree and the leading coefficient is never zero, so the zero
+/// polynomial has no coefficient.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Polynomial<T> {
+    coefficients: Vec<T>,
+}
+
+impl<T> Polynomial<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    /// Creates new polynomial from its coefficients, from the lowest to the
+    /// highest degree.
+    pub fn new(mut coefficients: Vec<T>) -> Self {
+        while coefficients.last() == Some(&T::default()) {
+            coefficients.pop();
+        }
+        Self { coefficients }
+    }
+
+    /// Returns the coefficients, from the lowest to the highest degree.
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    /// Returns the degree of the polynomial, or `None` for the zero
+    /// polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Evaluates the polynomial at `x` with Horner's rule.
+    ///
+    /// The polynomial is rewritten as `a[0] + x (a[1] + x (a[2] + ...))`,
+    /// which takes `n` additions and `n` multiplications.
+    pub fn eval(&self, x: T) -> T {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(T::default(), |value, &coefficient| value * x + coefficient)
+    }
+
+    /// Returns the derivative of the polynomial.
+    pub fn derivative(&self) -> Self
+    where
+        T: From<u8>,
+    {
+        let mut factor = T::default();
+        let coefficients = self
+            .coefficients
+            .iter()
+            .skip(1)
+            .map(|&coefficient| {
+                factor = factor + T::from(1);
+                factor * coefficient
+            })
+            .collect();
+        Self::new(coefficients)
+    }
+}
+
+impl<T> Add for &Polynomial<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let len = self.coefficients.len().max(rhs.coefficients.len());
+        let coefficients = (0..len)
+            .map(|degree| {
+                let lhs = self.coefficients.get(degree).copied().unwrap_or_default();
+                lhs + rhs.coefficients.get(degree).copied().unwrap_or_default()
+            })
+            .collect();
+        Polynomial::new(coefficients)
+    }
+}
+
+impl<T> Add for Polynomial<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<T> Mul for &Polynomial<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    /// Multiplies each coefficient of `self` by each coefficient of `rhs`,
+    /// which takes O(nm) time.
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Polynomial::new(vec![]);
+        }
+        let mut coefficients =
+            vec![T::default(); self.coefficients.len() + rhs.coefficients.len() - 1];
+        for (i, &lhs) in self.coefficients.iter().enumerate() {
+            for (j, &rhs) in rhs.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j] + lhs * rhs;
+            }
+        }
+        Polynomial::new(coefficients)
+    }
+}
+
+impl<T> Mul for Polynomial<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Evaluates the polynomial term by term.
+    fn naive_eval(coefficients: &[i64], x: i64) -> i64 {
+        coefficients
+            .iter()
+            .enumerate()
+            .map(|(degree, coefficient)| coefficient * x.pow(degree as u32))
+            .sum()
+    }
+
+    /// Keeps the coefficients and the point small enough not to overflow.
+    fn small(coefficients: &[i8]) -> Vec<i64> {
+        coefficients
+            .iter()
+            .take(6)
+            .map(|&coefficient| i64::from(coefficient))
+            .collect()
+    }
+
+    #[test]
+    fn create_polynomial() {
+        let p = Polynomial::new(vec![1, 2, 0, 0]);
+        assert_eq!(p.coefficients(), &[1, 2]);
+        assert_eq!(p.degree(), Some(1));
+        assert_eq!(Polynomial::new(vec![0, 0]).degree(), None);
+    }
+
+    #[test]
+    fn evaluate_polynomial() {
+        // 3 - 2x + x^3
+        let p = Polynomial::new(vec![3, -2, 0, 1]);
+        assert_eq!(p.eval(0), 3);
+        assert_eq!(p.eval(2), 7);
+        assert_eq!(p.eval(-3), -18);
+        assert_eq!(Polynomial::new(vec![]).eval(5), 0);
+
+        let p = Polynomial::new(vec![0.5, 0.0, 2.0]);
+        assert_eq!(p.eval(1.5), 5.0);
+    }
+
+    #[test]
+    fn polynomial_arithmetic() {
+        let p = Polynomial::new(vec![1, 1]);
+        let q = Polynomial::new(vec![-1, 1]);
+        assert_eq!(&p * &q, Polynomial::new(vec![-1, 0, 1]));
+        assert_eq!(p.clone() + q, Polynomial::new(vec![0, 2]));
+        assert_eq!(&p + &Polynomial::new(vec![0, -1]), Polynomial::new(vec![1]));
+        assert_eq!(p * Polynomial::new(vec![]), Polynomial::new(vec![]));
+    }
+
+    #[test]
+    fn differentiate_polynomial() {
+        let p = Polynomial::new(vec![3, -2, 0, 1]);
+        assert_eq!(p.derivative(), Polynomial::new(vec![-2, 0, 3]));
+        assert_eq!(
+            p.derivative().derivative().derivative(),
+            Polynomial::new(vec![6])
+        );
+        assert_eq!(Polynomial::new(vec![7]).derivative().degree(), None);
+    }
+
+    #[quickcheck]
+    fn operations_agree_with_evaluation(lhs: Vec<i8>, rhs: Vec<i8>, x: i8) -> bool {
+        let (lhs, rhs, x) = (small(&lhs), small(&rhs), i64::from(x % 10));
+        let p = Polynomial::new(lhs.clone());
+        let q = Polynomial::new(rhs.clone());
+        p.eval(x) == naive_eval(&lhs, x)
+            && (&p + &q).eval(x) == p.eval(x) + q.eval(x)
+            && (&p * &q).eval(x) == p.eval(x) * q.eval(x)
+    }
+}
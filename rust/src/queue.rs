@@ -1,5 +1,7 @@
 //! Queue data structures.
 
+use std::collections::VecDeque;
+
 use crate::{error::ErrorKind, Error};
 
 /// BoundedQueue is a circular queue implemented using a vector.
@@ -23,7 +25,7 @@ pub struct BoundedQueue<T> {
 
 impl<T> BoundedQueue<T>
 where
-    T: PartialEq + Clone,
+    T: Clone,
 {
     /// Create new queue with the given maximum capacity.
     pub fn with_capacity(capacity: usize) -> Self {
@@ -78,8 +80,121 @@ where
     }
 }
 
+/// Monotonicity is the order of the elements kept by a [`MonotonicQueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monotonicity {
+    /// The elements increase from the front, which is the minimum.
+    Increasing,
+
+    /// The elements decrease from the front, which is the maximum.
+    Decreasing,
+}
+
+/// MonotonicQueue is a FIFO queue which returns its minimum or maximum in
+/// O(1) time.
+///
+/// Only the elements which can still become the front are stored: pushing
+/// an element discards the elements before it which it dominates, since
+/// they leave the queue first. Each element is stored and discarded at
+/// most once, so all the operations take amortized O(1) time.
+#[derive(Debug, Clone)]
+pub struct MonotonicQueue<T> {
+    /// The stored elements with their sequence number, in monotonic order.
+    items: VecDeque<(usize, T)>,
+
+    /// The order of the stored elements.
+    order: Monotonicity,
+
+    /// The number of elements pushed.
+    pushed: usize,
+
+    /// The number of elements popped.
+    popped: usize,
+}
+
+impl<T: Ord> MonotonicQueue<T> {
+    /// Creates new empty queue keeping its elements in the given order.
+    pub fn new(order: Monotonicity) -> Self {
+        Self {
+            items: VecDeque::new(),
+            order,
+            pushed: 0,
+            popped: 0,
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    pub const fn len(&self) -> usize {
+        self.pushed - self.popped
+    }
+
+    /// Returns true if the queue is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts an element at the back of the queue.
+    pub fn push(&mut self, item: T) {
+        while let Some((_, last)) = self.items.back() {
+            let dominated = match self.order {
+                Monotonicity::Increasing => *last > item,
+                Monotonicity::Decreasing => *last < item,
+            };
+            if !dominated {
+                break;
+            }
+            self.items.pop_back();
+        }
+        self.items.push_back((self.pushed, item));
+        self.pushed += 1;
+    }
+
+    /// Removes the element at the front of the queue, that is the oldest
+    /// element.
+    pub fn pop(&mut self) -> Result<(), Error> {
+        if self.is_empty() {
+            return Err(Error::new(ErrorKind::QueueUnderflow));
+        }
+        if matches!(self.items.front(), Some((sequence, _)) if *sequence == self.popped) {
+            self.items.pop_front();
+        }
+        self.popped += 1;
+        Ok(())
+    }
+
+    /// Returns the minimum of an increasing queue, or the maximum of a
+    /// decreasing queue.
+    pub fn front(&self) -> Option<&T> {
+        self.items.front().map(|(_, item)| item)
+    }
+}
+
+/// Returns the maximum of each window of `width` consecutive elements.
+///
+/// It takes O(n) time with a decreasing [`MonotonicQueue`]. There is no
+/// window if the width is zero or larger than the number of elements.
+pub fn sliding_window_max<T: Ord + Clone>(data: &[T], width: usize) -> Vec<T> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut queue = MonotonicQueue::new(Monotonicity::Decreasing);
+    let mut maximums = Vec::with_capacity((data.len() + 1).saturating_sub(width));
+    for (index, item) in data.iter().enumerate() {
+        queue.push(item);
+        if index >= width {
+            queue.pop().expect("the window is not empty");
+        }
+        if index + 1 >= width {
+            maximums.extend(queue.front().copied().cloned());
+        }
+    }
+    maximums
+}
+
 #[cfg(test)]
 mod tests {
+    use quickcheck_macros::quickcheck;
+
     use super::*;
 
     #[test]
@@ -105,4 +220,46 @@ mod tests {
         assert_eq!(queue.dequeue(), Ok(4));
         assert_eq!(queue.dequeue(), Ok(5));
     }
+
+    #[test]
+    fn monotonic_queue() {
+        let mut queue = MonotonicQueue::new(Monotonicity::Increasing);
+        assert_eq!(queue.front(), None);
+        assert!(queue.pop().is_err());
+        for item in [4, 2, 5, 2, 7] {
+            queue.push(item);
+        }
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.front(), Some(&2));
+        assert_eq!(queue.pop(), Ok(()));
+        assert_eq!(queue.front(), Some(&2));
+        assert_eq!(queue.pop(), Ok(()));
+        assert_eq!(queue.front(), Some(&2));
+        assert_eq!(queue.pop(), Ok(()));
+        assert_eq!(queue.front(), Some(&2));
+        assert_eq!(queue.pop(), Ok(()));
+        assert_eq!(queue.front(), Some(&7));
+        assert_eq!(queue.pop(), Ok(()));
+        assert!(queue.is_empty());
+        assert_eq!(queue.front(), None);
+    }
+
+    #[test]
+    fn sliding_window_maximum() {
+        let data = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(sliding_window_max(&data, 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(sliding_window_max(&data, 1), data.to_vec());
+        assert!(sliding_window_max(&data, 0).is_empty());
+        assert!(sliding_window_max(&data, 9).is_empty());
+    }
+
+    #[quickcheck]
+    fn sliding_window_agrees_with_naive(data: Vec<i8>, width: u8) -> bool {
+        let width = usize::from(width % 8) + 1;
+        let naive: Vec<_> = data
+            .windows(width)
+            .filter_map(|window| window.iter().max().copied())
+            .collect();
+        sliding_window_max(&data, width) == naive
+    }
 }
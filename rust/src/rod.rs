@@ -0,0 +1,323 @@
+//! A solution to the rod cutting problem.
+//!
+//! Given a rod of length `n` and a table of prices `p[i]` for rods of
+//! length `i + 1`, the problem is to find the way to cut up the rod
+//! which maximizes the revenue. See CLRS section 15.1.
+
+use std::ops::Add;
+
+/// The `Rod` type holds the price table of the rod cutting problem.
+///
+/// Prices can be of any numeric type, `Default::default()` being used
+/// as zero, so both integer and floating point prices are supported.
+#[derive(Debug, Clone, Copy)]
+pub struct Rod<'a, P> {
+    /// `prices[i]` is the price of a piece of length `i + 1`.
+    prices: &'a [P],
+}
+
+/// The `CutPlan` type is an optimal way to cut a rod.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CutPlan<P> {
+    /// The maximum revenue.
+    pub revenue: P,
+
+    /// The lengths of the pieces to cut, which sum up to the rod length.
+    pub pieces: Vec<usize>,
+}
+
+impl<'a, P> Rod<'a, P>
+where
+    P: Copy + Default + PartialOrd + Add<Output = P>,
+{
+    /// Creates new rod with the given price table.
+    pub const fn new(prices: &'a [P]) -> Self {
+        Self { prices }
+    }
+
+    /// Returns the maximum revenue for a rod of the given size.
+    ///
+    /// It returns `None` if the rod cannot be cut, which only happens
+    /// when the price table is empty.
+    pub fn maximum_with_bottom_up(&self, size: usize) -> Option<P> {
+        let (revenues, _) = self.extended_bottom_up(size);
+        revenues[size]
+    }
+
+    /// Returns the lengths of the pieces of an optimal cut.
+    pub fn list_size(&self, size: usize) -> Option<Vec<usize>> {
+        let (revenues, first_pieces) = self.extended_bottom_up(size);
+        revenues[size].map(|_| Self::pieces(&first_pieces, size))
+    }
+
+    /// Returns the maximum revenue together with the pieces achieving it.
+    ///
+    /// Unlike calling [`Rod::maximum_with_bottom_up`] and
+    /// [`Rod::list_size`], the tables are only computed once.
+    pub fn cut_plan(&self, size: usize) -> Option<CutPlan<P>> {
+        let (revenues, first_pieces) = self.extended_bottom_up(size);
+        revenues[size].map(|revenue| CutPlan {
+            revenue,
+            pieces: Self::pieces(&first_pieces, size),
+        })
+    }
+
+    /// Returns an optimal way to cut the rod into at most `max_pieces`
+    /// pieces.
+    ///
+    /// It returns `None` if no cut uses few enough pieces.
+    pub fn maximum_with_piece_limit(&self, size: usize, max_pieces: usize) -> Option<CutPlan<P>> {
+        let (revenues, first_pieces) = self.bounded_bottom_up(size, max_pieces);
+        let mut best: Option<(usize, P)> = None;
+        for (count, revenue) in revenues.iter().enumerate() {
+            if let Some(revenue) = revenue[size] {
+                if best.map_or(true, |(_, best)| revenue > best) {
+                    best = Some((count, revenue));
+                }
+            }
+        }
+        best.map(|(count, revenue)| CutPlan {
+            revenue,
+            pieces: Self::bounded_pieces(&first_pieces, count, size),
+        })
+    }
+
+    /// Returns the trade-offs between the revenue and the number of cuts.
+    ///
+    /// Each plan of the frontier uses more pieces and earns strictly more
+    /// than the previous one, and no plan with at most as many pieces earns
+    /// more. The plans are sorted by increasing number of pieces.
+    pub fn pareto_frontier(&self, size: usize) -> Vec<CutPlan<P>> {
+        let (revenues, first_pieces) = self.bounded_bottom_up(size, size);
+        let mut frontier: Vec<CutPlan<P>> = Vec::new();
+        for (count, revenue) in revenues.iter().enumerate() {
+            let Some(revenue) = revenue[size] else {
+                continue;
+            };
+            if frontier.last().map_or(true, |plan| revenue > plan.revenue) {
+                frontier.push(CutPlan {
+                    revenue,
+                    pieces: Self::bounded_pieces(&first_pieces, count, size),
+                });
+            }
+        }
+        frontier
+    }
+
+    /// Solves every subproblem up to the given size and number of pieces.
+    ///
+    /// `revenues[k][n]` is the maximum revenue of cutting a rod of length
+    /// `n` into exactly `k` pieces and `first_pieces[k][n]` is the length
+    /// of the first piece of that cut.
+    fn bounded_bottom_up(
+        &self,
+        size: usize,
+        max_pieces: usize,
+    ) -> (Vec<Vec<Option<P>>>, Vec<Vec<usize>>) {
+        let max_pieces = max_pieces.min(size);
+        let mut revenues = vec![vec![None; size + 1]; max_pieces + 1];
+        let mut first_pieces = vec![vec![0; size + 1]; max_pieces + 1];
+        revenues[0][0] = Some(P::default());
+
+        for count in 1..=max_pieces {
+            for length in count..=size {
+                for piece in 1..=length.min(self.prices.len()) {
+                    let Some(rest) = revenues[count - 1][length - piece] else {
+                        continue;
+                    };
+                    let revenue = self.prices[piece - 1] + rest;
+                    if revenues[count][length].map_or(true, |best| revenue > best) {
+                        revenues[count][length] = Some(revenue);
+                        first_pieces[count][length] = piece;
+                    }
+                }
+            }
+        }
+        (revenues, first_pieces)
+    }
+
+    /// Lists the pieces of the cut of a length into `count` pieces.
+    fn bounded_pieces(first_pieces: &[Vec<usize>], count: usize, mut size: usize) -> Vec<usize> {
+        let mut pieces = Vec::with_capacity(count);
+        for count in (1..=count).rev() {
+            pieces.push(first_pieces[count][size]);
+            size -= first_pieces[count][size];
+        }
+        pieces
+    }
+
+    /// Solves every subproblem up to the given size.
+    ///
+    /// It returns the maximum revenue of each length, and the length of
+    /// the first piece to cut to reach it. A length without any possible
+    /// cut has no revenue; this is used instead of a zero revenue so that
+    /// negative prices are handled correctly.
+    fn extended_bottom_up(&self, size: usize) -> (Vec<Option<P>>, Vec<usize>) {
+        let mut revenues = vec![None; size + 1];
+        let mut first_pieces = vec![0; size + 1];
+        revenues[0] = Some(P::default());
+
+        for length in 1..=size {
+            for piece in 1..=length.min(self.prices.len()) {
+                let Some(rest) = revenues[length - piece] else {
+                    continue;
+                };
+                let revenue = self.prices[piece - 1] + rest;
+                if revenues[length].map_or(true, |best| revenue > best) {
+                    revenues[length] = Some(revenue);
+                    first_pieces[length] = piece;
+                }
+            }
+        }
+        (revenues, first_pieces)
+    }
+
+    /// Follows the first piece table to list the pieces of a length.
+    fn pieces(first_pieces: &[usize], mut size: usize) -> Vec<usize> {
+        let mut pieces = Vec::new();
+        while size > 0 {
+            pieces.push(first_pieces[size]);
+            size -= first_pieces[size];
+        }
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Price table from CLRS figure 15.1.
+    const PRICES: [u64; 10] = [1, 5, 8, 9, 10, 17, 17, 20, 24, 30];
+
+    #[test]
+    fn maximum_revenue() {
+        let rod = Rod::new(&PRICES);
+        let revenues: Vec<_> = (0..=10)
+            .filter_map(|size| rod.maximum_with_bottom_up(size))
+            .collect();
+        assert_eq!(revenues, vec![0, 1, 5, 8, 10, 13, 17, 18, 22, 25, 30]);
+    }
+
+    #[test]
+    fn list_pieces() {
+        let rod = Rod::new(&PRICES);
+        assert_eq!(rod.list_size(0), Some(vec![]));
+        assert_eq!(rod.list_size(4), Some(vec![2, 2]));
+        assert_eq!(rod.list_size(7), Some(vec![1, 6]));
+        assert_eq!(rod.list_size(10), Some(vec![10]));
+    }
+
+    #[test]
+    fn cut_plan() {
+        let rod = Rod::new(&PRICES);
+        assert_eq!(
+            rod.cut_plan(7),
+            Some(CutPlan {
+                revenue: 18,
+                pieces: vec![1, 6]
+            })
+        );
+
+        // Rods longer than the price table are cut in priced pieces.
+        let plan = rod.cut_plan(23).expect("rod can be cut");
+        assert_eq!(plan.revenue, 68);
+        assert_eq!(plan.pieces.iter().sum::<usize>(), 23);
+    }
+
+    #[test]
+    fn float_prices() {
+        let prices = [0.5, 1.25, 1.5];
+        let rod = Rod::new(&prices);
+        let plan = rod.cut_plan(4).expect("rod can be cut");
+        assert_eq!(plan.revenue, 2.5);
+        assert_eq!(plan.pieces, vec![2, 2]);
+    }
+
+    #[test]
+    fn negative_prices() {
+        let prices = [-3, -1];
+        let rod = Rod::new(&prices);
+        assert_eq!(
+            rod.cut_plan(3),
+            Some(CutPlan {
+                revenue: -4,
+                pieces: vec![1, 2]
+            })
+        );
+    }
+
+    #[test]
+    fn piece_limit() {
+        let rod = Rod::new(&PRICES);
+        let plan = rod.maximum_with_piece_limit(10, 1).expect("rod can be cut");
+        assert_eq!(plan.revenue, 30);
+        assert_eq!(plan.pieces, vec![10]);
+
+        // Without a limit the best cut of 9 is 2 + 6 or 3 + 6.
+        assert_eq!(rod.maximum_with_bottom_up(9), Some(25));
+        assert_eq!(
+            rod.maximum_with_piece_limit(9, 1).map(|plan| plan.revenue),
+            Some(24)
+        );
+        assert_eq!(
+            rod.maximum_with_piece_limit(9, 2).map(|plan| plan.revenue),
+            Some(25)
+        );
+
+        // A rod longer than the price table needs several pieces.
+        assert_eq!(rod.maximum_with_piece_limit(11, 1), None);
+        assert_eq!(
+            rod.maximum_with_piece_limit(0, 0),
+            Some(CutPlan {
+                revenue: 0,
+                pieces: vec![]
+            })
+        );
+    }
+
+    #[test]
+    fn piece_limit_agrees_with_unlimited_cut() {
+        let rod = Rod::new(&PRICES);
+        for size in 0..=20 {
+            let plan = rod
+                .maximum_with_piece_limit(size, size)
+                .expect("rod can be cut");
+            assert_eq!(Some(plan.revenue), rod.maximum_with_bottom_up(size));
+            assert_eq!(plan.pieces.iter().sum::<usize>(), size);
+        }
+    }
+
+    #[test]
+    fn pareto_frontier() {
+        let rod = Rod::new(&PRICES);
+        let frontier: Vec<_> = rod
+            .pareto_frontier(9)
+            .into_iter()
+            .map(|plan| (plan.pieces.len(), plan.revenue))
+            .collect();
+        assert_eq!(frontier, vec![(1, 24), (2, 25)]);
+
+        let frontier = rod.pareto_frontier(4);
+        assert_eq!(
+            frontier,
+            vec![
+                CutPlan {
+                    revenue: 9,
+                    pieces: vec![4]
+                },
+                CutPlan {
+                    revenue: 10,
+                    pieces: vec![2, 2]
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_price_table() {
+        let rod = Rod::<u64>::new(&[]);
+        assert_eq!(rod.maximum_with_bottom_up(0), Some(0));
+        assert_eq!(rod.cut_plan(1), None);
+    }
+}
@@ -7,6 +7,9 @@ use std::cmp::Ordering;
 use crate::Container;
 
 /// The [`Search`] trait specifies the various methods for searching.
+///
+/// It is implemented for slices, so vectors and arrays can be searched
+/// directly.
 pub trait Search<T> {
     /// Search linearly for a value in a container.
     ///
@@ -18,11 +21,25 @@ pub trait Search<T> {
 
     /// Recursively binary search for a value in a sorted container.
     fn rec_binsearch(&self, needle: &T) -> Option<usize>;
+
+    /// Returns the index of the first element which is not less than the
+    /// needle in a sorted container.
+    ///
+    /// It is the first index where the needle can be inserted while keeping
+    /// the container sorted.
+    fn lower_bound(&self, needle: &T) -> usize;
+
+    /// Returns the index of the first element which is greater than the
+    /// needle in a sorted container.
+    ///
+    /// It is the last index where the needle can be inserted while keeping
+    /// the container sorted.
+    fn upper_bound(&self, needle: &T) -> usize;
 }
 
-impl<T> Search<T> for Container<T>
+impl<T> Search<T> for [T]
 where
-    T: PartialEq + PartialOrd + Clone + Ord,
+    T: Ord,
 {
     fn linear(&self, needle: T) -> Option<usize> {
         for (index, value) in self.iter().enumerate() {
@@ -34,19 +51,16 @@ where
     }
 
     fn binsearch(&self, needle: &T) -> Option<usize> {
-        if self.is_empty() {
-            return None;
-        }
-        let (mut low, mut high) = (0, self.len() - 1);
-
-        while low <= high {
-            let middle = (low + high) / 2;
+        // The needle can only be in the half-open range `low..high`.
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
             match &self[middle].cmp(needle) {
                 Ordering::Less => {
                     low = middle + 1;
                 }
                 Ordering::Greater => {
-                    high = middle - 1;
+                    high = middle;
                 }
                 Ordering::Equal => return Some(middle),
             }
@@ -55,25 +69,77 @@ where
     }
 
     fn rec_binsearch(&self, needle: &T) -> Option<usize> {
-        fn rec<T: PartialEq + PartialOrd + Eq>(haystack: &[T], needle: &T) -> Option<usize> {
-            if haystack.is_empty() {
-                return None;
+        if self.is_empty() {
+            return None;
+        }
+        let middle = self.len() / 2;
+        if *needle == self[middle] {
+            Some(middle)
+        } else if *needle > self[middle] {
+            self[middle + 1..]
+                .rec_binsearch(needle)
+                .map(|index| middle + 1 + index)
+        } else {
+            self[..middle].rec_binsearch(needle)
+        }
+    }
+
+    fn lower_bound(&self, needle: &T) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self[middle] < *needle {
+                low = middle + 1;
+            } else {
+                high = middle;
             }
-            let middle = haystack.len() / 2;
-            if *needle == haystack[middle] {
-                Some(middle)
-            } else if *needle > haystack[middle] {
-                rec(&haystack[middle + 1..], needle)
+        }
+        low
+    }
+
+    fn upper_bound(&self, needle: &T) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self[middle] <= *needle {
+                low = middle + 1;
             } else {
-                rec(&haystack[..middle], needle)
+                high = middle;
             }
         }
-        rec(&self.data, needle)
+        low
+    }
+}
+
+impl<T> Search<T> for Container<T>
+where
+    T: Ord,
+{
+    fn linear(&self, needle: T) -> Option<usize> {
+        self.data.linear(needle)
+    }
+
+    fn binsearch(&self, needle: &T) -> Option<usize> {
+        self.data.binsearch(needle)
+    }
+
+    fn rec_binsearch(&self, needle: &T) -> Option<usize> {
+        self.data.rec_binsearch(needle)
+    }
+
+    fn lower_bound(&self, needle: &T) -> usize {
+        self.data.lower_bound(needle)
+    }
+
+    fn upper_bound(&self, needle: &T) -> usize {
+        self.data.upper_bound(needle)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use quickcheck_macros::quickcheck;
+
     use super::*;
 
     #[test]
@@ -124,6 +190,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn binsearch_value_below_the_minimum_return_none() {
+        assert_eq!([5].binsearch(&1), None);
+        assert_eq!([2, 4, 6].binsearch(&1), None);
+        assert_eq!([2, 4, 6].rec_binsearch(&1), None);
+        assert_eq!(Vec::<i32>::new().binsearch(&1), None);
+    }
+
+    #[quickcheck]
+    fn binsearch_finds_present_values(mut data: Vec<i8>, needle: i8) -> bool {
+        data.sort();
+        data.dedup();
+        let expected = data.iter().position(|item| *item == needle);
+        data.binsearch(&needle) == expected && data.rec_binsearch(&needle) == expected
+    }
+
     #[test]
     fn rec_binsearch_existing_value_return_the_index() {
         let container = Container {
@@ -135,4 +217,32 @@ mod tests {
             "failed to return the index"
         );
     }
+
+    #[test]
+    fn search_vectors_and_arrays() {
+        let data: Vec<_> = (1..12).step_by(2).collect();
+        assert_eq!(data.linear(9), Some(4));
+        assert_eq!(data.binsearch(&11), Some(5));
+        assert_eq!(data.rec_binsearch(&11), Some(5));
+        assert_eq!([2, 4, 6].rec_binsearch(&6), Some(2));
+        assert_eq!([2, 4, 6].binsearch(&5), None);
+    }
+
+    #[test]
+    fn bounds_of_duplicates() {
+        let container = Container::new(vec![1, 2, 2, 2, 5]);
+        assert_eq!(container.lower_bound(&2), 1);
+        assert_eq!(container.upper_bound(&2), 4);
+        assert_eq!(container.lower_bound(&3), 4);
+        assert_eq!(container.upper_bound(&3), 4);
+        assert_eq!(container.lower_bound(&0), 0);
+        assert_eq!(container.upper_bound(&9), 5);
+    }
+
+    #[quickcheck]
+    fn bounds_agree_with_partition_point(mut data: Vec<i8>, needle: i8) -> bool {
+        data.sort();
+        data.lower_bound(&needle) == data.partition_point(|item| *item < needle)
+            && data.upper_bound(&needle) == data.partition_point(|item| *item <= needle)
+    }
 }
@@ -0,0 +1,164 @@
+//! Selection algorithms.
+//!
+//! The `i`th order statistic of a set of `n` elements is its `i`th smallest
+//! element, counting from zero here, so the minimum is the order statistic
+//! `0` and the lower median is the order statistic `(n - 1) / 2`. It can be
+//! found in linear time, without sorting the elements, by partitioning them
+//! around a pivot as quicksort does, but then only recursing into the side
+//! where the order statistic lies. See CLRS chapter 9.
+
+use rand::Rng;
+
+use crate::sort::three_way_partition as partition;
+use crate::Container;
+
+/// Returns the `i`th smallest element of the slice, or `None` if `i` is out
+/// of bounds.
+///
+/// The pivots are chosen uniformly at random, so it takes O(n) expected
+/// time, and O(n²) time in the worst case. The elements are reordered so
+/// that the `i`th smallest one is at the index `i`, the elements before it
+/// being not greater and the elements after it not smaller. See CLRS
+/// section 9.2.
+pub fn randomized_select<T: Ord>(data: &mut [T], i: usize) -> Option<&T> {
+    if i >= data.len() {
+        return None;
+    }
+    let mut rng = rand::thread_rng();
+    let (mut start, mut end) = (0, data.len());
+    loop {
+        let pivot = rng.gen_range(start..end);
+        let (low, high) = partition(data, start, end, pivot, &mut ());
+        if i < low {
+            end = low;
+        } else if i >= high {
+            start = high;
+        } else {
+            return Some(&data[i]);
+        }
+    }
+}
+
+/// Returns the `i`th smallest element of the slice, or `None` if `i` is out
+/// of bounds.
+///
+/// The pivot is the median of the medians of the groups of five elements,
+/// found recursively. At least about 3/10 of the elements are on either
+/// side of it, so it takes O(n) time in the worst case. The elements are
+/// reordered as by [`randomized_select`]. See CLRS section 9.3.
+pub fn select<T: Ord>(data: &mut [T], i: usize) -> Option<&T> {
+    if i >= data.len() {
+        return None;
+    }
+    median_of_medians(data, i);
+    Some(&data[i])
+}
+
+/// Moves the `i`th smallest element of the slice to the index `i`, the
+/// elements before it being not greater and the elements after it not
+/// smaller.
+fn median_of_medians<T: Ord>(data: &mut [T], i: usize) {
+    let (mut start, mut end) = (0, data.len());
+    while end - start > 5 {
+        // The median of each group is moved to the front of the range.
+        let mut medians = start;
+        for group in (start..end).step_by(5) {
+            let group_end = (group + 5).min(end);
+            data[group..group_end].sort_unstable();
+            data.swap(medians, group + (group_end - group - 1) / 2);
+            medians += 1;
+        }
+        let pivot = (medians - start - 1) / 2;
+        median_of_medians(&mut data[start..medians], pivot);
+
+        let (low, high) = partition(data, start, end, start + pivot, &mut ());
+        if i < low {
+            end = low;
+        } else if i >= high {
+            start = high;
+        } else {
+            return;
+        }
+    }
+    data[start..end].sort_unstable();
+}
+
+impl<T> Container<T>
+where
+    T: Ord + Clone,
+{
+    /// Returns the lower median of the elements, or `None` if the container
+    /// is empty.
+    ///
+    /// It selects the order statistic `(n - 1) / 2` of a copy of the
+    /// elements with [`select`], in O(n) time.
+    pub fn median(&self) -> Option<T> {
+        let mut data = self.data.clone();
+        let i = data.len().checked_sub(1)? / 2;
+        select(&mut data, i).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if the element at the index is not smaller than the
+    /// elements before it nor greater than the elements after it.
+    fn is_partitioned<T: Ord>(data: &[T], i: usize) -> bool {
+        data[..i].iter().all(|value| *value <= data[i])
+            && data[i + 1..].iter().all(|value| *value >= data[i])
+    }
+
+    #[test]
+    fn order_statistics() {
+        let data = [16, 4, 10, 14, 7, 9, 3, 2, 8, 1, 14, 5];
+        let mut sorted = data;
+        sorted.sort_unstable();
+        for (i, expected) in sorted.iter().enumerate() {
+            let mut deterministic = data;
+            assert_eq!(select(&mut deterministic, i), Some(expected));
+            assert!(is_partitioned(&deterministic, i));
+
+            let mut randomized = data;
+            assert_eq!(randomized_select(&mut randomized, i), Some(expected));
+            assert!(is_partitioned(&randomized, i));
+        }
+        assert_eq!(select(&mut [1, 2], 2), None);
+        assert_eq!(randomized_select::<i32>(&mut [], 0), None);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(Container::new(vec![3, 1, 2]).median(), Some(2));
+        assert_eq!(Container::new(vec![4, 1, 3, 2]).median(), Some(2));
+        assert_eq!(Container::<i32>::new(vec![]).median(), None);
+        let repeated = Container::new(vec![7; 1000]);
+        assert_eq!(repeated.median(), Some(7));
+    }
+
+    #[quickcheck]
+    fn selects_like_sorting(data: Vec<i8>, i: usize) -> bool {
+        let mut sorted = data.clone();
+        sorted.sort_unstable();
+        let i = if data.is_empty() { 0 } else { i % data.len() };
+        let expected = sorted.get(i);
+
+        let mut deterministic = data.clone();
+        let mut randomized = data;
+        select(&mut deterministic, i) == expected
+            && randomized_select(&mut randomized, i) == expected
+            && (expected.is_none()
+                || is_partitioned(&deterministic, i) && is_partitioned(&randomized, i))
+    }
+
+    #[quickcheck]
+    fn median_of_container(data: Vec<i32>) -> bool {
+        let mut sorted = data.clone();
+        sorted.sort_unstable();
+        let expected = data.len().checked_sub(1).map(|last| sorted[last / 2]);
+        Container::new(data).median() == expected
+    }
+}
@@ -0,0 +1,510 @@
+//! Skip list.
+//!
+//! A skip list is a sorted linked list with express lanes: every node is on
+//! the bottom level, and each node on a level is also on the next level up
+//! with probability `p`. A search starts on the highest level and drops down
+//! a level whenever the next node overshoots the key, so it visits
+//! O(log n) nodes in expectation, as a balanced search tree would, while
+//! updates only relink the neighbours on each level of the node.
+//!
+//! The nodes live in a vector and refer to each other by index. The index 0
+//! is the head, a node without entry which is on every level.
+
+use std::ops::{Bound, RangeBounds};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::map::Map;
+
+/// Index of the head node.
+const HEAD: usize = 0;
+
+/// Node of a [`SkipList`].
+#[derive(Debug, Clone)]
+struct SkipNode<K, V> {
+    /// The key and value of the node, `None` for the head and the free
+    /// slots.
+    entry: Option<(K, V)>,
+
+    /// `forward[i]` is the next node on level `i`.
+    forward: Vec<Option<usize>>,
+}
+
+/// SkipList is a probabilistic sorted map.
+#[derive(Debug, Clone)]
+pub struct SkipList<K, V> {
+    /// The nodes, starting with the head.
+    nodes: Vec<SkipNode<K, V>>,
+
+    /// The indices of the slots left by removed nodes.
+    free: Vec<usize>,
+
+    /// The number of levels holding at least one node.
+    level: usize,
+
+    /// The probability for a node to be promoted to the next level.
+    probability: f64,
+    len: usize,
+    rng: StdRng,
+}
+
+impl<K, V> SkipList<K, V> {
+    /// The default probability for a node to be promoted to the next level.
+    pub const DEFAULT_PROBABILITY: f64 = 0.5;
+
+    /// The default maximum number of levels, which suits up to about
+    /// `2^16` entries with the default probability.
+    pub const DEFAULT_MAX_LEVEL: usize = 16;
+
+    /// Creates new empty skip list with the default parameters, whose
+    /// levels are drawn from a generator seeded from the operating system.
+    pub fn new() -> Self {
+        Self::with_rng(
+            Self::DEFAULT_PROBABILITY,
+            Self::DEFAULT_MAX_LEVEL,
+            StdRng::from_entropy(),
+        )
+    }
+
+    /// Creates new empty skip list with the default parameters and a fixed
+    /// seed, so that the levels drawn are reproducible.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_params(Self::DEFAULT_PROBABILITY, Self::DEFAULT_MAX_LEVEL, seed)
+    }
+
+    /// Creates new empty skip list where each node is promoted to the next
+    /// level with the given probability, up to the given number of levels.
+    ///
+    /// A list of n entries is fastest with about `log_{1/p} n` levels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probability is not strictly between 0 and 1, or if the
+    /// maximum level is zero.
+    pub fn with_params(probability: f64, max_level: usize, seed: u64) -> Self {
+        Self::with_rng(probability, max_level, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(probability: f64, max_level: usize, rng: StdRng) -> Self {
+        assert!(
+            probability > 0.0 && probability < 1.0,
+            "probability is not between 0 and 1"
+        );
+        assert!(max_level > 0, "maximum level is zero");
+        Self {
+            nodes: vec![SkipNode {
+                entry: None,
+                forward: vec![None; max_level],
+            }],
+            free: Vec::new(),
+            level: 0,
+            probability,
+            len: 0,
+            rng,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of levels holding at least one node.
+    pub const fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the maximum number of levels.
+    pub fn max_level(&self) -> usize {
+        self.nodes[HEAD].forward.len()
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> SkipListIter<'_, K, V> {
+        SkipListIter {
+            list: self,
+            current: self.nodes[HEAD].forward[0],
+            end: None,
+        }
+    }
+
+    /// Returns the key and value of a node which is not the head.
+    fn entry(&self, index: usize) -> (&K, &V) {
+        let (key, value) = self.nodes[index]
+            .entry
+            .as_ref()
+            .expect("the node is not the head");
+        (key, value)
+    }
+
+    /// Returns the key of a node which is not the head.
+    fn key(&self, index: usize) -> &K {
+        self.entry(index).0
+    }
+
+    /// Draws the number of levels of a new node.
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < self.max_level() && self.rng.gen_bool(self.probability) {
+            level += 1;
+        }
+        level
+    }
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let update = self.predecessors(|other| other < &key);
+        if let Some(next) = self.nodes[update[0]].forward[0] {
+            if let Some((other, old)) = self.nodes[next].entry.as_mut() {
+                if *other == key {
+                    return Some(std::mem::replace(old, value));
+                }
+            }
+        }
+
+        let level = self.random_level();
+        self.level = self.level.max(level);
+        let forward = (0..level)
+            .map(|i| self.nodes[update[i]].forward[i])
+            .collect();
+        let node = SkipNode {
+            entry: Some((key, value)),
+            forward,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (i, &predecessor) in update.iter().enumerate().take(level) {
+            self.nodes[predecessor].forward[i] = Some(index);
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|index| self.entry(index).1)
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        let (_, value) = self.nodes[index].entry.as_mut()?;
+        Some(value)
+    }
+
+    /// Returns true if the list contains the key.
+    pub fn contains(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Removes the key from the list and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(|other| other < key);
+        let index = self.nodes[update[0]].forward[0]?;
+        if self.key(index) != key {
+            return None;
+        }
+        for (i, &predecessor) in update.iter().enumerate().take(self.level) {
+            if self.nodes[predecessor].forward[i] == Some(index) {
+                self.nodes[predecessor].forward[i] = self.nodes[index].forward[i];
+            }
+        }
+        while self.level > 0 && self.nodes[HEAD].forward[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+
+        let node = std::mem::replace(
+            &mut self.nodes[index],
+            SkipNode {
+                entry: None,
+                forward: Vec::new(),
+            },
+        );
+        self.free.push(index);
+        self.len -= 1;
+        node.entry.map(|(_, value)| value)
+    }
+
+    /// Creates an iterator over the entries whose keys are in the range,
+    /// sorted by key.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> SkipListIter<'_, K, V> {
+        let first = |update: Vec<usize>| self.nodes[update[0]].forward[0];
+        let current = match range.start_bound() {
+            Bound::Included(start) => first(self.predecessors(|key| key < start)),
+            Bound::Excluded(start) => first(self.predecessors(|key| key <= start)),
+            Bound::Unbounded => self.nodes[HEAD].forward[0],
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => first(self.predecessors(|key| key <= end)),
+            Bound::Excluded(end) => first(self.predecessors(|key| key < end)),
+            Bound::Unbounded => None,
+        };
+        // An empty range may end before it starts.
+        let current = match (current, end) {
+            (Some(current), Some(end)) if self.key(current) > self.key(end) => None,
+            _ => current,
+        };
+        SkipListIter {
+            list: self,
+            current,
+            end,
+        }
+    }
+
+    /// Returns the index of the node with the key, if any.
+    fn find(&self, key: &K) -> Option<usize> {
+        let update = self.predecessors(|other| other < key);
+        let index = self.nodes[update[0]].forward[0]?;
+        (self.key(index) == key).then_some(index)
+    }
+
+    /// Returns, for each level, the last node whose key satisfies `before`,
+    /// which must hold for a prefix of the keys.
+    ///
+    /// The levels above the current level map to the head.
+    fn predecessors(&self, before: impl Fn(&K) -> bool) -> Vec<usize> {
+        let mut update = vec![HEAD; self.max_level()];
+        let mut current = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[current].forward[i] {
+                if !before(self.key(next)) {
+                    break;
+                }
+                current = next;
+            }
+            update[i] = current;
+        }
+        update
+    }
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Map<K, V> for SkipList<K, V> {
+    type Iter<'a>
+        = SkipListIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        SkipList::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        SkipList::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        SkipList::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        SkipList::len(self)
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    fn iter(&self) -> Self::Iter<'_> {
+        SkipList::iter(self)
+    }
+}
+
+/// Iterator over the entries of a [`SkipList`], sorted by key.
+#[derive(Debug)]
+pub struct SkipListIter<'a, K, V> {
+    list: &'a SkipList<K, V>,
+
+    /// The next node to visit.
+    current: Option<usize>,
+
+    /// The first node past the end of the iteration.
+    end: Option<usize>,
+}
+
+impl<'a, K, V> Iterator for SkipListIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.current.filter(|&index| Some(index) != self.end)?;
+        self.current = self.list.nodes[index].forward[0];
+        Some(self.list.entry(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    /// Returns true if every level is sorted and contained in the level
+    /// below it.
+    fn is_valid<K: Ord, V>(list: &SkipList<K, V>) -> bool {
+        let levels: Vec<Vec<usize>> = (0..list.max_level())
+            .map(|i| {
+                std::iter::successors(list.nodes[HEAD].forward[i], |&index| {
+                    list.nodes[index].forward[i]
+                })
+                .collect()
+            })
+            .collect();
+        levels[0].len() == list.len()
+            && levels.iter().enumerate().all(|(i, level)| {
+                level.is_empty() == (i >= list.level)
+                    && level
+                        .windows(2)
+                        .all(|pair| list.key(pair[0]) < list.key(pair[1]))
+                    && (i == 0 || level.iter().all(|index| levels[i - 1].contains(index)))
+            })
+    }
+
+    fn keys<'a>(iter: impl Iterator<Item = (&'a i32, &'a ())>) -> Vec<i32> {
+        iter.map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut list = SkipList::with_seed(7);
+        assert!(list.is_empty());
+        for key in [5, 1, 9, 3, 7] {
+            assert_eq!(list.insert(key, key * 10), None);
+        }
+        assert_eq!(list.insert(3, 0), Some(30));
+        assert!(list.contains(&3));
+        assert!(!list.contains(&4));
+        assert_eq!(list.get(&9), Some(&90));
+        assert!(is_valid(&list));
+
+        assert_eq!(list.remove(&5), Some(50));
+        assert_eq!(list.remove(&5), None);
+        assert_eq!(list.remove(&4), None);
+        assert_eq!(list.len(), 4);
+        assert!(is_valid(&list));
+        let keys: Vec<_> = list.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec![1, 3, 7, 9]);
+
+        for key in [1, 3, 7, 9] {
+            list.remove(&key);
+        }
+        assert_eq!(list.level(), 0);
+        assert!(is_valid(&list));
+    }
+
+    #[test]
+    fn range_bounds() {
+        let mut list = SkipList::with_seed(3);
+        for key in (0..20).step_by(2) {
+            list.insert(key, ());
+        }
+        assert_eq!(keys(list.range(4..10)), vec![4, 6, 8]);
+        assert_eq!(keys(list.range(3..=10)), vec![4, 6, 8, 10]);
+        assert_eq!(keys(list.range(..5)), vec![0, 2, 4]);
+        assert_eq!(keys(list.range(15..)), vec![16, 18]);
+        assert_eq!(
+            keys(list.range((Bound::Excluded(4), Bound::Excluded(8)))),
+            vec![6]
+        );
+        assert!(list.range(5..6).next().is_none());
+        assert!(list.range(30..).next().is_none());
+        assert_eq!(keys(list.range(..)), keys(list.iter()));
+    }
+
+    #[test]
+    fn same_seed_same_levels() {
+        let build = |seed| {
+            let mut list = SkipList::with_params(0.25, 8, seed);
+            for key in 0..256 {
+                list.insert(key, ());
+            }
+            list
+        };
+        let (first, second) = (build(11), build(11));
+        assert_eq!(first.level(), second.level());
+        assert!(first.level() <= 8);
+        assert!(first
+            .nodes
+            .iter()
+            .zip(&second.nodes)
+            .all(|(a, b)| a.forward == b.forward));
+    }
+
+    #[test]
+    fn levels_thin_out_geometrically() {
+        let mut list = SkipList::with_seed(5);
+        for key in 0..4096 {
+            list.insert(key, ());
+        }
+        // About half of the nodes of a level are on the next level.
+        let counts: Vec<_> = (1..4)
+            .map(|level| {
+                list.nodes
+                    .iter()
+                    .filter(|node| node.forward.len() >= level)
+                    .count()
+            })
+            .collect();
+        assert!(counts
+            .windows(2)
+            .all(|pair| pair[1] * 3 > pair[0] && pair[1] * 3 < pair[0] * 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "probability is not between 0 and 1")]
+    fn invalid_probability() {
+        SkipList::<u8, u8>::with_params(1.0, 4, 0);
+    }
+
+    #[quickcheck]
+    fn stays_valid(operations: Vec<Operation>, seed: u64) -> bool {
+        let mut list = SkipList::with_params(0.5, 4, seed);
+        operations.iter().all(|&(insert, key, value)| {
+            if insert {
+                list.insert(key, value);
+            } else {
+                list.remove(&key);
+            }
+            is_valid(&list)
+        })
+    }
+
+    #[quickcheck]
+    fn range_matches_filtered_iter(keys: Vec<u8>, start: u8, end: u8) -> bool {
+        let mut list = SkipList::with_seed(0);
+        for key in keys {
+            list.insert(key, ());
+        }
+        let expected: Vec<_> = list
+            .iter()
+            .filter(|(key, _)| (start..end).contains(*key))
+            .collect();
+        list.range(start..end).collect::<Vec<_>>() == expected
+    }
+
+    #[quickcheck]
+    fn skip_list_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut SkipList::new(), &operations)
+    }
+}
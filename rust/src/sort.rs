@@ -2,56 +2,377 @@
 //!
 //! This module implements various sorting algorithms.
 
+use std::cmp::Ordering;
+
 use rand::Rng;
 
-use crate::Container;
+use crate::search::Search;
+use crate::trace::{Event, Sink};
+use crate::{error::ErrorKind, Container, Error};
+
+pub mod fuzzy;
+pub mod linear;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod patience;
+pub mod sortedness;
+pub mod sorting_network;
+pub mod stats;
 
 /// Sort trait
 ///
 /// The [`Sort`] trait defines the various mechanism for sorting a
-/// container.
+/// container. It is implemented for slices, so the algorithms also run on
+/// vectors and arrays directly.
 pub trait Sort<T> {
     /// Cormen, Leiserson, Rivest, and Stein insertion sort algorithm.
     ///
     /// Sort the elements in the container using CLRS insertion sort
-    /// algorithm in 3rd Edition.
+    /// algorithm in 3rd Edition. Two elements are swapped when `compare`
+    /// returns true, so the sort is stable if `compare` is strict, like
+    /// `a > b`.
     fn naive_insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
 
     /// Alternative version of CLRS insertion algorithm.
+    ///
+    /// It is stable under the same condition as
+    /// [`Sort::naive_insertion_sort`].
     fn insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
 
+    /// Traced insertion sort.
+    ///
+    /// It is [`Sort::insertion_sort`], where each comparison and swap is
+    /// recorded to the sink. It compares every pair of elements, so it
+    /// makes `n (n - 1) / 2` comparisons, and one swap per inversion.
+    fn traced_insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool, sink: &mut impl Sink);
+
+    /// Binary insertion sort algorithm.
+    ///
+    /// It is an insertion sort which finds where to insert each element by
+    /// binary search, then shifts the larger elements by a rotation. It
+    /// makes O(n log n) comparisons but still O(n²) moves. Each element is
+    /// inserted after the elements equal to it, so the sort is stable.
+    fn binary_insertion_sort(&mut self);
+
     /// Selection sort algorithm.
     fn selection_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
 
+    /// Bubble sort algorithm.
+    ///
+    /// Each pass swaps the adjacent elements for which `compare` returns
+    /// true, which carries the last element of the order to the end of the
+    /// range, so the range shrinks by one on each pass. It stops after a
+    /// pass without swaps, so sorted elements take a single pass. Like
+    /// [`Sort::naive_insertion_sort`], it is stable if `compare` is strict.
+    fn bubble_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
+
+    /// Shell sort algorithm.
+    ///
+    /// It is an insertion sort of the elements a gap apart, where the gaps
+    /// are Knuth's sequence `1, 4, 13, 40, ...`, largest first, so elements
+    /// far from their place move there in a few long swaps. It takes
+    /// O(n^(3/2)) time in the worst case. Elements are swapped when
+    /// `compare` returns true, as in [`Sort::insertion_sort`], but the sort
+    /// is not stable.
+    fn shell_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
+
     /// Merge sort algorithm.
+    ///
+    /// It sorts the range `start..end` and panics if the range is out of
+    /// bounds. Equal elements keep their relative order, so the sort is
+    /// stable.
     fn merge_sort(&mut self, start: usize, end: usize);
 
-    /// Recursive insertion sort.
+    /// Traced merge sort.
+    ///
+    /// It is [`Sort::merge_sort`], where each comparison and swap is
+    /// recorded to the sink, each element moved by the rotation of a merge
+    /// as an [`Event::Write`], and each recursive call as an
+    /// [`Event::Call`] followed by an [`Event::Return`]. The depth of the
+    /// recursion is `1 + ⌈lg n⌉`.
+    fn traced_merge_sort(&mut self, start: usize, end: usize, sink: &mut impl Sink);
 
+    /// Bottom-up merge sort algorithm.
+    ///
+    /// It is a merge sort without recursion: the passes merge the pairs of
+    /// adjacent sorted runs of length 1, 2, 4 and so on, until a single run
+    /// is left. Like [`Sort::merge_sort`], it merges in place by rotations
+    /// rather than through a buffer, and equal elements keep their
+    /// relative order.
+    fn bottom_up_merge_sort(&mut self);
+
+    /// Merge sort algorithm with a buffer.
+    ///
+    /// It is the textbook merge sort of CLRS section 2.3.1: the first half
+    /// of each merge is cloned into a buffer, and the elements are merged
+    /// back from the buffer and the second half. The buffer is allocated
+    /// once for the whole sort and holds `n / 2` clones, so the sort takes
+    /// O(n log n) time and O(n) extra space, where [`Sort::merge_sort`]
+    /// takes O(n log² n) time without any buffer. The sort is stable.
+    fn buffered_merge_sort(&mut self)
+    where
+        T: Clone;
+
+    /// Merge sort algorithm on a validated range.
+    ///
+    /// It fails with [`ErrorKind::InvalidRange`] if `start > end`, and with
+    /// [`ErrorKind::IndexOutOfBounds`] if `end` is past the end.
+    fn try_merge_sort(&mut self, start: usize, end: usize) -> Result<(), Error>;
+
+    /// Recursive insertion sort.
+    ///
     /// Recursively sort the N - 1 elements in the container
     /// and the insert the N-th element in the sorted container.
+    /// The sort is stable.
     fn rec_insertion_sort(&mut self);
 
-    /// QuickSort algorithm.
-    fn quick_sort(&mut self, start: usize, end: usize);
+    /// Stable sort by key.
+    ///
+    /// It sorts the elements by the key extracted from each of them, the
+    /// elements of equal keys keeping their relative order. It is
+    /// [`Sort::bottom_up_merge_sort`] comparing the keys, so it takes
+    /// O(n log² n) time without any buffer, and extracts the keys of two
+    /// elements on each comparison.
+    ///
+    /// On slices and vectors, the inherent `sort_by_key` method of the
+    /// standard library takes precedence, so this one is called as
+    /// `Sort::sort_by_key(data, key)`.
+    fn sort_by_key<K: Ord>(&mut self, key: impl Fn(&T) -> K);
+
+    /// Heapsort algorithm.
+    ///
+    /// It builds a max heap in place, then repeatedly swaps the maximum
+    /// with the last element of the heap and shrinks the heap. It takes
+    /// O(n log n) time in the worst case and O(1) extra space. See CLRS
+    /// section 6.4.
+    fn heap_sort(&mut self);
+
+    /// Traced heapsort.
+    ///
+    /// It is [`Sort::heap_sort`], where each comparison and swap is
+    /// recorded to the sink, and each max-heapify of a subtree as an
+    /// [`Event::Heapify`]. It makes at most `2 n lg n` comparisons.
+    fn traced_heap_sort(&mut self, sink: &mut impl Sink);
 
     /// QuickSort algorithm.
+    ///
+    /// Each partition of the range `start..end` is done around a pivot
+    /// chosen with the given strategy.
+    fn quick_sort(&mut self, start: usize, end: usize, pivot: Pivot);
+
+    /// QuickSort algorithm with the given partition scheme.
+    ///
+    /// It is [`Sort::quick_sort`], where each range is partitioned around
+    /// its pivot with the given scheme rather than [`Partition::Lomuto`].
+    fn quick_sort_with(&mut self, start: usize, end: usize, pivot: Pivot, scheme: Partition);
+
+    /// Traced QuickSort.
+    ///
+    /// It is [`Sort::quick_sort_with`], where each comparison and swap,
+    /// including those made to choose the pivot, is recorded to the sink,
+    /// and each recursive call as an [`Event::Call`] followed by an
+    /// [`Event::Return`]. The recursion goes into the smaller side of each
+    /// partition, so its depth is O(log n) whatever the pivots.
+    fn traced_quick_sort(
+        &mut self,
+        start: usize,
+        end: usize,
+        pivot: Pivot,
+        scheme: Partition,
+        sink: &mut impl Sink,
+    );
+
+    /// QuickSort algorithm on a validated range.
+    ///
+    /// It fails like [`Sort::try_merge_sort`] on an invalid range.
+    fn try_quick_sort(&mut self, start: usize, end: usize, pivot: Pivot) -> Result<(), Error>;
+
+    /// Randomized QuickSort algorithm.
+    ///
+    /// It is the QuickSort algorithm with the [`Pivot::Random`] strategy.
     fn randomize_quick_sort(&mut self, start: usize, end: usize);
+
+    /// Hybrid sort algorithm, also known as introsort.
+    ///
+    /// It is a QuickSort with [`Pivot::MedianOfThree`] and
+    /// [`Partition::Hoare`], which switches to heapsort on a range once the
+    /// recursion is deeper than twice the logarithm of the length, so it
+    /// takes O(n log n) time in the worst case, and to insertion sort on
+    /// ranges of at most 16 elements, where it is faster.
+    fn hybrid_sort(&mut self);
+
+    /// Pancake sort algorithm.
+    ///
+    /// The only operation allowed is to reverse a prefix, like flipping
+    /// the top of a stack of pancakes with a spatula. Each pass brings the
+    /// largest unsorted element to the front, then flips it to its place.
+    fn pancake_sort(&mut self);
+
+    /// Gnome sort algorithm.
+    ///
+    /// It is an insertion sort where the element being inserted walks back
+    /// by swaps, and the scan then walks forward again to where it was.
+    /// Two adjacent elements are swapped when `compare` returns true, so
+    /// like [`Sort::insertion_sort`] the sort is stable if `compare` is
+    /// strict.
+    fn gnome_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
+
+    /// Comb sort algorithm.
+    ///
+    /// It is a bubble sort comparing elements a gap apart, where the gap
+    /// shrinks by a factor of 1.3 on each pass, so small elements near the
+    /// end quickly move to the front. Two elements are swapped when
+    /// `compare` returns true, as in [`Sort::bubble_sort`], but the sort is
+    /// not stable.
+    fn comb_sort(&mut self, compare: impl Fn(&T, &T) -> bool);
+
+    /// Cocktail shaker sort algorithm.
+    ///
+    /// It is a bubble sort whose passes alternate in both directions.
+    /// The sort is stable.
+    fn cocktail_sort(&mut self);
+
+    /// Cycle sort algorithm.
+    ///
+    /// The permutation sorting the elements is made of cycles, and each
+    /// cycle is rotated by moving its elements directly to their final
+    /// position, found by counting the smaller elements. Every element is
+    /// written at most once, so the number of writes is the minimum
+    /// possible, at the price of O(n²) comparisons. Each write is recorded
+    /// as an [`Event::Write`] to the sink.
+    fn cycle_sort(&mut self, sink: &mut impl Sink);
+
+    /// Bogosort algorithm.
+    ///
+    /// It shuffles the elements until they happen to be sorted, which
+    /// takes `n!` shuffles on average for distinct elements. It fails with
+    /// [`ErrorKind::IterationLimit`] if the elements are still not sorted
+    /// after `max_iters` shuffles.
+    fn bogo_sort(&mut self, max_iters: usize) -> Result<(), Error>;
 }
 
-impl<T> Sort<T> for Container<T>
+/// Shuffles the elements of the slice uniformly at random.
+///
+/// It is the Fisher-Yates shuffle: the element at each index is swapped
+/// with an element chosen at random at this index or after it. See CLRS
+/// section 5.3.
+pub fn shuffle<T>(data: &mut [T]) {
+    let mut rng = rand::thread_rng();
+    for index in 0..data.len() {
+        let other = rng.gen_range(index..data.len());
+        data.swap(index, other);
+    }
+}
+
+/// Pivot is the strategy used by QuickSort to choose the pivot of a
+/// partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pivot {
+    /// The last element of the range.
+    ///
+    /// Sorted and reverse-sorted inputs take quadratic time.
+    #[default]
+    Last,
+
+    /// An element of the range chosen uniformly at random.
+    ///
+    /// The expected running time is O(n log n) on every input. See CLRS
+    /// section 7.3.
+    Random,
+
+    /// The median of the first, middle and last elements of the range.
+    ///
+    /// Sorted and reverse-sorted inputs take O(n log n) time.
+    MedianOfThree,
+}
+
+impl Pivot {
+    /// Returns the index of the pivot of the non-empty range `start..end`.
+    fn select<T: Ord>(self, data: &[T], start: usize, end: usize, sink: &mut impl Sink) -> usize {
+        match self {
+            Self::Last => end - 1,
+            Self::Random => rand::thread_rng().gen_range(start..end),
+            Self::MedianOfThree => {
+                let mut candidates = [start, start + (end - start) / 2, end - 1];
+                candidates.sort_by(|&i, &j| {
+                    sink.record(Event::Compare { i, j });
+                    data[i].cmp(&data[j])
+                });
+                candidates[1]
+            }
+        }
+    }
+}
+
+/// Partition is the scheme used by QuickSort to partition a range around
+/// its pivot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Partition {
+    /// Lomuto's scheme, which grows the elements not greater than the pivot
+    /// from the start of the range in a single scan. See CLRS section 7.1.
+    ///
+    /// Equal elements all go to the same side, so many duplicates take
+    /// quadratic time.
+    #[default]
+    Lomuto,
+
+    /// Hoare's scheme, where two indices scan toward each other and swap
+    /// the pairs of elements on the wrong side. See CLRS problem 7-1.
+    ///
+    /// It makes fewer swaps, and both scans stop on elements equal to the
+    /// pivot, so duplicates are split evenly.
+    Hoare,
+
+    /// Three-way partition, also known as the Dutch national flag problem,
+    /// which groups the elements equal to the pivot between the smaller and
+    /// the greater ones.
+    ///
+    /// The equal elements are left out of both sides, so `n` equal elements
+    /// take linear time.
+    ThreeWay,
+}
+
+impl Partition {
+    /// Partitions the non-empty range `start..end` around the element at
+    /// the index, and returns the range of the elements left in their
+    /// final position, between the two sides still to be sorted.
+    fn partition<T: Ord>(
+        self,
+        data: &mut [T],
+        start: usize,
+        end: usize,
+        pivot: usize,
+        sink: &mut impl Sink,
+    ) -> (usize, usize) {
+        match self {
+            Self::Lomuto => {
+                swap(data, pivot, end - 1, sink);
+                let mid = partition(data, start, end, sink);
+                (mid, mid + 1)
+            }
+            Self::Hoare => {
+                swap(data, pivot, start, sink);
+                let mid = hoare_partition(data, start, end, sink);
+                (mid, mid + 1)
+            }
+            Self::ThreeWay => three_way_partition(data, start, end, pivot, sink),
+        }
+    }
+}
+
+impl<T> Sort<T> for [T]
 where
-    T: Eq + PartialEq + PartialOrd + Ord + Clone,
+    T: Ord,
 {
     fn naive_insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
         if self.len() <= 1 {
             return;
         }
+        // The key moves down with each swap, so it is always at `i + 1`.
         for j in 1..self.len() {
-            let key = self[j].clone();
             let mut i = j - 1;
 
-            while compare(&self[i], &key) {
+            while compare(&self[i], &self[i + 1]) {
                 self.swap(i + 1, i);
                 if i == 0 {
                     break;
@@ -62,19 +383,27 @@ where
     }
 
     fn insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
-        if self.len() <= 1 {
-            return;
-        }
+        self.traced_insertion_sort(compare, &mut ());
+    }
+
+    fn traced_insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool, sink: &mut impl Sink) {
         for j in 1..self.len() {
-            let key = self[j].clone();
             for i in (0..j).rev() {
-                if compare(&self[i], &key) {
-                    self.swap(i + 1, i);
+                sink.record(Event::Compare { i, j: i + 1 });
+                if compare(&self[i], &self[i + 1]) {
+                    swap(self, i + 1, i, sink);
                 }
             }
         }
     }
 
+    fn binary_insertion_sort(&mut self) {
+        for j in 1..self.len() {
+            let index = self[..j].upper_bound(&self[j]);
+            self[index..=j].rotate_right(1);
+        }
+    }
+
     fn selection_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
         if self.len() <= 1 {
             return;
@@ -90,54 +419,687 @@ where
         }
     }
 
+    fn bubble_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        let mut end = self.len();
+        let mut swapped = true;
+        while swapped && end > 1 {
+            swapped = false;
+            for index in 1..end {
+                if compare(&self[index - 1], &self[index]) {
+                    self.swap(index - 1, index);
+                    swapped = true;
+                }
+            }
+            end -= 1;
+        }
+    }
+
+    fn shell_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        let mut gap = 1;
+        while gap < self.len() / 3 {
+            gap = 3 * gap + 1;
+        }
+        while gap > 0 {
+            for index in gap..self.len() {
+                let mut i = index;
+                while i >= gap && compare(&self[i - gap], &self[i]) {
+                    self.swap(i - gap, i);
+                    i -= gap;
+                }
+            }
+            gap /= 3;
+        }
+    }
+
     fn merge_sort(&mut self, start: usize, end: usize) {
+        self.traced_merge_sort(start, end, &mut ());
+    }
+
+    fn traced_merge_sort(&mut self, start: usize, end: usize, sink: &mut impl Sink) {
+        sink.record(Event::Call { start, end });
         if end > 0 && start < end - 1 {
             let middle = (end + start) / 2;
-            self.merge_sort(start, middle);
-            self.merge_sort(middle, end);
-            self.merge(start, middle, end);
+            self.traced_merge_sort(start, middle, sink);
+            self.traced_merge_sort(middle, end, sink);
+            merge(self, start, middle, end, sink);
         }
+        sink.record(Event::Return);
+    }
+
+    fn bottom_up_merge_sort(&mut self) {
+        bottom_up_merge_sort_by(self, &|a, b| a < b);
+    }
+
+    fn buffered_merge_sort(&mut self)
+    where
+        T: Clone,
+    {
+        let mut buffer = Vec::with_capacity(self.len() / 2);
+        buffered_merge_sort(self, &mut buffer);
+    }
+
+    fn try_merge_sort(&mut self, start: usize, end: usize) -> Result<(), Error> {
+        check_range(self.len(), start, end)?;
+        self.merge_sort(start, end);
+        Ok(())
     }
 
     fn rec_insertion_sort(&mut self) {
-        fn insort<T: Clone + PartialOrd>(data: &mut [T]) {
-            if data.len() <= 1 {
-                return;
+        if self.len() <= 1 {
+            return;
+        }
+        let len = self.len() - 1;
+
+        self[..len].rec_insertion_sort();
+        for i in (0..len).rev() {
+            if self[i] > self[i + 1] {
+                self.swap(i + 1, i);
             }
-            let len = data.len() - 1;
-            let key = data[len].clone();
+        }
+    }
+
+    fn sort_by_key<K: Ord>(&mut self, key: impl Fn(&T) -> K) {
+        bottom_up_merge_sort_by(self, &|a, b| key(a) < key(b));
+    }
 
-            insort(&mut data[0..len]);
-            for i in (0..len).rev() {
-                if data[i] > key {
-                    data.swap(i + 1, i);
+    fn heap_sort(&mut self) {
+        self.traced_heap_sort(&mut ());
+    }
+
+    fn traced_heap_sort(&mut self, sink: &mut impl Sink) {
+        for index in (0..self.len() / 2).rev() {
+            sift_down(self, index, sink);
+        }
+        for end in (1..self.len()).rev() {
+            swap(self, 0, end, sink);
+            sift_down(&mut self[..end], 0, sink);
+        }
+    }
+
+    fn quick_sort(&mut self, start: usize, end: usize, pivot: Pivot) {
+        self.quick_sort_with(start, end, pivot, Partition::Lomuto);
+    }
+
+    fn quick_sort_with(&mut self, start: usize, end: usize, pivot: Pivot, scheme: Partition) {
+        self.traced_quick_sort(start, end, pivot, scheme, &mut ());
+    }
+
+    fn traced_quick_sort(
+        &mut self,
+        mut start: usize,
+        mut end: usize,
+        pivot: Pivot,
+        scheme: Partition,
+        sink: &mut impl Sink,
+    ) {
+        sink.record(Event::Call { start, end });
+        // Recursing into the smaller side and looping on the larger one
+        // bounds the depth of the recursion to O(log n).
+        while start + 1 < end {
+            let index = pivot.select(self, start, end, sink);
+            let (low, high) = scheme.partition(self, start, end, index, sink);
+            if low - start < end - high {
+                self.traced_quick_sort(start, low, pivot, scheme, sink);
+                start = high;
+            } else {
+                self.traced_quick_sort(high, end, pivot, scheme, sink);
+                end = low;
+            }
+        }
+        sink.record(Event::Return);
+    }
+
+    fn try_quick_sort(&mut self, start: usize, end: usize, pivot: Pivot) -> Result<(), Error> {
+        check_range(self.len(), start, end)?;
+        self.quick_sort(start, end, pivot);
+        Ok(())
+    }
+
+    fn randomize_quick_sort(&mut self, start: usize, end: usize) {
+        self.quick_sort(start, end, Pivot::Random);
+    }
+
+    fn hybrid_sort(&mut self) {
+        let depth = 2 * (usize::BITS - self.len().leading_zeros());
+        introsort(self, depth);
+    }
+
+    fn pancake_sort(&mut self) {
+        for size in (2..=self.len()).rev() {
+            let mut largest = 0;
+            for index in 1..size {
+                if self[index] > self[largest] {
+                    largest = index;
+                }
+            }
+            if largest != size - 1 {
+                self[..=largest].reverse();
+                self[..size].reverse();
+            }
+        }
+    }
+
+    fn gnome_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        let mut index = 0;
+        while index < self.len() {
+            if index == 0 || !compare(&self[index - 1], &self[index]) {
+                index += 1;
+            } else {
+                self.swap(index - 1, index);
+                index -= 1;
+            }
+        }
+    }
+
+    fn comb_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        if self.len() <= 1 {
+            return;
+        }
+        let mut gap = self.len();
+        let mut swapped = true;
+        while gap > 1 || swapped {
+            gap = (gap * 10 / 13).max(1);
+            swapped = false;
+            for index in 0..self.len() - gap {
+                if compare(&self[index], &self[index + gap]) {
+                    self.swap(index, index + gap);
+                    swapped = true;
                 }
             }
         }
-        insort(&mut self.data);
     }
 
-    fn quick_sort(&mut self, start: usize, end: usize) {
-        if start < end {
-            let mid = self.partition(start, end);
-            self.quick_sort(start, mid);
-            self.quick_sort(mid + 1, end);
+    fn cocktail_sort(&mut self) {
+        let (mut start, mut end) = (0, self.len());
+        while start + 1 < end {
+            // Each pass leaves the elements beyond its last swap in place.
+            let mut last_swap = start;
+            for index in start..end - 1 {
+                if self[index] > self[index + 1] {
+                    self.swap(index, index + 1);
+                    last_swap = index;
+                }
+            }
+            end = last_swap + 1;
+
+            last_swap = end;
+            for index in (start..end - 1).rev() {
+                if self[index] > self[index + 1] {
+                    self.swap(index, index + 1);
+                    last_swap = index;
+                }
+            }
+            start = last_swap;
         }
     }
 
+    fn cycle_sort(&mut self, sink: &mut impl Sink) {
+        for start in 0..self.len() {
+            // The element of the cycle being rotated is kept at `start`,
+            // and each swap moves the element at `start` to its place.
+            let mut moved = false;
+            loop {
+                let mut position = start
+                    + self[start + 1..]
+                        .iter()
+                        .filter(|item| **item < self[start])
+                        .count();
+                if position == start {
+                    if moved {
+                        sink.record(Event::Write { index: start });
+                    }
+                    break;
+                }
+                // Skip the equal elements already in place.
+                while self[position] == self[start] {
+                    position += 1;
+                }
+                self.swap(start, position);
+                sink.record(Event::Write { index: position });
+                moved = true;
+            }
+        }
+    }
+
+    fn bogo_sort(&mut self, max_iters: usize) -> Result<(), Error> {
+        let is_sorted = |data: &[T]| data.windows(2).all(|pair| pair[0] <= pair[1]);
+        for _ in 0..max_iters {
+            if is_sorted(self) {
+                return Ok(());
+            }
+            shuffle(self);
+        }
+        if is_sorted(self) {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::IterationLimit))
+        }
+    }
+}
+
+impl<T> Sort<T> for Container<T>
+where
+    T: Ord,
+{
+    fn naive_insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.naive_insertion_sort(compare);
+    }
+
+    fn insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.insertion_sort(compare);
+    }
+
+    fn traced_insertion_sort(&mut self, compare: impl Fn(&T, &T) -> bool, sink: &mut impl Sink) {
+        self.data.traced_insertion_sort(compare, sink);
+    }
+
+    fn binary_insertion_sort(&mut self) {
+        self.data.binary_insertion_sort();
+    }
+
+    fn selection_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.selection_sort(compare);
+    }
+
+    fn bubble_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.bubble_sort(compare);
+    }
+
+    fn shell_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.shell_sort(compare);
+    }
+
+    fn merge_sort(&mut self, start: usize, end: usize) {
+        self.data.merge_sort(start, end);
+    }
+
+    fn traced_merge_sort(&mut self, start: usize, end: usize, sink: &mut impl Sink) {
+        self.data.traced_merge_sort(start, end, sink);
+    }
+
+    fn bottom_up_merge_sort(&mut self) {
+        self.data.bottom_up_merge_sort();
+    }
+
+    fn buffered_merge_sort(&mut self)
+    where
+        T: Clone,
+    {
+        self.data.buffered_merge_sort();
+    }
+
+    fn try_merge_sort(&mut self, start: usize, end: usize) -> Result<(), Error> {
+        self.data.try_merge_sort(start, end)
+    }
+
+    fn rec_insertion_sort(&mut self) {
+        self.data.rec_insertion_sort();
+    }
+
+    fn sort_by_key<K: Ord>(&mut self, key: impl Fn(&T) -> K) {
+        self.data.sort_by_key(key);
+    }
+
+    fn heap_sort(&mut self) {
+        self.data.heap_sort();
+    }
+
+    fn traced_heap_sort(&mut self, sink: &mut impl Sink) {
+        self.data.traced_heap_sort(sink);
+    }
+
+    fn quick_sort(&mut self, start: usize, end: usize, pivot: Pivot) {
+        self.data.quick_sort(start, end, pivot);
+    }
+
+    fn quick_sort_with(&mut self, start: usize, end: usize, pivot: Pivot, scheme: Partition) {
+        self.data.quick_sort_with(start, end, pivot, scheme);
+    }
+
+    fn traced_quick_sort(
+        &mut self,
+        start: usize,
+        end: usize,
+        pivot: Pivot,
+        scheme: Partition,
+        sink: &mut impl Sink,
+    ) {
+        self.data.traced_quick_sort(start, end, pivot, scheme, sink);
+    }
+
+    fn try_quick_sort(&mut self, start: usize, end: usize, pivot: Pivot) -> Result<(), Error> {
+        self.data.try_quick_sort(start, end, pivot)
+    }
+
     fn randomize_quick_sort(&mut self, start: usize, end: usize) {
-        let index = rand::thread_rng().gen_range(start..end);
-        self.swap(index, end - 1);
-        if start < end {
-            let mid = self.partition(start, end);
-            self.quick_sort(start, mid);
-            self.quick_sort(mid + 1, end);
+        self.data.randomize_quick_sort(start, end);
+    }
+
+    fn hybrid_sort(&mut self) {
+        self.data.hybrid_sort();
+    }
+
+    fn pancake_sort(&mut self) {
+        self.data.pancake_sort();
+    }
+
+    fn gnome_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.gnome_sort(compare);
+    }
+
+    fn comb_sort(&mut self, compare: impl Fn(&T, &T) -> bool) {
+        self.data.comb_sort(compare);
+    }
+
+    fn cocktail_sort(&mut self) {
+        self.data.cocktail_sort();
+    }
+
+    fn cycle_sort(&mut self, sink: &mut impl Sink) {
+        self.data.cycle_sort(sink);
+    }
+
+    fn bogo_sort(&mut self, max_iters: usize) -> Result<(), Error> {
+        self.data.bogo_sort(max_iters)
+    }
+}
+
+/// The length up to which [`Sort::hybrid_sort`] uses insertion sort.
+const INSERTION_SORT_THRESHOLD: usize = 16;
+
+/// Sorts the slice by QuickSort, falling back to heapsort once the depth
+/// of the recursion is exhausted.
+fn introsort<T: Ord>(mut data: &mut [T], mut depth: u32) {
+    // Recursing into the smaller side and looping on the larger one
+    // bounds the depth of the recursion to O(log n).
+    loop {
+        if data.len() <= INSERTION_SORT_THRESHOLD {
+            data.insertion_sort(|a, b| a > b);
+            return;
+        }
+        if depth == 0 {
+            data.heap_sort();
+            return;
+        }
+        depth -= 1;
+        let pivot = Pivot::MedianOfThree.select(data, 0, data.len(), &mut ());
+        let (low, high) = Partition::Hoare.partition(data, 0, data.len(), pivot, &mut ());
+        let (left, right) = std::mem::take(&mut data).split_at_mut(low);
+        let right = &mut right[high - low..];
+        if left.len() < right.len() {
+            introsort(left, depth);
+            data = right;
+        } else {
+            introsort(right, depth);
+            data = left;
+        }
+    }
+}
+
+/// Moves the element at the index down the max heap stored in the slice
+/// until the subtree rooted at the index is a max heap.
+fn sift_down<T: Ord>(heap: &mut [T], mut index: usize, sink: &mut impl Sink) {
+    sink.record(Event::Heapify { index });
+    loop {
+        let mut largest = index;
+        for child in [2 * index + 1, 2 * index + 2] {
+            if child < heap.len() {
+                sink.record(Event::Compare {
+                    i: child,
+                    j: largest,
+                });
+                if heap[child] > heap[largest] {
+                    largest = child;
+                }
+            }
+        }
+        if largest == index {
+            return;
+        }
+        swap(heap, index, largest, sink);
+        index = largest;
+    }
+}
+
+/// Swaps the elements at the two indices, and records the swap to the sink
+/// unless the indices are equal.
+fn swap<T>(data: &mut [T], i: usize, j: usize, sink: &mut impl Sink) {
+    if i != j {
+        sink.record(Event::Swap { i, j });
+        data.swap(i, j);
+    }
+}
+
+/// Returns the first index of the range `low..high` for which the
+/// predicate is false, the predicate being true for the indices before it
+/// and false for the ones after.
+fn partition_point(mut low: usize, mut high: usize, mut pred: impl FnMut(usize) -> bool) -> usize {
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if pred(middle) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+/// Checks that `start..end` is a range of a slice of length `len`.
+const fn check_range(len: usize, start: usize, end: usize) -> Result<(), Error> {
+    if start > end {
+        Err(Error::new(ErrorKind::InvalidRange))
+    } else if end > len {
+        Err(Error::new(ErrorKind::IndexOutOfBounds))
+    } else {
+        Ok(())
+    }
+}
+
+/// Sorts the slice by merging the pairs of adjacent sorted runs of length
+/// 1, 2, 4 and so on, ordering the elements with `is_less`.
+fn bottom_up_merge_sort_by<T>(data: &mut [T], is_less: &impl Fn(&T, &T) -> bool) {
+    let len = data.len();
+    let mut width = 1;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let middle = (start + width).min(len);
+            let end = (middle + width).min(len);
+            merge_by(data, start, middle, end, is_less, &mut ());
         }
+        width *= 2;
     }
 }
 
+/// Sorts the slice by merging its sorted halves through the buffer.
+fn buffered_merge_sort<T: Ord + Clone>(data: &mut [T], buffer: &mut Vec<T>) {
+    if data.len() <= 1 {
+        return;
+    }
+    let middle = data.len() / 2;
+    buffered_merge_sort(&mut data[..middle], buffer);
+    buffered_merge_sort(&mut data[middle..], buffer);
+
+    buffer.clear();
+    buffer.extend_from_slice(&data[..middle]);
+    let (mut left, mut right) = (0, middle);
+    for index in 0..data.len() {
+        if left == buffer.len() {
+            // The rest of the second half is already in place.
+            break;
+        }
+        if right < data.len() && data[right] < buffer[left] {
+            // The element at the index was cloned into the buffer, or
+            // swapped out of a place already merged, so it can go anywhere.
+            data.swap(index, right);
+            right += 1;
+        } else {
+            data[index] = buffer[left].clone();
+            left += 1;
+        }
+    }
+}
+
+/// Merges the sorted ranges `start..middle` and `middle..end` of the slice.
+fn merge<T: Ord>(data: &mut [T], start: usize, middle: usize, end: usize, sink: &mut impl Sink) {
+    merge_by(data, start, middle, end, &|a, b| a < b, sink);
+}
+
+/// Merges the ranges `start..middle` and `middle..end` of the slice, sorted
+/// according to `is_less`.
+///
+/// The resulting elements with the index in the range `start..end` are in
+/// sorted order. The merge is done in place without any buffer: the longest
+/// range is cut in half, the matching cut of the other range is found by
+/// binary search, and rotating the elements between the cuts leaves two
+/// smaller merges. Equal elements keep their relative order.
+fn merge_by<T>(
+    data: &mut [T],
+    start: usize,
+    middle: usize,
+    end: usize,
+    is_less: &impl Fn(&T, &T) -> bool,
+    sink: &mut impl Sink,
+) {
+    if start == middle || middle == end {
+        return;
+    }
+    if end - start == 2 {
+        sink.record(Event::Compare {
+            i: middle,
+            j: start,
+        });
+        if is_less(&data[middle], &data[start]) {
+            swap(data, start, middle, sink);
+        }
+        return;
+    }
+
+    let (first_cut, second_cut) = if middle - start > end - middle {
+        let first_cut = start + (middle - start) / 2;
+        let second_cut = partition_point(middle, end, |index| {
+            sink.record(Event::Compare {
+                i: index,
+                j: first_cut,
+            });
+            is_less(&data[index], &data[first_cut])
+        });
+        (first_cut, second_cut)
+    } else {
+        let second_cut = middle + (end - middle) / 2;
+        let first_cut = partition_point(start, middle, |index| {
+            sink.record(Event::Compare {
+                i: second_cut,
+                j: index,
+            });
+            !is_less(&data[second_cut], &data[index])
+        });
+        (first_cut, second_cut)
+    };
+    if first_cut < middle && middle < second_cut {
+        for index in first_cut..second_cut {
+            sink.record(Event::Write { index });
+        }
+        data[first_cut..second_cut].rotate_left(middle - first_cut);
+    }
+    let middle = first_cut + second_cut - middle;
+    merge_by(data, start, first_cut, middle, is_less, sink);
+    merge_by(data, middle, second_cut, end, is_less, sink);
+}
+
+/// Partitions the elements of the slice in the specified bounds around
+/// the last element of the range, and returns the final index of the pivot.
+fn partition<T: Ord>(data: &mut [T], start: usize, end: usize, sink: &mut impl Sink) -> usize {
+    let pivot = end - 1;
+    let mut last_smallest = start;
+    for index in start..pivot {
+        sink.record(Event::Compare { i: index, j: pivot });
+        if data[index] <= data[pivot] {
+            swap(data, last_smallest, index, sink);
+            last_smallest += 1;
+        }
+    }
+    swap(data, last_smallest, pivot, sink);
+    last_smallest
+}
+
+/// Partitions the elements of the slice in the specified bounds around
+/// the first element of the range, and returns the final index of the
+/// pivot.
+///
+/// The elements before the index are not greater than the pivot, and the
+/// elements after it are not smaller.
+fn hoare_partition<T: Ord>(
+    data: &mut [T],
+    start: usize,
+    end: usize,
+    sink: &mut impl Sink,
+) -> usize {
+    let (mut low, mut high) = (start + 1, end - 1);
+    loop {
+        while low <= high {
+            sink.record(Event::Compare { i: low, j: start });
+            if data[low] >= data[start] {
+                break;
+            }
+            low += 1;
+        }
+        while low <= high {
+            sink.record(Event::Compare { i: high, j: start });
+            if data[high] <= data[start] {
+                break;
+            }
+            high -= 1;
+        }
+        if low >= high {
+            break;
+        }
+        swap(data, low, high, sink);
+        low += 1;
+        high -= 1;
+    }
+    swap(data, start, high, sink);
+    high
+}
+
+/// Partitions the elements of the slice in the specified bounds around the
+/// element at the index, and returns the range of the elements equal to it.
+///
+/// The smaller elements are moved before the range and the greater ones
+/// after it, so many equal elements do not unbalance the partition.
+pub(crate) fn three_way_partition<T: Ord>(
+    data: &mut [T],
+    start: usize,
+    end: usize,
+    pivot: usize,
+    sink: &mut impl Sink,
+) -> (usize, usize) {
+    swap(data, start, pivot, sink);
+    // The elements in `low..index` are equal to the pivot, which stays at
+    // the index `low`, and the elements in `high..end` are greater.
+    let (mut low, mut index, mut high) = (start, start + 1, end);
+    while index < high {
+        sink.record(Event::Compare { i: index, j: low });
+        match data[index].cmp(&data[low]) {
+            Ordering::Less => {
+                swap(data, low, index, sink);
+                low += 1;
+                index += 1;
+            }
+            Ordering::Equal => index += 1,
+            Ordering::Greater => {
+                high -= 1;
+                swap(data, index, high, sink);
+            }
+        }
+    }
+    (low, high)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+
     use quickcheck::{Arbitrary, Gen};
     use quickcheck_macros::quickcheck;
 
@@ -182,6 +1144,14 @@ mod tests {
         Container { data } == container
     }
 
+    #[quickcheck]
+    fn binary_insertion_sort_ascending(mut container: Container<i32>) -> bool {
+        let mut data = container.data.clone();
+        data.sort();
+        container.binary_insertion_sort();
+        Container { data } == container
+    }
+
     #[quickcheck]
     fn selection_sort_ascending(mut container: Container<i32>) -> bool {
         let mut data = container.data.clone();
@@ -198,6 +1168,28 @@ mod tests {
         Container { data } == container
     }
 
+    #[quickcheck]
+    fn bubble_sort_both_orders(mut container: Container<i32>) -> bool {
+        let mut data = container.data.clone();
+        data.sort();
+        container.bubble_sort(|a, b| a > b);
+        let ascending = container.data == data;
+        data.reverse();
+        container.bubble_sort(|a, b| a < b);
+        ascending && Container { data } == container
+    }
+
+    #[quickcheck]
+    fn shell_sort_both_orders(mut container: Container<i32>) -> bool {
+        let mut data = container.data.clone();
+        data.sort();
+        container.shell_sort(|a, b| a > b);
+        let ascending = container.data == data;
+        data.reverse();
+        container.shell_sort(|a, b| a < b);
+        ascending && Container { data } == container
+    }
+
     #[quickcheck]
     fn merge_sort_ascending(mut container: Container<i32>) -> bool {
         let mut data = container.data.clone();
@@ -220,7 +1212,390 @@ mod tests {
         let mut container = Container::new(vec![-9, 0, 1, 3, 2]);
         let mut data = container.data.clone();
         data.sort();
-        container.quick_sort(0, data.len());
+        container.quick_sort(0, data.len(), Pivot::Last);
         assert_eq!(Container { data }, container);
     }
+
+    #[quickcheck]
+    fn heap_sort_ascending(mut container: Container<i32>) -> bool {
+        let mut data = container.data.clone();
+        data.sort();
+        container.heap_sort();
+        Container { data } == container
+    }
+
+    #[quickcheck]
+    fn quick_sort_with_every_pivot(data: Vec<i32>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        [Pivot::Last, Pivot::Random, Pivot::MedianOfThree]
+            .into_iter()
+            .all(|pivot| {
+                let mut sorted = data.clone();
+                sorted.quick_sort(0, data.len(), pivot);
+                sorted == expected
+            })
+    }
+
+    #[quickcheck]
+    fn quick_sort_with_every_scheme(data: Vec<i8>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        [Partition::Lomuto, Partition::Hoare, Partition::ThreeWay]
+            .into_iter()
+            .all(|scheme| {
+                [Pivot::Last, Pivot::Random, Pivot::MedianOfThree]
+                    .into_iter()
+                    .all(|pivot| {
+                        let mut sorted = Container::new(data.clone());
+                        sorted.quick_sort_with(0, data.len(), pivot, scheme);
+                        sorted.inner() == expected
+                    })
+            })
+    }
+
+    #[quickcheck]
+    fn hybrid_sort_ascending(mut container: Container<i32>) -> bool {
+        let mut data = container.data.clone();
+        data.sort();
+        container.hybrid_sort();
+        Container { data } == container
+    }
+
+    #[test]
+    fn introsort_falls_back_to_heap_sort() {
+        let mut data: Vec<i32> = (0..100).map(|key| (key * 37) % 101).collect();
+        let mut expected = data.clone();
+        expected.sort();
+        introsort(&mut data, 0);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn validate_ranges() {
+        let mut container = Container::new(vec![4, 3, 2, 1]);
+        assert_eq!(
+            container.try_merge_sort(3, 1),
+            Err(Error::new(ErrorKind::InvalidRange))
+        );
+        assert_eq!(
+            container.try_quick_sort(0, 5, Pivot::Last),
+            Err(Error::new(ErrorKind::IndexOutOfBounds))
+        );
+        assert_eq!(container.inner(), [4, 3, 2, 1]);
+
+        assert_eq!(container.try_merge_sort(0, 2), Ok(()));
+        assert_eq!(container.inner(), [3, 4, 2, 1]);
+        assert_eq!(container.try_quick_sort(1, 4, Pivot::MedianOfThree), Ok(()));
+        assert_eq!(container.inner(), [3, 1, 2, 4]);
+        assert_eq!(container.try_quick_sort(4, 4, Pivot::Random), Ok(()));
+    }
+
+    #[test]
+    fn randomize_quick_sort_subrange() {
+        let mut data = vec![9, 4, 8, 1, 7, 0];
+        data.randomize_quick_sort(1, 5);
+        assert_eq!(data, vec![9, 1, 4, 7, 8, 0]);
+        data.randomize_quick_sort(3, 3);
+        assert_eq!(data, vec![9, 1, 4, 7, 8, 0]);
+    }
+
+    #[quickcheck]
+    fn exchange_sorts(data: Vec<i32>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        let sorts: [fn(&mut [i32]); 4] = [
+            <[i32]>::pancake_sort,
+            |data| data.gnome_sort(|a, b| a > b),
+            |data| data.comb_sort(|a, b| a > b),
+            <[i32]>::cocktail_sort,
+        ];
+        sorts.iter().all(|sort| {
+            let mut sorted = data.clone();
+            sort(&mut sorted);
+            sorted == expected
+        })
+    }
+
+    #[test]
+    fn exchange_sorts_containers() {
+        let mut container = Container::new(vec![3, -7, 0, 3, 12, -1]);
+        container.pancake_sort();
+        assert_eq!(container.inner(), [-7, -1, 0, 3, 3, 12]);
+        let sorts: [fn(&mut Container<i32>); 3] = [
+            |container| container.gnome_sort(|a, b| a > b),
+            |container| container.comb_sort(|a, b| a > b),
+            Container::cocktail_sort,
+        ];
+        for sort in sorts {
+            let mut container = Container::new(vec![5, 4, 3, 2, 1]);
+            sort(&mut container);
+            assert_eq!(container.inner(), [1, 2, 3, 4, 5]);
+        }
+        let mut container = Container::new(vec![1, 4, 2, 5, 3]);
+        container.gnome_sort(|a, b| a < b);
+        assert_eq!(container.inner(), [5, 4, 3, 2, 1]);
+        container.comb_sort(|a, b| a > b);
+        assert_eq!(container.inner(), [1, 2, 3, 4, 5]);
+    }
+
+    #[quickcheck]
+    fn cycle_sort_writes_each_misplaced_element_once(data: Vec<i8>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        let misplaced = data.iter().zip(&expected).filter(|(a, b)| a != b).count();
+
+        let mut events = Vec::new();
+        let mut sorted = data;
+        sorted.cycle_sort(&mut events);
+        let mut written: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Write { index } => Some(*index),
+                _ => None,
+            })
+            .collect();
+        written.sort();
+        written.dedup();
+        sorted == expected && events.len() == misplaced && written.len() == misplaced
+    }
+
+    #[test]
+    fn cycle_sort() {
+        let mut container = Container::new(vec![3, 0, 1, 2, 4]);
+        let mut events = Vec::new();
+        container.cycle_sort(&mut events);
+        assert_eq!(container.inner(), [0, 1, 2, 3, 4]);
+        assert_eq!(
+            events,
+            [3, 2, 1, 0].map(|index| Event::Write { index }).to_vec()
+        );
+
+        let mut data = [2, 1, 2, 1];
+        data.cycle_sort(&mut ());
+        assert_eq!(data, [1, 1, 2, 2]);
+    }
+
+    #[quickcheck]
+    fn shuffle_permutes(mut data: Vec<i32>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        shuffle(&mut data);
+        data.sort();
+        data == expected
+    }
+
+    #[test]
+    fn bogo_sort() {
+        let mut sorted = Container::new(vec![1, 2, 2, 3]);
+        assert_eq!(sorted.bogo_sort(0), Ok(()));
+
+        let mut data = vec![3, 1, 2];
+        assert_eq!(data.bogo_sort(10_000), Ok(()));
+        assert_eq!(data, vec![1, 2, 3]);
+
+        let mut data: Vec<_> = (0..20).rev().collect();
+        assert_eq!(
+            data.bogo_sort(100),
+            Err(Error::new(ErrorKind::IterationLimit))
+        );
+        data.sort();
+        assert!(data.into_iter().eq(0..20));
+    }
+
+    /// Returns the number of comparisons made by QuickSort on the input.
+    fn quick_sort_comparisons(
+        input: impl Iterator<Item = usize>,
+        pivot: Pivot,
+        scheme: Partition,
+    ) -> usize {
+        /// A key which counts the comparisons made with it.
+        #[derive(Debug)]
+        struct Counted<'a>(usize, &'a Cell<usize>);
+        impl PartialEq for Counted<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other).is_eq()
+            }
+        }
+        impl Eq for Counted<'_> {}
+        impl PartialOrd for Counted<'_> {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Counted<'_> {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.1.set(self.1.get() + 1);
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let comparisons = Cell::new(0);
+        let mut data: Vec<_> = input.map(|key| Counted(key, &comparisons)).collect();
+        let len = data.len();
+        data.quick_sort_with(0, len, pivot, scheme);
+        assert!(data.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        comparisons.get()
+    }
+
+    #[test]
+    fn quick_sort_adversarial_inputs() {
+        let n = 1024;
+        let quadratic = n * (n - 1) / 2;
+        let lomuto = Partition::Lomuto;
+        assert_eq!(quick_sort_comparisons(0..n, Pivot::Last, lomuto), quadratic);
+        assert_eq!(
+            quick_sort_comparisons((0..n).rev(), Pivot::Last, lomuto),
+            quadratic
+        );
+        for pivot in [Pivot::Random, Pivot::MedianOfThree] {
+            assert!(quick_sort_comparisons(0..n, pivot, lomuto) < quadratic / 8);
+            assert!(quick_sort_comparisons((0..n).rev(), pivot, lomuto) < quadratic / 8);
+        }
+    }
+
+    #[test]
+    fn quick_sort_duplicates() {
+        let n = 1024;
+        let equal = || std::iter::repeat(7).take(n);
+        let comparisons = |scheme| quick_sort_comparisons(equal(), Pivot::Last, scheme);
+        assert_eq!(comparisons(Partition::Lomuto), n * (n - 1) / 2);
+        assert!(comparisons(Partition::Hoare) < 2 * n * 11);
+        assert_eq!(comparisons(Partition::ThreeWay), n - 1);
+    }
+
+    #[quickcheck]
+    fn sort_vectors_in_place(data: Vec<i32>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+
+        let mut insertion = data.clone();
+        insertion.insertion_sort(|a, b| a > b);
+        let mut selection = data.clone();
+        selection.selection_sort(|a, b| a < b);
+        let mut merge = data.clone();
+        merge.merge_sort(0, data.len());
+        let mut bottom_up = data.clone();
+        bottom_up.bottom_up_merge_sort();
+        let mut buffered = data.clone();
+        buffered.buffered_merge_sort();
+        let mut recursive = data;
+        recursive.rec_insertion_sort();
+
+        [insertion, selection, merge, bottom_up, buffered, recursive]
+            .iter()
+            .all(|sorted| *sorted == expected)
+    }
+
+    /// A key which cannot be cloned.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Key(i32);
+
+    #[quickcheck]
+    fn sort_keys_without_cloning(data: Vec<i32>) -> bool {
+        let keys = || data.iter().copied().map(Key).collect::<Vec<_>>();
+        let mut expected = keys();
+        expected.sort();
+
+        let mut naive = keys();
+        naive.naive_insertion_sort(|a, b| a > b);
+        let mut insertion = keys();
+        insertion.insertion_sort(|a, b| a > b);
+        let mut merge = keys();
+        merge.merge_sort(0, data.len());
+        let mut bottom_up = keys();
+        bottom_up.bottom_up_merge_sort();
+        let mut recursive = Container::new(keys());
+        recursive.rec_insertion_sort();
+
+        [naive, insertion, merge, bottom_up, recursive.data]
+            .iter()
+            .all(|sorted| *sorted == expected)
+    }
+
+    /// An element decorated with its index in the input, compared by its
+    /// key only.
+    #[derive(Debug, Clone, Copy)]
+    pub(super) struct Decorated {
+        key: u8,
+        index: usize,
+    }
+
+    impl PartialEq for Decorated {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+    impl Eq for Decorated {}
+    impl PartialOrd for Decorated {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Decorated {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    /// Returns true if the sort orders the keys and leaves the elements of
+    /// equal keys in their input order.
+    ///
+    /// Each key is decorated with its index in the input, so the sorted
+    /// elements are stable if the pairs of their key and index increase.
+    pub(super) fn is_stable(keys: &[u8], sort: impl FnOnce(&mut Vec<Decorated>)) -> bool {
+        let mut data: Vec<Decorated> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, &key)| Decorated { key, index })
+            .collect();
+        sort(&mut data);
+        data.len() == keys.len()
+            && data
+                .windows(2)
+                .all(|pair| (pair[0].key, pair[0].index) < (pair[1].key, pair[1].index))
+    }
+
+    #[quickcheck]
+    fn stable_sorts(keys: Vec<u8>) -> bool {
+        // Few distinct keys make many ties.
+        let keys: Vec<u8> = keys.into_iter().map(|key| key % 8).collect();
+        let sorts: [fn(&mut Vec<Decorated>); 11] = [
+            |data| data.naive_insertion_sort(|a, b| a > b),
+            |data| data.insertion_sort(|a, b| a > b),
+            |data| data.bubble_sort(|a, b| a > b),
+            |data| data.binary_insertion_sort(),
+            |data| {
+                let len = data.len();
+                data.merge_sort(0, len);
+            },
+            |data| data.bottom_up_merge_sort(),
+            |data| data.buffered_merge_sort(),
+            |data| data.rec_insertion_sort(),
+            |data| data.gnome_sort(|a, b| a > b),
+            |data| data.cocktail_sort(),
+            |data| Sort::sort_by_key(data.as_mut_slice(), |item| item.key),
+        ];
+        sorts.into_iter().all(|sort| is_stable(&keys, sort))
+    }
+
+    #[quickcheck]
+    fn sort_by_key_is_stable(pairs: Vec<(i8, u8)>) -> bool {
+        let mut expected = pairs.clone();
+        expected.sort_by_key(|pair| std::cmp::Reverse(pair.0));
+        let mut container = Container::new(pairs);
+        container.sort_by_key(|pair| std::cmp::Reverse(pair.0));
+        container.inner() == expected
+    }
+
+    #[test]
+    fn sort_arrays_and_subslices() {
+        let mut array = [5, -1, 3, 0, 2];
+        array.naive_insertion_sort(|a, b| a > b);
+        assert_eq!(array, [-1, 0, 2, 3, 5]);
+
+        let mut data = vec![9, 8, 7, 3, 1, 2, 0];
+        data[2..5].merge_sort(0, 3);
+        assert_eq!(data, vec![9, 8, 1, 3, 7, 2, 0]);
+    }
 }
@@ -0,0 +1,167 @@
+//! Fuzzy sorting of intervals.
+//!
+//! Given closed intervals `[a_i, b_i]`, fuzzy sorting permutes them so that
+//! there are points `c_i` in `[a_i, b_i]` with `c_1 <= c_2 <= ... <= c_n`.
+//! Overlapping intervals can be put in any order, so the more the intervals
+//! overlap, the less work there is to do. See CLRS problem 7-6.
+
+use rand::Rng;
+
+/// Interval is a closed interval `[low, high]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    /// The left endpoint.
+    pub low: T,
+
+    /// The right endpoint.
+    pub high: T,
+}
+
+impl<T: Ord> Interval<T> {
+    /// Creates new interval between the two endpoints, in any order.
+    pub fn new(a: T, b: T) -> Self {
+        if a <= b {
+            Self { low: a, high: b }
+        } else {
+            Self { low: b, high: a }
+        }
+    }
+
+    /// Returns true if the two intervals have a point in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.low <= other.high && other.low <= self.high
+    }
+}
+
+/// Fuzzy sorts the intervals.
+///
+/// It is a randomized QuickSort where the pivot is the intersection of a
+/// random interval with the intervals overlapping it. The intervals which
+/// contain that intersection are all equal with respect to the order, so
+/// only the intervals on its left and on its right are sorted recursively.
+/// The expected running time is O(n log n), and O(n) when all the
+/// intervals have a point in common.
+pub fn fuzzy_sort<T: Ord + Clone>(intervals: &mut [Interval<T>]) {
+    let (mut start, mut end) = (0, intervals.len());
+    // Recursing into the smaller side and looping on the larger one
+    // bounds the depth of the recursion to O(log n).
+    while start + 1 < end {
+        let (left, right) = partition(intervals, start, end);
+        if left - start < end - right {
+            fuzzy_sort(&mut intervals[start..left]);
+            start = right;
+        } else {
+            fuzzy_sort(&mut intervals[right..end]);
+            end = left;
+        }
+    }
+}
+
+/// Returns true if the intervals are fuzzy sorted.
+///
+/// Each point `c_i` is chosen as small as possible, that is the largest of
+/// `a_i` and `c_{i - 1}`, which fails only if it is beyond `b_i`.
+pub fn is_fuzzy_sorted<T: Ord>(intervals: &[Interval<T>]) -> bool {
+    let mut point: Option<&T> = None;
+    for interval in intervals {
+        let current = match point {
+            Some(point) if *point > interval.low => point,
+            _ => &interval.low,
+        };
+        if *current > interval.high {
+            return false;
+        }
+        point = Some(current);
+    }
+    true
+}
+
+/// Partitions the intervals of the range `start..end` around the
+/// intersection of a random interval with the intervals overlapping it.
+///
+/// It returns the bounds `left..right` of the intervals containing the
+/// intersection: the intervals before them end before it, and the
+/// intervals after them start after it.
+fn partition<T: Ord + Clone>(
+    intervals: &mut [Interval<T>],
+    start: usize,
+    end: usize,
+) -> (usize, usize) {
+    let index = rand::thread_rng().gen_range(start..end);
+    let mut pivot = intervals[index].clone();
+    // The pivot only shrinks, so an interval overlapping the final pivot
+    // overlapped it when it was scanned, and contains it.
+    for interval in &intervals[start..end] {
+        if interval.overlaps(&pivot) {
+            pivot.low = pivot.low.max(interval.low.clone());
+            pivot.high = pivot.high.min(interval.high.clone());
+        }
+    }
+
+    // Dijkstra's three-way partition: `start..left` are before the pivot,
+    // `left..current` contain it and `right..end` are after it.
+    let (mut left, mut current, mut right) = (start, start, end);
+    while current < right {
+        if intervals[current].high < pivot.low {
+            intervals.swap(left, current);
+            left += 1;
+            current += 1;
+        } else if intervals[current].low > pivot.high {
+            right -= 1;
+            intervals.swap(current, right);
+        } else {
+            current += 1;
+        }
+    }
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn intervals(endpoints: &[(i16, i16)]) -> Vec<Interval<i16>> {
+        endpoints
+            .iter()
+            .map(|&(a, b)| Interval::new(a, b))
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_sort_intervals() {
+        let mut data = intervals(&[(8, 9), (1, 3), (2, 5), (10, 12), (4, 4), (0, 1)]);
+        assert!(!is_fuzzy_sorted(&data));
+        fuzzy_sort(&mut data);
+        assert!(is_fuzzy_sorted(&data));
+    }
+
+    #[test]
+    fn overlapping_intervals_keep_their_order() {
+        let original = intervals(&[(5, 9), (0, 6), (3, 7), (6, 6), (1, 8)]);
+        let mut data = original.clone();
+        fuzzy_sort(&mut data);
+        assert_eq!(data, original);
+    }
+
+    #[test]
+    fn verify_fuzzy_sorted() {
+        assert!(is_fuzzy_sorted::<i16>(&[]));
+        assert!(is_fuzzy_sorted(&intervals(&[(0, 5), (1, 2), (2, 9)])));
+        assert!(!is_fuzzy_sorted(&intervals(&[(0, 5), (3, 4), (1, 2)])));
+        assert!(!is_fuzzy_sorted(&intervals(&[(3, 3), (2, 2)])));
+    }
+
+    #[quickcheck]
+    fn fuzzy_sort_permutes_into_fuzzy_order(endpoints: Vec<(i16, i16)>) -> bool {
+        let mut data = intervals(&endpoints);
+        fuzzy_sort(&mut data);
+
+        let mut expected = intervals(&endpoints);
+        expected.sort_by_key(|interval| (interval.low, interval.high));
+        let mut permutation = data.clone();
+        permutation.sort_by_key(|interval| (interval.low, interval.high));
+        is_fuzzy_sorted(&data) && permutation == expected
+    }
+}
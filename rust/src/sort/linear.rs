@@ -0,0 +1,249 @@
+//! Sorting in linear time.
+//!
+//! Comparison sorts need Ω(n log n) comparisons in the worst case. The
+//! sorts of this module go below that bound by using the keys as integers
+//! instead of comparing them. See CLRS chapter 8.
+
+use super::Sort;
+use crate::Container;
+
+/// IntegerKey is a primitive integer type usable by the linear sorts.
+pub trait IntegerKey: Copy + Ord {
+    /// The number of bytes of the type.
+    const BYTES: usize;
+
+    /// Maps the integer to an unsigned integer of the same order.
+    ///
+    /// The sign bit of signed integers is flipped, so that negative
+    /// integers come before the positive ones.
+    fn ordered(self) -> u64;
+}
+
+macro_rules! impl_unsigned_key {
+    ($($t:ty),*) => {
+        $(
+            impl IntegerKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn ordered(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed_key {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl IntegerKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn ordered(self) -> u64 {
+                    ((self as $u) ^ (1 << (<$u>::BITS - 1))) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned_key!(u8, u16, u32, u64, usize);
+impl_signed_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+/// The [`LinearSort`] trait defines the sorts of integer containers which
+/// do not compare the elements.
+pub trait LinearSort {
+    /// Counting sort algorithm.
+    ///
+    /// It counts the occurrences of each key, then places the elements
+    /// from the last to the first at the positions given by the cumulated
+    /// counts, so the sort is stable. It takes O(n + k) time and space,
+    /// where `k` is the difference between the largest and the smallest
+    /// key, so it is meant for keys within a small range. See CLRS section
+    /// 8.2.
+    ///
+    /// When `k` is larger than both `n` and `2^16`, the counts would not
+    /// pay off, or not even fit in memory, so the elements are sorted by
+    /// [`LinearSort::radix_sort`] instead, which is stable too.
+    fn counting_sort(&mut self);
+
+    /// Least significant digit radix sort algorithm.
+    ///
+    /// The elements are sorted by a stable counting sort on each byte of
+    /// their keys, from the least to the most significant one. It takes
+    /// O(d (n + 256)) time for keys of `d` bytes. See CLRS section 8.3.
+    fn radix_sort(&mut self);
+
+    /// Bucket sort algorithm.
+    ///
+    /// The range of the keys is divided into `n` buckets of equal width,
+    /// each bucket is sorted by insertion, and the buckets are then
+    /// concatenated. It takes O(n) expected time when the keys are
+    /// uniformly distributed over their range. See CLRS section 8.4.
+    fn bucket_sort(&mut self);
+}
+
+impl<T: IntegerKey> LinearSort for [T] {
+    fn counting_sort(&mut self) {
+        let Some(min) = self.iter().map(|item| item.ordered()).min() else {
+            return;
+        };
+        let max = self.iter().map(|item| item.ordered()).max().unwrap_or(min);
+        if max - min > (self.len() as u64).max(COUNTING_SORT_MAX_RANGE) {
+            self.radix_sort();
+            return;
+        }
+        counting_sort_by(self, (max - min) as usize + 1, |item| {
+            (item.ordered() - min) as usize
+        });
+    }
+
+    fn radix_sort(&mut self) {
+        for byte in 0..T::BYTES {
+            counting_sort_by(self, 256, |item| {
+                (item.ordered() >> (8 * byte) & 0xff) as usize
+            });
+        }
+    }
+
+    fn bucket_sort(&mut self) {
+        let (Some(min), Some(max)) = (
+            self.iter().map(|item| item.ordered()).min(),
+            self.iter().map(|item| item.ordered()).max(),
+        ) else {
+            return;
+        };
+        let count = self.len();
+        let width = u128::from(max - min) + 1;
+        let mut buckets = vec![Vec::new(); count];
+        for item in self.iter() {
+            let offset = u128::from(item.ordered() - min);
+            buckets[(offset * count as u128 / width) as usize].push(*item);
+        }
+
+        let mut index = 0;
+        for mut bucket in buckets {
+            bucket.insertion_sort(|a, b| a > b);
+            self[index..index + bucket.len()].copy_from_slice(&bucket);
+            index += bucket.len();
+        }
+    }
+}
+
+impl<T: IntegerKey> LinearSort for Container<T> {
+    fn counting_sort(&mut self) {
+        self.data.counting_sort();
+    }
+
+    fn radix_sort(&mut self) {
+        self.data.radix_sort();
+    }
+
+    fn bucket_sort(&mut self) {
+        self.data.bucket_sort();
+    }
+}
+
+/// The range of the keys up to which [`LinearSort::counting_sort`] counts
+/// the keys whatever the number of elements.
+const COUNTING_SORT_MAX_RANGE: u64 = 1 << 16;
+
+/// Stable counting sort of the elements by a digit in `0..base`.
+fn counting_sort_by<T: Copy>(data: &mut [T], base: usize, digit: impl Fn(&T) -> usize) {
+    let mut positions = vec![0; base + 1];
+    for item in data.iter() {
+        positions[digit(item) + 1] += 1;
+    }
+    for index in 1..positions.len() {
+        positions[index] += positions[index - 1];
+    }
+
+    // `positions[d]` is now the index of the first element with digit `d`.
+    let mut sorted = data.to_vec();
+    for item in data.iter() {
+        let position = &mut positions[digit(item)];
+        sorted[*position] = *item;
+        *position += 1;
+    }
+    data.copy_from_slice(&sorted);
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn sorted<T: Ord + Clone>(data: &[T]) -> Vec<T> {
+        let mut sorted = data.to_vec();
+        sorted.sort();
+        sorted
+    }
+
+    #[test]
+    fn sort_integers() {
+        let data = [329, 457, 657, 839, 436, 720, 355, -1, 0, 329];
+        for sort in [
+            <[i16]>::counting_sort,
+            <[i16]>::radix_sort,
+            <[i16]>::bucket_sort,
+        ] {
+            let mut items = data;
+            sort(&mut items);
+            assert_eq!(items.to_vec(), sorted(&data));
+        }
+
+        let mut container = Container::new(vec![3_u64, u64::MAX, 0, 7]);
+        container.radix_sort();
+        assert_eq!(container.inner(), [0, 3, 7, u64::MAX]);
+        container.bucket_sort();
+        assert_eq!(container.inner(), [0, 3, 7, u64::MAX]);
+    }
+
+    #[test]
+    fn counting_sort_extreme_keys() {
+        let mut signed = [i64::MAX, 0, i64::MIN, -1];
+        signed.counting_sort();
+        assert_eq!(signed, [i64::MIN, -1, 0, i64::MAX]);
+
+        let mut unsigned = Container::new(vec![u32::MAX, 0, 7, u32::MAX]);
+        unsigned.counting_sort();
+        assert_eq!(unsigned.inner(), [0, 7, u32::MAX, u32::MAX]);
+
+        let mut spread: Vec<u32> = (0..1000).map(|key| key * 1000).rev().collect();
+        spread.counting_sort();
+        assert_eq!(spread, (0..1000).map(|key| key * 1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ordered_keys() {
+        assert!(i8::MIN.ordered() < (-1_i8).ordered());
+        assert!((-1_i64).ordered() < 0_i64.ordered());
+        assert_eq!(0_i32.ordered(), 1 << 31);
+        assert_eq!(u16::MAX.ordered(), 65535);
+    }
+
+    #[quickcheck]
+    fn counting_sort_small_keys(mut bytes: Vec<i8>, mut words: Vec<u16>) -> bool {
+        let (expected_bytes, expected_words) = (sorted(&bytes), sorted(&words));
+        bytes.counting_sort();
+        words.counting_sort();
+        bytes == expected_bytes && words == expected_words
+    }
+
+    #[quickcheck]
+    fn radix_sort_agrees_with_sort(mut data: Vec<i64>, mut sizes: Vec<usize>) -> bool {
+        let (expected, expected_sizes) = (sorted(&data), sorted(&sizes));
+        data.radix_sort();
+        sizes.radix_sort();
+        data == expected && sizes == expected_sizes
+    }
+
+    #[quickcheck]
+    fn bucket_sort_agrees_with_sort(mut data: Vec<i32>, mut bytes: Vec<u8>) -> bool {
+        let (expected, expected_bytes) = (sorted(&data), sorted(&bytes));
+        data.bucket_sort();
+        bytes.bucket_sort();
+        data == expected && bytes == expected_bytes
+    }
+}
@@ -0,0 +1,106 @@
+//! Parallel sorting.
+//!
+//! Merge sort and QuickSort divide the elements into two parts which are
+//! sorted independently, so the parts can be sorted in parallel with
+//! [`rayon::join`]. Below a threshold, a part is sorted sequentially, as
+//! sending it to another thread would cost more than sorting it.
+
+use super::{merge, Partition, Pivot, Sort};
+use crate::Container;
+
+/// The [`ParallelSort`] trait defines the sorts which sort the parts of the
+/// elements in parallel.
+pub trait ParallelSort<T> {
+    /// Parallel merge sort algorithm.
+    ///
+    /// Both halves are sorted in parallel, and then merged in place like
+    /// [`Sort::merge_sort`] does. The halves of at most `threshold`
+    /// elements are sorted by [`Sort::merge_sort`]. The sort is stable.
+    fn par_merge_sort(&mut self, threshold: usize);
+
+    /// Parallel QuickSort algorithm.
+    ///
+    /// Each range is partitioned around its [`Pivot::MedianOfThree`] with
+    /// [`Partition::Hoare`], and both sides are sorted in parallel. The
+    /// sides of at most `threshold` elements are sorted by
+    /// [`Sort::quick_sort`].
+    fn par_quick_sort(&mut self, threshold: usize);
+}
+
+impl<T> ParallelSort<T> for [T]
+where
+    T: Ord + Send,
+{
+    fn par_merge_sort(&mut self, threshold: usize) {
+        let len = self.len();
+        if len <= threshold.max(1) {
+            self.merge_sort(0, len);
+            return;
+        }
+        let middle = len / 2;
+        let (left, right) = self.split_at_mut(middle);
+        rayon::join(
+            || left.par_merge_sort(threshold),
+            || right.par_merge_sort(threshold),
+        );
+        merge(self, 0, middle, len, &mut ());
+    }
+
+    fn par_quick_sort(&mut self, threshold: usize) {
+        let len = self.len();
+        if len <= threshold.max(1) {
+            self.quick_sort(0, len, Pivot::MedianOfThree);
+            return;
+        }
+        let pivot = Pivot::MedianOfThree.select(self, 0, len, &mut ());
+        let (low, high) = Partition::Hoare.partition(self, 0, len, pivot, &mut ());
+        let (left, right) = self.split_at_mut(low);
+        let right = &mut right[high - low..];
+        rayon::join(
+            || left.par_quick_sort(threshold),
+            || right.par_quick_sort(threshold),
+        );
+    }
+}
+
+impl<T> ParallelSort<T> for Container<T>
+where
+    T: Ord + Send,
+{
+    fn par_merge_sort(&mut self, threshold: usize) {
+        self.data.par_merge_sort(threshold);
+    }
+
+    fn par_quick_sort(&mut self, threshold: usize) {
+        self.data.par_quick_sort(threshold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::sort::tests::is_stable;
+
+    #[quickcheck]
+    fn parallel_sorts(data: Vec<i32>, threshold: u8) -> bool {
+        let threshold = usize::from(threshold % 16);
+        let mut expected = data.clone();
+        expected.sort();
+
+        let mut merge = Container::new(data.clone());
+        merge.par_merge_sort(threshold);
+        let mut quick = Container::new(data);
+        quick.par_quick_sort(threshold);
+        merge.inner() == expected && quick.inner() == expected
+    }
+
+    #[quickcheck]
+    fn par_merge_sort_is_stable(keys: Vec<u8>, threshold: u8) -> bool {
+        let keys: Vec<u8> = keys.into_iter().map(|key| key % 8).collect();
+        is_stable(&keys, |data| {
+            data.par_merge_sort(usize::from(threshold % 16))
+        })
+    }
+}
@@ -0,0 +1,152 @@
+//! Patience sorting.
+//!
+//! The elements are dealt one by one onto piles, like the cards of the
+//! solitaire game: each element goes on the leftmost pile whose top is not
+//! smaller, or on a new pile to the right. The tops of the piles are then
+//! increasing from left to right, and each pile is sorted from its top to
+//! its bottom, so merging the piles sorts the elements.
+//!
+//! The number of piles is the length of the longest increasing subsequence,
+//! and remembering the top of the previous pile when dealing an element
+//! gives such a subsequence.
+
+use crate::heap::{Heap, MinHeap, Value};
+use crate::stack::DummyStack;
+
+/// Sorts the slice by patience sorting.
+///
+/// It returns the indices, in the slice before sorting, of the elements of
+/// a longest strictly increasing subsequence. Dealing takes O(n log n)
+/// time with a binary search over the piles, and merging the `k` piles
+/// with a min heap takes O(n log k) time.
+pub fn patience_sort<T: Ord>(data: &mut [T]) -> Vec<usize> {
+    // The piles and the predecessors hold indices of the elements, so the
+    // elements are only moved once their final order is known.
+    let mut piles: Vec<DummyStack<usize>> = Vec::new();
+    let mut predecessors = vec![None; data.len()];
+    for index in 0..data.len() {
+        let pile = piles.partition_point(|pile| data[top(pile)] < data[index]);
+        if pile > 0 {
+            predecessors[index] = Some(top(&piles[pile - 1]));
+        }
+        if pile == piles.len() {
+            piles.push(DummyStack::new());
+        }
+        piles[pile].push(index);
+    }
+
+    let mut subsequence = Vec::with_capacity(piles.len());
+    let mut last = piles.last().map(top);
+    while let Some(index) = last {
+        subsequence.push(index);
+        last = predecessors[index];
+    }
+    subsequence.reverse();
+
+    let order = merge(data, piles);
+    permute(data, &order);
+    subsequence
+}
+
+/// Returns the element on top of a pile.
+fn top(pile: &DummyStack<usize>) -> usize {
+    *pile.peek().expect("piles are never empty")
+}
+
+/// Merges the piles, and returns the indices of the elements in sorted
+/// order.
+fn merge<T: Ord>(data: &[T], mut piles: Vec<DummyStack<usize>>) -> Vec<usize> {
+    let mut order = Vec::with_capacity(data.len());
+    let tops = piles
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, pile)| {
+            pile.pop().map(|item| Value {
+                key: (&data[item], item),
+                index,
+            })
+        })
+        .collect();
+    let mut heap = Heap::<_, MinHeap>::new(tops);
+    heap.build_min_heap();
+
+    while let Some(&Value {
+        key: (_, item),
+        index,
+    }) = heap.min()
+    {
+        order.push(item);
+        // The next element of the pile replaces the smallest element.
+        match piles[index].pop() {
+            Some(next) => {
+                heap[0] = Value {
+                    key: (&data[next], next),
+                    index,
+                };
+                heap.min_heapify(0);
+            }
+            None => {
+                heap.extract_min();
+            }
+        }
+    }
+    order
+}
+
+/// Moves the element at `order[k]` to the index `k`, for every `k`.
+///
+/// The permutation is applied cycle by cycle with swaps.
+fn permute<T>(data: &mut [T], order: &[usize]) {
+    let mut done = vec![false; data.len()];
+    for start in 0..data.len() {
+        let mut current = start;
+        while !done[current] {
+            done[current] = true;
+            let next = order[current];
+            if next == start {
+                break;
+            }
+            data.swap(current, next);
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::lis;
+
+    #[test]
+    fn sort_cards() {
+        let mut cards = [6, 3, 5, 10, 11, 2, 9, 14, 13, 7, 4, 8, 12];
+        let original = cards;
+        let subsequence = patience_sort(&mut cards);
+        assert_eq!(cards, [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(
+            subsequence
+                .iter()
+                .map(|&index| original[index])
+                .collect::<Vec<_>>(),
+            vec![3, 5, 7, 8, 12]
+        );
+        assert_eq!(patience_sort::<i8>(&mut []), vec![]);
+    }
+
+    #[quickcheck]
+    fn sorts_and_finds_longest_increasing_subsequence(data: Vec<i8>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        let mut sorted = data.clone();
+        let subsequence = patience_sort(&mut sorted);
+
+        sorted == expected
+            && subsequence.len() == lis::quadratic(&data).len()
+            && subsequence.len() == lis::patience(&data).len()
+            && subsequence
+                .windows(2)
+                .all(|pair| pair[0] < pair[1] && data[pair[0]] < data[pair[1]])
+    }
+}
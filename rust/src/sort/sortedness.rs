@@ -0,0 +1,143 @@
+//! Sortedness analysis.
+//!
+//! These measures tell how far a container is from being sorted. An
+//! inversion is a pair of elements in the wrong order, so a sorted
+//! container has none and a reversed one has `n (n - 1) / 2`, and every
+//! swap of adjacent elements made by insertion sort removes exactly one.
+//! A run is a maximal range of elements already in order.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::Container;
+
+impl<T> Container<T> {
+    /// Returns true if no element is greater than the next one according
+    /// to `compare`.
+    pub fn is_sorted_by(&self, compare: impl Fn(&T, &T) -> Ordering) -> bool {
+        self.data
+            .windows(2)
+            .all(|pair| compare(&pair[0], &pair[1]) != Ordering::Greater)
+    }
+}
+
+impl<T: Ord> Container<T> {
+    /// Returns true if the elements are in increasing order.
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted_by(T::cmp)
+    }
+
+    /// Returns the maximal ranges of elements in increasing order, from
+    /// the first to the last.
+    ///
+    /// The ranges cover the container, and each one starts at an element
+    /// smaller than the previous one.
+    pub fn runs(&self) -> Vec<Range<usize>> {
+        let mut runs = Vec::new();
+        let mut start = 0;
+        for index in 1..=self.len() {
+            if index == self.len() || self.data[index] < self.data[index - 1] {
+                runs.push(start..index);
+                start = index;
+            }
+        }
+        runs
+    }
+}
+
+impl<T: Ord + Clone> Container<T> {
+    /// Returns the number of inversions, that is the pairs of indices
+    /// `i < j` such that the element at `i` is greater than the one at `j`.
+    ///
+    /// A copy of the elements is merge sorted: when an element of the right
+    /// half is merged, it makes an inversion with each element left in the
+    /// left half. It takes O(n log n) time. See CLRS problem 2-4.
+    pub fn count_inversions(&self) -> usize {
+        let mut data = self.data.clone();
+        sort_counting_inversions(&mut data)
+    }
+}
+
+/// Sorts the slice by merge sort and returns its number of inversions.
+fn sort_counting_inversions<T: Ord + Clone>(data: &mut [T]) -> usize {
+    if data.len() <= 1 {
+        return 0;
+    }
+    let middle = data.len() / 2;
+    let mut inversions = sort_counting_inversions(&mut data[..middle])
+        + sort_counting_inversions(&mut data[middle..]);
+
+    let (left, right) = (data[..middle].to_vec(), data[middle..].to_vec());
+    let (mut i, mut j) = (0, 0);
+    for slot in data.iter_mut() {
+        if j == right.len() || i < left.len() && left[i] <= right[j] {
+            *slot = left[i].clone();
+            i += 1;
+        } else {
+            *slot = right[j].clone();
+            j += 1;
+            inversions += left.len() - i;
+        }
+    }
+    inversions
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::sort::Sort;
+
+    /// Returns the number of inversions by comparing every pair.
+    fn brute_force_inversions(data: &[i32]) -> usize {
+        (0..data.len())
+            .map(|i| data[i + 1..].iter().filter(|&&b| data[i] > b).count())
+            .sum()
+    }
+
+    #[test]
+    fn sortedness() {
+        // CLRS problem 2-4(a).
+        let container = Container::new(vec![2, 3, 8, 6, 1]);
+        assert_eq!(container.count_inversions(), 5);
+        assert_eq!(container.runs(), vec![0..3, 3..4, 4..5]);
+        assert!(!container.is_sorted());
+        assert!(Container::new(vec![8, 6, 3, 2, 1]).is_sorted_by(|a, b| b.cmp(a)));
+
+        let reversed = Container::new((0..10).rev().collect::<Vec<_>>());
+        assert_eq!(reversed.count_inversions(), 45);
+        let empty = Container::<i32>::new(vec![]);
+        assert!(empty.is_sorted());
+        assert!(empty.runs().is_empty());
+        assert_eq!(Container::new(vec![1, 1, 2]).runs(), vec![0..3]);
+    }
+
+    #[quickcheck]
+    fn inversions_agree_with_brute_force(data: Vec<i32>) -> bool {
+        let container = Container::new(data);
+        container.count_inversions() == brute_force_inversions(container.inner())
+    }
+
+    #[quickcheck]
+    fn runs_cover_the_container(data: Vec<i8>) -> bool {
+        let container = Container::new(data);
+        let runs = container.runs();
+        runs.iter().flat_map(Clone::clone).eq(0..container.len())
+            && runs
+                .iter()
+                .all(|run| Container::new(container.inner()[run.clone()].to_vec()).is_sorted())
+            && runs
+                .windows(2)
+                .all(|pair| container[pair[1].start] < container[pair[0].end - 1])
+            && (runs.len() == 1) == (container.is_sorted() && !container.is_empty())
+    }
+
+    #[quickcheck]
+    fn sorts_leave_no_inversion(data: Vec<i32>) -> bool {
+        let mut container = Container::new(data);
+        let len = container.len();
+        container.merge_sort(0, len);
+        container.is_sorted() && container.count_inversions() == 0 && container.runs().len() <= 1
+    }
+}
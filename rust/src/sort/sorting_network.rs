@@ -0,0 +1,199 @@
+//! Sorting networks.
+//!
+//! A sorting network is a fixed sequence of comparators, each exchanging
+//! two elements if they are out of order. The comparisons do not depend on
+//! the data, so a network sorts small inputs without any branch on the
+//! control flow of the algorithm. By the 0-1 principle, a network sorts
+//! every input if it sorts every sequence of zeros and ones. See Knuth, The
+//! Art of Computer Programming, volume 3, section 5.3.4.
+
+/// Comparators of the networks with the fewest comparators known for
+/// `n <= 8` elements, indexed by `n`.
+const OPTIMAL: [&[(usize, usize)]; 9] = [
+    &[],
+    &[],
+    &[(0, 1)],
+    &[(0, 1), (1, 2), (0, 1)],
+    &[(0, 1), (2, 3), (0, 2), (1, 3), (1, 2)],
+    &[
+        (0, 1),
+        (3, 4),
+        (2, 4),
+        (2, 3),
+        (0, 3),
+        (0, 2),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+    ],
+    &[
+        (1, 2),
+        (4, 5),
+        (0, 2),
+        (3, 5),
+        (0, 1),
+        (3, 4),
+        (1, 4),
+        (0, 3),
+        (2, 5),
+        (1, 3),
+        (2, 4),
+        (2, 3),
+    ],
+    &[
+        (1, 2),
+        (3, 4),
+        (5, 6),
+        (0, 2),
+        (3, 5),
+        (4, 6),
+        (0, 1),
+        (4, 5),
+        (2, 6),
+        (0, 4),
+        (1, 5),
+        (0, 3),
+        (2, 5),
+        (1, 3),
+        (2, 4),
+        (2, 3),
+    ],
+    &[
+        (0, 2),
+        (1, 3),
+        (4, 6),
+        (5, 7),
+        (0, 4),
+        (1, 5),
+        (2, 6),
+        (3, 7),
+        (0, 1),
+        (2, 3),
+        (4, 5),
+        (6, 7),
+        (2, 4),
+        (3, 5),
+        (1, 4),
+        (3, 6),
+        (1, 2),
+        (3, 4),
+        (5, 6),
+    ],
+];
+
+/// Returns the comparators of a sorting network for `n` elements.
+///
+/// A comparator `(i, j)`, with `i < j`, moves the smaller of the elements
+/// at `i` and `j` to `i`. The networks for `n <= 8` are optimal; larger
+/// networks are Batcher's odd-even merge sort, with O(n log² n)
+/// comparators.
+pub fn comparators(n: usize) -> Vec<(usize, usize)> {
+    match OPTIMAL.get(n) {
+        Some(network) => network.to_vec(),
+        None => odd_even_merge(n),
+    }
+}
+
+/// Sorts the array with a sorting network.
+pub fn sort_network<T: Ord, const N: usize>(data: &mut [T; N]) {
+    sort_slice(data);
+}
+
+/// Sorts the slice with a sorting network for its length.
+///
+/// It is meant for small slices, as the base case of other sorts.
+pub fn sort_slice<T: Ord>(data: &mut [T]) {
+    match OPTIMAL.get(data.len()) {
+        Some(network) => apply(data, network),
+        None => apply(data, &odd_even_merge(data.len())),
+    }
+}
+
+/// Applies the comparators to the slice.
+fn apply<T: Ord>(data: &mut [T], comparators: &[(usize, usize)]) {
+    for &(i, j) in comparators {
+        if data[j] < data[i] {
+            data.swap(i, j);
+        }
+    }
+}
+
+/// Returns the comparators of Batcher's odd-even merge sort for `n`
+/// elements.
+///
+/// It sorts runs of length `2p` by merging sorted runs of length `p`. The
+/// comparators reaching beyond `n` are dropped, as if the input was padded
+/// with elements larger than all the others up to a power of two.
+fn odd_even_merge(n: usize) -> Vec<(usize, usize)> {
+    let mut comparators = Vec::new();
+    let mut p = 1;
+    while p < n {
+        let mut k = p;
+        while k > 0 {
+            for j in (k % p..n.saturating_sub(k)).step_by(2 * k) {
+                for i in 0..k.min(n - j - k) {
+                    if (i + j) / (2 * p) == (i + j + k) / (2 * p) {
+                        comparators.push((i + j, i + j + k));
+                    }
+                }
+            }
+            k /= 2;
+        }
+        p *= 2;
+    }
+    comparators
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns true if the network sorts all the sequences of `n` bits.
+    fn sorts_all_bits(n: usize) -> bool {
+        let network = comparators(n);
+        (0..1u32 << n).all(|bits| {
+            let mut data: Vec<_> = (0..n).map(|i| bits >> i & 1).collect();
+            apply(&mut data, &network);
+            data.windows(2).all(|pair| pair[0] <= pair[1])
+        })
+    }
+
+    #[test]
+    fn optimal_networks() {
+        let sizes: Vec<_> = (0..=8).map(|n| comparators(n).len()).collect();
+        assert_eq!(sizes, vec![0, 0, 1, 3, 5, 9, 12, 16, 19]);
+        assert!((0..=8).all(sorts_all_bits));
+    }
+
+    #[test]
+    fn odd_even_merge_networks() {
+        assert_eq!(odd_even_merge(8).len(), 19);
+        assert_eq!(odd_even_merge(16).len(), 63);
+        assert!((0..=16).all(|n| {
+            let network = odd_even_merge(n);
+            network.iter().all(|&(i, j)| i < j && j < n)
+        }));
+        assert!((9..=14).all(sorts_all_bits));
+    }
+
+    #[test]
+    fn sort_arrays() {
+        let mut data = [5, 2, 7, 1, 1, 0, 9, 3];
+        sort_network(&mut data);
+        assert_eq!(data, [0, 1, 1, 2, 3, 5, 7, 9]);
+
+        let mut words = ["pear", "fig", "apple"];
+        sort_network(&mut words);
+        assert_eq!(words, ["apple", "fig", "pear"]);
+    }
+
+    #[quickcheck]
+    fn sort_slices(mut data: Vec<i32>) -> bool {
+        let mut expected = data.clone();
+        expected.sort();
+        sort_slice(&mut data);
+        data == expected
+    }
+}
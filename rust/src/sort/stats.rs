@@ -0,0 +1,137 @@
+//! Sort statistics.
+//!
+//! The traced sorts of [`Sort`](super::Sort) record their comparisons,
+//! swaps and recursive calls to a [`Sink`]. [`SortStats`] counts them, so
+//! the textbook bounds on the number of steps can be checked empirically:
+//! for example insertion sort makes exactly one swap per inversion, and
+//! QuickSort with [`Pivot::Last`](super::Pivot::Last) makes `n (n - 1) / 2`
+//! comparisons on a sorted input.
+
+use crate::trace::{Event, Sink};
+
+/// SortStats counts the steps recorded by a traced sort.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SortStats {
+    /// Number of comparisons of two elements.
+    pub comparisons: usize,
+    /// Number of swaps of two elements.
+    pub swaps: usize,
+    /// Number of elements written other than by a swap.
+    pub writes: usize,
+    /// Maximum number of nested recursive calls.
+    pub recursion_depth: usize,
+    /// Number of recursive calls which have not returned yet.
+    depth: usize,
+}
+
+impl SortStats {
+    /// Returns the statistics of the steps the sort records to its sink.
+    pub fn measure(sort: impl FnOnce(&mut Self)) -> Self {
+        let mut stats = Self::default();
+        sort(&mut stats);
+        stats
+    }
+}
+
+impl Sink for SortStats {
+    fn record(&mut self, event: Event) {
+        match event {
+            Event::Compare { .. } => self.comparisons += 1,
+            Event::Swap { .. } => self.swaps += 1,
+            Event::Write { .. } => self.writes += 1,
+            Event::Call { .. } => {
+                self.depth += 1;
+                self.recursion_depth = self.recursion_depth.max(self.depth);
+            }
+            Event::Return => self.depth = self.depth.saturating_sub(1),
+            Event::Heapify { .. } | Event::RotateLeft { .. } | Event::RotateRight { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::sort::{Partition, Pivot, Sort};
+    use crate::Container;
+
+    /// Returns the number of bits of `n`, that is `⌊lg n⌋ + 1` for a
+    /// positive `n`.
+    const fn bits(n: usize) -> usize {
+        (usize::BITS - n.leading_zeros()) as usize
+    }
+
+    #[test]
+    fn quick_sort_worst_case() {
+        let n = 100;
+        let mut data: Vec<_> = (0..n).collect();
+        let stats = SortStats::measure(|sink| {
+            data.traced_quick_sort(0, n, Pivot::Last, Partition::Lomuto, sink);
+        });
+        assert_eq!(data, (0..n).collect::<Vec<_>>());
+        assert_eq!(stats.comparisons, n * (n - 1) / 2);
+        assert_eq!(stats.swaps, 0);
+        // Every partition leaves the larger side to the loop.
+        assert_eq!(stats.recursion_depth, 2);
+    }
+
+    #[test]
+    fn cycle_sort_writes() {
+        let mut container = Container::new(vec![3, 2, 1, 0]);
+        let stats = SortStats::measure(|sink| container.cycle_sort(sink));
+        assert_eq!(container.inner(), &[0, 1, 2, 3]);
+        assert_eq!(stats.writes, 4);
+        assert_eq!(stats.comparisons, 0);
+    }
+
+    #[quickcheck]
+    fn insertion_sort_swaps_inversions(data: Vec<i16>) -> bool {
+        let mut container = Container::new(data);
+        let (n, inversions) = (container.len(), container.count_inversions());
+        let stats = SortStats::measure(|sink| container.traced_insertion_sort(|a, b| a > b, sink));
+        container.is_sorted()
+            && stats.swaps == inversions
+            && stats.comparisons == n * n.saturating_sub(1) / 2
+            && stats.recursion_depth == 0
+    }
+
+    #[quickcheck]
+    fn merge_sort_depth(data: Vec<i16>) -> bool {
+        let mut container = Container::new(data);
+        let n = container.len();
+        let stats = SortStats::measure(|sink| container.traced_merge_sort(0, n, sink));
+        container.is_sorted()
+            && stats.recursion_depth == 1 + bits(n.saturating_sub(1))
+            && stats.comparisons <= n * bits(n) * bits(n)
+    }
+
+    #[quickcheck]
+    fn heap_sort_comparisons(data: Vec<i16>) -> bool {
+        let mut container = Container::new(data);
+        let n = container.len();
+        let stats = SortStats::measure(|sink| container.traced_heap_sort(sink));
+        container.is_sorted() && stats.comparisons <= 2 * n * bits(n) && stats.recursion_depth == 0
+    }
+
+    #[quickcheck]
+    fn quick_sort_depth_is_logarithmic(data: Vec<i16>, sorted: bool) -> bool {
+        let schemes = [Partition::Lomuto, Partition::Hoare, Partition::ThreeWay];
+        let pivots = [Pivot::Last, Pivot::Random, Pivot::MedianOfThree];
+        let mut data = data;
+        if sorted {
+            data.sort_unstable();
+        }
+        schemes.iter().all(|&scheme| {
+            pivots.iter().all(|&pivot| {
+                let mut container = Container::new(data.clone());
+                let n = container.len();
+                let stats = SortStats::measure(|sink| {
+                    container.traced_quick_sort(0, n, pivot, scheme, sink);
+                });
+                container.is_sorted() && stats.recursion_depth <= bits(n) + 1
+            })
+        })
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This module implements various stack data structures.
 
+pub mod expr;
+
 /// DummyStack is a stack as a wrapper around vector.
 #[derive(Clone, Debug, Default)]
 pub struct DummyStack<T> {
@@ -12,6 +14,14 @@ pub struct DummyStack<T> {
 }
 
 impl<T> DummyStack<T> {
+    /// Creates new empty stack.
+    pub const fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            top: 0,
+        }
+    }
+
     /// Pushes an element onto the stack.
     pub fn push(&mut self, elem: T) {
         self.buf.push(elem);
@@ -23,6 +33,11 @@ impl<T> DummyStack<T> {
         self.buf.is_empty()
     }
 
+    /// Returns a reference to the element on top of the stack.
+    pub fn peek(&self) -> Option<&T> {
+        self.top.checked_sub(1).and_then(|top| self.buf.get(top))
+    }
+
     /// Pop an element from the stack.
     pub fn pop(&mut self) -> Option<T> {
         // Actually the body of this method can be replaced by
@@ -46,6 +61,7 @@ mod dummy_stack_tests {
         stack.push(2);
         stack.push(3);
         assert_eq!(stack.top, 3);
+        assert_eq!(stack.peek(), Some(&3));
         assert_eq!(stack.pop(), Some(3));
         assert_eq!(stack.pop(), Some(2));
         assert_eq!(stack.top, 1);
@@ -0,0 +1,298 @@
+//! Arithmetic expressions.
+//!
+//! Stacks are the natural tool to process nested structures. This module
+//! checks that brackets are balanced, converts infix expressions to postfix
+//! notation with Dijkstra's shunting-yard algorithm, and evaluates postfix
+//! expressions, also known as reverse Polish notation.
+
+use std::fmt;
+
+use super::DummyStack;
+use crate::{error::ErrorKind, Error};
+
+/// Operator is a binary arithmetic operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// Addition, `+`.
+    Add,
+    /// Subtraction, `-`.
+    Sub,
+    /// Multiplication, `*`.
+    Mul,
+    /// Integer division rounding toward zero, `/`.
+    Div,
+    /// Exponentiation, `^`.
+    Pow,
+}
+
+impl Operator {
+    /// Returns the precedence of the operator, higher binding tighter.
+    const fn precedence(self) -> u8 {
+        match self {
+            Self::Add | Self::Sub => 1,
+            Self::Mul | Self::Div => 2,
+            Self::Pow => 3,
+        }
+    }
+
+    /// Returns true if the operator groups from the left, so that
+    /// `a - b - c` is `(a - b) - c`. Only exponentiation groups from the
+    /// right.
+    const fn is_left_associative(self) -> bool {
+        !matches!(self, Self::Pow)
+    }
+
+    /// Applies the operator to the operands.
+    ///
+    /// It fails with [`ErrorKind::DivisionByZero`] when dividing by zero,
+    /// with [`ErrorKind::Overflow`] if the result does not fit into an
+    /// `i64`, and with [`ErrorKind::InvalidExpression`] for a negative
+    /// exponent.
+    pub fn apply(self, lhs: i64, rhs: i64) -> Result<i64, Error> {
+        let result = match self {
+            Self::Add => lhs.checked_add(rhs),
+            Self::Sub => lhs.checked_sub(rhs),
+            Self::Mul => lhs.checked_mul(rhs),
+            Self::Div if rhs == 0 => return Err(Error::new(ErrorKind::DivisionByZero)),
+            Self::Div => lhs.checked_div(rhs),
+            Self::Pow => {
+                let exponent =
+                    u32::try_from(rhs).map_err(|_| Error::new(ErrorKind::InvalidExpression))?;
+                lhs.checked_pow(exponent)
+            }
+        };
+        result.ok_or_else(|| Error::new(ErrorKind::Overflow))
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+            Self::Pow => '^',
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+/// Token is a lexical unit of an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Token {
+    /// A non-negative integer literal.
+    Number(i64),
+    /// A binary operator.
+    Operator(Operator),
+    /// An opening parenthesis.
+    LeftParen,
+    /// A closing parenthesis.
+    RightParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Operator(operator) => write!(f, "{operator}"),
+            Self::LeftParen => write!(f, "("),
+            Self::RightParen => write!(f, ")"),
+        }
+    }
+}
+
+/// Returns true if every bracket of the text is closed by a bracket of the
+/// same kind, in the reverse order they were opened.
+///
+/// Parentheses, square brackets and curly braces are checked; the other
+/// characters are ignored.
+pub fn is_balanced(text: &str) -> bool {
+    let mut stack = DummyStack::new();
+    for c in text.chars() {
+        match c {
+            '(' => stack.push(')'),
+            '[' => stack.push(']'),
+            '{' => stack.push('}'),
+            ')' | ']' | '}' if stack.pop() != Some(c) => return false,
+            _ => {}
+        }
+    }
+    stack.is_empty()
+}
+
+/// Splits an infix expression into tokens.
+///
+/// Whitespace separates tokens and is otherwise ignored. It fails with
+/// [`ErrorKind::InvalidNumber`] if a literal does not fit into an `i64`,
+/// and with [`ErrorKind::InvalidExpression`] on any other character.
+pub fn tokenize(text: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            '+' => Token::Operator(Operator::Add),
+            '-' => Token::Operator(Operator::Sub),
+            '*' => Token::Operator(Operator::Mul),
+            '/' => Token::Operator(Operator::Div),
+            '^' => Token::Operator(Operator::Pow),
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            c if c.is_whitespace() => continue,
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((index, _)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = index + 1;
+                }
+                let number = text[start..end]
+                    .parse()
+                    .map_err(|_| Error::new(ErrorKind::InvalidNumber))?;
+                Token::Number(number)
+            }
+            _ => return Err(Error::new(ErrorKind::InvalidExpression)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Converts an infix expression to postfix notation.
+///
+/// Numbers go straight to the output, while operators wait on a stack
+/// until an operator of lower precedence, or a closing parenthesis, forces
+/// them out. It fails with [`ErrorKind::InvalidExpression`] if the
+/// parentheses are unbalanced.
+pub fn to_postfix(tokens: &[Token]) -> Result<Vec<Token>, Error> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut stack = DummyStack::new();
+    for &token in tokens {
+        match token {
+            Token::Number(_) => output.push(token),
+            Token::Operator(operator) => {
+                while let Some(&Token::Operator(top)) = stack.peek() {
+                    let precedence = top.precedence().cmp(&operator.precedence());
+                    if precedence.is_lt() || precedence.is_eq() && !operator.is_left_associative() {
+                        break;
+                    }
+                    output.push(Token::Operator(top));
+                    stack.pop();
+                }
+                stack.push(token);
+            }
+            Token::LeftParen => stack.push(token),
+            Token::RightParen => loop {
+                match stack.pop() {
+                    Some(Token::LeftParen) => break,
+                    Some(top) => output.push(top),
+                    None => return Err(Error::new(ErrorKind::InvalidExpression)),
+                }
+            },
+        }
+    }
+    while let Some(token) = stack.pop() {
+        if token == Token::LeftParen {
+            return Err(Error::new(ErrorKind::InvalidExpression));
+        }
+        output.push(token);
+    }
+    Ok(output)
+}
+
+/// Evaluates an expression in postfix notation.
+///
+/// Each operator replaces the two values on top of the stack by its
+/// result. It fails with [`ErrorKind::InvalidExpression`] if an operator
+/// lacks an operand, if values are left without operator, or on a
+/// parenthesis, and with the errors of [`Operator::apply`].
+pub fn evaluate_postfix(tokens: &[Token]) -> Result<i64, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidExpression);
+    let mut stack = DummyStack::new();
+    for &token in tokens {
+        match token {
+            Token::Number(number) => stack.push(number),
+            Token::Operator(operator) => {
+                let rhs = stack.pop().ok_or_else(invalid)?;
+                let lhs = stack.pop().ok_or_else(invalid)?;
+                stack.push(operator.apply(lhs, rhs)?);
+            }
+            Token::LeftParen | Token::RightParen => return Err(invalid()),
+        }
+    }
+    match (stack.pop(), stack.is_empty()) {
+        (Some(value), true) => Ok(value),
+        _ => Err(invalid()),
+    }
+}
+
+/// Evaluates an infix expression.
+pub fn evaluate(text: &str) -> Result<i64, Error> {
+    evaluate_postfix(&to_postfix(&tokenize(text)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn postfix(text: &str) -> String {
+        let tokens = tokenize(text).expect("the expression is valid");
+        let postfix = to_postfix(&tokens).expect("the parentheses are balanced");
+        postfix
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn balanced_brackets() {
+        assert!(is_balanced(""));
+        assert!(is_balanced("{[()()]}"));
+        assert!(is_balanced("fn main() { let v = [1, (2)]; }"));
+        assert!(!is_balanced("(]"));
+        assert!(!is_balanced("(()"));
+        assert!(!is_balanced("())("));
+    }
+
+    #[test]
+    fn shunting_yard() {
+        assert_eq!(postfix("1 + 2 * 3"), "1 2 3 * +");
+        assert_eq!(postfix("(1 + 2) * 3"), "1 2 + 3 *");
+        assert_eq!(postfix("10 - 4 - 3"), "10 4 - 3 -");
+        assert_eq!(postfix("2 ^ 3 ^ 2"), "2 3 2 ^ ^");
+        assert_eq!(
+            postfix("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3"),
+            "3 4 2 * 1 5 - 2 3 ^ ^ / +"
+        );
+    }
+
+    #[test]
+    fn evaluate_expressions() {
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(7));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+        assert_eq!(evaluate("10 - 4 - 3"), Ok(3));
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), Ok(512));
+        assert_eq!(evaluate("((7))/2"), Ok(3));
+        assert_eq!(evaluate("42"), Ok(42));
+    }
+
+    #[test]
+    fn reject_invalid_expressions() {
+        let invalid = Err(Error::new(ErrorKind::InvalidExpression));
+        assert_eq!(evaluate(""), invalid);
+        assert_eq!(evaluate("1 +"), invalid);
+        assert_eq!(evaluate("1 2"), invalid);
+        assert_eq!(evaluate("(1 + 2"), invalid);
+        assert_eq!(evaluate("1 + 2)"), invalid);
+        assert_eq!(evaluate("1 % 2"), invalid);
+        assert_eq!(evaluate("2 ^ (0 - 1)"), invalid);
+        assert_eq!(
+            evaluate("1 / (2 - 2)"),
+            Err(Error::new(ErrorKind::DivisionByZero))
+        );
+        assert_eq!(evaluate("2 ^ 64"), Err(Error::new(ErrorKind::Overflow)));
+        assert_eq!(
+            evaluate("99999999999999999999"),
+            Err(Error::new(ErrorKind::InvalidNumber))
+        );
+    }
+}
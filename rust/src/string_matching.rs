@@ -0,0 +1,329 @@
+//! String matching.
+//!
+//! String matching finds every shift `s` at which a pattern `P` of length
+//! `m` occurs in a text `T` of length `n`, that is `T[s..s + m] == P`. The
+//! algorithms of this module all report the same shifts, overlapping
+//! occurrences included, but trade preprocessing of the pattern for fewer
+//! character comparisons. See CLRS chapter 32.
+//!
+//! The texts and patterns are byte strings; the shifts of a `str` pattern
+//! in a `str` text are byte offsets.
+
+/// Matcher finds the occurrences of a pattern in a text.
+pub trait Matcher {
+    /// Returns the shifts of every occurrence of the pattern in the text,
+    /// in increasing order.
+    ///
+    /// The empty pattern occurs at every shift from 0 to the length of the
+    /// text.
+    fn find_all(&self, text: &[u8], pattern: &[u8]) -> Vec<usize>;
+}
+
+/// Returns the shifts of the empty pattern, or of a pattern longer than
+/// the text, if the pattern is one of those.
+fn trivial_shifts(text: &[u8], pattern: &[u8]) -> Option<Vec<usize>> {
+    if pattern.is_empty() {
+        Some((0..=text.len()).collect())
+    } else if pattern.len() > text.len() {
+        Some(Vec::new())
+    } else {
+        None
+    }
+}
+
+/// Naive compares the pattern with the text at every shift.
+///
+/// It takes O((n - m + 1) m) time and no preprocessing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Naive;
+
+impl Matcher for Naive {
+    fn find_all(&self, text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        if let Some(shifts) = trivial_shifts(text, pattern) {
+            return shifts;
+        }
+        text.windows(pattern.len())
+            .enumerate()
+            .filter(|(_, window)| *window == pattern)
+            .map(|(shift, _)| shift)
+            .collect()
+    }
+}
+
+/// RabinKarp compares the pattern with the text only at the shifts where
+/// their hashes agree.
+///
+/// The hash of a string is its value in radix 256 modulo a prime `q`, and
+/// the hash of each window of the text is computed from the previous one in
+/// O(1) time. A shift where the hashes agree but the strings differ is a
+/// spurious hit, which happens with probability about `1 / q`, so matching
+/// takes O(n + m) expected time and O((n - m + 1) m) worst-case time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RabinKarp {
+    prime: u64,
+}
+
+impl RabinKarp {
+    /// The radix of the hashes, the size of the byte alphabet.
+    const RADIX: u64 = 256;
+
+    /// Creates new matcher hashing modulo the given prime.
+    ///
+    /// A small prime makes spurious hits frequent, which is only useful to
+    /// observe them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prime is zero or not smaller than `2^32`.
+    pub fn with_prime(prime: u64) -> Self {
+        assert!(prime > 0 && prime < 1 << 32, "prime is out of range");
+        Self { prime }
+    }
+
+    /// Returns the prime modulus of the hashes.
+    pub const fn prime(&self) -> u64 {
+        self.prime
+    }
+}
+
+impl Default for RabinKarp {
+    /// Creates new matcher hashing modulo `2^31 - 1`.
+    fn default() -> Self {
+        Self::with_prime((1 << 31) - 1)
+    }
+}
+
+impl Matcher for RabinKarp {
+    fn find_all(&self, text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        if let Some(shifts) = trivial_shifts(text, pattern) {
+            return shifts;
+        }
+        let (n, m, q) = (text.len(), pattern.len(), self.prime);
+        let hash = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .fold(0, |hash, &byte| (hash * Self::RADIX + u64::from(byte)) % q)
+        };
+        // The weight of the first byte of a window.
+        let high = (1..m).fold(1, |high, _| high * Self::RADIX % q);
+
+        let target = hash(pattern);
+        let mut window = hash(&text[..m]);
+        let mut shifts = Vec::new();
+        for shift in 0..=n - m {
+            if window == target && text[shift..shift + m] == *pattern {
+                shifts.push(shift);
+            }
+            if shift < n - m {
+                let dropped = u64::from(text[shift]) * high % q;
+                window = ((window + q - dropped) * Self::RADIX + u64::from(text[shift + m])) % q;
+            }
+        }
+        shifts
+    }
+}
+
+/// Returns the prefix function of the pattern.
+///
+/// `pi[q]` is the length of the longest proper prefix of `pattern[..=q]`
+/// which is also a suffix of it. It takes O(m) amortized time.
+pub fn prefix_function(pattern: &[u8]) -> Vec<usize> {
+    let mut pi = vec![0; pattern.len()];
+    let mut k = 0;
+    for q in 1..pattern.len() {
+        while k > 0 && pattern[k] != pattern[q] {
+            k = pi[k - 1];
+        }
+        if pattern[k] == pattern[q] {
+            k += 1;
+        }
+        pi[q] = k;
+    }
+    pi
+}
+
+/// KnuthMorrisPratt scans the text once, falling back along the prefix
+/// function of the pattern on a mismatch.
+///
+/// The prefix function tells how much of the pattern still matches after a
+/// mismatch, so no text character is compared again after a match. It
+/// takes O(m) preprocessing time and O(n) matching time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KnuthMorrisPratt;
+
+impl Matcher for KnuthMorrisPratt {
+    fn find_all(&self, text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        if let Some(shifts) = trivial_shifts(text, pattern) {
+            return shifts;
+        }
+        let pi = prefix_function(pattern);
+        let m = pattern.len();
+        let mut shifts = Vec::new();
+        // The number of characters matched.
+        let mut q = 0;
+        for (i, &byte) in text.iter().enumerate() {
+            while q > 0 && pattern[q] != byte {
+                q = pi[q - 1];
+            }
+            if pattern[q] == byte {
+                q += 1;
+            }
+            if q == m {
+                shifts.push(i + 1 - m);
+                q = pi[q - 1];
+            }
+        }
+        shifts
+    }
+}
+
+/// BoyerMoore compares the pattern with the text from right to left, and
+/// shifts the pattern by the largest of two heuristics on a mismatch.
+///
+/// The bad-character heuristic aligns the mismatched text character with
+/// its last occurrence in the pattern, and the good-suffix heuristic aligns
+/// the matched suffix with its previous occurrence in the pattern. Long
+/// shifts skip most of the text on large alphabets, although the worst
+/// case takes O((n - m + 1) m) time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoyerMoore;
+
+impl BoyerMoore {
+    /// Returns the last position, counting from 1, of each byte in the
+    /// pattern, 0 for the bytes which do not occur.
+    fn last_occurrence(pattern: &[u8]) -> [usize; 256] {
+        let mut last = [0; 256];
+        for (index, &byte) in pattern.iter().enumerate() {
+            last[usize::from(byte)] = index + 1;
+        }
+        last
+    }
+
+    /// Returns the good-suffix shifts: `shift[j]` is the smallest shift of
+    /// the pattern which is consistent with the match of its suffix
+    /// `pattern[j..]`.
+    fn good_suffix(pattern: &[u8]) -> Vec<usize> {
+        let m = pattern.len();
+        let pi = prefix_function(pattern);
+        let reversed: Vec<u8> = pattern.iter().rev().copied().collect();
+        let pi_reversed = prefix_function(&reversed);
+
+        // The shift aligning the longest prefix which is also a suffix.
+        let mut shift = vec![m - pi[m - 1]; m + 1];
+        for l in 1..=m {
+            let border = pi_reversed[l - 1];
+            let j = m - border;
+            shift[j] = shift[j].min(l - border);
+        }
+        shift
+    }
+}
+
+impl Matcher for BoyerMoore {
+    fn find_all(&self, text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        if let Some(shifts) = trivial_shifts(text, pattern) {
+            return shifts;
+        }
+        let (n, m) = (text.len(), pattern.len());
+        let last = Self::last_occurrence(pattern);
+        let good_suffix = Self::good_suffix(pattern);
+
+        let mut shifts = Vec::new();
+        let mut shift = 0;
+        while shift <= n - m {
+            // The number of characters left to match, from the right.
+            let mut j = m;
+            while j > 0 && pattern[j - 1] == text[shift + j - 1] {
+                j -= 1;
+            }
+            if j == 0 {
+                shifts.push(shift);
+                shift += good_suffix[0];
+            } else {
+                let bad_character = j.saturating_sub(last[usize::from(text[shift + j - 1])]);
+                shift += good_suffix[j].max(bad_character);
+            }
+        }
+        shifts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn matchers() -> [Box<dyn Matcher>; 5] {
+        [
+            Box::new(Naive),
+            Box::new(RabinKarp::default()),
+            Box::new(RabinKarp::with_prime(13)),
+            Box::new(KnuthMorrisPratt),
+            Box::new(BoyerMoore),
+        ]
+    }
+
+    #[test]
+    fn clrs_examples() {
+        for matcher in matchers() {
+            // CLRS figure 32.1.
+            assert_eq!(matcher.find_all(b"abcabaabcabac", b"abaa"), vec![3]);
+            // Overlapping occurrences.
+            assert_eq!(matcher.find_all(b"aaaaa", b"aa"), vec![0, 1, 2, 3]);
+            assert_eq!(
+                matcher.find_all(b"bacbababaabcbab", b"ababaca"),
+                Vec::<usize>::new()
+            );
+            assert_eq!(matcher.find_all(b"abc", b""), vec![0, 1, 2, 3]);
+            assert!(matcher.find_all(b"ab", b"abc").is_empty());
+        }
+    }
+
+    #[test]
+    fn prefix_function_of_clrs_pattern() {
+        // CLRS figure 32.10.
+        assert_eq!(prefix_function(b"ababaca"), vec![0, 0, 1, 2, 3, 0, 1]);
+        assert!(prefix_function(b"").is_empty());
+    }
+
+    #[test]
+    fn boyer_moore_shifts() {
+        // CLRS first edition section 34.5: the suffixes "e" and "ce" occur
+        // again 3 characters to the left, but "nce" does not.
+        let shifts = BoyerMoore::good_suffix(b"reminiscence");
+        assert_eq!(&shifts[9..], [12, 3, 3, 1]);
+        assert_eq!(
+            BoyerMoore::last_occurrence(b"reminiscence")[usize::from(b'e')],
+            12
+        );
+    }
+
+    #[test]
+    fn byte_offsets_in_strings() {
+        let text = "théâtre et thé";
+        assert_eq!(
+            KnuthMorrisPratt.find_all(text.as_bytes(), "thé".as_bytes()),
+            vec![0, 13]
+        );
+    }
+
+    #[quickcheck]
+    fn matchers_agree(text: Vec<u8>, pattern: Vec<u8>) -> bool {
+        // A small alphabet makes occurrences frequent.
+        let text: Vec<u8> = text.iter().map(|byte| b'a' + byte % 3).collect();
+        let pattern: Vec<u8> = pattern.iter().take(4).map(|byte| b'a' + byte % 3).collect();
+        let expected = Naive.find_all(&text, &pattern);
+        matchers()
+            .iter()
+            .all(|matcher| matcher.find_all(&text, &pattern) == expected)
+    }
+
+    #[quickcheck]
+    fn finds_embedded_pattern(prefix: Vec<u8>, pattern: Vec<u8>, suffix: Vec<u8>) -> bool {
+        let text = [prefix.as_slice(), &pattern, &suffix].concat();
+        matchers()
+            .iter()
+            .all(|matcher| matcher.find_all(&text, &pattern).contains(&prefix.len()))
+    }
+}
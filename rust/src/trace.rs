@@ -0,0 +1,83 @@
+//! Execution traces.
+//!
+//! Algorithms report the elementary steps they perform as [`Event`]s to a
+//! [`Sink`]. A sink can record the events in memory to inspect them, or
+//! serialize them so that external tools can animate the algorithm.
+
+pub mod json;
+
+/// Event is an elementary step of an algorithm.
+///
+/// Items are identified by their index in the container being processed,
+/// and tree nodes by an identifier chosen by the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// The items at the two indices are compared.
+    Compare {
+        /// Index of the first item.
+        i: usize,
+        /// Index of the second item.
+        j: usize,
+    },
+
+    /// The items at the two indices are swapped.
+    Swap {
+        /// Index of the first item.
+        i: usize,
+        /// Index of the second item.
+        j: usize,
+    },
+
+    /// An item is written at the index.
+    Write {
+        /// Index of the written item.
+        index: usize,
+    },
+
+    /// The subtree rooted at the index is turned into a heap.
+    Heapify {
+        /// Index of the root of the subtree.
+        index: usize,
+    },
+
+    /// The node is rotated to the left, its right child taking its place.
+    RotateLeft {
+        /// Identifier of the node.
+        node: usize,
+    },
+
+    /// The node is rotated to the right, its left child taking its place.
+    RotateRight {
+        /// Identifier of the node.
+        node: usize,
+    },
+
+    /// A recursive call starts on the items in the range `start..end`.
+    Call {
+        /// Index of the first item of the range.
+        start: usize,
+        /// Index past the last item of the range.
+        end: usize,
+    },
+
+    /// The innermost recursive call which has not returned yet returns.
+    Return,
+}
+
+/// Sink receives the events of a trace.
+pub trait Sink {
+    /// Records an event.
+    fn record(&mut self, event: Event);
+}
+
+/// The unit type discards the events.
+impl Sink for () {
+    fn record(&mut self, _event: Event) {}
+}
+
+/// A vector records the events in memory.
+impl Sink for Vec<Event> {
+    fn record(&mut self, event: Event) {
+        self.push(event);
+    }
+}
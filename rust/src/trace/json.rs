@@ -0,0 +1,147 @@
+//! JSON lines export.
+//!
+//! Each event is written as a JSON object on its own line, with a `type`
+//! field naming the event and one field per attribute, for example
+//! `{"type":"swap","i":0,"j":3}`. The format can be streamed and parsed one
+//! line at a time by web visualizers.
+
+use std::io::{self, Write};
+
+use super::{Event, Sink};
+
+/// JsonLines writes the events it receives as JSON lines.
+///
+/// Recording an event cannot fail, so the first I/O error is kept and
+/// reported by [`JsonLines::finish`]; the events following it are dropped.
+#[derive(Debug)]
+pub struct JsonLines<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonLines<W> {
+    /// Creates new sink writing to the given writer.
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Flushes the writer and returns it, or the first error which occurred.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Sink for JsonLines<W> {
+    fn record(&mut self, event: Event) {
+        if self.error.is_none() {
+            if let Err(error) = writeln!(self.writer, "{}", to_json(&event)) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+/// Serializes an event as a JSON object.
+pub fn to_json(event: &Event) -> String {
+    match event {
+        Event::Compare { i, j } => format!(r#"{{"type":"compare","i":{i},"j":{j}}}"#),
+        Event::Swap { i, j } => format!(r#"{{"type":"swap","i":{i},"j":{j}}}"#),
+        Event::Write { index } => format!(r#"{{"type":"write","index":{index}}}"#),
+        Event::Heapify { index } => format!(r#"{{"type":"heapify","index":{index}}}"#),
+        Event::RotateLeft { node } => format!(r#"{{"type":"rotate_left","node":{node}}}"#),
+        Event::RotateRight { node } => format!(r#"{{"type":"rotate_right","node":{node}}}"#),
+        Event::Call { start, end } => {
+            format!(r#"{{"type":"call","start":{start},"end":{end}}}"#)
+        }
+        Event::Return => r#"{"type":"return"}"#.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sorts the items by insertion, reporting every comparison and swap.
+    fn traced_insertion_sort<S: Sink>(items: &mut [i32], sink: &mut S) {
+        for end in 1..items.len() {
+            let mut j = end;
+            while j > 0 {
+                sink.record(Event::Compare { i: j - 1, j });
+                if items[j - 1] <= items[j] {
+                    break;
+                }
+                sink.record(Event::Swap { i: j - 1, j });
+                items.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    #[test]
+    fn serialize_events() {
+        assert_eq!(
+            to_json(&Event::Compare { i: 1, j: 2 }),
+            r#"{"type":"compare","i":1,"j":2}"#
+        );
+        assert_eq!(
+            to_json(&Event::Write { index: 0 }),
+            r#"{"type":"write","index":0}"#
+        );
+        assert_eq!(
+            to_json(&Event::Heapify { index: 4 }),
+            r#"{"type":"heapify","index":4}"#
+        );
+        assert_eq!(
+            to_json(&Event::RotateRight { node: 7 }),
+            r#"{"type":"rotate_right","node":7}"#
+        );
+        assert_eq!(
+            to_json(&Event::Call { start: 0, end: 3 }),
+            r#"{"type":"call","start":0,"end":3}"#
+        );
+        assert_eq!(to_json(&Event::Return), r#"{"type":"return"}"#);
+    }
+
+    #[test]
+    fn export_sort_trace() {
+        let mut items = [3, 1, 2];
+        let mut sink = JsonLines::new(Vec::new());
+        traced_insertion_sort(&mut items, &mut sink);
+        let output = sink.finish().expect("writing to a vector cannot fail");
+        assert_eq!(items, [1, 2, 3]);
+        assert_eq!(
+            String::from_utf8(output).expect("JSON is valid UTF-8"),
+            concat!(
+                "{\"type\":\"compare\",\"i\":0,\"j\":1}\n",
+                "{\"type\":\"swap\",\"i\":0,\"j\":1}\n",
+                "{\"type\":\"compare\",\"i\":1,\"j\":2}\n",
+                "{\"type\":\"swap\",\"i\":1,\"j\":2}\n",
+                "{\"type\":\"compare\",\"i\":0,\"j\":1}\n",
+            )
+        );
+
+        let mut events = Vec::new();
+        traced_insertion_sort(&mut [2, 1], &mut events);
+        assert_eq!(
+            events,
+            vec![Event::Compare { i: 0, j: 1 }, Event::Swap { i: 0, j: 1 }]
+        );
+    }
+
+    #[test]
+    fn keep_first_error() {
+        // The buffer is too small for a single event.
+        let mut buffer = [0_u8; 8];
+        let mut sink = JsonLines::new(&mut buffer[..]);
+        sink.record(Event::Heapify { index: 0 });
+        sink.record(Event::Heapify { index: 1 });
+        assert!(sink.finish().is_err());
+    }
+}
@@ -0,0 +1,1105 @@
+//! Tree data structures.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+
+use crate::queue::BoundedQueue;
+use crate::stack::DummyStack;
+use crate::{error::ErrorKind, Error};
+
+pub mod avl;
+pub mod btree;
+pub mod flat;
+pub mod optimal;
+pub mod red_black;
+pub mod rope;
+pub mod treap;
+
+type NodeRef<T> = Rc<RefCell<Node<T>>>;
+
+type Link<T> = Option<NodeRef<T>>;
+
+/// Node represents a node in the binary tree.
+#[derive(Debug)]
+struct Node<T> {
+    /// The node key value.
+    key: T,
+
+    /// `parent` points to the parent node.
+    parent: Weak<RefCell<Node<T>>>,
+
+    /// `left` points to the left child.
+    left: Link<T>,
+
+    /// `right` points to the right child.
+    right: Link<T>,
+}
+
+impl<T> Node<T> {
+    /// Creates new node with the given key and parent.
+    fn with_parent(key: T, parent: Weak<RefCell<Node<T>>>) -> NodeRef<T> {
+        Rc::new(RefCell::new(Self {
+            key,
+            parent,
+            left: None,
+            right: None,
+        }))
+    }
+}
+
+/// A BinaryTree is a binary search tree.
+///
+/// For every node `x`, the keys in the left subtree of `x` are at most
+/// `x.key` and the keys in the right subtree of `x` are at least `x.key`.
+#[derive(Debug)]
+pub struct BinaryTree<T> {
+    root: Link<T>,
+}
+
+impl<T> BinaryTree<T> {
+    /// Creates new empty tree.
+    pub const fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns true if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+}
+
+impl<T> BinaryTree<T>
+where
+    T: Ord + Clone,
+{
+    /// Inserts new node with the given key into the tree.
+    pub fn insert(&mut self, key: T) -> &mut Self {
+        let mut parent = None;
+        let mut current = self.root.clone();
+        while let Some(node) = current {
+            current = if key < node.borrow().key {
+                node.borrow().left.clone()
+            } else {
+                node.borrow().right.clone()
+            };
+            parent = Some(node);
+        }
+
+        match parent {
+            None => self.root = Some(Node::with_parent(key, Weak::new())),
+            Some(parent) => {
+                let is_left = key < parent.borrow().key;
+                let node = Some(Node::with_parent(key, Rc::downgrade(&parent)));
+                if is_left {
+                    parent.borrow_mut().left = node;
+                } else {
+                    parent.borrow_mut().right = node;
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns true if a node with the given key is in the tree.
+    pub fn search(&self, key: &T) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Returns the minimum key in the tree.
+    pub fn min(&self) -> Option<T> {
+        self.root
+            .clone()
+            .map(|root| Self::minimum(root).borrow().key.clone())
+    }
+
+    /// Returns the maximum key in the tree.
+    pub fn max(&self) -> Option<T> {
+        self.root
+            .clone()
+            .map(|root| Self::maximum(root).borrow().key.clone())
+    }
+
+    /// Returns the key following the given key in sorted order.
+    ///
+    /// It returns `None` if the key is not in the tree or if it is
+    /// the largest key.
+    pub fn successor(&self, key: &T) -> Option<T> {
+        let node = self.find(key)?;
+        let right = node.borrow().right.clone();
+        if let Some(right) = right {
+            return Some(Self::minimum(right).borrow().key.clone());
+        }
+
+        // Go up until we leave a left subtree.
+        let mut node = node;
+        let mut parent = node.borrow().parent.upgrade();
+        while let Some(ancestor) = parent.clone() {
+            if !Self::is_right_child(&ancestor, &node) {
+                break;
+            }
+            parent = ancestor.borrow().parent.upgrade();
+            node = ancestor;
+        }
+        parent.map(|node| node.borrow().key.clone())
+    }
+
+    /// Returns the key preceding the given key in sorted order.
+    ///
+    /// It returns `None` if the key is not in the tree or if it is
+    /// the smallest key.
+    pub fn predecessor(&self, key: &T) -> Option<T> {
+        let node = self.find(key)?;
+        let left = node.borrow().left.clone();
+        if let Some(left) = left {
+            return Some(Self::maximum(left).borrow().key.clone());
+        }
+
+        // Go up until we leave a right subtree.
+        let mut node = node;
+        let mut parent = node.borrow().parent.upgrade();
+        while let Some(ancestor) = parent.clone() {
+            if !Self::is_left_child(&ancestor, &node) {
+                break;
+            }
+            parent = ancestor.borrow().parent.upgrade();
+            node = ancestor;
+        }
+        parent.map(|node| node.borrow().key.clone())
+    }
+
+    /// Returns the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        fn len<T>(link: &Link<T>) -> usize {
+            link.as_ref().map_or(0, |node| {
+                let node = node.borrow();
+                1 + len(&node.left) + len(&node.right)
+            })
+        }
+        len(&self.root)
+    }
+
+    /// Returns the height of the tree.
+    ///
+    /// The height is the number of nodes on the longest path from the
+    /// root down to a leaf, so an empty tree has height zero and a tree
+    /// with a single node has height one.
+    pub fn height(&self) -> usize {
+        Self::subtree_height(&self.root)
+    }
+
+    /// Returns the depth of the node with the given key.
+    ///
+    /// The depth is the number of edges on the path from the root to
+    /// the node, so the root has depth zero.
+    pub fn depth(&self, key: &T) -> Option<usize> {
+        let mut depth = 0;
+        let mut current = self.root.clone();
+        while let Some(node) = current {
+            current = match key.cmp(&node.borrow().key) {
+                std::cmp::Ordering::Less => node.borrow().left.clone(),
+                std::cmp::Ordering::Greater => node.borrow().right.clone(),
+                std::cmp::Ordering::Equal => return Some(depth),
+            };
+            depth += 1;
+        }
+        None
+    }
+
+    /// Returns true if the tree is height-balanced.
+    ///
+    /// A tree is height-balanced if the heights of the left and right
+    /// subtrees of every node differ by at most one.
+    pub fn is_balanced(&self) -> bool {
+        // Returns the height of a balanced subtree, or `None` as soon as
+        // an unbalanced subtree is found.
+        fn balanced_height<T>(link: &Link<T>) -> Option<usize> {
+            let Some(node) = link else {
+                return Some(0);
+            };
+            let node = node.borrow();
+            let left = balanced_height(&node.left)?;
+            let right = balanced_height(&node.right)?;
+            (left.abs_diff(right) <= 1).then_some(1 + left.max(right))
+        }
+        balanced_height(&self.root).is_some()
+    }
+
+    /// Returns the keys of the tree in sorted order.
+    pub fn inorder_walk(&self) -> Vec<T> {
+        self.inorder().collect()
+    }
+
+    /// Returns the keys of the tree with each subtree root before its
+    /// left and right subtrees.
+    pub fn preorder_walk(&self) -> Vec<T> {
+        self.preorder().collect()
+    }
+
+    /// Returns the keys of the tree with each subtree root after its
+    /// left and right subtrees.
+    pub fn postorder_walk(&self) -> Vec<T> {
+        self.postorder().collect()
+    }
+
+    /// Visits the keys in sorted order using constant extra space.
+    ///
+    /// This is Morris traversal. Before descending into the left subtree
+    /// of a node, the right link of its predecessor, which is always
+    /// missing, is temporarily threaded back to the node. Reaching the
+    /// node again through that thread means its left subtree is done, so
+    /// the thread is removed and the walk continues to the right. Once
+    /// the walk is over the tree is back to its original shape.
+    ///
+    /// The tree is borrowed mutably, so it cannot be read while it is
+    /// threaded. If `visit` panics, the rest of the walk still runs while
+    /// unwinding, without visiting the keys, to remove the threads.
+    pub fn morris_inorder_walk(&mut self, mut visit: impl FnMut(&T)) {
+        let mut walk = MorrisWalk {
+            current: self.root.clone(),
+        };
+        while let Some(node) = walk.next_node() {
+            visit(&node.borrow().key);
+        }
+    }
+
+    /// Creates an iterator over the keys in sorted order.
+    pub fn inorder(&self) -> Inorder<T> {
+        let mut iter = Inorder {
+            stack: DummyStack::new(),
+        };
+        iter.push_left_spine(self.root.clone());
+        iter
+    }
+
+    /// Creates an iterator over the keys in sorted order.
+    ///
+    /// This is the same iterator as [`BinaryTree::inorder`], under the name
+    /// used by the other collections of the crate.
+    pub fn iter(&self) -> Inorder<T> {
+        self.inorder()
+    }
+
+    /// Creates an iterator over the keys in `[low, high]` in sorted order.
+    ///
+    /// Subtrees whose keys all fall outside of the range are never
+    /// visited, so the iterator runs in O(h + k) time for a tree of
+    /// height h and k reported keys.
+    pub fn range(&self, low: &T, high: &T) -> Range<T> {
+        let mut iter = Range {
+            stack: DummyStack::new(),
+            low: low.clone(),
+            high: high.clone(),
+        };
+        iter.push_left_spine(self.root.clone());
+        iter
+    }
+
+    /// Creates an iterator over the keys with each subtree root visited
+    /// before its left and right subtrees.
+    pub fn preorder(&self) -> Preorder<T> {
+        let mut stack = DummyStack::new();
+        if let Some(root) = &self.root {
+            stack.push(root.clone());
+        }
+        Preorder { stack }
+    }
+
+    /// Creates an iterator over the keys with each subtree root visited
+    /// after its left and right subtrees.
+    pub fn postorder(&self) -> Postorder<T> {
+        let mut stack = DummyStack::new();
+        if let Some(root) = &self.root {
+            stack.push((root.clone(), false));
+        }
+        Postorder { stack }
+    }
+
+    /// Creates an iterator over the keys level by level, from the root
+    /// down and from left to right within a level.
+    pub fn level_order(&self) -> LevelOrder<T> {
+        let mut queue = BoundedQueue::with_capacity(self.len());
+        if let Some(root) = &self.root {
+            // The queue is sized to hold every node of the tree.
+            let _ = queue.enqueue(root.clone());
+        }
+        LevelOrder { queue }
+    }
+
+    /// Returns the keys grouped by depth, from the root down and from left
+    /// to right within a level.
+    pub fn levels(&self) -> Vec<Vec<T>> {
+        let mut levels = Vec::new();
+        let mut level: Vec<NodeRef<T>> = self.root.iter().cloned().collect();
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            let mut keys = Vec::with_capacity(level.len());
+            for node in level {
+                let node = node.borrow();
+                keys.push(node.key.clone());
+                next.extend(node.left.iter().chain(&node.right).cloned());
+            }
+            levels.push(keys);
+            level = next;
+        }
+        levels
+    }
+
+    /// Deletes a node with the given key from the tree.
+    ///
+    /// It returns true if a node was deleted. This follows CLRS section
+    /// 12.3: a node with at most one child is replaced by that child,
+    /// otherwise it is replaced by its successor, which is the minimum
+    /// of its right subtree and has no left child.
+    pub fn delete(&mut self, key: &T) -> bool {
+        let Some(node) = self.find(key) else {
+            return false;
+        };
+
+        let (left, right) = {
+            let node = node.borrow();
+            (node.left.clone(), node.right.clone())
+        };
+        match (left, right) {
+            (None, right) => self.transplant(&node, right),
+            (left, None) => self.transplant(&node, left),
+            (Some(left), Some(right)) => {
+                let successor = Self::minimum(right.clone());
+                let is_child = successor
+                    .borrow()
+                    .parent
+                    .upgrade()
+                    .map_or(false, |parent| Rc::ptr_eq(&parent, &node));
+                if !is_child {
+                    let successor_right = successor.borrow().right.clone();
+                    self.transplant(&successor, successor_right);
+                    right.borrow_mut().parent = Rc::downgrade(&successor);
+                    successor.borrow_mut().right = Some(right);
+                }
+                self.transplant(&node, Some(successor.clone()));
+                left.borrow_mut().parent = Rc::downgrade(&successor);
+                successor.borrow_mut().left = Some(left);
+            }
+        }
+        true
+    }
+
+    /// Replaces the subtree rooted at `old` with the subtree rooted at `new`.
+    fn transplant(&mut self, old: &NodeRef<T>, new: Link<T>) {
+        let parent = old.borrow().parent.clone();
+        if let Some(new) = &new {
+            new.borrow_mut().parent = parent.clone();
+        }
+        match parent.upgrade() {
+            None => self.root = new,
+            Some(parent) => {
+                if Self::is_right_child(&parent, old) {
+                    parent.borrow_mut().right = new;
+                } else {
+                    parent.borrow_mut().left = new;
+                }
+            }
+        }
+    }
+
+    /// Returns the height of the subtree rooted at the given node.
+    fn subtree_height(link: &Link<T>) -> usize {
+        link.as_ref().map_or(0, |node| {
+            let node = node.borrow();
+            1 + Self::subtree_height(&node.left).max(Self::subtree_height(&node.right))
+        })
+    }
+
+    /// Finds the node with the given key.
+    fn find(&self, key: &T) -> Link<T> {
+        let mut current = self.root.clone();
+        while let Some(node) = current {
+            current = match key.cmp(&node.borrow().key) {
+                std::cmp::Ordering::Less => node.borrow().left.clone(),
+                std::cmp::Ordering::Greater => node.borrow().right.clone(),
+                std::cmp::Ordering::Equal => return Some(node.clone()),
+            };
+        }
+        None
+    }
+
+    /// Returns the node with the minimum key in the subtree rooted at `node`.
+    fn minimum(mut node: NodeRef<T>) -> NodeRef<T> {
+        loop {
+            let left = node.borrow().left.clone();
+            match left {
+                Some(left) => node = left,
+                None => return node,
+            }
+        }
+    }
+
+    /// Returns the node with the maximum key in the subtree rooted at `node`.
+    fn maximum(mut node: NodeRef<T>) -> NodeRef<T> {
+        loop {
+            let right = node.borrow().right.clone();
+            match right {
+                Some(right) => node = right,
+                None => return node,
+            }
+        }
+    }
+
+    /// Returns true if `child` is the left child of `parent`.
+    fn is_left_child(parent: &NodeRef<T>, child: &NodeRef<T>) -> bool {
+        parent
+            .borrow()
+            .left
+            .as_ref()
+            .map_or(false, |left| Rc::ptr_eq(left, child))
+    }
+
+    /// Returns true if `child` is the right child of `parent`.
+    fn is_right_child(parent: &NodeRef<T>, child: &NodeRef<T>) -> bool {
+        parent
+            .borrow()
+            .right
+            .as_ref()
+            .map_or(false, |right| Rc::ptr_eq(right, child))
+    }
+}
+
+/// Marker of a missing child in the tree encoding.
+const NIL: &str = "#";
+
+impl<T: Display> BinaryTree<T> {
+    /// Draws the tree with one key per line.
+    ///
+    /// Each node is followed by its left and right children, indented
+    /// under it; a missing child is drawn as `#` when its sibling is not.
+    /// For example the tree built from `[6, 4, 7, 2, 8]` is drawn as:
+    ///
+    /// ```text
+    /// 6
+    /// +-- 4
+    /// |   +-- 2
+    /// |   `-- #
+    /// `-- 7
+    ///     +-- #
+    ///     `-- 8
+    /// ```
+    pub fn render(&self) -> String {
+        fn draw_children<T: Display>(node: &NodeRef<T>, prefix: &str, out: &mut String) {
+            let node = node.borrow();
+            if node.left.is_none() && node.right.is_none() {
+                return;
+            }
+            for (child, last) in [(&node.left, false), (&node.right, true)] {
+                let (branch, indent) = if last {
+                    ("`-- ", "    ")
+                } else {
+                    ("+-- ", "|   ")
+                };
+                out.push_str(prefix);
+                out.push_str(branch);
+                match child {
+                    None => out.push_str(NIL),
+                    Some(child) => out.push_str(&child.borrow().key.to_string()),
+                }
+                out.push('\n');
+                if let Some(child) = child {
+                    draw_children(child, &format!("{prefix}{indent}"), out);
+                }
+            }
+        }
+
+        let Some(root) = &self.root else {
+            return format!("{NIL}\n");
+        };
+        let mut out = format!("{}\n", root.borrow().key);
+        draw_children(root, "", &mut out);
+        out
+    }
+}
+
+impl<T> BinaryTree<T>
+where
+    T: Ord + Clone + Display + FromStr,
+{
+    /// Encodes the tree as a string.
+    ///
+    /// The keys are written in preorder, separated by spaces, and each
+    /// missing child is written as `#`. The encoding of a tree is unique,
+    /// so [`BinaryTree::deserialize`] rebuilds the exact same shape.
+    /// The keys must not display as `#` or contain whitespace.
+    pub fn serialize(&self) -> String {
+        fn encode<T: Display>(link: &Link<T>, tokens: &mut Vec<String>) {
+            match link {
+                None => tokens.push(NIL.to_string()),
+                Some(node) => {
+                    let node = node.borrow();
+                    tokens.push(node.key.to_string());
+                    encode(&node.left, tokens);
+                    encode(&node.right, tokens);
+                }
+            }
+        }
+        let mut tokens = Vec::new();
+        encode(&self.root, &mut tokens);
+        tokens.join(" ")
+    }
+
+    /// Decodes a tree encoded by [`BinaryTree::serialize`].
+    ///
+    /// It fails if a key cannot be parsed, if the encoding is truncated
+    /// or has trailing tokens, or if the keys violate the binary search
+    /// tree property.
+    pub fn deserialize(encoding: &str) -> Result<Self, Error> {
+        fn decode<'a, T: Ord + Clone + FromStr>(
+            tokens: &mut impl Iterator<Item = &'a str>,
+            parent: Weak<RefCell<Node<T>>>,
+            low: Option<&T>,
+            high: Option<&T>,
+        ) -> Result<Link<T>, Error> {
+            let invalid = || Error::new(ErrorKind::InvalidTreeEncoding);
+            let token = tokens.next().ok_or_else(invalid)?;
+            if token == NIL {
+                return Ok(None);
+            }
+            let key: T = token.parse().map_err(|_| invalid())?;
+            if low.map_or(false, |low| key < *low) || high.map_or(false, |high| key > *high) {
+                return Err(invalid());
+            }
+
+            let node = Node::with_parent(key.clone(), parent);
+            let left = decode(tokens, Rc::downgrade(&node), low, Some(&key))?;
+            let right = decode(tokens, Rc::downgrade(&node), Some(&key), high)?;
+            node.borrow_mut().left = left;
+            node.borrow_mut().right = right;
+            Ok(Some(node))
+        }
+
+        let mut tokens = encoding.split_whitespace();
+        let root = decode(&mut tokens, Weak::new(), None, None)?;
+        if tokens.next().is_some() {
+            return Err(Error::new(ErrorKind::InvalidTreeEncoding));
+        }
+        Ok(Self { root })
+    }
+}
+
+impl<T> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of a Morris traversal of a [`BinaryTree`].
+///
+/// Dropping the walk before it is over completes it, which removes the
+/// threads it left in the tree.
+struct MorrisWalk<T> {
+    /// The root of the subtree still to be walked.
+    current: Link<T>,
+}
+
+impl<T> MorrisWalk<T> {
+    /// Returns the next node in sorted order, threading and unthreading
+    /// the predecessors on the way.
+    fn next_node(&mut self) -> Link<T> {
+        while let Some(node) = self.current.take() {
+            let left = node.borrow().left.clone();
+            let Some(left) = left else {
+                self.current = node.borrow().right.clone();
+                return Some(node);
+            };
+
+            let mut predecessor = left.clone();
+            loop {
+                let right = predecessor.borrow().right.clone();
+                match right {
+                    Some(right) if !Rc::ptr_eq(&right, &node) => predecessor = right,
+                    _ => break,
+                }
+            }
+
+            let threaded = predecessor.borrow().right.is_some();
+            if threaded {
+                predecessor.borrow_mut().right = None;
+                self.current = node.borrow().right.clone();
+                return Some(node);
+            }
+            predecessor.borrow_mut().right = Some(node.clone());
+            self.current = Some(left);
+        }
+        None
+    }
+}
+
+impl<T> Drop for MorrisWalk<T> {
+    fn drop(&mut self) {
+        while self.next_node().is_some() {}
+    }
+}
+
+/// Inorder iterator over the keys of a [`BinaryTree`].
+#[derive(Debug)]
+pub struct Inorder<T> {
+    /// Nodes whose key and right subtree are still to be visited.
+    stack: DummyStack<NodeRef<T>>,
+}
+
+impl<T> Inorder<T> {
+    fn push_left_spine(&mut self, mut link: Link<T>) {
+        while let Some(node) = link {
+            link = node.borrow().left.clone();
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Inorder<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let node = node.borrow();
+        self.push_left_spine(node.right.clone());
+        Some(node.key.clone())
+    }
+}
+
+/// Iterator over the keys of a [`BinaryTree`] within a range.
+#[derive(Debug)]
+pub struct Range<T> {
+    /// Nodes in the range whose key and right subtree are still to be
+    /// visited.
+    stack: DummyStack<NodeRef<T>>,
+
+    /// Lower bound of the range.
+    low: T,
+
+    /// Upper bound of the range.
+    high: T,
+}
+
+impl<T: Ord> Range<T> {
+    fn push_left_spine(&mut self, mut link: Link<T>) {
+        while let Some(node) = link {
+            if node.borrow().key < self.low {
+                // The whole left subtree is below the range.
+                link = node.borrow().right.clone();
+            } else {
+                link = node.borrow().left.clone();
+                self.stack.push(node);
+            }
+        }
+    }
+}
+
+impl<T: Ord + Clone> Iterator for Range<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let node = node.borrow();
+        if node.key > self.high {
+            // Every key left to visit is above the range.
+            self.stack = DummyStack::new();
+            return None;
+        }
+        self.push_left_spine(node.right.clone());
+        Some(node.key.clone())
+    }
+}
+
+/// Preorder iterator over the keys of a [`BinaryTree`].
+#[derive(Debug)]
+pub struct Preorder<T> {
+    /// Roots of the subtrees still to be visited.
+    stack: DummyStack<NodeRef<T>>,
+}
+
+impl<T: Clone> Iterator for Preorder<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let node = node.borrow();
+        if let Some(right) = &node.right {
+            self.stack.push(right.clone());
+        }
+        if let Some(left) = &node.left {
+            self.stack.push(left.clone());
+        }
+        Some(node.key.clone())
+    }
+}
+
+/// Postorder iterator over the keys of a [`BinaryTree`].
+#[derive(Debug)]
+pub struct Postorder<T> {
+    /// Nodes still to be visited, flagged once their children have
+    /// been pushed on the stack.
+    stack: DummyStack<(NodeRef<T>, bool)>,
+}
+
+impl<T: Clone> Iterator for Postorder<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node.borrow().key.clone());
+            }
+            let (left, right) = {
+                let node = node.borrow();
+                (node.left.clone(), node.right.clone())
+            };
+            self.stack.push((node, true));
+            if let Some(right) = right {
+                self.stack.push((right, false));
+            }
+            if let Some(left) = left {
+                self.stack.push((left, false));
+            }
+        }
+    }
+}
+
+/// Level order iterator over the keys of a [`BinaryTree`].
+#[derive(Debug)]
+pub struct LevelOrder<T> {
+    /// Nodes discovered but not visited yet.
+    queue: BoundedQueue<NodeRef<T>>,
+}
+
+impl<T: Clone> Iterator for LevelOrder<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.dequeue().ok()?;
+        let node = node.borrow();
+        // Each node is enqueued once, so the queue never overflows.
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            let _ = self.queue.enqueue(child.clone());
+        }
+        Some(node.key.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    fn tree_with(keys: &[i32]) -> BinaryTree<i32> {
+        let mut tree = BinaryTree::new();
+        for key in keys {
+            tree.insert(*key);
+        }
+        tree
+    }
+
+    /// Returns true if every node satisfies the binary search tree
+    /// property and points back to its parent.
+    fn is_valid(tree: &BinaryTree<i32>) -> bool {
+        fn check(link: &Link<i32>, parent: &Link<i32>, low: i32, high: i32) -> bool {
+            let Some(node) = link else {
+                return true;
+            };
+            let node_ref = node.borrow();
+            let parent_ok = match (node_ref.parent.upgrade(), parent) {
+                (Some(lhs), Some(rhs)) => Rc::ptr_eq(&lhs, rhs),
+                (None, None) => true,
+                _ => false,
+            };
+            parent_ok
+                && low <= node_ref.key
+                && node_ref.key <= high
+                && check(&node_ref.left, link, low, node_ref.key)
+                && check(&node_ref.right, link, node_ref.key, high)
+        }
+        check(&tree.root, &None, i32::MIN, i32::MAX)
+    }
+
+    #[test]
+    fn binary_tree_operations() {
+        let tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert_eq!(tree.inorder_walk(), vec![2, 4, 5, 6, 7, 8]);
+        assert!(tree.iter().eq([2, 4, 5, 6, 7, 8]));
+        assert_eq!(tree.postorder_walk(), vec![2, 5, 4, 8, 7, 6]);
+        assert_eq!(tree.preorder_walk(), vec![6, 4, 2, 5, 7, 8]);
+        assert_eq!(
+            tree.level_order().collect::<Vec<_>>(),
+            vec![6, 4, 7, 2, 5, 8]
+        );
+        assert!(tree.search(&7));
+        assert!(!tree.search(&3));
+        assert_eq!(tree.min(), Some(2));
+        assert_eq!(tree.max(), Some(8));
+        assert_eq!(tree.successor(&5), Some(6));
+        assert_eq!(tree.successor(&4), Some(5));
+        assert_eq!(tree.successor(&8), None);
+        assert_eq!(tree.successor(&3), None);
+        assert_eq!(tree.predecessor(&7), Some(6));
+        assert_eq!(tree.predecessor(&5), Some(4));
+        assert_eq!(tree.predecessor(&6), Some(5));
+        assert_eq!(tree.predecessor(&2), None);
+        assert_eq!(tree.predecessor(&3), None);
+    }
+
+    #[quickcheck]
+    fn successor_chain_is_inorder(mut keys: Vec<i32>) -> bool {
+        keys.sort();
+        keys.dedup();
+        let mut shuffled = keys.clone();
+        shuffled.reverse();
+        shuffled.rotate_left(keys.len() / 3);
+        let tree = tree_with(&shuffled);
+
+        let chain: Vec<_> = std::iter::successors(tree.min(), |key| tree.successor(key)).collect();
+        chain == keys && chain == tree.inorder_walk()
+    }
+
+    #[quickcheck]
+    fn predecessor_chain_is_reverse_inorder(keys: Vec<i32>) -> bool {
+        let tree = tree_with(&keys);
+        let mut expected = keys;
+        expected.sort();
+        expected.dedup();
+        expected.reverse();
+
+        let chain: Vec<_> =
+            std::iter::successors(tree.max(), |key| tree.predecessor(key)).collect();
+        chain == expected
+    }
+
+    #[test]
+    fn tree_metrics() {
+        let mut tree = BinaryTree::new();
+        assert_eq!(tree.len(), 0);
+        assert_eq!(tree.height(), 0);
+        assert!(tree.is_balanced());
+
+        tree.insert(6);
+        assert_eq!(tree.height(), 1);
+        assert_eq!(tree.depth(&6), Some(0));
+
+        for key in [4, 7, 2, 5, 8] {
+            tree.insert(key);
+        }
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.height(), 3);
+        assert_eq!(tree.depth(&4), Some(1));
+        assert_eq!(tree.depth(&8), Some(2));
+        assert_eq!(tree.depth(&3), None);
+        assert!(tree.is_balanced());
+
+        tree.insert(9);
+        assert_eq!(tree.height(), 4);
+        assert!(!tree.is_balanced());
+    }
+
+    #[test]
+    fn sorted_insertions_degenerate_into_a_path() {
+        let tree = tree_with(&(0..32).collect::<Vec<_>>());
+        assert_eq!(tree.len(), 32);
+        assert_eq!(tree.height(), 32);
+        assert_eq!(tree.depth(&31), Some(31));
+        assert!(!tree.is_balanced());
+    }
+
+    #[quickcheck]
+    fn morris_walk_restores_tree(keys: Vec<i32>) -> bool {
+        let mut tree = tree_with(&keys);
+        let encoding = tree.serialize();
+        let mut walked = Vec::new();
+        tree.morris_inorder_walk(|key| walked.push(*key));
+        walked == tree.inorder_walk() && tree.serialize() == encoding && is_valid(&tree)
+    }
+
+    #[test]
+    fn morris_walk_restores_tree_on_panic() {
+        let keys: Vec<i32> = (0..25).map(|key| (key * 7) % 25).collect();
+        let mut tree = tree_with(&keys);
+        let encoding = tree.serialize();
+        let walk = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tree.morris_inorder_walk(|key| assert!(*key < 12, "visited {key}"));
+        }));
+        assert!(walk.is_err());
+        assert_eq!(tree.serialize(), encoding);
+        assert_eq!(tree.inorder_walk(), (0..25).collect::<Vec<_>>());
+        assert!(is_valid(&tree));
+    }
+
+    #[test]
+    fn range_query() {
+        let tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert_eq!(tree.range(&4, &7).collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+        assert_eq!(tree.range(&3, &3).collect::<Vec<_>>(), vec![]);
+        assert_eq!(
+            tree.range(&0, &100).collect::<Vec<_>>(),
+            tree.inorder_walk()
+        );
+        assert_eq!(tree.range(&7, &4).collect::<Vec<_>>(), vec![]);
+    }
+
+    #[quickcheck]
+    fn range_matches_filtered_inorder(keys: Vec<i32>, low: i32, high: i32) -> bool {
+        let tree = tree_with(&keys);
+        let expected: Vec<_> = tree
+            .inorder()
+            .filter(|key| low <= *key && *key <= high)
+            .collect();
+        tree.range(&low, &high).collect::<Vec<_>>() == expected
+    }
+
+    #[test]
+    fn traversals_of_empty_tree() {
+        let tree = BinaryTree::<i32>::new();
+        assert_eq!(tree.inorder().next(), None);
+        assert_eq!(tree.preorder().next(), None);
+        assert_eq!(tree.postorder().next(), None);
+        assert_eq!(tree.level_order().next(), None);
+    }
+
+    #[quickcheck]
+    fn traversals_visit_every_key(keys: Vec<i32>) -> bool {
+        let tree = tree_with(&keys);
+        let mut expected = keys;
+        expected.sort();
+
+        let mut preorder: Vec<_> = tree.preorder().collect();
+        let mut postorder: Vec<_> = tree.postorder().collect();
+        let mut level_order: Vec<_> = tree.level_order().collect();
+        preorder.sort();
+        postorder.sort();
+        level_order.sort();
+        tree.inorder().collect::<Vec<_>>() == expected
+            && preorder == expected
+            && postorder == expected
+            && level_order == expected
+    }
+
+    #[test]
+    fn levels_and_render() {
+        let tree = tree_with(&[6, 4, 7, 2, 8]);
+        assert_eq!(tree.levels(), vec![vec![6], vec![4, 7], vec![2, 8]]);
+        assert_eq!(
+            tree.render(),
+            "6\n+-- 4\n|   +-- 2\n|   `-- #\n`-- 7\n    +-- #\n    `-- 8\n"
+        );
+
+        let empty = BinaryTree::<i32>::new();
+        assert!(empty.levels().is_empty());
+        assert_eq!(empty.render(), "#\n");
+    }
+
+    #[quickcheck]
+    fn levels_follow_depths(mut keys: Vec<i32>) -> bool {
+        // Duplicate keys have no single depth.
+        keys.sort();
+        keys.dedup();
+        let middle = keys.len() / 2;
+        keys.rotate_left(middle);
+        let tree = tree_with(&keys);
+        let levels = tree.levels();
+        levels.len() == tree.height()
+            && levels.concat() == tree.level_order().collect::<Vec<_>>()
+            && levels
+                .iter()
+                .enumerate()
+                .all(|(depth, level)| level.iter().all(|key| tree.depth(key) == Some(depth)))
+            && tree.render().lines().count() >= tree.len()
+    }
+
+    #[test]
+    fn serialize_tree() {
+        let tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert_eq!(tree.serialize(), "6 4 2 # # 5 # # 7 # 8 # #");
+        assert_eq!(BinaryTree::<i32>::new().serialize(), "#");
+    }
+
+    #[test]
+    fn deserialize_malformed_encoding() {
+        for encoding in ["", "6 4", "6 # # #", "6 x # #", "6 7 # # #"] {
+            assert_eq!(
+                BinaryTree::<i32>::deserialize(encoding).err(),
+                Some(Error::new(ErrorKind::InvalidTreeEncoding)),
+                "{encoding:?} was accepted"
+            );
+        }
+    }
+
+    #[quickcheck]
+    fn serialization_round_trip(keys: Vec<i32>) -> bool {
+        let tree = tree_with(&keys);
+        let encoding = tree.serialize();
+        match BinaryTree::<i32>::deserialize(&encoding) {
+            Ok(decoded) => {
+                is_valid(&decoded)
+                    && decoded.serialize() == encoding
+                    && decoded.preorder_walk() == tree.preorder_walk()
+                    && decoded.inorder_walk() == tree.inorder_walk()
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[test]
+    fn delete_leaf_and_single_child() {
+        let mut tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert!(tree.delete(&2));
+        assert_eq!(tree.preorder_walk(), vec![6, 4, 5, 7, 8]);
+        assert!(tree.delete(&7));
+        assert_eq!(tree.preorder_walk(), vec![6, 4, 5, 8]);
+        assert!(!tree.delete(&7));
+        assert!(is_valid(&tree));
+    }
+
+    #[test]
+    fn delete_promotes_successor() {
+        let mut tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert!(tree.delete(&4));
+        assert_eq!(tree.preorder_walk(), vec![6, 5, 2, 7, 8]);
+
+        // The successor of 10 is deep in its right subtree.
+        let mut tree = tree_with(&[10, 5, 20, 15, 25, 12, 13]);
+        assert!(tree.delete(&10));
+        assert_eq!(tree.preorder_walk(), vec![12, 5, 20, 15, 13, 25]);
+        assert!(is_valid(&tree));
+    }
+
+    #[test]
+    fn delete_root() {
+        let mut tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert!(tree.delete(&6));
+        assert_eq!(tree.preorder_walk(), vec![7, 4, 2, 5, 8]);
+        assert!(is_valid(&tree));
+
+        let mut tree = tree_with(&[1]);
+        assert!(tree.delete(&1));
+        assert!(tree.is_empty());
+    }
+
+    #[quickcheck]
+    fn delete_keeps_search_tree_property(keys: Vec<i32>, deleted: Vec<i32>) -> bool {
+        let mut tree = tree_with(&keys);
+        let mut expected = keys;
+        expected.sort();
+        let deleted: Vec<_> = deleted
+            .into_iter()
+            .chain(expected.iter().step_by(2).copied())
+            .collect();
+        for key in &deleted {
+            if let Ok(index) = expected.binary_search(key) {
+                expected.remove(index);
+                if !tree.delete(key) {
+                    return false;
+                }
+            } else if tree.delete(key) {
+                return false;
+            }
+        }
+        is_valid(&tree) && tree.inorder_walk() == expected
+    }
+}
@@ -0,0 +1,502 @@
+//! AVL tree.
+//!
+//! An AVL tree is a binary search tree where the heights of the two
+//! subtrees of every node differ by at most one, so its height is at most
+//! about `1.44 lg n`. Each node stores the height of its subtree, and
+//! insertions and deletions rebalance the nodes on the path back to the
+//! root with single or double rotations. See CLRS problem 13-3.
+//!
+//! The AVL condition is stricter than the coloring of a
+//! [`RedBlackTree`](super::red_black::RedBlackTree): lookups visit fewer
+//! nodes, but updates may rotate at every level of the path.
+
+use std::cmp::Ordering;
+
+use crate::map::Map;
+use crate::stack::DummyStack;
+use crate::trace::{Event, Sink};
+
+type Link<K, V> = Option<Box<AvlNode<K, V>>>;
+
+/// Node of an [`AvlTree`].
+#[derive(Debug, Clone)]
+struct AvlNode<K, V> {
+    key: K,
+    value: V,
+
+    /// The identifier of the node in traces, the number of nodes created
+    /// before it.
+    id: usize,
+
+    /// The number of nodes on the longest path from the node down to a
+    /// leaf.
+    height: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> AvlNode<K, V> {
+    /// Creates new leaf.
+    fn new(key: K, value: V, id: usize) -> Box<Self> {
+        Box::new(Self {
+            key,
+            value,
+            id,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// Returns the height of the left subtree minus the height of the
+    /// right subtree.
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+
+    /// Recomputes the height from the heights of the subtrees.
+    fn update_height(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+    }
+}
+
+/// Returns the height of the subtree.
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+/// Makes the right child of the node the root of the subtree.
+fn rotate_left<K, V>(mut node: Box<AvlNode<K, V>>, sink: &mut impl Sink) -> Box<AvlNode<K, V>> {
+    sink.record(Event::RotateLeft { node: node.id });
+    let mut root = node.right.take().expect("the node has a right child");
+    node.right = root.left.take();
+    node.update_height();
+    root.left = Some(node);
+    root.update_height();
+    root
+}
+
+/// Makes the left child of the node the root of the subtree.
+fn rotate_right<K, V>(mut node: Box<AvlNode<K, V>>, sink: &mut impl Sink) -> Box<AvlNode<K, V>> {
+    sink.record(Event::RotateRight { node: node.id });
+    let mut root = node.left.take().expect("the node has a left child");
+    node.left = root.right.take();
+    node.update_height();
+    root.right = Some(node);
+    root.update_height();
+    root
+}
+
+/// Restores the AVL condition at the node, whose subtrees are AVL trees
+/// with heights differing by at most two.
+fn rebalance<K, V>(mut node: Box<AvlNode<K, V>>, sink: &mut impl Sink) -> Box<AvlNode<K, V>> {
+    node.update_height();
+    match node.balance_factor() {
+        2 => {
+            let left = node.left.take().expect("the left subtree is higher");
+            // A right-heavy left child needs a double rotation.
+            node.left = Some(if left.balance_factor() < 0 {
+                rotate_left(left, sink)
+            } else {
+                left
+            });
+            rotate_right(node, sink)
+        }
+        -2 => {
+            let right = node.right.take().expect("the right subtree is higher");
+            node.right = Some(if right.balance_factor() > 0 {
+                rotate_right(right, sink)
+            } else {
+                right
+            });
+            rotate_left(node, sink)
+        }
+        _ => node,
+    }
+}
+
+/// Removes the minimum node of the subtree, and returns it with the
+/// rebalanced rest of the subtree.
+fn remove_min<K, V>(
+    mut node: Box<AvlNode<K, V>>,
+    sink: &mut impl Sink,
+) -> (Box<AvlNode<K, V>>, Link<K, V>) {
+    match node.left.take() {
+        None => {
+            let rest = node.right.take();
+            (node, rest)
+        }
+        Some(left) => {
+            let (min, rest) = remove_min(left, sink);
+            node.left = rest;
+            (min, Some(rebalance(node, sink)))
+        }
+    }
+}
+
+/// AvlTree is a height-balanced binary search tree mapping keys to values.
+#[derive(Debug, Clone)]
+pub struct AvlTree<K, V> {
+    root: Link<K, V>,
+    len: usize,
+
+    /// The number of nodes created, which identifies the next one.
+    created: usize,
+}
+
+impl<K, V> AvlTree<K, V> {
+    /// Creates new empty tree.
+    pub const fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            created: 0,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the height of the tree.
+    ///
+    /// The height is the number of nodes on the longest path from the
+    /// root down to a leaf.
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    /// Returns the balance factor of every node, the height of its left
+    /// subtree minus the height of its right subtree, sorted by key.
+    ///
+    /// The factors are recomputed from the subtrees rather than read from
+    /// the stored heights, so they expose a stale height as well as an
+    /// unbalanced node. They are all in `-1..=1` in a valid tree.
+    pub fn balance_factors(&self) -> Vec<(&K, isize)> {
+        fn walk<'a, K, V>(link: &'a Link<K, V>, factors: &mut Vec<(&'a K, isize)>) -> usize {
+            let Some(node) = link else {
+                return 0;
+            };
+            let left = walk(&node.left, factors);
+            factors.push((&node.key, 0));
+            let index = factors.len() - 1;
+            let right = walk(&node.right, factors);
+            factors[index].1 = left as isize - right as isize;
+            let height = 1 + left.max(right);
+            if height != node.height {
+                // A stale height cannot be balanced.
+                factors[index].1 = isize::MAX;
+            }
+            height
+        }
+        let mut factors = Vec::with_capacity(self.len);
+        walk(&self.root, &mut factors);
+        factors
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> AvlIter<'_, K, V> {
+        let mut iter = AvlIter {
+            stack: DummyStack::new(),
+        };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+}
+
+impl<K: Ord, V> AvlTree<K, V> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.traced_insert(key, value, &mut ())
+    }
+
+    /// Traced insertion.
+    ///
+    /// It is [`AvlTree::insert`], where each rotation is recorded to the
+    /// sink as an [`Event::RotateLeft`] or [`Event::RotateRight`] of the
+    /// node which moves down. The nodes are identified by the number of
+    /// nodes created before them. An insertion makes at most two rotations.
+    pub fn traced_insert(&mut self, key: K, value: V, sink: &mut impl Sink) -> Option<V> {
+        fn insert<K: Ord, V>(
+            link: &mut Link<K, V>,
+            key: K,
+            value: V,
+            id: usize,
+            sink: &mut impl Sink,
+        ) -> Option<V> {
+            let Some(mut node) = link.take() else {
+                *link = Some(AvlNode::new(key, value, id));
+                return None;
+            };
+            let previous = match key.cmp(&node.key) {
+                Ordering::Less => insert(&mut node.left, key, value, id, sink),
+                Ordering::Greater => insert(&mut node.right, key, value, id, sink),
+                Ordering::Equal => Some(std::mem::replace(&mut node.value, value)),
+            };
+            *link = Some(rebalance(node, sink));
+            previous
+        }
+        let previous = insert(&mut self.root, key, value, self.created, sink);
+        if previous.is_none() {
+            self.len += 1;
+            self.created += 1;
+        }
+        previous
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_ref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_ref(),
+                Ordering::Greater => node.right.as_ref(),
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+
+    /// Removes the key from the tree and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.traced_remove(key, &mut ())
+    }
+
+    /// Traced removal.
+    ///
+    /// It is [`AvlTree::remove`], where each rotation is recorded to the
+    /// sink as in [`AvlTree::traced_insert`]. A removal may rotate at every
+    /// level of the path.
+    pub fn traced_remove(&mut self, key: &K, sink: &mut impl Sink) -> Option<V> {
+        fn remove<K: Ord, V>(link: &mut Link<K, V>, key: &K, sink: &mut impl Sink) -> Option<V> {
+            let mut node = link.take()?;
+            let removed = match key.cmp(&node.key) {
+                Ordering::Less => remove(&mut node.left, key, sink),
+                Ordering::Greater => remove(&mut node.right, key, sink),
+                Ordering::Equal => {
+                    // The successor of the node takes its place.
+                    let replacement = match (node.left.take(), node.right.take()) {
+                        (left, None) => left,
+                        (None, right) => right,
+                        (left, Some(right)) => {
+                            let (mut successor, rest) = remove_min(right, sink);
+                            successor.left = left;
+                            successor.right = rest;
+                            Some(rebalance(successor, sink))
+                        }
+                    };
+                    *link = replacement;
+                    return Some(node.value);
+                }
+            };
+            *link = Some(rebalance(node, sink));
+            removed
+        }
+        let removed = remove(&mut self.root, key, sink);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+impl<K, V> Default for AvlTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Map<K, V> for AvlTree<K, V> {
+    type Iter<'a>
+        = AvlIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        AvlTree::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        AvlTree::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        AvlTree::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        AvlTree::len(self)
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    fn iter(&self) -> Self::Iter<'_> {
+        AvlTree::iter(self)
+    }
+}
+
+/// Iterator over the entries of an [`AvlTree`], sorted by key.
+#[derive(Debug)]
+pub struct AvlIter<'a, K, V> {
+    /// The nodes whose left subtree is being visited.
+    stack: DummyStack<&'a AvlNode<K, V>>,
+}
+
+impl<'a, K, V> AvlIter<'a, K, V> {
+    fn push_left_spine(&mut self, mut link: &'a Link<K, V>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for AvlIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some((&node.key, &node.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::map::suite::{agrees_with_model, Operation};
+    use crate::tree::red_black::RedBlackTree;
+
+    fn is_balanced<K, V>(tree: &AvlTree<K, V>) -> bool {
+        tree.balance_factors()
+            .iter()
+            .all(|(_, factor)| (-1..=1).contains(factor))
+    }
+
+    #[test]
+    fn rotations() {
+        // Each insertion triggers one of the four rebalancing cases.
+        for keys in [[3, 2, 1], [1, 2, 3], [3, 1, 2], [1, 3, 2]] {
+            let mut tree = AvlTree::new();
+            for key in keys {
+                tree.insert(key, ());
+            }
+            assert_eq!(tree.height(), 2, "{keys:?}");
+            assert_eq!(tree.root.as_ref().map(|root| root.key), Some(2));
+            assert_eq!(tree.balance_factors(), vec![(&1, 0), (&2, 0), (&3, 0)]);
+        }
+    }
+
+    #[test]
+    fn traced_rotations() {
+        // The nodes are identified by their order of insertion, and the
+        // double rotation of the last case rotates the child first.
+        let cases = [
+            ([3, 2, 1], vec![Event::RotateRight { node: 0 }]),
+            ([1, 2, 3], vec![Event::RotateLeft { node: 0 }]),
+            (
+                [3, 1, 2],
+                vec![
+                    Event::RotateLeft { node: 1 },
+                    Event::RotateRight { node: 0 },
+                ],
+            ),
+            (
+                [1, 3, 2],
+                vec![
+                    Event::RotateRight { node: 1 },
+                    Event::RotateLeft { node: 0 },
+                ],
+            ),
+        ];
+        for (keys, expected) in cases {
+            let mut tree = AvlTree::new();
+            let mut events = Vec::new();
+            for key in keys {
+                tree.traced_insert(key, (), &mut events);
+            }
+            assert_eq!(events, expected, "{keys:?}");
+        }
+
+        let mut tree = AvlTree::new();
+        for key in [2, 1, 3, 4] {
+            tree.insert(key, ());
+        }
+        let mut events = Vec::new();
+        tree.traced_remove(&1, &mut events);
+        assert_eq!(events, vec![Event::RotateLeft { node: 0 }]);
+        assert!(is_balanced(&tree));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut tree = AvlTree::new();
+        for key in 0..100 {
+            assert_eq!(tree.insert(key, key * 2), None);
+        }
+        assert_eq!(tree.insert(7, 0), Some(14));
+        assert_eq!(tree.len(), 100);
+        assert_eq!(tree.height(), 7);
+        assert!(is_balanced(&tree));
+        assert_eq!(tree.insert(7, 14), Some(0));
+        for key in (0..100).filter(|key| key % 3 != 0) {
+            assert_eq!(tree.remove(&key), Some(key * 2));
+            assert!(is_balanced(&tree));
+        }
+        assert_eq!(tree.remove(&1), None);
+        assert_eq!(tree.get(&0), Some(&0));
+        assert_eq!(tree.get(&7), None);
+        assert_eq!(tree.len(), 34);
+    }
+
+    #[test]
+    fn stricter_than_red_black() {
+        let mut avl = AvlTree::new();
+        let mut red_black = RedBlackTree::new();
+        for key in 0..4095 {
+            avl.insert(key, ());
+            red_black.insert(key, ());
+        }
+        assert_eq!(avl.height(), 12);
+        assert!(red_black.height() > avl.height());
+    }
+
+    #[test]
+    fn stale_height() {
+        let mut tree = AvlTree::new();
+        tree.insert(1, ());
+        tree.insert(2, ());
+        if let Some(root) = tree.root.as_mut() {
+            root.height = 5;
+        }
+        assert!(!is_balanced(&tree));
+    }
+
+    #[quickcheck]
+    fn stays_balanced(operations: Vec<Operation>) -> bool {
+        let mut tree = AvlTree::new();
+        operations.iter().all(|&(insert, key, value)| {
+            if insert {
+                tree.insert(key, value);
+            } else {
+                tree.remove(&key);
+            }
+            is_balanced(&tree) && tree.balance_factors().len() == tree.len()
+        })
+    }
+
+    #[quickcheck]
+    fn avl_tree_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut AvlTree::new(), &operations)
+    }
+}
@@ -0,0 +1,523 @@
+//! B-tree.
+//!
+//! A B-tree of minimum degree `t` is a balanced search tree whose nodes
+//! hold between `t - 1` and `2t - 1` keys, except the root which may hold
+//! fewer, and whose leaves all have the same depth. A node with `n` keys
+//! has `n + 1` children, the keys of the `i`-th child lying between the
+//! keys `i - 1` and `i` of the node. Wide nodes keep the tree shallow,
+//! which is what matters when each node is a disk page. See CLRS chapter
+//! 18.
+//!
+//! Insertions split full nodes and deletions refill minimal nodes on the
+//! way down, so both operations make a single pass from the root.
+
+use std::cmp::Ordering;
+
+use crate::map::Map;
+use crate::stack::DummyStack;
+
+/// Node of a [`BTree`].
+#[derive(Debug, Clone)]
+struct BTreeNode<K, V> {
+    /// The entries of the node, sorted by key.
+    entries: Vec<(K, V)>,
+
+    /// The children of the node, empty for a leaf.
+    children: Vec<BTreeNode<K, V>>,
+}
+
+impl<K, V> BTreeNode<K, V> {
+    /// Creates new leaf without entries.
+    const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<K: Ord, V> BTreeNode<K, V> {
+    /// Returns the position of the key among the entries, or the index of
+    /// the child whose subtree would hold it.
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(other, _)| other.cmp(key))
+    }
+}
+
+/// BTree is a B-tree of minimum degree `T` mapping keys to values.
+///
+/// The minimum degree must be at least 2; a B-tree of minimum degree 2 is
+/// a 2-3-4 tree.
+#[derive(Debug, Clone)]
+pub struct BTree<K, V, const T: usize> {
+    root: BTreeNode<K, V>,
+    len: usize,
+}
+
+impl<K, V, const T: usize> BTree<K, V, T> {
+    /// Creates new empty tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the minimum degree `T` is less than 2.
+    pub const fn new() -> Self {
+        assert!(T >= 2, "minimum degree is less than 2");
+        Self {
+            root: BTreeNode::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the minimum degree of the tree.
+    pub const fn min_degree(&self) -> usize {
+        T
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the height of the tree.
+    ///
+    /// The height is the number of edges on the path from the root down to
+    /// a leaf, so a tree with a single node has height 0.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            height += 1;
+            node = child;
+        }
+        height
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> BTreeIter<'_, K, V> {
+        let mut iter = BTreeIter {
+            stack: DummyStack::new(),
+        };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+
+    /// Returns true if the node is full.
+    fn is_full(node: &BTreeNode<K, V>) -> bool {
+        node.entries.len() == 2 * T - 1
+    }
+
+    /// Splits the full child `i` of the node around its median entry, which
+    /// moves up into the node.
+    fn split_child(node: &mut BTreeNode<K, V>, i: usize) {
+        let child = &mut node.children[i];
+        let mut sibling = BTreeNode {
+            entries: child.entries.split_off(T),
+            children: Vec::new(),
+        };
+        if !child.is_leaf() {
+            sibling.children = child.children.split_off(T);
+        }
+        let median = child.entries.pop().expect("the child is full");
+        node.entries.insert(i, median);
+        node.children.insert(i + 1, sibling);
+    }
+
+    /// Merges the child `i + 1` of the node and the entry `i` into the
+    /// child `i`.
+    fn merge_children(node: &mut BTreeNode<K, V>, i: usize) {
+        let sibling = node.children.remove(i + 1);
+        let median = node.entries.remove(i);
+        let child = &mut node.children[i];
+        child.entries.push(median);
+        child.entries.extend(sibling.entries);
+        child.children.extend(sibling.children);
+    }
+
+    /// Ensures that the child `i` of the node has at least `T` entries,
+    /// moving an entry from a sibling through the node or merging the
+    /// child with a sibling.
+    ///
+    /// It returns the index of the child, which is `i - 1` when it was
+    /// merged into its left sibling.
+    fn fill_child(node: &mut BTreeNode<K, V>, i: usize) -> usize {
+        if node.children[i].entries.len() >= T {
+            return i;
+        }
+        if i > 0 && node.children[i - 1].entries.len() >= T {
+            let (left, right) = node.children.split_at_mut(i);
+            let (sibling, child) = (&mut left[i - 1], &mut right[0]);
+            let entry = sibling.entries.pop().expect("the sibling has entries");
+            let separator = std::mem::replace(&mut node.entries[i - 1], entry);
+            child.entries.insert(0, separator);
+            if let Some(grandchild) = sibling.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+            i
+        } else if i + 1 < node.children.len() && node.children[i + 1].entries.len() >= T {
+            let (left, right) = node.children.split_at_mut(i + 1);
+            let (child, sibling) = (&mut left[i], &mut right[0]);
+            let entry = sibling.entries.remove(0);
+            let separator = std::mem::replace(&mut node.entries[i], entry);
+            child.entries.push(separator);
+            if !sibling.is_leaf() {
+                child.children.push(sibling.children.remove(0));
+            }
+            i
+        } else if i + 1 < node.children.len() {
+            Self::merge_children(node, i);
+            i
+        } else {
+            Self::merge_children(node, i - 1);
+            i - 1
+        }
+    }
+
+    /// Removes the entry with the smallest key from the subtree, whose
+    /// root has at least `T` entries unless it is the root of the tree.
+    fn remove_min(node: &mut BTreeNode<K, V>) -> (K, V) {
+        if node.is_leaf() {
+            return node.entries.remove(0);
+        }
+        let i = Self::fill_child(node, 0);
+        Self::remove_min(&mut node.children[i])
+    }
+
+    /// Removes the entry with the largest key from the subtree, whose root
+    /// has at least `T` entries unless it is the root of the tree.
+    fn remove_max(node: &mut BTreeNode<K, V>) -> (K, V) {
+        if node.is_leaf() {
+            return node.entries.pop().expect("the node has entries");
+        }
+        let i = Self::fill_child(node, node.children.len() - 1);
+        Self::remove_max(&mut node.children[i])
+    }
+}
+
+impl<K: Ord, V, const T: usize> BTree<K, V, T> {
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut node = &self.root;
+        loop {
+            match node.search(key) {
+                Ok(i) => return Some(&node.entries[i].1),
+                Err(_) if node.is_leaf() => return None,
+                Err(i) => node = &node.children[i],
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        loop {
+            match node.search(key) {
+                Ok(i) => return Some(&mut node.entries[i].1),
+                Err(_) if node.is_leaf() => return None,
+                Err(i) => node = &mut node.children[i],
+            }
+        }
+    }
+
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if Self::is_full(&self.root) {
+            // The tree grows at the root.
+            let root = std::mem::replace(&mut self.root, BTreeNode::new());
+            self.root.children.push(root);
+            Self::split_child(&mut self.root, 0);
+        }
+
+        let mut node = &mut self.root;
+        loop {
+            let mut i = match node.search(&key) {
+                Ok(i) => return Some(std::mem::replace(&mut node.entries[i].1, value)),
+                Err(i) => i,
+            };
+            if node.is_leaf() {
+                node.entries.insert(i, (key, value));
+                self.len += 1;
+                return None;
+            }
+            if Self::is_full(&node.children[i]) {
+                Self::split_child(node, i);
+                match key.cmp(&node.entries[i].0) {
+                    Ordering::Less => {}
+                    Ordering::Greater => i += 1,
+                    Ordering::Equal => {
+                        return Some(std::mem::replace(&mut node.entries[i].1, value));
+                    }
+                }
+            }
+            node = &mut node.children[i];
+        }
+    }
+
+    /// Removes the key from the tree and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let removed = Self::remove_from(&mut self.root, key);
+        if self.root.entries.is_empty() && !self.root.is_leaf() {
+            // The tree shrinks at the root.
+            self.root = self.root.children.remove(0);
+        }
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Removes the key from the subtree, whose root has at least `T`
+    /// entries unless it is the root of the tree.
+    fn remove_from(node: &mut BTreeNode<K, V>, key: &K) -> Option<V> {
+        match node.search(key) {
+            Ok(i) if node.is_leaf() => Some(node.entries.remove(i).1),
+            Ok(i) => {
+                // The predecessor or the successor of the key takes its place
+                // if the child holding it can spare an entry.
+                if node.children[i].entries.len() >= T {
+                    let predecessor = Self::remove_max(&mut node.children[i]);
+                    Some(std::mem::replace(&mut node.entries[i], predecessor).1)
+                } else if node.children[i + 1].entries.len() >= T {
+                    let successor = Self::remove_min(&mut node.children[i + 1]);
+                    Some(std::mem::replace(&mut node.entries[i], successor).1)
+                } else {
+                    Self::merge_children(node, i);
+                    Self::remove_from(&mut node.children[i], key)
+                }
+            }
+            Err(_) if node.is_leaf() => None,
+            Err(i) => {
+                let i = Self::fill_child(node, i);
+                Self::remove_from(&mut node.children[i], key)
+            }
+        }
+    }
+}
+
+impl<K, V, const T: usize> Default for BTree<K, V, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V, const T: usize> Map<K, V> for BTree<K, V, T> {
+    type Iter<'a>
+        = BTreeIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTree::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        BTree::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTree::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        BTree::len(self)
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    fn iter(&self) -> Self::Iter<'_> {
+        BTree::iter(self)
+    }
+}
+
+/// Iterator over the entries of a [`BTree`], sorted by key.
+#[derive(Debug)]
+pub struct BTreeIter<'a, K, V> {
+    /// The nodes being visited, with the index of their next entry.
+    stack: DummyStack<(&'a BTreeNode<K, V>, usize)>,
+}
+
+impl<'a, K, V> BTreeIter<'a, K, V> {
+    fn push_left_spine(&mut self, mut node: &'a BTreeNode<K, V>) {
+        loop {
+            self.stack.push((node, 0));
+            match node.children.first() {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for BTreeIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, i) = self.stack.pop()?;
+            if let Some((key, value)) = node.entries.get(i) {
+                self.stack.push((node, i + 1));
+                if let Some(child) = node.children.get(i + 1) {
+                    self.push_left_spine(child);
+                }
+                return Some((key, value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    /// Returns true if the tree satisfies the B-tree properties.
+    fn is_valid<K: Ord, V, const T: usize>(tree: &BTree<K, V, T>) -> bool {
+        /// Returns the height and the number of entries of the subtree, if
+        /// it is valid, with keys between the bounds.
+        fn check<K: Ord, V, const T: usize>(
+            node: &BTreeNode<K, V>,
+            is_root: bool,
+            low: Option<&K>,
+            high: Option<&K>,
+        ) -> Option<(usize, usize)> {
+            let min = if is_root { 0 } else { T - 1 };
+            let keys: Vec<&K> = low
+                .into_iter()
+                .chain(node.entries.iter().map(|(key, _)| key))
+                .chain(high)
+                .collect();
+            if !(min..2 * T).contains(&node.entries.len())
+                || keys.windows(2).any(|pair| pair[0] >= pair[1])
+            {
+                return None;
+            }
+            if node.is_leaf() {
+                return Some((0, node.entries.len()));
+            }
+            if node.children.len() != node.entries.len() + 1 || node.entries.is_empty() {
+                return None;
+            }
+            let mut height = None;
+            let mut len = node.entries.len();
+            for (i, child) in node.children.iter().enumerate() {
+                let low = if i == 0 {
+                    low
+                } else {
+                    Some(&node.entries[i - 1].0)
+                };
+                let high = node.entries.get(i).map(|(key, _)| key).or(high);
+                let (child_height, child_len) = check::<K, V, T>(child, false, low, high)?;
+                if *height.get_or_insert(child_height) != child_height {
+                    return None;
+                }
+                len += child_len;
+            }
+            height.map(|height| (height + 1, len))
+        }
+        check::<K, V, T>(&tree.root, true, None, None).map_or(false, |(height, len)| {
+            height == tree.height() && len == tree.len()
+        })
+    }
+
+    #[test]
+    fn split_and_merge() {
+        let mut tree = BTree::<_, _, 2>::new();
+        assert_eq!(tree.min_degree(), 2);
+        assert_eq!(tree.get(&0), None);
+        for key in 0..3 {
+            tree.insert(key, ());
+        }
+        assert_eq!(tree.height(), 0);
+        // Inserting into the full root splits it.
+        tree.insert(3, ());
+        assert_eq!(tree.height(), 1);
+        assert_eq!(tree.root.entries.len(), 1);
+        assert_eq!(tree.root.entries[0].0, 1);
+        assert!(is_valid(&tree));
+        // Deleting merges the children back into the root.
+        tree.remove(&3);
+        tree.remove(&0);
+        assert_eq!(tree.height(), 0);
+        assert!(is_valid(&tree));
+    }
+
+    #[test]
+    fn deletion_cases() {
+        // The keys of CLRS figure 18.8, deleted in the order of the figure.
+        let mut tree = BTree::<char, (), 3>::new();
+        for key in "PCGMTXABDEFJKLNOQRSUVYZ".chars() {
+            tree.insert(key, ());
+        }
+        assert!(is_valid(&tree));
+        for key in "FMGDB".chars() {
+            assert_eq!(tree.remove(&key), Some(()));
+            assert!(is_valid(&tree));
+        }
+        assert_eq!(tree.remove(&'F'), None);
+        let keys: String = tree.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, "ACEJKLNOPQRSTUVXYZ");
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut tree = BTree::<_, _, 3>::new();
+        for key in 0..1000 {
+            assert_eq!(tree.insert(key, key * 2), None);
+        }
+        assert_eq!(tree.insert(7, 0), Some(14));
+        if let Some(value) = tree.get_mut(&7) {
+            *value = 14;
+        }
+        assert_eq!(tree.len(), 1000);
+        assert!(tree.height() <= 5);
+        assert!(is_valid(&tree));
+        for key in (0..1000).rev().filter(|key| key % 3 != 0) {
+            assert_eq!(tree.remove(&key), Some(key * 2));
+        }
+        assert!(is_valid(&tree));
+        assert_eq!(tree.get(&999), Some(&1998));
+        assert_eq!(tree.get(&998), None);
+        assert_eq!(tree.len(), 334);
+        assert!(tree
+            .iter()
+            .map(|(key, _)| key)
+            .eq((0..1000).step_by(3).collect::<Vec<_>>().iter()));
+    }
+
+    #[test]
+    #[should_panic(expected = "minimum degree is less than 2")]
+    fn min_degree_one() {
+        BTree::<u8, u8, 1>::new();
+    }
+
+    #[quickcheck]
+    fn stays_valid(operations: Vec<Operation>) -> bool {
+        let mut tree = BTree::<_, _, 2>::new();
+        operations.iter().all(|&(insert, key, value)| {
+            if insert {
+                tree.insert(key, value);
+            } else {
+                tree.remove(&key);
+            }
+            is_valid(&tree)
+        })
+    }
+
+    #[quickcheck]
+    fn btree_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut BTree::<_, _, 2>::new(), &operations)
+            && agrees_with_model::<u8, _>(&mut BTree::<_, _, 4>::new(), &operations)
+    }
+}
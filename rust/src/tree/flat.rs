@@ -0,0 +1,452 @@
+//! Arena based binary search tree.
+//!
+//! This module implements the same binary search tree as
+//! [`BinaryTree`](super::BinaryTree) but the nodes live in a vector and
+//! refer to their parent and children by index. There is no reference
+//! counting, no interior mutability and no unsafe code, and the keys can
+//! be borrowed directly from the tree.
+
+use std::cmp::Ordering;
+
+use crate::queue::BoundedQueue;
+use crate::stack::DummyStack;
+
+/// Node of a [`FlatBinaryTree`].
+#[derive(Debug, Clone)]
+struct FlatNode<T> {
+    /// The node key value.
+    key: T,
+
+    /// Index of the parent node.
+    parent: Option<usize>,
+
+    /// Index of the left child.
+    left: Option<usize>,
+
+    /// Index of the right child.
+    right: Option<usize>,
+}
+
+/// A binary search tree whose nodes are stored in a vector.
+#[derive(Debug, Clone)]
+pub struct FlatBinaryTree<T> {
+    nodes: Vec<FlatNode<T>>,
+    root: Option<usize>,
+}
+
+impl<T> FlatBinaryTree<T> {
+    /// Creates new empty tree.
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: None,
+        }
+    }
+
+    /// Returns true if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the height of the tree.
+    ///
+    /// The height is the number of nodes on the longest path from the
+    /// root down to a leaf.
+    pub fn height(&self) -> usize {
+        fn height<T>(tree: &FlatBinaryTree<T>, index: Option<usize>) -> usize {
+            index.map_or(0, |index| {
+                let node = &tree.nodes[index];
+                1 + height(tree, node.left).max(height(tree, node.right))
+            })
+        }
+        height(self, self.root)
+    }
+
+    /// Returns the minimum key in the tree.
+    pub fn min(&self) -> Option<&T> {
+        self.root.map(|root| &self.nodes[self.minimum(root)].key)
+    }
+
+    /// Returns the maximum key in the tree.
+    pub fn max(&self) -> Option<&T> {
+        self.root.map(|root| &self.nodes[self.maximum(root)].key)
+    }
+
+    /// Creates an iterator over the keys in sorted order.
+    ///
+    /// The iterator follows the successor of each node through the
+    /// parent indices, so it does not need a stack.
+    pub fn inorder(&self) -> FlatInorder<'_, T> {
+        FlatInorder {
+            tree: self,
+            next: self.root.map(|root| self.minimum(root)),
+        }
+    }
+
+    /// Creates an iterator over the keys with each subtree root visited
+    /// before its left and right subtrees.
+    pub fn preorder(&self) -> FlatPreorder<'_, T> {
+        let mut stack = DummyStack::new();
+        if let Some(root) = self.root {
+            stack.push(root);
+        }
+        FlatPreorder { tree: self, stack }
+    }
+
+    /// Creates an iterator over the keys with each subtree root visited
+    /// after its left and right subtrees.
+    pub fn postorder(&self) -> FlatPostorder<'_, T> {
+        let mut stack = DummyStack::new();
+        if let Some(root) = self.root {
+            stack.push((root, false));
+        }
+        FlatPostorder { tree: self, stack }
+    }
+
+    /// Creates an iterator over the keys level by level.
+    pub fn level_order(&self) -> FlatLevelOrder<'_, T> {
+        let mut queue = BoundedQueue::with_capacity(self.len());
+        if let Some(root) = self.root {
+            // The queue is sized to hold every node of the tree.
+            let _ = queue.enqueue(root);
+        }
+        FlatLevelOrder { tree: self, queue }
+    }
+
+    /// Returns the index of the minimum node in the subtree rooted at `index`.
+    fn minimum(&self, mut index: usize) -> usize {
+        while let Some(left) = self.nodes[index].left {
+            index = left;
+        }
+        index
+    }
+
+    /// Returns the index of the maximum node in the subtree rooted at `index`.
+    fn maximum(&self, mut index: usize) -> usize {
+        while let Some(right) = self.nodes[index].right {
+            index = right;
+        }
+        index
+    }
+
+    /// Returns the index of the node following `index` in sorted order.
+    fn next(&self, index: usize) -> Option<usize> {
+        if let Some(right) = self.nodes[index].right {
+            return Some(self.minimum(right));
+        }
+        let mut index = index;
+        let mut parent = self.nodes[index].parent;
+        while let Some(ancestor) = parent {
+            if self.nodes[ancestor].right != Some(index) {
+                break;
+            }
+            index = ancestor;
+            parent = self.nodes[ancestor].parent;
+        }
+        parent
+    }
+
+    /// Returns the index of the node preceding `index` in sorted order.
+    fn prev(&self, index: usize) -> Option<usize> {
+        if let Some(left) = self.nodes[index].left {
+            return Some(self.maximum(left));
+        }
+        let mut index = index;
+        let mut parent = self.nodes[index].parent;
+        while let Some(ancestor) = parent {
+            if self.nodes[ancestor].left != Some(index) {
+                break;
+            }
+            index = ancestor;
+            parent = self.nodes[ancestor].parent;
+        }
+        parent
+    }
+
+    /// Replaces the subtree rooted at `old` with the subtree rooted at `new`.
+    fn transplant(&mut self, old: usize, new: Option<usize>) {
+        let parent = self.nodes[old].parent;
+        match parent {
+            None => self.root = new,
+            Some(parent) if self.nodes[parent].left == Some(old) => {
+                self.nodes[parent].left = new;
+            }
+            Some(parent) => self.nodes[parent].right = new,
+        }
+        if let Some(new) = new {
+            self.nodes[new].parent = parent;
+        }
+    }
+
+    /// Removes the unlinked node at `index` from the storage.
+    ///
+    /// The last node is moved into the free slot, so every index which
+    /// referred to it is updated.
+    fn remove_slot(&mut self, index: usize) -> T {
+        let last = self.nodes.len() - 1;
+        let node = self.nodes.swap_remove(index);
+        if index == last {
+            return node.key;
+        }
+
+        let moved = &self.nodes[index];
+        let (parent, left, right) = (moved.parent, moved.left, moved.right);
+        match parent {
+            None => self.root = Some(index),
+            Some(parent) if self.nodes[parent].left == Some(last) => {
+                self.nodes[parent].left = Some(index);
+            }
+            Some(parent) => self.nodes[parent].right = Some(index),
+        }
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(index);
+        }
+        node.key
+    }
+}
+
+impl<T> FlatBinaryTree<T>
+where
+    T: Ord,
+{
+    /// Inserts new node with the given key into the tree.
+    pub fn insert(&mut self, key: T) -> &mut Self {
+        let mut parent = None;
+        let mut current = self.root;
+        while let Some(index) = current {
+            parent = Some(index);
+            current = if key < self.nodes[index].key {
+                self.nodes[index].left
+            } else {
+                self.nodes[index].right
+            };
+        }
+
+        let index = self.nodes.len();
+        match parent {
+            None => self.root = Some(index),
+            Some(parent) if key < self.nodes[parent].key => self.nodes[parent].left = Some(index),
+            Some(parent) => self.nodes[parent].right = Some(index),
+        }
+        self.nodes.push(FlatNode {
+            key,
+            parent,
+            left: None,
+            right: None,
+        });
+        self
+    }
+
+    /// Returns true if a node with the given key is in the tree.
+    pub fn search(&self, key: &T) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Returns the key following the given key in sorted order.
+    pub fn successor(&self, key: &T) -> Option<&T> {
+        let index = self.next(self.find(key)?)?;
+        Some(&self.nodes[index].key)
+    }
+
+    /// Returns the key preceding the given key in sorted order.
+    pub fn predecessor(&self, key: &T) -> Option<&T> {
+        let index = self.prev(self.find(key)?)?;
+        Some(&self.nodes[index].key)
+    }
+
+    /// Deletes a node with the given key and returns its key.
+    ///
+    /// The node is unlinked following CLRS section 12.3, then its slot
+    /// is reclaimed.
+    pub fn delete(&mut self, key: &T) -> Option<T> {
+        let index = self.find(key)?;
+        let FlatNode { left, right, .. } = self.nodes[index];
+        match (left, right) {
+            (None, right) => self.transplant(index, right),
+            (left, None) => self.transplant(index, left),
+            (Some(left), Some(right)) => {
+                let successor = self.minimum(right);
+                if self.nodes[successor].parent != Some(index) {
+                    self.transplant(successor, self.nodes[successor].right);
+                    self.nodes[successor].right = Some(right);
+                    self.nodes[right].parent = Some(successor);
+                }
+                self.transplant(index, Some(successor));
+                self.nodes[successor].left = Some(left);
+                self.nodes[left].parent = Some(successor);
+            }
+        }
+        Some(self.remove_slot(index))
+    }
+
+    /// Finds the index of the node with the given key.
+    fn find(&self, key: &T) -> Option<usize> {
+        let mut current = self.root;
+        while let Some(index) = current {
+            current = match key.cmp(&self.nodes[index].key) {
+                Ordering::Less => self.nodes[index].left,
+                Ordering::Greater => self.nodes[index].right,
+                Ordering::Equal => return Some(index),
+            };
+        }
+        None
+    }
+}
+
+impl<T> Default for FlatBinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Inorder iterator over the keys of a [`FlatBinaryTree`].
+#[derive(Debug)]
+pub struct FlatInorder<'a, T> {
+    tree: &'a FlatBinaryTree<T>,
+    next: Option<usize>,
+}
+
+impl<'a, T> Iterator for FlatInorder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next?;
+        self.next = self.tree.next(index);
+        Some(&self.tree.nodes[index].key)
+    }
+}
+
+/// Preorder iterator over the keys of a [`FlatBinaryTree`].
+#[derive(Debug)]
+pub struct FlatPreorder<'a, T> {
+    tree: &'a FlatBinaryTree<T>,
+    stack: DummyStack<usize>,
+}
+
+impl<'a, T> Iterator for FlatPreorder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.tree.nodes[self.stack.pop()?];
+        for child in [node.right, node.left].into_iter().flatten() {
+            self.stack.push(child);
+        }
+        Some(&node.key)
+    }
+}
+
+/// Postorder iterator over the keys of a [`FlatBinaryTree`].
+#[derive(Debug)]
+pub struct FlatPostorder<'a, T> {
+    tree: &'a FlatBinaryTree<T>,
+    stack: DummyStack<(usize, bool)>,
+}
+
+impl<'a, T> Iterator for FlatPostorder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, expanded) = self.stack.pop()?;
+            let node = &self.tree.nodes[index];
+            if expanded {
+                return Some(&node.key);
+            }
+            self.stack.push((index, true));
+            for child in [node.right, node.left].into_iter().flatten() {
+                self.stack.push((child, false));
+            }
+        }
+    }
+}
+
+/// Level order iterator over the keys of a [`FlatBinaryTree`].
+#[derive(Debug)]
+pub struct FlatLevelOrder<'a, T> {
+    tree: &'a FlatBinaryTree<T>,
+    queue: BoundedQueue<usize>,
+}
+
+impl<'a, T> Iterator for FlatLevelOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.tree.nodes[self.queue.dequeue().ok()?];
+        // Each node is enqueued once, so the queue never overflows.
+        for child in [node.left, node.right].into_iter().flatten() {
+            let _ = self.queue.enqueue(child);
+        }
+        Some(&node.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::FlatBinaryTree;
+    use crate::tree::BinaryTree;
+
+    fn tree_with(keys: &[i32]) -> FlatBinaryTree<i32> {
+        let mut tree = FlatBinaryTree::new();
+        for key in keys {
+            tree.insert(*key);
+        }
+        tree
+    }
+
+    #[test]
+    fn flat_binary_tree_operations() {
+        let tree = tree_with(&[6, 4, 7, 2, 5, 8]);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.height(), 3);
+        assert!(tree.search(&5));
+        assert!(!tree.search(&3));
+        assert_eq!(tree.min(), Some(&2));
+        assert_eq!(tree.max(), Some(&8));
+        assert_eq!(tree.successor(&5), Some(&6));
+        assert_eq!(tree.successor(&8), None);
+        assert_eq!(tree.predecessor(&7), Some(&6));
+        assert_eq!(tree.predecessor(&2), None);
+        assert!(tree.inorder().eq([2, 4, 5, 6, 7, 8].iter()));
+        assert!(tree.preorder().eq([6, 4, 2, 5, 7, 8].iter()));
+        assert!(tree.postorder().eq([2, 5, 4, 8, 7, 6].iter()));
+        assert!(tree.level_order().eq([6, 4, 7, 2, 5, 8].iter()));
+    }
+
+    #[test]
+    fn delete_reclaims_storage() {
+        let mut tree = tree_with(&[10, 5, 20, 15, 25, 12, 13]);
+        assert_eq!(tree.delete(&10), Some(10));
+        assert_eq!(tree.delete(&10), None);
+        assert_eq!(tree.len(), 6);
+        assert!(tree.preorder().eq([12, 5, 20, 15, 13, 25].iter()));
+        assert_eq!(tree.delete(&12), Some(12));
+        assert_eq!(tree.delete(&5), Some(5));
+        assert!(tree.preorder().eq([13, 20, 15, 25].iter()));
+    }
+
+    #[quickcheck]
+    fn matches_pointer_tree(keys: Vec<i32>, deleted: Vec<i32>) -> bool {
+        let mut flat = tree_with(&keys);
+        let mut tree = BinaryTree::new();
+        for key in &keys {
+            tree.insert(*key);
+        }
+        for key in deleted.iter().chain(keys.iter().step_by(3)) {
+            if flat.delete(key).is_some() != tree.delete(key) {
+                return false;
+            }
+        }
+        flat.len() == tree.len()
+            && flat.preorder().copied().eq(tree.preorder())
+            && flat.postorder().copied().eq(tree.postorder())
+            && flat.level_order().copied().eq(tree.level_order())
+            && flat.inorder().copied().eq(tree.inorder())
+    }
+}
@@ -0,0 +1,179 @@
+//! Optimal binary search trees.
+//!
+//! Given sorted keys `k1 < k2 < ... < kn` searched with probabilities
+//! `p1, ..., pn`, and the gaps between them, `d0, d1, ..., dn`, standing
+//! for unsuccessful searches, searched with probabilities `q0, ..., qn`,
+//! the problem is to build the binary search tree minimizing the expected
+//! cost of a search, that is the expected number of nodes it visits. See
+//! CLRS section 15.5.
+
+use super::BinaryTree;
+
+/// OptimalTree is a binary search tree of minimum expected search cost.
+#[derive(Debug)]
+pub struct OptimalTree<T> {
+    /// The expected number of nodes a search visits, counting the gap it
+    /// ends in for an unsuccessful search.
+    pub cost: f64,
+
+    /// The tree of the keys.
+    pub tree: BinaryTree<T>,
+}
+
+/// Returns an optimal binary search tree of the keys.
+///
+/// `key_probabilities[i]` is the probability of searching `keys[i]`, and
+/// `gap_probabilities[i]` the probability of searching between `keys[i - 1]`
+/// and `keys[i]`, the first and last gaps being unbounded. The subproblem
+/// `(i, j)` is the optimal tree of the keys from `i` to `j`: trying each of
+/// them as the root, its subtrees are optimal trees of the keys on either
+/// side, which sink one level deeper. It takes O(n³) time and O(n²)
+/// memory, and the tree is then built from the table of roots.
+///
+/// # Panics
+///
+/// Panics if the keys are not strictly increasing, or if there is not one
+/// probability per key and one more per gap.
+pub fn optimal_bst<T: Ord + Clone>(
+    keys: &[T],
+    key_probabilities: &[f64],
+    gap_probabilities: &[f64],
+) -> OptimalTree<T> {
+    let n = keys.len();
+    assert!(
+        keys.windows(2).all(|pair| pair[0] < pair[1]),
+        "keys are not strictly increasing"
+    );
+    assert!(
+        key_probabilities.len() == n && gap_probabilities.len() == n + 1,
+        "probabilities do not match the keys"
+    );
+    let (p, q) = (key_probabilities, gap_probabilities);
+
+    // As in CLRS, the keys are counted from 1 and the subproblem `(i, j)`
+    // with `j = i - 1` is the empty tree of the gap `i - 1`. `costs[i][j]`
+    // is its expected cost, `weights[i][j]` the sum of its probabilities
+    // and `roots[i][j]` the key at its root.
+    let mut costs = vec![vec![0.0; n + 1]; n + 2];
+    let mut weights = vec![vec![0.0; n + 1]; n + 2];
+    let mut roots = vec![vec![0; n + 1]; n + 2];
+    for i in 1..=n + 1 {
+        costs[i][i - 1] = q[i - 1];
+        weights[i][i - 1] = q[i - 1];
+    }
+    for length in 1..=n {
+        for i in 1..=n - length + 1 {
+            let j = i + length - 1;
+            weights[i][j] = weights[i][j - 1] + p[j - 1] + q[j];
+            costs[i][j] = f64::INFINITY;
+            for root in i..=j {
+                let cost = costs[i][root - 1] + costs[root + 1][j] + weights[i][j];
+                if cost < costs[i][j] {
+                    costs[i][j] = cost;
+                    roots[i][j] = root;
+                }
+            }
+        }
+    }
+
+    // Inserting the keys in preorder rebuilds the tree of the roots.
+    fn insert<T: Ord + Clone>(
+        tree: &mut BinaryTree<T>,
+        keys: &[T],
+        roots: &[Vec<usize>],
+        i: usize,
+        j: usize,
+    ) {
+        if i > j {
+            return;
+        }
+        let root = roots[i][j];
+        tree.insert(keys[root - 1].clone());
+        insert(tree, keys, roots, i, root - 1);
+        insert(tree, keys, roots, root + 1, j);
+    }
+    let mut tree = BinaryTree::new();
+    insert(&mut tree, keys, &roots, 1, n);
+    OptimalTree {
+        cost: costs[1][n],
+        tree,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns the expected search cost of the tree from the depths of its
+    /// nodes. The gap between two keys hangs below the deeper one.
+    fn expected_cost(tree: &BinaryTree<usize>, p: &[f64], q: &[f64]) -> f64 {
+        let depth = |key: usize| tree.depth(&key).expect("the key is in the tree") as f64;
+        let n = p.len();
+        let keys: f64 = (1..=n).map(|key| (depth(key) + 1.0) * p[key - 1]).sum();
+        let gaps: f64 = (0..=n)
+            .map(|gap| {
+                let below = match (gap, gap + 1) {
+                    (0, next) if next > n => -1.0,
+                    (0, next) => depth(next),
+                    (previous, next) if next > n => depth(previous),
+                    (previous, next) => depth(previous).max(depth(next)),
+                };
+                (below + 2.0) * q[gap]
+            })
+            .sum();
+        keys + gaps
+    }
+
+    /// Returns the minimum expected cost by trying every root, in
+    /// exponential time.
+    fn brute_force(p: &[f64], q: &[f64], i: usize, j: usize) -> f64 {
+        if i > j {
+            return q[i - 1];
+        }
+        let weight: f64 = p[i - 1..j].iter().sum::<f64>() + q[i - 1..=j].iter().sum::<f64>();
+        (i..=j)
+            .map(|root| brute_force(p, q, i, root - 1) + brute_force(p, q, root + 1, j) + weight)
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn clrs_tree() {
+        // CLRS figure 15.9(b).
+        let p = [0.15, 0.10, 0.05, 0.10, 0.20];
+        let q = [0.05, 0.10, 0.05, 0.05, 0.05, 0.10];
+        let optimal = optimal_bst(&[1, 2, 3, 4, 5], &p, &q);
+        assert!((optimal.cost - 2.75).abs() < 1e-9);
+        assert_eq!(optimal.tree.preorder_walk(), vec![2, 1, 5, 4, 3]);
+        assert!((expected_cost(&optimal.tree, &p, &q) - 2.75).abs() < 1e-9);
+
+        let empty = optimal_bst::<u8>(&[], &[], &[1.0]);
+        assert!(empty.tree.is_empty());
+        assert!((empty.cost - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "not strictly increasing")]
+    fn unsorted_keys() {
+        optimal_bst(&[2, 1], &[0.5, 0.5], &[0.0, 0.0, 0.0]);
+    }
+
+    #[quickcheck]
+    fn minimizes_expected_cost(weights: Vec<u8>) -> bool {
+        let weights: Vec<f64> = weights.iter().take(13).map(|&w| f64::from(w)).collect();
+        let total: f64 = weights.iter().sum();
+        if weights.len() % 2 == 0 || total == 0.0 {
+            return true;
+        }
+        let n = weights.len() / 2;
+        let probabilities: Vec<f64> = weights.iter().map(|w| w / total).collect();
+        let (p, q) = probabilities.split_at(n);
+        let keys: Vec<usize> = (1..=n).collect();
+
+        let optimal = optimal_bst(&keys, p, q);
+        (optimal.cost - brute_force(p, q, 1, n)).abs() < 1e-9
+            && (optimal.cost - expected_cost(&optimal.tree, p, q)).abs() < 1e-9
+            && optimal.tree.inorder_walk() == keys
+    }
+}
@@ -0,0 +1,848 @@
+//! Red-black tree.
+//!
+//! A red-black tree is a binary search tree whose nodes are colored red or
+//! black so that no path from the root to a leaf is more than twice as long
+//! as another, which keeps its height below `2 lg(n + 1)`. Insertions and
+//! deletions restore the coloring with O(1) rotations and O(lg n)
+//! recolorings. See CLRS chapter 13.
+//!
+//! The nodes live in a vector and refer to each other by index, as in
+//! [`FlatBinaryTree`](super::flat::FlatBinaryTree). The index 0 is the
+//! sentinel `nil` of CLRS, a black node standing for every leaf and for the
+//! parent of the root, which spares the special cases of missing children.
+//!
+//! Every node also stores the size of its subtree, which makes the tree an
+//! order-statistic tree: [`RedBlackTree::select`] and [`RedBlackTree::rank`]
+//! find the entry of a given rank and the rank of a key in O(lg n) time.
+//! See CLRS section 14.1.
+
+use std::cmp::Ordering;
+
+use crate::map::Map;
+use crate::stack::DummyStack;
+use crate::trace::{Event, Sink};
+
+/// Index of the sentinel node.
+const NIL: usize = 0;
+
+/// Color of a node of a [`RedBlackTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+/// Node of a [`RedBlackTree`].
+#[derive(Debug, Clone)]
+struct RbNode<K, V> {
+    /// The key and value of the node, `None` for the sentinel and the free
+    /// slots.
+    entry: Option<(K, V)>,
+    color: Color,
+
+    /// The number of nodes in the subtree rooted at the node, 0 for the
+    /// sentinel.
+    size: usize,
+    parent: usize,
+    left: usize,
+    right: usize,
+}
+
+impl<K, V> RbNode<K, V> {
+    /// Creates new black node without entry, linked to the sentinel.
+    const fn nil() -> Self {
+        Self {
+            entry: None,
+            color: Color::Black,
+            size: 0,
+            parent: NIL,
+            left: NIL,
+            right: NIL,
+        }
+    }
+}
+
+/// Violation is a property of red-black trees which does not hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// The keys are not in search tree order.
+    UnorderedKeys,
+
+    /// A child does not point back to its parent.
+    BrokenParentLink,
+
+    /// The root is red.
+    RedRoot,
+
+    /// A leaf is red.
+    RedLeaf,
+
+    /// A red node has a red child.
+    RedChildOfRed,
+
+    /// Two paths from a node down to leaves have different numbers of
+    /// black nodes.
+    UnequalBlackHeights,
+
+    /// The number of entries does not match the number of nodes.
+    WrongLength,
+
+    /// The size of a subtree is not stored in its root.
+    WrongSize,
+}
+
+/// RedBlackTree is a balanced binary search tree mapping keys to values.
+#[derive(Debug, Clone)]
+pub struct RedBlackTree<K, V> {
+    /// The nodes, starting with the sentinel.
+    nodes: Vec<RbNode<K, V>>,
+    root: usize,
+
+    /// The indices of the slots left by removed nodes.
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<K, V> RedBlackTree<K, V> {
+    /// Creates new empty tree.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![RbNode::nil()],
+            root: NIL,
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the height of the tree.
+    ///
+    /// The height is the number of nodes on the longest path from the
+    /// root down to a leaf.
+    pub fn height(&self) -> usize {
+        fn height<K, V>(tree: &RedBlackTree<K, V>, index: usize) -> usize {
+            if index == NIL {
+                return 0;
+            }
+            let node = &tree.nodes[index];
+            1 + height(tree, node.left).max(height(tree, node.right))
+        }
+        height(self, self.root)
+    }
+
+    /// Returns the entry with the minimum key.
+    pub fn min(&self) -> Option<(&K, &V)> {
+        (self.root != NIL).then(|| self.entry(self.minimum(self.root)))
+    }
+
+    /// Returns the entry with the maximum key.
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut index = self.root;
+        if index == NIL {
+            return None;
+        }
+        while self.nodes[index].right != NIL {
+            index = self.nodes[index].right;
+        }
+        Some(self.entry(index))
+    }
+
+    /// Returns the entry whose key has the given rank, counting from 0, that
+    /// is the entry with the `i + 1`-th smallest key.
+    pub fn select(&self, mut i: usize) -> Option<(&K, &V)> {
+        let mut current = self.root;
+        while current != NIL {
+            let left = self.nodes[current].left;
+            let left_size = self.nodes[left].size;
+            current = match i.cmp(&left_size) {
+                Ordering::Less => left,
+                Ordering::Greater => {
+                    i -= left_size + 1;
+                    self.nodes[current].right
+                }
+                Ordering::Equal => return Some(self.entry(current)),
+            };
+        }
+        None
+    }
+
+    /// Returns the key and value of a node which is not the sentinel.
+    fn entry(&self, index: usize) -> (&K, &V) {
+        let (key, value) = self.nodes[index]
+            .entry
+            .as_ref()
+            .expect("the node is not the sentinel");
+        (key, value)
+    }
+
+    /// Returns the key of a node which is not the sentinel.
+    fn key(&self, index: usize) -> &K {
+        self.entry(index).0
+    }
+
+    /// Returns the index of the minimum node in the subtree rooted at `index`.
+    fn minimum(&self, mut index: usize) -> usize {
+        while self.nodes[index].left != NIL {
+            index = self.nodes[index].left;
+        }
+        index
+    }
+
+    /// Makes the right child `y` of `x` the root of the subtree, with `x`
+    /// as its left child.
+    fn left_rotate(&mut self, x: usize, sink: &mut impl Sink) {
+        sink.record(Event::RotateLeft { node: x });
+        let y = self.nodes[x].right;
+        self.nodes[x].right = self.nodes[y].left;
+        if self.nodes[y].left != NIL {
+            let left = self.nodes[y].left;
+            self.nodes[left].parent = x;
+        }
+        self.replace_child(x, y);
+        self.nodes[y].left = x;
+        self.nodes[x].parent = y;
+        self.rotate_sizes(x, y);
+    }
+
+    /// Makes the left child `y` of `x` the root of the subtree, with `x`
+    /// as its right child.
+    fn right_rotate(&mut self, x: usize, sink: &mut impl Sink) {
+        sink.record(Event::RotateRight { node: x });
+        let y = self.nodes[x].left;
+        self.nodes[x].left = self.nodes[y].right;
+        if self.nodes[y].right != NIL {
+            let right = self.nodes[y].right;
+            self.nodes[right].parent = x;
+        }
+        self.replace_child(x, y);
+        self.nodes[y].right = x;
+        self.nodes[x].parent = y;
+        self.rotate_sizes(x, y);
+    }
+
+    /// Updates the sizes after a rotation which made `y` the parent of `x`.
+    fn rotate_sizes(&mut self, x: usize, y: usize) {
+        self.nodes[y].size = self.nodes[x].size;
+        let (left, right) = (self.nodes[x].left, self.nodes[x].right);
+        self.nodes[x].size = self.nodes[left].size + self.nodes[right].size + 1;
+    }
+
+    /// Replaces the subtree rooted at `old` with the subtree rooted at `new`.
+    ///
+    /// The parent of `new` is set even if it is the sentinel, as the
+    /// deletion fixup starts from there.
+    fn replace_child(&mut self, old: usize, new: usize) {
+        let parent = self.nodes[old].parent;
+        if parent == NIL {
+            self.root = new;
+        } else if self.nodes[parent].left == old {
+            self.nodes[parent].left = new;
+        } else {
+            self.nodes[parent].right = new;
+        }
+        self.nodes[new].parent = parent;
+    }
+
+    /// Restores the red-black properties after inserting the red node `z`.
+    fn insert_fixup(&mut self, mut z: usize, sink: &mut impl Sink) {
+        while self.nodes[self.nodes[z].parent].color == Color::Red {
+            let parent = self.nodes[z].parent;
+            let grandparent = self.nodes[parent].parent;
+            let parent_is_left = self.nodes[grandparent].left == parent;
+            let uncle = if parent_is_left {
+                self.nodes[grandparent].right
+            } else {
+                self.nodes[grandparent].left
+            };
+
+            if self.nodes[uncle].color == Color::Red {
+                // Case 1: push the blackness of the grandparent down.
+                self.nodes[parent].color = Color::Black;
+                self.nodes[uncle].color = Color::Black;
+                self.nodes[grandparent].color = Color::Red;
+                z = grandparent;
+                continue;
+            }
+
+            let mut parent = parent;
+            if parent_is_left {
+                if self.nodes[parent].right == z {
+                    // Case 2: turn into case 3.
+                    z = parent;
+                    self.left_rotate(z, sink);
+                    parent = self.nodes[z].parent;
+                }
+                // Case 3.
+                self.nodes[parent].color = Color::Black;
+                self.nodes[grandparent].color = Color::Red;
+                self.right_rotate(grandparent, sink);
+            } else {
+                if self.nodes[parent].left == z {
+                    z = parent;
+                    self.right_rotate(z, sink);
+                    parent = self.nodes[z].parent;
+                }
+                self.nodes[parent].color = Color::Black;
+                self.nodes[grandparent].color = Color::Red;
+                self.left_rotate(grandparent, sink);
+            }
+        }
+        let root = self.root;
+        self.nodes[root].color = Color::Black;
+    }
+
+    /// Unlinks the node `z` from the tree and returns its entry.
+    fn delete(&mut self, z: usize, sink: &mut impl Sink) -> (K, V) {
+        // The node which leaves its position is `z`, or its successor if
+        // `z` has two children.
+        let moved = if self.nodes[z].left == NIL || self.nodes[z].right == NIL {
+            z
+        } else {
+            self.minimum(self.nodes[z].right)
+        };
+        let mut ancestor = self.nodes[moved].parent;
+        while ancestor != NIL {
+            self.nodes[ancestor].size -= 1;
+            ancestor = self.nodes[ancestor].parent;
+        }
+
+        let mut removed_color = self.nodes[z].color;
+        let x;
+        if self.nodes[z].left == NIL {
+            x = self.nodes[z].right;
+            self.replace_child(z, x);
+        } else if self.nodes[z].right == NIL {
+            x = self.nodes[z].left;
+            self.replace_child(z, x);
+        } else {
+            // The successor `y` of `z` takes its place, color and size.
+            let y = moved;
+            removed_color = self.nodes[y].color;
+            x = self.nodes[y].right;
+            if self.nodes[y].parent == z {
+                self.nodes[x].parent = y;
+            } else {
+                self.replace_child(y, x);
+                self.nodes[y].right = self.nodes[z].right;
+                let right = self.nodes[y].right;
+                self.nodes[right].parent = y;
+            }
+            self.replace_child(z, y);
+            self.nodes[y].left = self.nodes[z].left;
+            let left = self.nodes[y].left;
+            self.nodes[left].parent = y;
+            self.nodes[y].color = self.nodes[z].color;
+            self.nodes[y].size = self.nodes[z].size;
+        }
+        if removed_color == Color::Black {
+            self.delete_fixup(x, sink);
+        }
+
+        self.nodes[NIL] = RbNode::nil();
+        self.free.push(z);
+        self.len -= 1;
+        let node = std::mem::replace(&mut self.nodes[z], RbNode::nil());
+        node.entry.expect("the node is not the sentinel")
+    }
+
+    /// Restores the red-black properties after removing a black node, `x`
+    /// being the node which carries an extra black.
+    fn delete_fixup(&mut self, mut x: usize, sink: &mut impl Sink) {
+        while x != self.root && self.nodes[x].color == Color::Black {
+            let parent = self.nodes[x].parent;
+            if self.nodes[parent].left == x {
+                let mut w = self.nodes[parent].right;
+                if self.nodes[w].color == Color::Red {
+                    // Case 1: make the sibling black.
+                    self.nodes[w].color = Color::Black;
+                    self.nodes[parent].color = Color::Red;
+                    self.left_rotate(parent, sink);
+                    w = self.nodes[parent].right;
+                }
+                let (near, far) = (self.nodes[w].left, self.nodes[w].right);
+                if self.nodes[near].color == Color::Black && self.nodes[far].color == Color::Black {
+                    // Case 2: move the extra black up.
+                    self.nodes[w].color = Color::Red;
+                    x = parent;
+                    continue;
+                }
+                if self.nodes[far].color == Color::Black {
+                    // Case 3: turn into case 4.
+                    self.nodes[near].color = Color::Black;
+                    self.nodes[w].color = Color::Red;
+                    self.right_rotate(w, sink);
+                    w = self.nodes[parent].right;
+                }
+                // Case 4: absorb the extra black.
+                self.nodes[w].color = self.nodes[parent].color;
+                self.nodes[parent].color = Color::Black;
+                let far = self.nodes[w].right;
+                self.nodes[far].color = Color::Black;
+                self.left_rotate(parent, sink);
+                x = self.root;
+            } else {
+                let mut w = self.nodes[parent].left;
+                if self.nodes[w].color == Color::Red {
+                    self.nodes[w].color = Color::Black;
+                    self.nodes[parent].color = Color::Red;
+                    self.right_rotate(parent, sink);
+                    w = self.nodes[parent].left;
+                }
+                let (near, far) = (self.nodes[w].right, self.nodes[w].left);
+                if self.nodes[near].color == Color::Black && self.nodes[far].color == Color::Black {
+                    self.nodes[w].color = Color::Red;
+                    x = parent;
+                    continue;
+                }
+                if self.nodes[far].color == Color::Black {
+                    self.nodes[near].color = Color::Black;
+                    self.nodes[w].color = Color::Red;
+                    self.left_rotate(w, sink);
+                    w = self.nodes[parent].left;
+                }
+                self.nodes[w].color = self.nodes[parent].color;
+                self.nodes[parent].color = Color::Black;
+                let far = self.nodes[w].left;
+                self.nodes[far].color = Color::Black;
+                self.right_rotate(parent, sink);
+                x = self.root;
+            }
+        }
+        self.nodes[x].color = Color::Black;
+    }
+
+    /// Stores a new red node and returns its index.
+    fn allocate(&mut self, key: K, value: V, parent: usize) -> usize {
+        let node = RbNode {
+            entry: Some((key, value)),
+            color: Color::Red,
+            size: 1,
+            parent,
+            left: NIL,
+            right: NIL,
+        };
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> RedBlackTree<K, V> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.traced_insert(key, value, &mut ())
+    }
+
+    /// Traced insertion.
+    ///
+    /// It is [`RedBlackTree::insert`], where each rotation is recorded to
+    /// the sink as an [`Event::RotateLeft`] or [`Event::RotateRight`] of
+    /// the node which moves down, identified by its index. An insertion
+    /// makes at most two rotations.
+    pub fn traced_insert(&mut self, key: K, value: V, sink: &mut impl Sink) -> Option<V> {
+        let mut parent = NIL;
+        let mut current = self.root;
+        let mut ordering = Ordering::Equal;
+        while current != NIL {
+            parent = current;
+            ordering = key.cmp(self.key(current));
+            current = match ordering {
+                Ordering::Less => self.nodes[current].left,
+                Ordering::Greater => self.nodes[current].right,
+                Ordering::Equal => {
+                    let (_, old) = self.nodes[current]
+                        .entry
+                        .as_mut()
+                        .expect("the node is not the sentinel");
+                    return Some(std::mem::replace(old, value));
+                }
+            };
+        }
+
+        let z = self.allocate(key, value, parent);
+        match ordering {
+            _ if parent == NIL => self.root = z,
+            Ordering::Less => self.nodes[parent].left = z,
+            _ => self.nodes[parent].right = z,
+        }
+        let mut ancestor = parent;
+        while ancestor != NIL {
+            self.nodes[ancestor].size += 1;
+            ancestor = self.nodes[ancestor].parent;
+        }
+        self.len += 1;
+        self.insert_fixup(z, sink);
+        None
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let index = self.find(key);
+        (index != NIL).then(|| self.entry(index).1)
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key);
+        let (_, value) = self.nodes[index].entry.as_mut()?;
+        Some(value)
+    }
+
+    /// Removes the key from the tree and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.traced_remove(key, &mut ())
+    }
+
+    /// Traced removal.
+    ///
+    /// It is [`RedBlackTree::remove`], where each rotation is recorded to
+    /// the sink as in [`RedBlackTree::traced_insert`]. A removal makes at
+    /// most three rotations.
+    pub fn traced_remove(&mut self, key: &K, sink: &mut impl Sink) -> Option<V> {
+        let index = self.find(key);
+        (index != NIL).then(|| self.delete(index, sink).1)
+    }
+
+    /// Returns the number of keys less than the key, which is the rank of
+    /// the key counting from 0 if it is in the tree.
+    pub fn rank(&self, key: &K) -> usize {
+        let mut rank = 0;
+        let mut current = self.root;
+        while current != NIL {
+            let left = self.nodes[current].left;
+            current = match key.cmp(self.key(current)) {
+                Ordering::Less => left,
+                Ordering::Greater => {
+                    rank += self.nodes[left].size + 1;
+                    self.nodes[current].right
+                }
+                Ordering::Equal => return rank + self.nodes[left].size,
+            };
+        }
+        rank
+    }
+
+    /// Checks the properties of red-black trees, and returns the
+    /// black-height of the tree.
+    ///
+    /// Besides the search tree order and the parent links, the five
+    /// properties are: every node is red or black, the root is black, every
+    /// leaf is black, both children of a red node are black, and all the
+    /// paths from a node down to the leaves have the same number of black
+    /// nodes. The first holds by construction. The size stored in every node
+    /// is checked as well.
+    pub fn check_invariants(&self) -> Result<usize, Violation> {
+        if self.nodes[self.root].color == Color::Red {
+            return Err(Violation::RedRoot);
+        }
+        if self.nodes[NIL].color == Color::Red {
+            return Err(Violation::RedLeaf);
+        }
+        if self.root != NIL && self.nodes[self.root].parent != NIL {
+            return Err(Violation::BrokenParentLink);
+        }
+        let height = self.check_subtree(self.root, None, None)?;
+        let count = self.nodes.len() - 1 - self.free.len();
+        if count != self.len || self.iter().count() != self.len {
+            return Err(Violation::WrongLength);
+        }
+        Ok(height)
+    }
+
+    /// Checks the subtree rooted at `index`, whose keys must lie between
+    /// `low` and `high`, and returns its black-height.
+    fn check_subtree(
+        &self,
+        index: usize,
+        low: Option<&K>,
+        high: Option<&K>,
+    ) -> Result<usize, Violation> {
+        if index == NIL {
+            return Ok(0);
+        }
+        let node = &self.nodes[index];
+        let key = self.key(index);
+        if low.map_or(false, |low| key <= low) || high.map_or(false, |high| key >= high) {
+            return Err(Violation::UnorderedKeys);
+        }
+        for child in [node.left, node.right] {
+            if child != NIL && self.nodes[child].parent != index {
+                return Err(Violation::BrokenParentLink);
+            }
+            if node.color == Color::Red && self.nodes[child].color == Color::Red {
+                return Err(Violation::RedChildOfRed);
+            }
+        }
+        let (left_size, right_size) = (self.nodes[node.left].size, self.nodes[node.right].size);
+        if node.size != left_size + right_size + 1 {
+            return Err(Violation::WrongSize);
+        }
+        let left = self.check_subtree(node.left, low, Some(key))?;
+        let right = self.check_subtree(node.right, Some(key), high)?;
+        if left != right {
+            return Err(Violation::UnequalBlackHeights);
+        }
+        Ok(left + usize::from(node.color == Color::Black))
+    }
+
+    /// Returns the index of the node with the key, or the sentinel if there
+    /// is none.
+    fn find(&self, key: &K) -> usize {
+        let mut current = self.root;
+        while current != NIL {
+            current = match key.cmp(self.key(current)) {
+                Ordering::Less => self.nodes[current].left,
+                Ordering::Greater => self.nodes[current].right,
+                Ordering::Equal => return current,
+            };
+        }
+        NIL
+    }
+}
+
+impl<K, V> RedBlackTree<K, V> {
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> RedBlackIter<'_, K, V> {
+        let mut iter = RedBlackIter {
+            tree: self,
+            stack: DummyStack::new(),
+        };
+        iter.push_left_spine(self.root);
+        iter
+    }
+}
+
+impl<K, V> Default for RedBlackTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Map<K, V> for RedBlackTree<K, V> {
+    type Iter<'a>
+        = RedBlackIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        RedBlackTree::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        RedBlackTree::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        RedBlackTree::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        RedBlackTree::len(self)
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    fn iter(&self) -> Self::Iter<'_> {
+        RedBlackTree::iter(self)
+    }
+}
+
+/// Iterator over the entries of a [`RedBlackTree`], sorted by key.
+#[derive(Debug)]
+pub struct RedBlackIter<'a, K, V> {
+    tree: &'a RedBlackTree<K, V>,
+
+    /// The nodes whose left subtree is being visited.
+    stack: DummyStack<usize>,
+}
+
+impl<'a, K, V> RedBlackIter<'a, K, V> {
+    fn push_left_spine(&mut self, mut index: usize) {
+        while index != NIL {
+            self.stack.push(index);
+            index = self.tree.nodes[index].left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for RedBlackIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.stack.pop()?;
+        self.push_left_spine(self.tree.nodes[index].right);
+        Some(self.tree.entry(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    #[test]
+    fn clrs_insertions() {
+        // The insertions of CLRS exercise 13.3-2.
+        let mut tree = RedBlackTree::new();
+        for key in [41, 38, 31, 12, 19, 8] {
+            assert_eq!(tree.insert(key, key * 10), None);
+            assert!(tree.check_invariants().is_ok());
+        }
+        assert_eq!(tree.check_invariants(), Ok(2));
+        assert_eq!(tree.key(tree.root), &38);
+        assert_eq!(tree.insert(19, 0), Some(190));
+        assert_eq!(tree.get(&19), Some(&0));
+        assert_eq!(tree.min(), Some((&8, &80)));
+        assert_eq!(tree.max(), Some((&41, &410)));
+        let keys: Vec<_> = tree.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec![8, 12, 19, 31, 38, 41]);
+
+        // The deletions of CLRS exercise 13.4-3.
+        for key in [8, 12, 19, 31, 38, 41] {
+            assert!(tree.remove(&key).is_some());
+            assert!(tree.check_invariants().is_ok());
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.remove(&8), None);
+    }
+
+    #[test]
+    fn sorted_insertions_stay_balanced() {
+        let mut tree = RedBlackTree::new();
+        for key in 0..1023 {
+            tree.insert(key, ());
+        }
+        assert!(tree.check_invariants().is_ok());
+        assert!(tree.height() <= 2 * 10);
+        for key in (0..1023).step_by(2) {
+            tree.remove(&key);
+        }
+        assert!(tree.check_invariants().is_ok());
+        assert_eq!(tree.len(), 511);
+        assert_eq!(tree.nodes.len(), 1024);
+    }
+
+    #[test]
+    fn traced_rotations() {
+        // The nodes are stored at index 1, 2, 3 in the order of insertion.
+        // Inserting 3, 2, 1 rotates the node of 3 to the right.
+        let mut tree = RedBlackTree::new();
+        let mut events = Vec::new();
+        for key in [3, 2, 1] {
+            tree.traced_insert(key, (), &mut events);
+        }
+        assert_eq!(events, vec![Event::RotateRight { node: 1 }]);
+
+        // Removing 1 leaves the red child 3 under the root 2, so inserting
+        // 4 rotates the root to the left.
+        events.clear();
+        tree.traced_remove(&1, &mut events);
+        assert!(events.is_empty());
+        tree.traced_insert(4, (), &mut events);
+        assert_eq!(events, vec![Event::RotateLeft { node: 2 }]);
+        assert_eq!(tree.key(tree.root), &3);
+        assert!(tree.check_invariants().is_ok());
+    }
+
+    #[test]
+    fn detect_violations() {
+        let mut tree = RedBlackTree::new();
+        for key in 0..8 {
+            tree.insert(key, ());
+        }
+        let root = tree.root;
+        tree.nodes[root].color = Color::Red;
+        assert_eq!(tree.check_invariants(), Err(Violation::RedRoot));
+        tree.nodes[root].color = Color::Black;
+
+        let left = tree.nodes[root].left;
+        tree.nodes[left].color = Color::Red;
+        let grandchild = tree.nodes[left].left;
+        tree.nodes[grandchild].color = Color::Red;
+        assert!(tree.check_invariants().is_err());
+    }
+
+    #[test]
+    fn order_statistics() {
+        // The keys of CLRS figure 14.1.
+        let mut tree = RedBlackTree::new();
+        for key in [
+            26, 17, 41, 14, 21, 30, 47, 10, 16, 19, 23, 28, 38, 7, 12, 15, 20, 35, 39, 3,
+        ] {
+            tree.insert(key, ());
+        }
+        assert_eq!(tree.select(0), Some((&3, &())));
+        assert_eq!(tree.select(16), Some((&38, &())));
+        assert_eq!(tree.select(19), Some((&47, &())));
+        assert_eq!(tree.select(20), None);
+        assert_eq!(tree.rank(&38), 16);
+        assert_eq!(tree.rank(&3), 0);
+        assert_eq!(tree.rank(&2), 0);
+        assert_eq!(tree.rank(&40), 18);
+        assert_eq!(tree.rank(&50), 20);
+
+        tree.remove(&17);
+        assert_eq!(tree.rank(&38), 15);
+        assert_eq!(tree.select(15), Some((&38, &())));
+        assert!(tree.check_invariants().is_ok());
+
+        let root = tree.root;
+        tree.nodes[root].size += 1;
+        assert_eq!(tree.check_invariants(), Err(Violation::WrongSize));
+    }
+
+    #[quickcheck]
+    fn select_inverts_rank(operations: Vec<Operation>) -> bool {
+        let mut tree = RedBlackTree::new();
+        for &(insert, key, value) in &operations {
+            if insert {
+                tree.insert(key, value);
+            } else {
+                tree.remove(&key);
+            }
+        }
+        tree.iter().enumerate().all(|(i, (key, _))| {
+            tree.rank(key) == i && tree.select(i).map(|(key, _)| key) == Some(key)
+        })
+    }
+
+    #[quickcheck]
+    fn keeps_invariants(operations: Vec<Operation>) -> bool {
+        let mut tree = RedBlackTree::new();
+        operations.iter().all(|&(insert, key, value)| {
+            if insert {
+                tree.insert(key, value);
+            } else {
+                tree.remove(&key);
+            }
+            tree.check_invariants()
+                .map_or(false, |height| tree.height() <= 2 * height + 1)
+        })
+    }
+
+    #[quickcheck]
+    fn red_black_tree_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut RedBlackTree::new(), &operations)
+    }
+}
@@ -0,0 +1,444 @@
+//! Rope data structure.
+//!
+//! A rope is a string stored in the leaves of a binary tree. Each internal
+//! node knows the number of characters of its left subtree, so a character
+//! is found by descending the tree, and inserting or deleting text only
+//! rebuilds the path to the edited position instead of moving the whole
+//! string.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::stack::DummyStack;
+
+/// Maximum number of characters of a leaf.
+const CHUNK_SIZE: usize = 64;
+
+/// Node of a [`Rope`].
+#[derive(Debug, Clone)]
+enum Node {
+    /// A chunk of the text.
+    Leaf(String),
+
+    /// The concatenation of two ropes.
+    Branch {
+        left: Box<Node>,
+        right: Box<Node>,
+
+        /// Number of characters of the left subtree.
+        weight: usize,
+
+        /// Number of characters of the whole subtree.
+        len: usize,
+
+        /// Number of nodes on the longest path down to a leaf.
+        height: usize,
+
+        /// Number of leaves of the subtree.
+        leaves: usize,
+    },
+}
+
+impl Node {
+    /// Creates new leaf, or `None` if the text is empty.
+    fn leaf(text: String) -> Option<Box<Self>> {
+        (!text.is_empty()).then(|| Box::new(Self::Leaf(text)))
+    }
+
+    /// Returns the number of characters of the subtree.
+    fn len(&self) -> usize {
+        match self {
+            Self::Leaf(text) => text.chars().count(),
+            Self::Branch { len, .. } => *len,
+        }
+    }
+
+    /// Returns the height of the subtree.
+    const fn height(&self) -> usize {
+        match self {
+            Self::Leaf(_) => 1,
+            Self::Branch { height, .. } => *height,
+        }
+    }
+
+    /// Returns the number of leaves of the subtree.
+    const fn leaves(&self) -> usize {
+        match self {
+            Self::Leaf(_) => 1,
+            Self::Branch { leaves, .. } => *leaves,
+        }
+    }
+
+    /// Concatenates two subtrees under a new branch.
+    fn branch(left: Box<Self>, right: Box<Self>) -> Box<Self> {
+        let weight = left.len();
+        Box::new(Self::Branch {
+            weight,
+            len: weight + right.len(),
+            height: left.height().max(right.height()) + 1,
+            leaves: left.leaves() + right.leaves(),
+            left,
+            right,
+        })
+    }
+
+    /// Concatenates two optional subtrees.
+    fn join(left: Option<Box<Self>>, right: Option<Box<Self>>) -> Option<Box<Self>> {
+        match (left, right) {
+            (Some(left), Some(right)) => Some(Self::branch(left, right)),
+            (left, None) => left,
+            (None, right) => right,
+        }
+    }
+
+    /// Splits the subtree before the character at the given index.
+    fn split(self, index: usize) -> (Option<Box<Self>>, Option<Box<Self>>) {
+        match self {
+            Self::Leaf(mut text) => {
+                let offset = text
+                    .char_indices()
+                    .nth(index)
+                    .map_or(text.len(), |(offset, _)| offset);
+                let right = text.split_off(offset);
+                (Self::leaf(text), Self::leaf(right))
+            }
+            Self::Branch {
+                left,
+                right,
+                weight,
+                ..
+            } => {
+                if index < weight {
+                    let (left_left, left_right) = (*left).split(index);
+                    (left_left, Self::join(left_right, Some(right)))
+                } else {
+                    let (right_left, right_right) = (*right).split(index - weight);
+                    (Self::join(Some(left), right_left), right_right)
+                }
+            }
+        }
+    }
+
+    /// Builds a balanced subtree from its chunks.
+    fn build(chunks: &mut [String]) -> Option<Box<Self>> {
+        match chunks {
+            [] => None,
+            [chunk] => Self::leaf(std::mem::take(chunk)),
+            _ => {
+                let middle = chunks.len() / 2;
+                let (left, right) = chunks.split_at_mut(middle);
+                Self::join(Self::build(left), Self::build(right))
+            }
+        }
+    }
+
+    /// Returns true if the height is logarithmic in the number of leaves.
+    const fn is_balanced(&self) -> bool {
+        let log = usize::BITS - self.leaves().leading_zeros();
+        self.height() <= 2 * log as usize + 1
+    }
+}
+
+/// A Rope is a string stored as a balanced tree of chunks.
+///
+/// Positions are counted in characters, not in bytes.
+#[derive(Debug, Clone, Default)]
+pub struct Rope {
+    root: Option<Box<Node>>,
+}
+
+impl Rope {
+    /// Creates new empty rope.
+    pub const fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns the number of characters.
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |root| root.len())
+    }
+
+    /// Returns true if the rope is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the character at the given index.
+    pub fn char_at(&self, mut index: usize) -> Option<char> {
+        let mut node = self.root.as_deref()?;
+        loop {
+            match node {
+                Node::Leaf(text) => return text.chars().nth(index),
+                Node::Branch {
+                    left,
+                    right,
+                    weight,
+                    ..
+                } => {
+                    if index < *weight {
+                        node = left;
+                    } else {
+                        index -= weight;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Inserts the text before the character at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of the rope.
+    pub fn insert(&mut self, index: usize, text: &str) {
+        assert!(index <= self.len(), "insertion index is out of bounds");
+        let (left, right) = self.take_root().split_at(index);
+        *self = left.concat(Self::from(text)).concat(right);
+    }
+
+    /// Deletes the characters in the given range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or ends after the end of the rope.
+    pub fn delete(&mut self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "deletion range is out of bounds"
+        );
+        let (left, right) = self.take_root().split_at(range.end);
+        let (left, _) = left.split_at(range.start);
+        *self = left.concat(right);
+    }
+
+    /// Concatenates two ropes.
+    pub fn concat(self, other: Self) -> Self {
+        Self::balanced(Node::join(self.root, other.root))
+    }
+
+    /// Splits the rope before the character at the given index.
+    ///
+    /// If `index` is greater than the length of the rope, the second rope
+    /// is empty.
+    pub fn split_at(self, index: usize) -> (Self, Self) {
+        match self.root {
+            Some(root) => {
+                let (left, right) = (*root).split(index);
+                (Self::balanced(left), Self::balanced(right))
+            }
+            None => (Self::new(), Self::new()),
+        }
+    }
+
+    /// Creates an iterator over the chunks of text, from left to right.
+    pub fn chunks(&self) -> Chunks<'_> {
+        let mut stack = DummyStack::new();
+        if let Some(root) = self.root.as_deref() {
+            stack.push(root);
+        }
+        Chunks { stack }
+    }
+
+    /// Takes the content of the rope, leaving it empty.
+    fn take_root(&mut self) -> Self {
+        Self {
+            root: self.root.take(),
+        }
+    }
+
+    /// Creates new rope from a tree, rebuilding the tree if it is too deep.
+    ///
+    /// Adjacent chunks are merged when they fit into a single leaf, so that
+    /// repeated edits do not leave many tiny leaves behind.
+    fn balanced(root: Option<Box<Node>>) -> Self {
+        let Some(root) = root else {
+            return Self::new();
+        };
+        if root.is_balanced() {
+            return Self { root: Some(root) };
+        }
+
+        let rope = Self { root: Some(root) };
+        let mut chunks: Vec<String> = Vec::with_capacity(rope.len() / CHUNK_SIZE + 1);
+        for chunk in rope.chunks() {
+            match chunks.last_mut() {
+                Some(last) if last.chars().count() + chunk.chars().count() <= CHUNK_SIZE => {
+                    last.push_str(chunk);
+                }
+                _ => chunks.push(chunk.to_string()),
+            }
+        }
+        Self {
+            root: Node::build(&mut chunks),
+        }
+    }
+}
+
+impl From<&str> for Rope {
+    /// Creates new rope, cutting the text in chunks of `CHUNK_SIZE`
+    /// characters.
+    fn from(text: &str) -> Self {
+        let mut chunks = Vec::with_capacity(text.len() / CHUNK_SIZE + 1);
+        let mut chars = text.chars().peekable();
+        while chars.peek().is_some() {
+            chunks.push(chars.by_ref().take(CHUNK_SIZE).collect());
+        }
+        Self {
+            root: Node::build(&mut chunks),
+        }
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+/// Iterator over the chunks of a [`Rope`].
+#[derive(Debug)]
+pub struct Chunks<'a> {
+    stack: DummyStack<&'a Node>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.pop()? {
+                Node::Leaf(text) => return Some(text),
+                Node::Branch { left, right, .. } => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Returns the byte offset of the character at the given index.
+    fn offset(s: &str, index: usize) -> usize {
+        s.char_indices()
+            .nth(index)
+            .map_or(s.len(), |(offset, _)| offset)
+    }
+
+    fn is_valid(rope: &Rope) -> bool {
+        fn check(node: &Node) -> bool {
+            match node {
+                Node::Leaf(text) => !text.is_empty(),
+                Node::Branch {
+                    left,
+                    right,
+                    weight,
+                    len,
+                    ..
+                } => {
+                    *weight == left.len()
+                        && *len == left.len() + right.len()
+                        && check(left)
+                        && check(right)
+                }
+            }
+        }
+        rope.root
+            .as_deref()
+            .map_or(true, |root| check(root) && root.is_balanced())
+    }
+
+    #[test]
+    fn create_rope() {
+        let text = "a".repeat(1000);
+        let rope = Rope::from(text.as_str());
+        assert_eq!(rope.len(), 1000);
+        assert_eq!(rope.chunks().count(), 1000 / CHUNK_SIZE + 1);
+        assert!(rope.chunks().all(|chunk| chunk.len() <= CHUNK_SIZE));
+        assert_eq!(rope.to_string(), text);
+        assert!(is_valid(&rope));
+
+        let rope = Rope::new();
+        assert!(rope.is_empty());
+        assert_eq!(rope.char_at(0), None);
+        assert_eq!(rope.to_string(), "");
+    }
+
+    #[test]
+    fn edit_rope() {
+        let mut rope = Rope::from("Hello world");
+        rope.insert(5, ",");
+        rope.insert(12, "!");
+        assert_eq!(rope.to_string(), "Hello, world!");
+        rope.delete(5..12);
+        assert_eq!(rope.to_string(), "Hello!");
+        rope.insert(0, "¡");
+        assert_eq!(rope.to_string(), "¡Hello!");
+        assert_eq!(rope.char_at(0), Some('¡'));
+        assert_eq!(rope.char_at(6), Some('!'));
+        assert_eq!(rope.char_at(7), None);
+    }
+
+    #[test]
+    fn split_and_concat() {
+        let rope = Rope::from("crème brûlée");
+        let (left, right) = rope.split_at(6);
+        assert_eq!(left.to_string(), "crème ");
+        assert_eq!(right.to_string(), "brûlée");
+        assert_eq!(right.concat(left).to_string(), "brûléecrème ");
+
+        let (left, right) = Rope::from("abc").split_at(10);
+        assert_eq!((left.len(), right.len()), (3, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn insert_out_of_bounds() {
+        Rope::from("abc").insert(4, "d");
+    }
+
+    #[test]
+    fn many_small_edits_stay_balanced() {
+        let mut rope = Rope::new();
+        let mut expected = String::new();
+        for index in 0..2000 {
+            let position = index * 7 % (expected.len() + 1);
+            rope.insert(position, "x");
+            expected.insert(position, 'x');
+        }
+        assert_eq!(rope.to_string(), expected);
+        assert!(is_valid(&rope));
+    }
+
+    #[quickcheck]
+    fn edits_agree_with_string(edits: Vec<(bool, u8, u8, String)>) -> bool {
+        let mut rope = Rope::new();
+        let mut expected = String::new();
+        for (insert, first, second, text) in edits {
+            let len = expected.chars().count();
+            let first = usize::from(first) % (len + 1);
+            if insert {
+                rope.insert(first, &text);
+                expected.insert_str(offset(&expected, first), &text);
+            } else {
+                let last = (first + usize::from(second)).min(len);
+                rope.delete(first..last);
+                expected.replace_range(offset(&expected, first)..offset(&expected, last), "");
+            }
+            if !is_valid(&rope) || rope.len() != expected.chars().count() {
+                return false;
+            }
+        }
+        rope.to_string() == expected
+            && expected
+                .chars()
+                .enumerate()
+                .all(|(index, c)| rope.char_at(index) == Some(c))
+    }
+}
@@ -0,0 +1,420 @@
+//! Treap.
+//!
+//! A treap is a binary search tree whose nodes also carry a random
+//! priority, arranged in heap order: the priority of a node is smaller than
+//! the priorities of its children. The shape of the tree is the one a
+//! binary search tree gets when its keys are inserted by increasing
+//! priority, that is in random order, so its expected height is O(lg n)
+//! whatever the order of the operations. See CLRS problem 13-4.
+//!
+//! Every update is built on two primitives: splitting a treap around a key
+//! and merging two treaps whose keys do not overlap.
+
+use std::cmp::Ordering;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::map::Map;
+use crate::stack::DummyStack;
+
+type Link<K, V> = Option<Box<TreapNode<K, V>>>;
+
+/// Node of a [`Treap`].
+#[derive(Debug, Clone)]
+struct TreapNode<K, V> {
+    key: K,
+    value: V,
+    priority: u64,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+/// Splits the subtree into the nodes with keys less than the key, and the
+/// nodes with keys greater than or equal to it.
+fn split<K: Ord, V>(link: Link<K, V>, key: &K) -> (Link<K, V>, Link<K, V>) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    if node.key < *key {
+        let (less, rest) = split(node.right.take(), key);
+        node.right = less;
+        (Some(node), rest)
+    } else {
+        let (less, rest) = split(node.left.take(), key);
+        node.left = rest;
+        (less, Some(node))
+    }
+}
+
+/// Merges two subtrees, the keys of the first being less than the keys of
+/// the second.
+fn merge<K, V>(first: Link<K, V>, second: Link<K, V>) -> Link<K, V> {
+    match (first, second) {
+        (None, link) | (link, None) => link,
+        (Some(mut first), Some(mut second)) => {
+            if first.priority < second.priority {
+                first.right = merge(first.right.take(), Some(second));
+                Some(first)
+            } else {
+                second.left = merge(Some(first), second.left.take());
+                Some(second)
+            }
+        }
+    }
+}
+
+/// Treap is a randomized binary search tree mapping keys to values.
+#[derive(Debug, Clone)]
+pub struct Treap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+
+    /// The source of the priorities.
+    rng: StdRng,
+}
+
+impl<K, V> Treap<K, V> {
+    /// Creates new empty treap whose priorities are seeded from the
+    /// operating system.
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates new empty treap with a fixed seed, so that the priorities
+    /// drawn, and hence the shape of the tree, are reproducible.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    const fn with_rng(rng: StdRng) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            rng,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the treap is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the height of the treap.
+    ///
+    /// The height is the number of nodes on the longest path from the
+    /// root down to a leaf.
+    pub fn height(&self) -> usize {
+        fn height<K, V>(link: &Link<K, V>) -> usize {
+            link.as_ref()
+                .map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> TreapIter<'_, K, V> {
+        let mut iter = TreapIter {
+            stack: DummyStack::new(),
+        };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+}
+
+impl<K: Ord, V> Treap<K, V> {
+    /// Inserts a value with the given key.
+    ///
+    /// It returns the value previously associated with the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(std::mem::replace(old, value));
+        }
+        let (less, greater) = split(self.root.take(), &key);
+        let node = Box::new(TreapNode {
+            key,
+            value,
+            priority: self.rng.gen(),
+            left: None,
+            right: None,
+        });
+        self.root = merge(merge(less, Some(node)), greater);
+        self.len += 1;
+        None
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_ref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_ref(),
+                Ordering::Greater => node.right.as_ref(),
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_mut();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_mut(),
+                Ordering::Greater => node.right.as_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    /// Removes the key from the treap and returns its value, if any.
+    ///
+    /// The node is replaced by the merge of its subtrees.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let mut link = &mut self.root;
+        loop {
+            match key.cmp(&link.as_ref()?.key) {
+                Ordering::Less => link = &mut link.as_mut()?.left,
+                Ordering::Greater => link = &mut link.as_mut()?.right,
+                Ordering::Equal => break,
+            }
+        }
+        let mut node = link.take()?;
+        *link = merge(node.left.take(), node.right.take());
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// Moves the entries with keys greater than or equal to the key into a
+    /// new treap, and returns it.
+    pub fn split_off(&mut self, key: &K) -> Self {
+        let (less, greater) = split(self.root.take(), key);
+        self.root = less;
+        let mut other = Self::with_seed(self.rng.gen());
+        other.root = greater;
+        other.len = other.iter().count();
+        self.len -= other.len;
+        other
+    }
+
+    /// Moves the entries of the other treap, whose keys must all be greater
+    /// than the keys of this one, into this treap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key of the other treap is not greater than every key of
+    /// this treap.
+    pub fn append(&mut self, other: Self) {
+        if let (Some(max), Some(min)) = (self.max(), other.min()) {
+            assert!(max < min, "keys overlap");
+        }
+        self.root = merge(self.root.take(), other.root);
+        self.len += other.len;
+    }
+
+    /// Returns the largest key.
+    fn max(&self) -> Option<&K> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = node.right.as_ref() {
+            node = right;
+        }
+        Some(&node.key)
+    }
+
+    /// Returns the smallest key.
+    fn min(&self) -> Option<&K> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = node.left.as_ref() {
+            node = left;
+        }
+        Some(&node.key)
+    }
+}
+
+impl<K, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Map<K, V> for Treap<K, V> {
+    type Iter<'a>
+        = TreapIter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        Treap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        Treap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        Treap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        Treap::len(self)
+    }
+
+    /// Creates an iterator over the entries, sorted by key.
+    fn iter(&self) -> Self::Iter<'_> {
+        Treap::iter(self)
+    }
+}
+
+/// Iterator over the entries of a [`Treap`], sorted by key.
+#[derive(Debug)]
+pub struct TreapIter<'a, K, V> {
+    /// The nodes whose left subtree is being visited.
+    stack: DummyStack<&'a TreapNode<K, V>>,
+}
+
+impl<'a, K, V> TreapIter<'a, K, V> {
+    fn push_left_spine(&mut self, mut link: &'a Link<K, V>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for TreapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some((&node.key, &node.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::map::suite::{agrees_with_model, Operation};
+
+    /// Returns true if the keys are in search tree order and the
+    /// priorities in heap order.
+    fn is_valid<K: Ord, V>(treap: &Treap<K, V>) -> bool {
+        fn check<K: Ord, V>(link: &Link<K, V>, low: Option<&K>, high: Option<&K>) -> bool {
+            let Some(node) = link else {
+                return true;
+            };
+            let heap_ordered = [&node.left, &node.right]
+                .into_iter()
+                .flatten()
+                .all(|child| child.priority >= node.priority);
+            heap_ordered
+                && low.map_or(true, |low| *low < node.key)
+                && high.map_or(true, |high| node.key < *high)
+                && check(&node.left, low, Some(&node.key))
+                && check(&node.right, Some(&node.key), high)
+        }
+        check(&treap.root, None, None) && treap.iter().count() == treap.len()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut treap = Treap::with_seed(7);
+        for key in 0..100 {
+            assert_eq!(treap.insert(key, key * 2), None);
+        }
+        assert_eq!(treap.insert(7, 0), Some(14));
+        assert_eq!(treap.get(&7), Some(&0));
+        assert_eq!(treap.len(), 100);
+        assert!(is_valid(&treap));
+        for key in (0..100).step_by(2) {
+            assert!(treap.remove(&key).is_some());
+        }
+        assert_eq!(treap.remove(&0), None);
+        assert_eq!(treap.get(&3), Some(&6));
+        assert_eq!(treap.len(), 50);
+        assert!(is_valid(&treap));
+    }
+
+    #[test]
+    fn sorted_insertions_have_logarithmic_height() {
+        // The expected height is about 4.3 ln n, which is 30 for 1024 keys.
+        for seed in 0..8 {
+            let mut treap = Treap::with_seed(seed);
+            for key in 0..1024 {
+                treap.insert(key, ());
+            }
+            assert!(treap.height() <= 40, "height {}", treap.height());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_shape() {
+        let build = |seed| {
+            let mut treap = Treap::with_seed(seed);
+            for key in 0..64 {
+                treap.insert(key, ());
+            }
+            treap
+        };
+        let (first, second) = (build(3), build(3));
+        let shape = |treap: &Treap<i32, ()>| treap.root.as_ref().map(|root| root.key);
+        assert_eq!(shape(&first), shape(&second));
+        assert_eq!(first.height(), second.height());
+    }
+
+    #[test]
+    fn split_off_and_append() {
+        let mut treap = Treap::with_seed(1);
+        for key in 0..10 {
+            treap.insert(key, ());
+        }
+        let upper = treap.split_off(&4);
+        assert!(treap.iter().map(|(key, _)| *key).eq(0..4));
+        assert!(upper.iter().map(|(key, _)| *key).eq(4..10));
+        assert_eq!((treap.len(), upper.len()), (4, 6));
+        assert!(is_valid(&treap) && is_valid(&upper));
+
+        treap.append(upper);
+        assert!(treap.iter().map(|(key, _)| *key).eq(0..10));
+        assert_eq!(treap.len(), 10);
+        assert!(is_valid(&treap));
+    }
+
+    #[test]
+    #[should_panic(expected = "keys overlap")]
+    fn append_overlapping_keys() {
+        let mut treap = Treap::with_seed(1);
+        treap.insert(5, ());
+        let mut other = Treap::with_seed(2);
+        other.insert(5, ());
+        treap.append(other);
+    }
+
+    #[quickcheck]
+    fn stays_valid(operations: Vec<Operation>, seed: u64) -> bool {
+        let mut treap = Treap::with_seed(seed);
+        operations.iter().all(|&(insert, key, value)| {
+            if insert {
+                treap.insert(key, value);
+            } else {
+                treap.remove(&key);
+            }
+            is_valid(&treap)
+        })
+    }
+
+    #[quickcheck]
+    fn treap_agrees_with_model(operations: Vec<Operation>) -> bool {
+        agrees_with_model::<u8, _>(&mut Treap::new(), &operations)
+    }
+}
@@ -0,0 +1,278 @@
+//! Tries.
+//!
+//! A trie, or prefix tree, stores a set of strings as paths from its root:
+//! each edge is labeled by a character and each node stands for the prefix
+//! spelled by the path leading to it. The strings sharing a prefix share
+//! the nodes of that prefix, so prefix queries take time proportional to
+//! the length of the prefix, whatever the number of strings.
+//!
+//! The [`RadixTrie`](radix::RadixTrie) compresses the chains of nodes with
+//! a single child into one edge labeled by a string.
+
+use std::collections::BTreeMap;
+
+pub mod radix;
+
+/// Node of a [`Trie`].
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: BTreeMap<char, TrieNode>,
+
+    /// True if the prefix of the node is in the set.
+    terminal: bool,
+}
+
+/// Trie is a set of strings stored as a prefix tree.
+#[derive(Debug, Clone, Default)]
+pub struct Trie {
+    root: TrieNode,
+    len: usize,
+}
+
+impl Trie {
+    /// Creates new empty trie.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of strings.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the trie is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        fn count(node: &TrieNode) -> usize {
+            1 + node.children.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+
+    /// Inserts the string, and returns true if it was not in the trie.
+    pub fn insert(&mut self, key: &str) -> bool {
+        let mut node = &mut self.root;
+        for c in key.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        let inserted = !node.terminal;
+        node.terminal = true;
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Returns true if the string is in the trie.
+    pub fn contains(&self, key: &str) -> bool {
+        self.find(key).map_or(false, |node| node.terminal)
+    }
+
+    /// Removes the string, and returns true if it was in the trie.
+    ///
+    /// The nodes left without any string below them are removed as well.
+    pub fn remove(&mut self, key: &str) -> bool {
+        // Returns true if the string was removed from the subtree.
+        fn remove(node: &mut TrieNode, mut chars: std::str::Chars) -> bool {
+            let Some(c) = chars.next() else {
+                return std::mem::take(&mut node.terminal);
+            };
+            let Some(child) = node.children.get_mut(&c) else {
+                return false;
+            };
+            let removed = remove(child, chars);
+            if !child.terminal && child.children.is_empty() {
+                node.children.remove(&c);
+            }
+            removed
+        }
+        let removed = remove(&mut self.root, key.chars());
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    /// Returns the longest string of the trie which is a prefix of the
+    /// text.
+    pub fn longest_prefix<'a>(&self, text: &'a str) -> Option<&'a str> {
+        let mut node = &self.root;
+        let mut longest = node.terminal.then_some(0);
+        for (index, c) in text.char_indices() {
+            match node.children.get(&c) {
+                Some(child) => node = child,
+                None => break,
+            }
+            if node.terminal {
+                longest = Some(index + c.len_utf8());
+            }
+        }
+        longest.map(|end| &text[..end])
+    }
+
+    /// Creates an iterator over the strings starting with the prefix, in
+    /// lexicographic order.
+    pub fn starts_with(&self, prefix: &str) -> Keys<'_> {
+        let mut stack = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            stack.push((node, prefix.to_string()));
+        }
+        Keys { stack }
+    }
+
+    /// Creates an iterator over the strings, in lexicographic order.
+    pub fn iter(&self) -> Keys<'_> {
+        self.starts_with("")
+    }
+
+    /// Returns the node of the prefix, if any.
+    fn find(&self, prefix: &str) -> Option<&TrieNode> {
+        prefix
+            .chars()
+            .try_fold(&self.root, |node, c| node.children.get(&c))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Trie {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        for key in iter {
+            trie.insert(key);
+        }
+        trie
+    }
+}
+
+/// Iterator over the strings of a [`Trie`], in lexicographic order.
+#[derive(Debug)]
+pub struct Keys<'a> {
+    /// The nodes to visit, with their prefix.
+    stack: Vec<(&'a TrieNode, String)>,
+}
+
+impl<'a> Iterator for Keys<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, prefix)) = self.stack.pop() {
+            // The children are pushed in reverse order to pop the smallest
+            // one first.
+            for (c, child) in node.children.iter().rev() {
+                let mut key = prefix.clone();
+                key.push(*c);
+                self.stack.push((child, key));
+            }
+            if node.terminal {
+                return Some(prefix);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::BTreeSet;
+
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    /// Operation is an insertion or a removal of a string over a small
+    /// alphabet, so that the strings share many prefixes.
+    pub(crate) type Operation = (bool, Vec<u8>);
+
+    pub(crate) fn key(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .take(6)
+            .map(|byte| char::from(b'a' + byte % 3))
+            .collect()
+    }
+
+    /// Returns the longest string of the set which is a prefix of the text.
+    pub(crate) fn longest_prefix<'a>(set: &BTreeSet<String>, text: &'a str) -> Option<&'a str> {
+        let mut ends: Vec<usize> = text.char_indices().map(|(index, _)| index).collect();
+        ends.push(text.len());
+        ends.into_iter()
+            .rev()
+            .map(|end| &text[..end])
+            .find(|prefix| set.contains(*prefix))
+    }
+
+    #[test]
+    fn prefix_queries() {
+        let mut trie: Trie = ["tea", "ten", "to", "inn", "in", "i", "ted"]
+            .into_iter()
+            .collect();
+        assert_eq!(trie.len(), 7);
+        assert!(!trie.insert("ten"));
+        assert!(trie.contains("in"));
+        assert!(!trie.contains("te"));
+        assert_eq!(
+            trie.starts_with("te").collect::<Vec<_>>(),
+            vec!["tea", "ted", "ten"]
+        );
+        assert_eq!(trie.starts_with("x").count(), 0);
+        assert_eq!(trie.longest_prefix("inner"), Some("inn"));
+        assert_eq!(trie.longest_prefix("ink"), Some("in"));
+        assert_eq!(trie.longest_prefix("te"), None);
+
+        assert!(trie.remove("inn"));
+        assert!(!trie.remove("inn"));
+        assert!(!trie.remove("t"));
+        assert_eq!(trie.longest_prefix("inner"), Some("in"));
+        assert_eq!(
+            trie.iter().collect::<Vec<_>>(),
+            vec!["i", "in", "tea", "ted", "ten", "to"]
+        );
+        // The root, "t", "te" and one node per string are left.
+        assert_eq!(trie.node_count(), 9);
+    }
+
+    #[test]
+    fn empty_string() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.longest_prefix("abc"), None);
+        assert!(trie.insert(""));
+        assert!(trie.contains(""));
+        assert_eq!(trie.longest_prefix("abc"), Some(""));
+        assert_eq!(trie.iter().collect::<Vec<_>>(), vec![""]);
+        assert!(trie.remove(""));
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn multibyte_characters() {
+        let trie: Trie = ["été", "étage", "ô"].into_iter().collect();
+        assert_eq!(trie.longest_prefix("étés"), Some("été"));
+        assert_eq!(
+            trie.starts_with("ét").collect::<Vec<_>>(),
+            vec!["étage", "été"]
+        );
+    }
+
+    #[quickcheck]
+    fn agrees_with_set(operations: Vec<Operation>, prefix: Vec<u8>) -> bool {
+        let mut trie = Trie::new();
+        let mut set = BTreeSet::new();
+        for (insert, bytes) in &operations {
+            let key = key(bytes);
+            let agree = if *insert {
+                trie.insert(&key) == set.insert(key.clone())
+            } else {
+                trie.remove(&key) == set.remove(&key)
+            };
+            if !agree || trie.contains(&key) != set.contains(&key) {
+                return false;
+            }
+        }
+        let prefix = key(&prefix);
+        trie.len() == set.len()
+            && trie.iter().eq(set.iter().cloned())
+            && trie
+                .starts_with(&prefix)
+                .eq(set.iter().filter(|key| key.starts_with(&prefix)).cloned())
+            && trie.longest_prefix(&prefix) == longest_prefix(&set, &prefix)
+    }
+}
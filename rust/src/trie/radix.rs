@@ -0,0 +1,347 @@
+//! Radix trie.
+
+use std::collections::BTreeMap;
+
+/// Edge of a [`RadixTrie`], labeled by a non-empty string.
+#[derive(Debug, Clone)]
+struct Edge {
+    label: String,
+    node: RadixNode,
+}
+
+/// Node of a [`RadixTrie`].
+#[derive(Debug, Clone, Default)]
+struct RadixNode {
+    /// The edges to the children, by the first character of their label.
+    children: BTreeMap<char, Edge>,
+
+    /// True if the prefix of the node is in the set.
+    terminal: bool,
+}
+
+impl RadixNode {
+    /// Returns the edge whose label starts with the first character of the
+    /// text.
+    fn edge(&self, text: &str) -> Option<&Edge> {
+        self.children.get(&text.chars().next()?)
+    }
+}
+
+/// Returns the length in bytes of the longest common prefix of the strings.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or_else(|| a.len().min(b.len()), |((index, _), _)| index)
+}
+
+/// RadixTrie is a set of strings stored as a compressed prefix tree.
+///
+/// Every node other than the root either holds a string of the set or has
+/// at least two children, so the trie has at most `2n` nodes for `n`
+/// strings, however long they are. The edges are labeled by strings
+/// instead of characters.
+#[derive(Debug, Clone, Default)]
+pub struct RadixTrie {
+    root: RadixNode,
+    len: usize,
+}
+
+impl RadixTrie {
+    /// Creates new empty trie.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of strings.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the trie is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        fn count(node: &RadixNode) -> usize {
+            1 + node
+                .children
+                .values()
+                .map(|edge| count(&edge.node))
+                .sum::<usize>()
+        }
+        count(&self.root)
+    }
+
+    /// Inserts the string, and returns true if it was not in the trie.
+    pub fn insert(&mut self, key: &str) -> bool {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while let Some(c) = rest.chars().next() {
+            let edge = node.children.entry(c).or_insert_with(|| Edge {
+                label: rest.to_string(),
+                node: RadixNode::default(),
+            });
+            let common = common_prefix_len(&edge.label, rest);
+            if common < edge.label.len() {
+                // Split the edge at the end of the common prefix.
+                let suffix = edge.label.split_off(common);
+                let first = suffix.chars().next().expect("the suffix is not empty");
+                let below = std::mem::take(&mut edge.node);
+                edge.node.children.insert(
+                    first,
+                    Edge {
+                        label: suffix,
+                        node: below,
+                    },
+                );
+            }
+            node = &mut edge.node;
+            rest = &rest[common..];
+        }
+        let inserted = !node.terminal;
+        node.terminal = true;
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Returns true if the string is in the trie.
+    pub fn contains(&self, key: &str) -> bool {
+        let mut node = &self.root;
+        let mut rest = key;
+        while !rest.is_empty() {
+            let Some(edge) = node.edge(rest) else {
+                return false;
+            };
+            let Some(tail) = rest.strip_prefix(edge.label.as_str()) else {
+                return false;
+            };
+            node = &edge.node;
+            rest = tail;
+        }
+        node.terminal
+    }
+
+    /// Removes the string, and returns true if it was in the trie.
+    ///
+    /// A node left without string and with a single child is merged with
+    /// it, so that the trie stays compressed.
+    pub fn remove(&mut self, key: &str) -> bool {
+        // Returns true if the string was removed from the subtree.
+        fn remove(node: &mut RadixNode, rest: &str) -> bool {
+            let Some(c) = rest.chars().next() else {
+                return std::mem::take(&mut node.terminal);
+            };
+            let Some(edge) = node.children.get_mut(&c) else {
+                return false;
+            };
+            let Some(tail) = rest.strip_prefix(edge.label.as_str()) else {
+                return false;
+            };
+            if !remove(&mut edge.node, tail) {
+                return false;
+            }
+            if !edge.node.terminal {
+                match edge.node.children.len() {
+                    0 => {
+                        node.children.remove(&c);
+                    }
+                    1 => {
+                        let first = *edge
+                            .node
+                            .children
+                            .keys()
+                            .next()
+                            .expect("the node has a child");
+                        let child = edge
+                            .node
+                            .children
+                            .remove(&first)
+                            .expect("the node has a child");
+                        edge.label.push_str(&child.label);
+                        edge.node = child.node;
+                    }
+                    _ => {}
+                }
+            }
+            true
+        }
+        let removed = remove(&mut self.root, key);
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    /// Returns the longest string of the trie which is a prefix of the
+    /// text.
+    pub fn longest_prefix<'a>(&self, text: &'a str) -> Option<&'a str> {
+        let mut node = &self.root;
+        let mut end = 0;
+        let mut longest = node.terminal.then_some(0);
+        while let Some(edge) = node.edge(&text[end..]) {
+            if !text[end..].starts_with(edge.label.as_str()) {
+                break;
+            }
+            node = &edge.node;
+            end += edge.label.len();
+            if node.terminal {
+                longest = Some(end);
+            }
+        }
+        longest.map(|end| &text[..end])
+    }
+
+    /// Creates an iterator over the strings starting with the prefix, in
+    /// lexicographic order.
+    pub fn starts_with(&self, prefix: &str) -> RadixKeys<'_> {
+        let mut node = &self.root;
+        let mut path = String::new();
+        let mut stack = Vec::new();
+        loop {
+            let rest = &prefix[path.len()..];
+            if rest.is_empty() {
+                stack.push((node, path));
+                break;
+            }
+            let Some(edge) = node.edge(rest) else {
+                break;
+            };
+            path.push_str(&edge.label);
+            if edge.label.starts_with(rest) {
+                // Every string below the edge starts with the prefix.
+                stack.push((&edge.node, path));
+                break;
+            }
+            if !rest.starts_with(edge.label.as_str()) {
+                break;
+            }
+            node = &edge.node;
+        }
+        RadixKeys { stack }
+    }
+
+    /// Creates an iterator over the strings, in lexicographic order.
+    pub fn iter(&self) -> RadixKeys<'_> {
+        self.starts_with("")
+    }
+}
+
+impl<'a> FromIterator<&'a str> for RadixTrie {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        for key in iter {
+            trie.insert(key);
+        }
+        trie
+    }
+}
+
+/// Iterator over the strings of a [`RadixTrie`], in lexicographic order.
+#[derive(Debug)]
+pub struct RadixKeys<'a> {
+    /// The nodes to visit, with their prefix.
+    stack: Vec<(&'a RadixNode, String)>,
+}
+
+impl<'a> Iterator for RadixKeys<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, prefix)) = self.stack.pop() {
+            for edge in node.children.values().rev() {
+                self.stack
+                    .push((&edge.node, format!("{prefix}{}", edge.label)));
+            }
+            if node.terminal {
+                return Some(prefix);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::trie::tests::{key, longest_prefix, Operation};
+    use crate::trie::Trie;
+
+    /// Returns true if every node other than the root holds a string or
+    /// has two children, and no edge label is empty.
+    fn is_compressed(trie: &RadixTrie) -> bool {
+        fn check(node: &RadixNode) -> bool {
+            node.children.iter().all(|(c, edge)| {
+                edge.label.starts_with(*c)
+                    && (edge.node.terminal || edge.node.children.len() >= 2)
+                    && check(&edge.node)
+            })
+        }
+        check(&trie.root)
+    }
+
+    #[test]
+    fn split_and_merge_edges() {
+        let mut trie: RadixTrie = ["romane", "romanus", "romulus"].into_iter().collect();
+        // The root, "rom", "an", and the three strings.
+        assert_eq!(trie.node_count(), 1 + 5);
+        assert!(trie.contains("romanus"));
+        assert!(!trie.contains("roman"));
+        assert!(!trie.contains("romanes"));
+
+        assert!(trie.insert("roman"));
+        assert!(trie.contains("roman"));
+        assert_eq!(
+            trie.starts_with("rom").collect::<Vec<_>>(),
+            vec!["roman", "romane", "romanus", "romulus"]
+        );
+        assert_eq!(trie.starts_with("roma").count(), 3);
+        assert_eq!(trie.starts_with("romx").count(), 0);
+        assert_eq!(trie.longest_prefix("romanesque"), Some("romane"));
+        assert_eq!(trie.longest_prefix("romano"), Some("roman"));
+
+        assert!(trie.remove("romulus"));
+        assert!(!trie.remove("rom"));
+        assert!(is_compressed(&trie));
+        // "rom" is merged with its only child "an".
+        assert_eq!(trie.node_count(), 1 + 3);
+    }
+
+    #[test]
+    fn fewer_nodes_than_trie() {
+        let words = ["algorithm", "algorithms", "alignment", "allocation"];
+        let trie: Trie = words.into_iter().collect();
+        let radix: RadixTrie = words.into_iter().collect();
+        assert!(radix.iter().eq(trie.iter()));
+        assert_eq!(radix.node_count(), 6);
+        assert_eq!(trie.node_count(), 26);
+    }
+
+    #[quickcheck]
+    fn agrees_with_set(operations: Vec<Operation>, prefix: Vec<u8>) -> bool {
+        let mut trie = RadixTrie::new();
+        let mut set = BTreeSet::new();
+        for (insert, bytes) in &operations {
+            let key = key(bytes);
+            let agree = if *insert {
+                trie.insert(&key) == set.insert(key.clone())
+            } else {
+                trie.remove(&key) == set.remove(&key)
+            };
+            if !agree || trie.contains(&key) != set.contains(&key) || !is_compressed(&trie) {
+                return false;
+            }
+        }
+        let prefix = key(&prefix);
+        trie.len() == set.len()
+            && trie.iter().eq(set.iter().cloned())
+            && trie
+                .starts_with(&prefix)
+                .eq(set.iter().filter(|key| key.starts_with(&prefix)).cloned())
+            && trie.longest_prefix(&prefix) == longest_prefix(&set, &prefix)
+    }
+}